@@ -0,0 +1,627 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Mutex,
+};
+
+use eyre::Result;
+use hyperlane_base::{db::HyperlaneRocksDB, CoreMetrics};
+use hyperlane_core::{
+    DeliveryLedgerEntry, GasPaymentKey, HyperlaneDomain, HyperlaneMessage, H256, U256,
+};
+use prometheus::{CounterVec, IntCounterVec, IntGaugeVec};
+use serde::Serialize;
+use tracing::warn;
+
+use crate::msg::gas_payment::token_prices::TokenPrices;
+
+/// Running totals of what senders paid for delivered messages and what delivering
+/// them cost the relayer, per origin, destination and app context. Amounts are valued
+/// in USD using the token prices at the time of delivery, so routes whose native tokens
+/// differ can be compared, e.g. to see which warp routes subsidise others.
+///
+/// Each delivery is persisted in the origin's DB with its USD values, from which the totals
+/// are rebuilt when the relayer starts.
+#[derive(Debug)]
+pub struct ProfitabilityLedger {
+    token_prices: TokenPrices,
+    domain_names: HashMap<u32, String>,
+    state: Mutex<LedgerState>,
+    metrics: LedgerMetrics,
+}
+
+#[derive(Debug, Default)]
+struct LedgerState {
+    routes: BTreeMap<RouteKey, RouteTotals>,
+    senders: HashMap<(u32, H256), SenderTotals>,
+    /// Origins whose persisted deliveries were loaded
+    loaded_origins: HashSet<u32>,
+    /// Deliveries recorded before their origin was loaded, which loading skips as they
+    /// are already in the totals
+    recorded_before_load: HashSet<(u32, H256)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct RouteKey {
+    origin: u32,
+    destination: u32,
+    app_context: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct RouteTotals {
+    messages: u64,
+    /// Messages without a price for the origin or destination token, which aren't
+    /// included in the USD totals
+    unpriced_messages: u64,
+    gas_used: U256,
+    /// In the origin's native token
+    payment: U256,
+    /// In the destination's native token
+    cost: U256,
+    payment_usd: f64,
+    cost_usd: f64,
+}
+
+#[derive(Debug, Default)]
+struct SenderTotals {
+    messages: u64,
+    payment_usd: f64,
+    cost_usd: f64,
+}
+
+impl SenderTotals {
+    fn is_loss_making(&self) -> bool {
+        self.cost_usd > self.payment_usd
+    }
+}
+
+#[derive(Debug)]
+struct LedgerMetrics {
+    messages: IntCounterVec,
+    payment_usd: CounterVec,
+    cost_usd: CounterVec,
+    loss_making_senders: IntGaugeVec,
+}
+
+impl LedgerMetrics {
+    fn new(metrics: &CoreMetrics) -> Result<Self> {
+        Ok(Self {
+            messages: metrics.new_int_counter(
+                "ledger_delivered_messages",
+                "Number of delivered messages recorded in the profitability ledger",
+                &["origin", "remote", "app_context"],
+            )?,
+            payment_usd: metrics.new_counter(
+                "ledger_payment_usd",
+                "Gas payments for delivered messages, in USD",
+                &["origin", "remote", "app_context"],
+            )?,
+            cost_usd: metrics.new_counter(
+                "ledger_cost_usd",
+                "Cost of delivering messages, in USD",
+                &["origin", "remote", "app_context"],
+            )?,
+            loss_making_senders: metrics.new_int_gauge(
+                "ledger_loss_making_senders",
+                "Number of senders whose messages cost more to deliver than they paid",
+                &["origin"],
+            )?,
+        })
+    }
+}
+
+/// Report of the ledger totals, served by the relayer API
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfitabilityReport {
+    pub routes: Vec<RouteReport>,
+    /// Senders that cost more than they paid, most loss-making first
+    pub loss_making_senders: Vec<SenderReport>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteReport {
+    pub origin: String,
+    pub destination: String,
+    pub app_context: Option<String>,
+    pub messages: u64,
+    pub unpriced_messages: u64,
+    pub gas_used: String,
+    /// Total payment in the smallest unit of the origin's native token
+    pub payment: String,
+    /// Total cost in the smallest unit of the destination's native token
+    pub cost: String,
+    pub payment_usd: f64,
+    pub cost_usd: f64,
+    pub profit_usd: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SenderReport {
+    pub origin: String,
+    pub sender: H256,
+    pub messages: u64,
+    pub payment_usd: f64,
+    pub cost_usd: f64,
+    pub loss_usd: f64,
+}
+
+impl ProfitabilityLedger {
+    pub fn new<'a>(
+        metrics: &CoreMetrics,
+        token_prices: TokenPrices,
+        domains: impl IntoIterator<Item = &'a HyperlaneDomain>,
+    ) -> Result<Self> {
+        Ok(Self {
+            token_prices,
+            domain_names: domains
+                .into_iter()
+                .map(|domain| (domain.id(), domain.name().to_owned()))
+                .collect(),
+            state: Default::default(),
+            metrics: LedgerMetrics::new(metrics)?,
+        })
+    }
+
+    fn domain_name(&self, domain: u32) -> String {
+        self.domain_names
+            .get(&domain)
+            .cloned()
+            .unwrap_or_else(|| domain.to_string())
+    }
+
+    /// Values the payment and cost of a message in USD. Returns `None` if the price of
+    /// the origin or destination token is unknown.
+    async fn value_in_usd(
+        &self,
+        message: &HyperlaneMessage,
+        payment: U256,
+        cost: U256,
+    ) -> Result<Option<(f64, f64)>> {
+        let (Some(origin_price), Some(destination_price)) = (
+            self.token_prices.price(message.origin).await?,
+            self.token_prices.price(message.destination).await?,
+        ) else {
+            return Ok(None);
+        };
+        Ok(Some((
+            origin_price.to_usd(payment)?.to_f64_lossy(),
+            destination_price.to_usd(cost)?.to_f64_lossy(),
+        )))
+    }
+
+    /// Records a message delivered by the relayer with the total payment made for it and
+    /// the total spent on delivering it, as found in the origin's DB. Messages that were
+    /// already recorded are ignored.
+    pub async fn record_delivery(
+        &self,
+        db: &HyperlaneRocksDB,
+        message: &HyperlaneMessage,
+        app_context: Option<String>,
+    ) -> Result<()> {
+        let message_id = message.id();
+        let payment = db.retrieve_gas_payment_by_gas_payment_key(GasPaymentKey {
+            message_id,
+            destination: message.destination,
+        })?;
+        let expenditure = db.retrieve_gas_expenditure_by_message_id(message_id)?;
+        let usd = self
+            .value_in_usd(message, payment.payment, expenditure.tokens_used)
+            .await
+            .unwrap_or_else(|err| {
+                warn!(?err, msg=%message, "Failed to value message in USD");
+                None
+            });
+        let entry = DeliveryLedgerEntry {
+            message_id,
+            destination: message.destination,
+            sender: message.sender,
+            app_context,
+            payment: payment.payment,
+            gas_used: expenditure.gas_used,
+            tokens_used: expenditure.tokens_used,
+            usd,
+        };
+
+        // Hold the lock while persisting, so that `load` either skips the delivery or
+        // doesn't see it
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if db
+            .retrieve_delivery_ledger_entry_by_message_id(&message_id)?
+            .is_some()
+        {
+            return Ok(());
+        }
+        // Persist first, so that the totals are only updated once per message
+        db.store_delivery_ledger_entry_by_message_id(&message_id, &entry)?;
+        if !state.loaded_origins.contains(&message.origin) {
+            state
+                .recorded_before_load
+                .insert((message.origin, message_id));
+        }
+        self.add_to_totals(&mut state, message.origin, entry);
+        Ok(())
+    }
+
+    /// Adds the deliveries persisted in the origin's DB to the totals, valued as they were
+    /// when recorded. Only reads the ledger's entries, so it can run alongside
+    /// `record_delivery`. Returns the number of deliveries loaded.
+    pub fn load(&self, db: &HyperlaneRocksDB) -> Result<u32> {
+        let origin = db.domain().id();
+        let mut loaded = 0;
+        for entry in db.retrieve_delivery_ledger_entries() {
+            let entry = entry?;
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            if state
+                .recorded_before_load
+                .remove(&(origin, entry.message_id))
+            {
+                continue;
+            }
+            self.add_to_totals(&mut state, origin, entry);
+            loaded += 1;
+        }
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.loaded_origins.insert(origin);
+        state
+            .recorded_before_load
+            .retain(|(recorded_origin, _)| *recorded_origin != origin);
+        Ok(loaded)
+    }
+
+    fn add_to_totals(
+        &self,
+        state: &mut LedgerState,
+        origin_domain: u32,
+        entry: DeliveryLedgerEntry,
+    ) {
+        let DeliveryLedgerEntry {
+            message_id: _,
+            destination: destination_domain,
+            sender,
+            app_context,
+            payment,
+            gas_used,
+            tokens_used,
+            usd,
+        } = entry;
+
+        let origin = self.domain_name(origin_domain);
+        let destination = self.domain_name(destination_domain);
+        let labels = [
+            origin.as_str(),
+            destination.as_str(),
+            app_context.as_deref().unwrap_or("Unknown"),
+        ];
+        self.metrics.messages.with_label_values(&labels).inc();
+        if let Some((payment_usd, cost_usd)) = usd {
+            self.metrics
+                .payment_usd
+                .with_label_values(&labels)
+                .inc_by(payment_usd);
+            self.metrics
+                .cost_usd
+                .with_label_values(&labels)
+                .inc_by(cost_usd);
+        }
+
+        let route = state
+            .routes
+            .entry(RouteKey {
+                origin: origin_domain,
+                destination: destination_domain,
+                app_context,
+            })
+            .or_default();
+        route.messages += 1;
+        route.gas_used = route.gas_used.saturating_add(gas_used);
+        route.payment = route.payment.saturating_add(payment);
+        route.cost = route.cost.saturating_add(tokens_used);
+        let Some((payment_usd, cost_usd)) = usd else {
+            route.unpriced_messages += 1;
+            return;
+        };
+        route.payment_usd += payment_usd;
+        route.cost_usd += cost_usd;
+
+        let sender = state.senders.entry((origin_domain, sender)).or_default();
+        let was_loss_making = sender.is_loss_making();
+        sender.messages += 1;
+        sender.payment_usd += payment_usd;
+        sender.cost_usd += cost_usd;
+        let loss_making_senders = self
+            .metrics
+            .loss_making_senders
+            .with_label_values(&[origin.as_str()]);
+        match (was_loss_making, sender.is_loss_making()) {
+            (false, true) => loss_making_senders.inc(),
+            (true, false) => loss_making_senders.dec(),
+            _ => {}
+        }
+    }
+
+    pub fn report(&self) -> ProfitabilityReport {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let routes = state
+            .routes
+            .iter()
+            .map(|(key, totals)| RouteReport {
+                origin: self.domain_name(key.origin),
+                destination: self.domain_name(key.destination),
+                app_context: key.app_context.clone(),
+                messages: totals.messages,
+                unpriced_messages: totals.unpriced_messages,
+                gas_used: totals.gas_used.to_string(),
+                payment: totals.payment.to_string(),
+                cost: totals.cost.to_string(),
+                payment_usd: totals.payment_usd,
+                cost_usd: totals.cost_usd,
+                profit_usd: totals.payment_usd - totals.cost_usd,
+            })
+            .collect();
+        let mut loss_making_senders: Vec<_> = state
+            .senders
+            .iter()
+            .filter(|(_, totals)| totals.is_loss_making())
+            .map(|((origin, sender), totals)| SenderReport {
+                origin: self.domain_name(*origin),
+                sender: *sender,
+                messages: totals.messages,
+                payment_usd: totals.payment_usd,
+                cost_usd: totals.cost_usd,
+                loss_usd: totals.cost_usd - totals.payment_usd,
+            })
+            .collect();
+        loss_making_senders.sort_by(|a, b| b.loss_usd.total_cmp(&a.loss_usd));
+        ProfitabilityReport {
+            routes,
+            loss_making_senders,
+        }
+    }
+
+    /// The per-route totals as CSV, with a header row
+    pub fn report_csv(&self) -> String {
+        let mut csv = String::from(
+            "origin,destination,app_context,messages,unpriced_messages,gas_used,payment,cost,payment_usd,cost_usd,profit_usd\n",
+        );
+        for route in self.report().routes {
+            let row = [
+                csv_field(&route.origin),
+                csv_field(&route.destination),
+                csv_field(route.app_context.as_deref().unwrap_or_default()),
+                route.messages.to_string(),
+                route.unpriced_messages.to_string(),
+                route.gas_used,
+                route.payment,
+                route.cost,
+                route.payment_usd.to_string(),
+                route.cost_usd.to_string(),
+                route.profit_usd.to_string(),
+            ];
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+/// Quotes a CSV field if it contains a separator, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use hyperlane_base::db::test_utils;
+    use hyperlane_core::{
+        FixedPointNumber, InterchainGasExpenditure, InterchainGasPayment, KnownHyperlaneDomain,
+    };
+    use prometheus::Registry;
+
+    use super::*;
+    use crate::settings::TokenPriceConf;
+
+    fn price(usd: &str, decimals: u32) -> TokenPriceConf {
+        TokenPriceConf {
+            usd: FixedPointNumber::from_str(usd).unwrap(),
+            decimals,
+        }
+    }
+
+    fn ledger_with_prices(token_prices: HashMap<u32, TokenPriceConf>) -> ProfitabilityLedger {
+        let metrics = CoreMetrics::new("test", 9090, Registry::new()).unwrap();
+        let domains = [
+            HyperlaneDomain::Known(KnownHyperlaneDomain::Ethereum),
+            HyperlaneDomain::Known(KnownHyperlaneDomain::Polygon),
+        ];
+        ProfitabilityLedger::new(&metrics, TokenPrices::new(token_prices, None), &domains).unwrap()
+    }
+
+    fn ledger() -> ProfitabilityLedger {
+        ledger_with_prices(HashMap::from([
+            (KnownHyperlaneDomain::Ethereum as u32, price("3000", 18)),
+            (KnownHyperlaneDomain::Polygon as u32, price("0.5", 18)),
+        ]))
+    }
+
+    /// Delivers a message from Polygon to Ethereum. Amounts are valued with the prices
+    /// of `ledger()`.
+    fn deliver(
+        ledger: &ProfitabilityLedger,
+        sender: H256,
+        app_context: Option<&str>,
+        payment: U256,
+        cost: U256,
+    ) {
+        let to_usd = |amount: U256, usd: f64| amount.to_f64_lossy() / 1e18 * usd;
+        ledger.add_to_totals(
+            &mut ledger.state.lock().unwrap(),
+            KnownHyperlaneDomain::Polygon as u32,
+            DeliveryLedgerEntry {
+                message_id: H256::random(),
+                destination: KnownHyperlaneDomain::Ethereum as u32,
+                sender,
+                app_context: app_context.map(str::to_owned),
+                payment,
+                gas_used: 100_000.into(),
+                tokens_used: cost,
+                usd: Some((to_usd(payment, 0.5), to_usd(cost, 3000.))),
+            },
+        );
+    }
+
+    #[test]
+    fn test_totals_and_loss_making_senders() {
+        let ledger = ledger();
+        let (profitable, loss_making) = (H256::repeat_byte(1), H256::repeat_byte(2));
+        let one_token = U256::exp10(18);
+
+        // Pays 10 POL ($5) for a delivery costing 0.001 ETH ($3)
+        deliver(
+            &ledger,
+            profitable,
+            Some("warp"),
+            one_token * 10,
+            one_token / 1000,
+        );
+        // Pays 1 POL ($0.5) for a delivery costing 0.001 ETH ($3)
+        deliver(
+            &ledger,
+            loss_making,
+            Some("warp"),
+            one_token,
+            one_token / 1000,
+        );
+
+        let report = ledger.report();
+        assert_eq!(report.routes.len(), 1);
+        let route = &report.routes[0];
+        assert_eq!(
+            (route.origin.as_str(), route.destination.as_str()),
+            ("polygon", "ethereum")
+        );
+        assert_eq!(route.messages, 2);
+        assert_eq!(route.payment, (one_token * 11).to_string());
+        assert_eq!(route.cost, (one_token / 500).to_string());
+        assert!((route.profit_usd - -0.5).abs() < 1e-9);
+
+        assert_eq!(report.loss_making_senders.len(), 1);
+        assert_eq!(report.loss_making_senders[0].sender, loss_making);
+        assert!((report.loss_making_senders[0].loss_usd - 2.5).abs() < 1e-9);
+        assert_eq!(
+            ledger
+                .metrics
+                .loss_making_senders
+                .with_label_values(&["polygon"])
+                .get(),
+            1
+        );
+
+        // The sender is no longer loss making once it overpays
+        deliver(
+            &ledger,
+            loss_making,
+            Some("warp"),
+            one_token * 10,
+            U256::zero(),
+        );
+        assert!(ledger.report().loss_making_senders.is_empty());
+        assert_eq!(
+            ledger
+                .metrics
+                .loss_making_senders
+                .with_label_values(&["polygon"])
+                .get(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_report_csv() {
+        let ledger = ledger();
+        deliver(
+            &ledger,
+            H256::zero(),
+            Some("warp, usdc"),
+            5.into(),
+            7.into(),
+        );
+        deliver(&ledger, H256::zero(), None, 1.into(), 1.into());
+
+        let csv = ledger.report_csv();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("origin,destination,app_context,"));
+        assert!(lines[1].starts_with("polygon,ethereum,,1,0,100000,1,1,"));
+        assert!(lines[2].starts_with("polygon,ethereum,\"warp, usdc\",1,0,100000,5,7,"));
+    }
+
+    #[tokio::test]
+    async fn test_deliveries_are_recorded_once_and_reloaded() {
+        test_utils::run_test_db(|db| async move {
+            let origin = HyperlaneDomain::Known(KnownHyperlaneDomain::Polygon);
+            let db = HyperlaneRocksDB::new(&origin, db);
+            let message = HyperlaneMessage {
+                origin: origin.id(),
+                destination: KnownHyperlaneDomain::Ethereum as u32,
+                ..Default::default()
+            };
+            db.store_message(&message, 1).unwrap();
+            db.process_gas_payment(
+                InterchainGasPayment {
+                    message_id: message.id(),
+                    destination: message.destination,
+                    payment: 5.into(),
+                    gas_amount: U256::zero(),
+                },
+                &Default::default(),
+            )
+            .unwrap();
+            db.process_gas_expenditure(InterchainGasExpenditure {
+                message_id: message.id(),
+                gas_used: 100_000.into(),
+                tokens_used: 7.into(),
+            })
+            .unwrap();
+
+            let ledger = ledger();
+            for _ in 0..2 {
+                ledger
+                    .record_delivery(&db, &message, Some("warp".to_owned()))
+                    .await
+                    .unwrap();
+            }
+            // Deliveries recorded before loading are not counted twice
+            assert_eq!(ledger.load(&db).unwrap(), 0);
+            let report = ledger.report();
+            assert_eq!(report.routes.len(), 1);
+            assert_eq!(report.routes[0].messages, 1);
+            assert_eq!(report.routes[0].unpriced_messages, 0);
+            assert_eq!(report.routes[0].payment, "5");
+            assert_eq!(report.routes[0].cost, "7");
+
+            // The totals survive a restart, valued at the prices they were recorded with
+            let restarted = ledger_with_prices(HashMap::new());
+            assert_eq!(restarted.load(&db).unwrap(), 1);
+            let reloaded = restarted.report();
+            assert_eq!(reloaded.routes.len(), 1);
+            assert_eq!(reloaded.routes[0].messages, 1);
+            assert_eq!(reloaded.routes[0].unpriced_messages, 0);
+            assert_eq!(reloaded.routes[0].payment, "5");
+            assert_eq!(reloaded.routes[0].cost, "7");
+            assert!(
+                (reloaded.routes[0].profit_usd / report.routes[0].profit_usd - 1.).abs() < 1e-9
+            );
+            assert_eq!(reloaded.loss_making_senders.len(), 1);
+        })
+        .await;
+    }
+}
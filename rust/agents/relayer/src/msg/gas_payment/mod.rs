@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
 use async_trait::async_trait;
 use eyre::Result;
@@ -7,9 +7,10 @@ use hyperlane_core::{
     FixedPointNumber, GasPaymentKey, HyperlaneMessage, InterchainGasExpenditure,
     InterchainGasPayment, TxCostEstimate, TxOutcome, U256,
 };
+use tokio::sync::RwLock;
 use tracing::{debug, error, trace};
 
-use self::policies::{GasPaymentPolicyMinimum, GasPaymentPolicyNone};
+use self::policies::{GasPaymentPolicyMinimum, GasPaymentPolicyNone, GasPaymentPolicyUsdCost};
 use crate::{
     msg::gas_payment::policies::GasPaymentPolicyOnChainFeeQuoting,
     settings::{
@@ -17,7 +18,9 @@ use crate::{
     },
 };
 
+pub mod ledger;
 mod policies;
+pub mod token_prices;
 
 pub const GAS_EXPENDITURE_LOG_MESSAGE: &str = "Recording gas expenditure for message";
 
@@ -41,10 +44,13 @@ pub struct GasPaymentEnforcer {
     /// use a wild-card white list to ensure all messages fall into one
     /// policy or another. If a message matches multiple policies'
     /// whitelists, then whichever is first in the list will be used.
-    policies: Vec<(Box<dyn GasPaymentPolicy>, MatchingList)>,
+    /// The policies can be replaced while the relayer is running.
+    policies: RwLock<Arc<GasPaymentPolicies>>,
     db: HyperlaneRocksDB,
 }
 
+type GasPaymentPolicies = Vec<(Box<dyn GasPaymentPolicy>, MatchingList)>;
+
 impl GasPaymentEnforcer {
     /// Note that `policy_configs` should not be empty. In the settings,
     /// a default of vec![GasPaymentEnforcementConf::default()] is used.
@@ -52,7 +58,25 @@ impl GasPaymentEnforcer {
         policy_configs: impl IntoIterator<Item = GasPaymentEnforcementConf>,
         db: HyperlaneRocksDB,
     ) -> Self {
-        let policies = policy_configs
+        Self {
+            policies: RwLock::new(Arc::new(Self::build_policies(policy_configs))),
+            db,
+        }
+    }
+
+    /// Replace the policies in use, e.g. after the relayer policies were reloaded
+    pub async fn set_policies(
+        &self,
+        policy_configs: impl IntoIterator<Item = GasPaymentEnforcementConf>,
+    ) {
+        let policies = Arc::new(Self::build_policies(policy_configs));
+        *self.policies.write().await = policies;
+    }
+
+    fn build_policies(
+        policy_configs: impl IntoIterator<Item = GasPaymentEnforcementConf>,
+    ) -> GasPaymentPolicies {
+        policy_configs
             .into_iter()
             .map(|cfg| {
                 let p: Box<dyn GasPaymentPolicy> = match cfg.policy {
@@ -64,12 +88,19 @@ impl GasPaymentEnforcer {
                         gas_fraction_numerator: n,
                         gas_fraction_denominator: d,
                     } => Box::new(GasPaymentPolicyOnChainFeeQuoting::new(n, d)),
+                    GasPaymentEnforcementPolicy::UsdCost {
+                        margin,
+                        token_prices,
+                        token_prices_path,
+                    } => Box::new(GasPaymentPolicyUsdCost::new(
+                        margin,
+                        token_prices,
+                        token_prices_path,
+                    )),
                 };
                 (p, cfg.matching_list)
             })
-            .collect();
-
-        Self { policies, db }
+            .collect()
     }
 }
 
@@ -91,7 +122,9 @@ impl GasPaymentEnforcer {
             .retrieve_gas_payment_by_gas_payment_key(gas_payment_key)?;
         let current_expenditure = self.db.retrieve_gas_expenditure_by_message_id(msg_id)?;
 
-        for (policy, whitelist) in &self.policies {
+        // Take a snapshot, so that the lock isn't held while evaluating the policy
+        let policies = self.policies.read().await.clone();
+        for (policy, whitelist) in policies.iter() {
             if !whitelist.msg_matches(message, true) {
                 trace!(
                     msg=%message,
@@ -127,7 +160,7 @@ impl GasPaymentEnforcer {
 
         error!(
             msg=%message,
-            policies=?self.policies,
+            ?policies,
             "No gas payment policy matched for message; consider adding a default policy to the end of the policies array which uses a wildcard whitelist."
         );
         Ok(None)
@@ -0,0 +1,210 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use async_trait::async_trait;
+use eyre::Result;
+use hyperlane_core::{
+    FixedPointNumber, HyperlaneMessage, InterchainGasExpenditure, InterchainGasPayment,
+    TxCostEstimate, U256,
+};
+use tracing::{debug, warn};
+
+use crate::{
+    msg::gas_payment::{token_prices::TokenPrices, GasPaymentPolicy},
+    settings::TokenPriceConf,
+};
+
+/// Approves messages whose payment covers the cost of delivering them times a margin,
+/// comparing both in USD. This stops the relayer from delivering loss-making messages
+/// between chains whose native tokens have very different values.
+#[derive(Debug)]
+pub struct GasPaymentPolicyUsdCost {
+    /// The payment must be at least the cost times this value
+    margin: FixedPointNumber,
+    token_prices: TokenPrices,
+}
+
+impl GasPaymentPolicyUsdCost {
+    pub fn new(
+        margin: FixedPointNumber,
+        token_prices: HashMap<u32, TokenPriceConf>,
+        token_prices_path: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            margin,
+            token_prices: TokenPrices::new(token_prices, token_prices_path),
+        }
+    }
+}
+
+#[async_trait]
+impl GasPaymentPolicy for GasPaymentPolicyUsdCost {
+    async fn message_meets_gas_payment_requirement(
+        &self,
+        message: &HyperlaneMessage,
+        current_payment: &InterchainGasPayment,
+        current_expenditure: &InterchainGasExpenditure,
+        tx_cost_estimate: &TxCostEstimate,
+    ) -> Result<Option<U256>> {
+        let (Some(origin_price), Some(destination_price)) = (
+            self.token_prices.price(message.origin).await?,
+            self.token_prices.price(message.destination).await?,
+        ) else {
+            warn!(
+                origin = message.origin,
+                destination = message.destination,
+                "Missing token price, unable to evaluate UsdCost gas payment policy"
+            );
+            return Ok(None);
+        };
+
+        let payment_usd = origin_price.to_usd(current_payment.payment)?;
+        let estimated_cost: U256 = (FixedPointNumber::try_from(tx_cost_estimate.gas_limit)?
+            * tx_cost_estimate.gas_price.clone())
+        .try_into()?;
+        let cost_usd = destination_price
+            // Tokens already spent on previous delivery attempts are part of the cost
+            .to_usd(estimated_cost.saturating_add(current_expenditure.tokens_used))?;
+        let required_usd = cost_usd * self.margin.clone();
+
+        debug!(
+            msg = %message,
+            ?payment_usd,
+            ?required_usd,
+            "Evaluated USD cost of delivery"
+        );
+        if payment_usd >= required_usd {
+            Ok(Some(tx_cost_estimate.gas_limit))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use hyperlane_core::H256;
+
+    use super::*;
+
+    const ORIGIN: u32 = 1;
+    const DESTINATION: u32 = 2;
+
+    fn price(usd: &str, decimals: u32) -> TokenPriceConf {
+        TokenPriceConf {
+            usd: FixedPointNumber::from_str(usd).unwrap(),
+            decimals,
+        }
+    }
+
+    fn message() -> HyperlaneMessage {
+        HyperlaneMessage {
+            origin: ORIGIN,
+            destination: DESTINATION,
+            ..Default::default()
+        }
+    }
+
+    fn current_payment(payment: impl Into<U256>) -> InterchainGasPayment {
+        InterchainGasPayment {
+            message_id: H256::zero(),
+            destination: DESTINATION,
+            payment: payment.into(),
+            gas_amount: U256::zero(),
+        }
+    }
+
+    fn current_expenditure(tokens_used: impl Into<U256>) -> InterchainGasExpenditure {
+        InterchainGasExpenditure {
+            message_id: H256::zero(),
+            gas_used: U256::zero(),
+            tokens_used: tokens_used.into(),
+        }
+    }
+
+    /// Costs 100_000 * 10 = 1_000_000 destination tokens
+    fn cost_estimate() -> TxCostEstimate {
+        TxCostEstimate {
+            gas_limit: U256::from(100_000u64),
+            gas_price: 10u32.into(),
+            l2_gas_limit: None,
+        }
+    }
+
+    /// The origin token is worth 1000x the destination token, so a payment of 1_000 origin
+    /// tokens covers the cost. The margin requires 10% more.
+    fn policy() -> GasPaymentPolicyUsdCost {
+        GasPaymentPolicyUsdCost::new(
+            FixedPointNumber::from_str("1.1").unwrap(),
+            HashMap::from([(ORIGIN, price("2", 6)), (DESTINATION, price("0.002", 6))]),
+            None,
+        )
+    }
+
+    async fn evaluate(
+        policy: &GasPaymentPolicyUsdCost,
+        payment: u32,
+        tokens_used: u32,
+    ) -> Option<U256> {
+        policy
+            .message_meets_gas_payment_requirement(
+                &message(),
+                &current_payment(payment),
+                &current_expenditure(tokens_used),
+                &cost_estimate(),
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_payment_covers_cost_with_margin() {
+        let policy = policy();
+        assert_eq!(evaluate(&policy, 1_099, 0).await, None);
+        assert_eq!(
+            evaluate(&policy, 1_100, 0).await,
+            Some(cost_estimate().gas_limit)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_accounts_for_expenditure() {
+        let policy = policy();
+        // Another 100_000 destination tokens were already spent
+        assert_eq!(evaluate(&policy, 1_100, 100_000).await, None);
+        assert_eq!(
+            evaluate(&policy, 1_210, 100_000).await,
+            Some(cost_estimate().gas_limit)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_price_is_not_approved() {
+        let policy = GasPaymentPolicyUsdCost::new(
+            FixedPointNumber::from(1u32),
+            HashMap::from([(ORIGIN, price("2", 6))]),
+            None,
+        );
+        assert_eq!(evaluate(&policy, u32::MAX, 0).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_price_feed_file_takes_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("prices.json");
+        std::fs::write(&path, r#"{"2": {"usd": "0.004", "decimals": 6}}"#).unwrap();
+        let policy = GasPaymentPolicyUsdCost::new(
+            FixedPointNumber::from(1u32),
+            HashMap::from([(ORIGIN, price("2", 6)), (DESTINATION, price("0.002", 6))]),
+            Some(path),
+        );
+
+        // The destination token is twice as expensive according to the price feed
+        assert_eq!(evaluate(&policy, 1_999, 0).await, None);
+        assert_eq!(
+            evaluate(&policy, 2_000, 0).await,
+            Some(cost_estimate().gas_limit)
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use std::{collections::HashMap, path::PathBuf, time::SystemTime};
+
+use eyre::{Context, Result};
+use hyperlane_base::settings::parser::ValueParser;
+use hyperlane_core::config::{ConfigParsingError, ConfigPath};
+use serde_json::Value;
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+use crate::settings::{parse_token_prices, TokenPriceConf};
+
+/// Native token prices by domain id, from the config and optionally a price feed file
+/// whose prices take precedence.
+#[derive(Debug, Default)]
+pub struct TokenPrices {
+    /// Prices from the config, by domain id
+    configured: HashMap<u32, TokenPriceConf>,
+    price_feed: Option<PriceFeedFile>,
+}
+
+/// Token prices read from a file that is updated by an external price feed.
+/// The file is reloaded whenever it has been modified.
+#[derive(Debug)]
+struct PriceFeedFile {
+    path: PathBuf,
+    cache: Mutex<Option<(SystemTime, HashMap<u32, TokenPriceConf>)>>,
+}
+
+impl PriceFeedFile {
+    async fn prices(&self) -> Result<HashMap<u32, TokenPriceConf>> {
+        let modified = tokio::fs::metadata(&self.path)
+            .await
+            .and_then(|metadata| metadata.modified())
+            .with_context(|| format!("Reading token prices file {:?}", self.path))?;
+
+        let mut cache = self.cache.lock().await;
+        if let Some((cached_at, prices)) = cache.as_ref() {
+            if *cached_at == modified {
+                return Ok(prices.clone());
+            }
+        }
+
+        let contents = tokio::fs::read_to_string(&self.path)
+            .await
+            .with_context(|| format!("Reading token prices file {:?}", self.path))?;
+        let value: Value = serde_json::from_str(&contents)
+            .with_context(|| format!("Parsing token prices file {:?}", self.path))?;
+        let mut err = ConfigParsingError::default();
+        let prices = parse_token_prices(ValueParser::new(ConfigPath::default(), &value), &mut err);
+        let prices = err
+            .into_result(prices)
+            .with_context(|| format!("Parsing token prices file {:?}", self.path))?;
+        debug!(path = ?self.path, ?prices, "Loaded token prices");
+
+        *cache = Some((modified, prices.clone()));
+        Ok(prices)
+    }
+}
+
+impl TokenPrices {
+    pub fn new(configured: HashMap<u32, TokenPriceConf>, price_feed_path: Option<PathBuf>) -> Self {
+        Self {
+            configured,
+            price_feed: price_feed_path.map(|path| PriceFeedFile {
+                path,
+                cache: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// The price of the domain's native token, if it is known
+    pub async fn price(&self, domain: u32) -> Result<Option<TokenPriceConf>> {
+        if let Some(price_feed) = &self.price_feed {
+            match price_feed.prices().await {
+                Ok(prices) => {
+                    if let Some(price) = prices.get(&domain) {
+                        return Ok(Some(price.clone()));
+                    }
+                }
+                // Fall back to the configured prices, if there are any
+                Err(err) if !self.configured.is_empty() => {
+                    warn!(
+                        ?err,
+                        "Failed to read token prices file, using configured prices"
+                    )
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(self.configured.get(&domain).cloned())
+    }
+}
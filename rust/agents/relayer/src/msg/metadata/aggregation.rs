@@ -1,13 +1,14 @@
+use std::future::Future;
+
 use async_trait::async_trait;
 use derive_more::Deref;
-use futures_util::future::join_all;
+use futures_util::{stream::FuturesUnordered, FutureExt, StreamExt};
 
 use derive_new::new;
 use eyre::Context;
-use itertools::{Either, Itertools};
-use tracing::{info, instrument};
+use tracing::{debug, info, instrument};
 
-use hyperlane_core::{HyperlaneMessage, InterchainSecurityModule, ModuleType, H256, U256};
+use hyperlane_core::{HyperlaneMessage, ModuleType, H256, U256};
 
 use super::{MessageMetadataBuilder, MetadataBuilder};
 
@@ -21,30 +22,22 @@ pub struct AggregationIsmMetadataBuilder {
 }
 
 #[derive(Clone, Debug, new, PartialEq, Eq)]
-struct SubModuleMetadata {
+pub(super) struct SubModuleMetadata {
     /// The index of the sub-module (ISM) in the aggregation ISM.
     index: usize,
     /// The metadata for the sub-module.
     metadata: Vec<u8>,
 }
 
-#[derive(Debug)]
-struct IsmAndMetadata {
-    ism: Box<dyn InterchainSecurityModule>,
-    meta: SubModuleMetadata,
-}
-
-impl IsmAndMetadata {
-    fn new(ism: Box<dyn InterchainSecurityModule>, index: usize, metadata: Vec<u8>) -> Self {
-        Self {
-            ism,
-            meta: SubModuleMetadata::new(index, metadata),
-        }
-    }
-}
+/// Either the metadata of a sub-module with the gas its verification costs, or the
+/// address and, if known, the type of a sub-module without valid metadata.
+type SubModuleResult = Result<(SubModuleMetadata, U256), (H256, Option<ModuleType>)>;
 
 impl AggregationIsmMetadataBuilder {
-    fn format_metadata(metadatas: &mut [SubModuleMetadata], ism_count: usize) -> Vec<u8> {
+    pub(super) fn format_metadata(
+        metadatas: &mut [SubModuleMetadata],
+        ism_count: usize,
+    ) -> Vec<u8> {
         // See test solidity implementation of this fn at:
         // https://github.com/hyperlane-xyz/hyperlane-monorepo/blob/445da4fb0d8140a08c4b314e3051b7a934b0f968/solidity/test/isms/AggregationIsm.t.sol#L35
         fn encode_byte_index(i: usize) -> [u8; 4] {
@@ -74,7 +67,7 @@ impl AggregationIsmMetadataBuilder {
         buffer
     }
 
-    fn n_cheapest_metas(
+    pub(super) fn n_cheapest_metas(
         mut metas_and_gas: Vec<(SubModuleMetadata, U256)>,
         n: usize,
     ) -> Vec<SubModuleMetadata> {
@@ -87,31 +80,61 @@ impl AggregationIsmMetadataBuilder {
         cheapest.into_iter().map(|(meta, _)| meta).collect()
     }
 
-    async fn cheapest_valid_metas(
-        sub_modules: Vec<IsmAndMetadata>,
+    /// Builds the metadata of a sub-module and estimates the gas its verification costs
+    async fn build_sub_module(
+        &self,
+        index: usize,
+        ism_address: H256,
         message: &HyperlaneMessage,
-        threshold: usize,
-        err_isms: Vec<(H256, Option<ModuleType>)>,
-    ) -> Option<Vec<SubModuleMetadata>> {
-        let gas_cost_results: Vec<_> = join_all(
-            sub_modules
-                .iter()
-                .map(|module| module.ism.dry_run_verify(message, &(module.meta.metadata))),
-        )
-        .await;
-        // Filter out the ISMs with a gas cost estimate
-        let metas_and_gas: Vec<_> = sub_modules
-            .into_iter()
-            .zip(gas_cost_results.into_iter())
-            .filter_map(|(module, gas_cost)| gas_cost.ok().flatten().map(|gc| (module.meta, gc)))
-            .collect();
+    ) -> SubModuleResult {
+        let sub_module = match self.base.build_ism_and_metadata(ism_address, message).await {
+            Ok(sub_module) => sub_module,
+            Err(err) => {
+                debug!(?ism_address, ?err, "Failed to build sub-module metadata");
+                return Err((ism_address, None));
+            }
+        };
+        let Some(metadata) = sub_module.metadata else {
+            return Err((ism_address, Some(sub_module.module_type)));
+        };
+        match sub_module.ism.dry_run_verify(message, &metadata).await {
+            Ok(Some(gas)) => Ok((SubModuleMetadata::new(index, metadata), gas)),
+            _ => Err((ism_address, Some(sub_module.module_type))),
+        }
+    }
 
-        let metas_and_gas_count = metas_and_gas.len();
-        if metas_and_gas_count < threshold {
-            info!(?err_isms, %metas_and_gas_count, %threshold, message_id=?message.id(), "Could not fetch all metadata, ISM metadata count did not reach aggregation threshold");
-            return None;
+    /// Drives the sub-module futures concurrently until `threshold` of them succeeded,
+    /// or until so many failed that the threshold can't be reached anymore. Sub-modules
+    /// that are still pending at that point are cancelled, except for those that
+    /// completed at the same time, which are included as they may be cheaper.
+    async fn collect_until_quorum<F>(
+        mut pending: FuturesUnordered<F>,
+        threshold: usize,
+    ) -> (
+        Vec<(SubModuleMetadata, U256)>,
+        Vec<(H256, Option<ModuleType>)>,
+    )
+    where
+        F: Future<Output = SubModuleResult>,
+    {
+        let mut metas_and_gas = vec![];
+        let mut err_isms = vec![];
+        while metas_and_gas.len() < threshold && metas_and_gas.len() + pending.len() >= threshold {
+            match pending.next().await {
+                Some(Ok(meta_and_gas)) => metas_and_gas.push(meta_and_gas),
+                Some(Err(err_ism)) => err_isms.push(err_ism),
+                None => break,
+            }
+        }
+        if metas_and_gas.len() >= threshold {
+            while let Some(Some(result)) = pending.next().now_or_never() {
+                match result {
+                    Ok(meta_and_gas) => metas_and_gas.push(meta_and_gas),
+                    Err(err_ism) => err_isms.push(err_ism),
+                }
+            }
         }
-        Some(Self::n_cheapest_metas(metas_and_gas, threshold))
+        (metas_and_gas, err_isms)
     }
 }
 
@@ -128,45 +151,45 @@ impl MetadataBuilder for AggregationIsmMetadataBuilder {
         let (ism_addresses, threshold) = ism.modules_and_threshold(message).await.context(CTX)?;
         let threshold = threshold as usize;
 
-        let sub_modules_and_metas = join_all(
-            ism_addresses
-                .iter()
-                .map(|ism_address| self.base.build_ism_and_metadata(*ism_address, message)),
-        )
-        .await;
-
-        // Partitions things into
-        // 1. ok_sub_modules: ISMs with metadata with valid metadata
-        // 2. err_sub_modules: ISMs with invalid metadata
-        let (ok_sub_modules, err_sub_modules): (Vec<_>, Vec<_>) = sub_modules_and_metas
-            .into_iter()
-            .zip(ism_addresses.iter())
+        let pending: FuturesUnordered<_> = ism_addresses
+            .iter()
             .enumerate()
-            .partition_map(|(index, (result, ism_address))| match result {
-                Ok(sub_module_and_meta) => match sub_module_and_meta.metadata {
-                    Some(metadata) => Either::Left(IsmAndMetadata::new(
-                        sub_module_and_meta.ism,
-                        index,
-                        metadata,
-                    )),
-                    None => Either::Right((*ism_address, Some(sub_module_and_meta.module_type))),
-                },
-                Err(_) => Either::Right((*ism_address, None)),
-            });
-        let maybe_aggregation_metadata =
-            Self::cheapest_valid_metas(ok_sub_modules, message, threshold, err_sub_modules)
-                .await
-                .map(|mut metas| Self::format_metadata(&mut metas, ism_addresses.len()));
-        Ok(maybe_aggregation_metadata)
+            .map(|(index, ism_address)| self.build_sub_module(index, *ism_address, message))
+            .collect();
+        let (metas_and_gas, err_isms) = Self::collect_until_quorum(pending, threshold).await;
+
+        let metas_and_gas_count = metas_and_gas.len();
+        if metas_and_gas_count < threshold {
+            info!(?err_isms, %metas_and_gas_count, %threshold, message_id=?message.id(), "Could not fetch all metadata, ISM metadata count did not reach aggregation threshold");
+            return Ok(None);
+        }
+        let mut metas = Self::n_cheapest_metas(metas_and_gas, threshold);
+        Ok(Some(Self::format_metadata(&mut metas, ism_addresses.len())))
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::pin::Pin;
+
     use ethers::utils::hex::FromHex;
 
     use super::*;
 
+    type BoxedSubModuleFuture = Pin<Box<dyn Future<Output = SubModuleResult>>>;
+
+    fn ready_sub_module(index: usize, gas: u32) -> BoxedSubModuleFuture {
+        Box::pin(async move { Ok((SubModuleMetadata::new(index, vec![]), gas.into())) })
+    }
+
+    fn failing_sub_module() -> BoxedSubModuleFuture {
+        Box::pin(async { Err((H256::zero(), None)) })
+    }
+
+    fn never_ready_sub_module() -> BoxedSubModuleFuture {
+        Box::pin(std::future::pending())
+    }
+
     #[test]
     fn test_format_n_of_n_metadata_works_correctly() {
         let mut metadatas = vec![
@@ -259,4 +282,46 @@ mod test {
             ]
         )
     }
+
+    #[tokio::test]
+    async fn test_collect_stops_at_quorum() {
+        let pending = FuturesUnordered::from_iter([
+            ready_sub_module(0, 5),
+            never_ready_sub_module(),
+            ready_sub_module(2, 1),
+        ]);
+        let (metas_and_gas, err_isms) =
+            AggregationIsmMetadataBuilder::collect_until_quorum(pending, 2).await;
+        assert_eq!(metas_and_gas.len(), 2);
+        assert!(err_isms.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_collect_includes_ready_sub_modules_after_quorum() {
+        let pending = FuturesUnordered::from_iter([
+            ready_sub_module(0, 5),
+            ready_sub_module(1, 3),
+            ready_sub_module(2, 1),
+        ]);
+        let (metas_and_gas, _) =
+            AggregationIsmMetadataBuilder::collect_until_quorum(pending, 1).await;
+        assert_eq!(metas_and_gas.len(), 3);
+        assert_eq!(
+            AggregationIsmMetadataBuilder::n_cheapest_metas(metas_and_gas, 1),
+            vec![SubModuleMetadata::new(2, vec![])]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_collect_stops_once_quorum_is_unreachable() {
+        let pending = FuturesUnordered::from_iter([
+            failing_sub_module(),
+            never_ready_sub_module(),
+            failing_sub_module(),
+        ]);
+        let (metas_and_gas, err_isms) =
+            AggregationIsmMetadataBuilder::collect_until_quorum(pending, 2).await;
+        assert!(metas_and_gas.is_empty());
+        assert_eq!(err_isms.len(), 2);
+    }
 }
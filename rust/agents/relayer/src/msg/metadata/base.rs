@@ -11,9 +11,10 @@ use crate::{
     merkle_tree::builder::MerkleTreeBuilder,
     msg::metadata::{
         multisig::{MerkleRootMultisigMetadataBuilder, MessageIdMultisigMetadataBuilder},
-        AggregationIsmMetadataBuilder, CcipReadIsmMetadataBuilder, NullMetadataBuilder,
-        RoutingIsmMetadataBuilder,
+        AggregationIsmMetadataBuilder, CcipReadGateways, CcipReadIsmMetadataBuilder,
+        NullMetadataBuilder, RoutingIsmMetadataBuilder,
     },
+    msg::policies::PolicyStore,
     settings::matching_list::MatchingList,
 };
 use async_trait::async_trait;
@@ -22,7 +23,7 @@ use eyre::{Context, Result};
 use hyperlane_base::db::HyperlaneRocksDB;
 use hyperlane_base::{
     settings::{ChainConf, CheckpointSyncerConf},
-    CheckpointSyncer, CoreMetrics, MultisigCheckpointSyncer,
+    CheckpointSyncer, CoreMetrics, MirroredCheckpointSyncer, MultisigCheckpointSyncer,
 };
 use hyperlane_core::{
     accumulator::merkle::Proof, AggregationIsm, CcipReadIsm, Checkpoint, HyperlaneDomain,
@@ -107,17 +108,15 @@ impl DefaultIsmCache {
 #[derive(Debug)]
 pub struct IsmAwareAppContextClassifier {
     default_ism: DefaultIsmCache,
-    app_context_classifier: AppContextClassifier,
+    /// The app contexts are read from the relayer policies, so they can be reloaded
+    policies: PolicyStore,
 }
 
 impl IsmAwareAppContextClassifier {
-    pub fn new(
-        destination_mailbox: Arc<dyn Mailbox>,
-        app_matching_lists: Vec<(MatchingList, String)>,
-    ) -> Self {
+    pub fn new(destination_mailbox: Arc<dyn Mailbox>, policies: PolicyStore) -> Self {
         Self {
             default_ism: DefaultIsmCache::new(destination_mailbox),
-            app_context_classifier: AppContextClassifier::new(app_matching_lists),
+            policies,
         }
     }
 
@@ -126,7 +125,10 @@ impl IsmAwareAppContextClassifier {
         message: &HyperlaneMessage,
         root_ism: H256,
     ) -> Result<Option<String>> {
-        if let Some(app_context) = self.app_context_classifier.get_app_context(message).await? {
+        let policies = self.policies.current().await;
+        let app_context_classifier =
+            AppContextClassifier::new(policies.conf.metric_app_contexts.clone());
+        if let Some(app_context) = app_context_classifier.get_app_context(message).await? {
             return Ok(Some(app_context));
         }
 
@@ -215,7 +217,7 @@ impl MessageMetadataBuilder {
         })
     }
 
-    fn clone_with_incremented_depth(&self) -> Result<MessageMetadataBuilder> {
+    pub(crate) fn clone_with_incremented_depth(&self) -> Result<MessageMetadataBuilder> {
         let mut cloned = self.clone();
         cloned.depth += 1;
         if cloned.depth > cloned.max_depth {
@@ -225,21 +227,12 @@ impl MessageMetadataBuilder {
         }
     }
 
-    #[instrument(err, skip(self), fields(destination_domain=self.destination_domain().name()), ret)]
-    pub async fn build_ism_and_metadata(
+    /// Returns the builder for the metadata of a module of the given type, one level
+    /// deeper in the ISM tree
+    pub(crate) fn metadata_builder_for(
         &self,
-        ism_address: H256,
-        message: &HyperlaneMessage,
-    ) -> Result<IsmWithMetadataAndType> {
-        let ism: Box<dyn InterchainSecurityModule> = self
-            .build_ism(ism_address)
-            .await
-            .context("When building ISM")?;
-
-        let module_type = ism
-            .module_type()
-            .await
-            .context("When fetching module type")?;
+        module_type: ModuleType,
+    ) -> Result<Box<dyn MetadataBuilder>> {
         let cloned = self.clone_with_incremented_depth()?;
 
         let metadata_builder: Box<dyn MetadataBuilder> = match module_type {
@@ -255,6 +248,25 @@ impl MessageMetadataBuilder {
             ModuleType::CcipRead => Box::new(CcipReadIsmMetadataBuilder::new(cloned)),
             _ => return Err(MetadataBuilderError::UnsupportedModuleType(module_type).into()),
         };
+        Ok(metadata_builder)
+    }
+
+    #[instrument(err, skip(self), fields(destination_domain=self.destination_domain().name()), ret)]
+    pub async fn build_ism_and_metadata(
+        &self,
+        ism_address: H256,
+        message: &HyperlaneMessage,
+    ) -> Result<IsmWithMetadataAndType> {
+        let ism: Box<dyn InterchainSecurityModule> = self
+            .build_ism(ism_address)
+            .await
+            .context("When building ISM")?;
+
+        let module_type = ism
+            .module_type()
+            .await
+            .context("When fetching module type")?;
+        let metadata_builder = self.metadata_builder_for(module_type)?;
         let meta = metadata_builder
             .build(ism_address, message)
             .await
@@ -279,6 +291,7 @@ pub struct BaseMetadataBuilder {
     metrics: Arc<CoreMetrics>,
     db: HyperlaneRocksDB,
     app_context_classifier: IsmAwareAppContextClassifier,
+    ccip_read_gateways: Arc<CcipReadGateways>,
     #[new(value = "7")]
     max_depth: u32,
 }
@@ -302,6 +315,10 @@ impl BaseMetadataBuilder {
         &self.destination_chain_setup.domain
     }
 
+    pub fn ccip_read_gateways(&self) -> &CcipReadGateways {
+        &self.ccip_read_gateways
+    }
+
     pub async fn get_proof(&self, leaf_index: u32, checkpoint: Checkpoint) -> Result<Proof> {
         const CTX: &str = "When fetching message proof";
         let proof = self
@@ -372,9 +389,10 @@ impl BaseMetadataBuilder {
             .get_announced_storage_locations(validators)
             .await?;
 
-        // Only use the most recently announced location for now.
+        // Read from all announced locations, failing over from the most recently announced one
         let mut checkpoint_syncers: HashMap<H160, Arc<dyn CheckpointSyncer>> = HashMap::new();
         for (&validator, validator_storage_locations) in validators.iter().zip(storage_locations) {
+            let mut validator_syncers: Vec<Arc<dyn CheckpointSyncer>> = vec![];
             for storage_location in validator_storage_locations.iter().rev() {
                 let Ok(config) = CheckpointSyncerConf::from_str(storage_location) else {
                     debug!(
@@ -398,11 +416,7 @@ impl BaseMetadataBuilder {
                 }
 
                 match config.build(None).await {
-                    Ok(checkpoint_syncer) => {
-                        // found the syncer for this validator
-                        checkpoint_syncers.insert(validator.into(), checkpoint_syncer.into());
-                        break;
-                    }
+                    Ok(checkpoint_syncer) => validator_syncers.push(checkpoint_syncer.into()),
                     Err(err) => {
                         debug!(
                             error=%err,
@@ -413,7 +427,7 @@ impl BaseMetadataBuilder {
                     }
                 }
             }
-            if checkpoint_syncers.get(&validator.into()).is_none() {
+            if validator_syncers.is_empty() {
                 if validator_storage_locations.is_empty() {
                     warn!(?validator, "Validator has not announced any storage locations; see https://docs.hyperlane.xyz/docs/operators/validators/announcing-your-validator");
                 } else {
@@ -423,7 +437,11 @@ impl BaseMetadataBuilder {
                         "No valid checkpoint syncer configs for validator"
                     );
                 }
+                continue;
             }
+            let checkpoint_syncer = MirroredCheckpointSyncer::new(validator_syncers)?
+                .with_expected_signer(validator.into());
+            checkpoint_syncers.insert(validator.into(), Arc::new(checkpoint_syncer));
         }
         Ok(MultisigCheckpointSyncer::new(
             checkpoint_syncers,
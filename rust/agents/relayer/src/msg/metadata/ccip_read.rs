@@ -1,17 +1,27 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
 use async_trait::async_trait;
 use derive_more::Deref;
 use derive_new::new;
 use ethers::{abi::AbiDecode, core::utils::hex::decode as hex_decode};
 use eyre::Context;
+use hyperlane_base::CoreMetrics;
 use hyperlane_core::{utils::bytes_to_hex, HyperlaneMessage, RawHyperlaneMessage, H256};
 use hyperlane_ethereum::OffchainLookup;
+use prometheus::{IntCounterVec, IntGaugeVec};
 use regex::Regex;
-use reqwest::Client;
+use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use tracing::{info, instrument};
+use thiserror::Error;
+use tracing::{debug, info, instrument, warn};
 
 use super::{base::MessageMetadataBuilder, MetadataBuilder};
+use crate::settings::CcipReadGatewayConf;
 
 #[derive(Serialize, Deserialize)]
 struct OffchainResponse {
@@ -37,56 +47,170 @@ impl MetadataBuilder for CcipReadIsmMetadataBuilder {
         let response = ism
             .get_offchain_verify_info(RawHyperlaneMessage::from(message).to_vec())
             .await;
-        let info: OffchainLookup = match response {
+        let info = match response {
             Ok(_) => {
                 info!("incorrectly configured getOffchainVerifyInfo, expected revert");
                 return Ok(None);
             }
-            Err(raw_error) => {
-                let matching_regex = Regex::new(r"0x[[:xdigit:]]+")?;
-                if let Some(matching) = &matching_regex.captures(&raw_error.to_string()) {
-                    OffchainLookup::decode(hex_decode(&matching[0][2..])?)?
-                } else {
+            Err(raw_error) => match decode_offchain_lookup(&raw_error.to_string()) {
+                Some(info) => info,
+                None => {
                     info!("unable to parse custom error out of revert");
                     return Ok(None);
                 }
-            }
+            },
         };
 
-        for url in info.urls.iter() {
-            // Need to explicitly convert the sender H160 the hex because the `ToString` implementation
-            // for `H160` truncates the output. (e.g. `0xc66a…7b6f` instead of returning
-            // the full address)
-            let sender_as_bytes = &bytes_to_hex(info.sender.as_bytes());
-            let data_as_bytes = &info.call_data.to_string();
-            let interpolated_url = url
-                .replace("{sender}", sender_as_bytes)
-                .replace("{data}", data_as_bytes);
-            let res = if !url.contains("{data}") {
-                let body = json!({
-                    "sender": sender_as_bytes,
-                    "data": data_as_bytes
-                });
-                Client::new()
-                    .post(interpolated_url)
-                    .header("Content-Type", "application/json")
-                    .json(&body)
-                    .send()
-                    .await?
-            } else {
-                reqwest::get(interpolated_url).await?
-            };
+        // EIP-3668 requires the lookup to come from the contract that was called
+        if info.sender.as_bytes() != &ism_address.as_bytes()[12..] {
+            warn!(
+                sender = ?info.sender,
+                "OffchainLookup sender is not the ISM, refusing to query its gateways"
+            );
+            return Ok(None);
+        }
+
+        self.ccip_read_gateways()
+            .fetch(message.id(), ism_address, &info)
+            .await
+    }
+}
+
+/// Finds the `OffchainLookup` error in a stringified revert. The revert data is the
+/// first hex string in the error that decodes as an `OffchainLookup`.
+fn decode_offchain_lookup(revert: &str) -> Option<OffchainLookup> {
+    let hex_regex = Regex::new(r"0x[[:xdigit:]]+").expect("valid regex");
+    hex_regex.find_iter(revert).find_map(|matching| {
+        let bytes = hex_decode(&matching.as_str()[2..]).ok()?;
+        OffchainLookup::decode(bytes).ok()
+    })
+}
+
+/// Queries the gateways of CCIP-read ISMs as specified by EIP-3668. It is shared by
+/// all metadata builders, so a gateway that is down is skipped for every message
+/// instead of stalling each of them until the request times out.
+#[derive(Debug)]
+pub struct CcipReadGateways {
+    client: Client,
+    conf: CcipReadGatewayConf,
+    /// Circuit breakers by gateway
+    breakers: Mutex<HashMap<String, CircuitBreaker>>,
+    /// Gateway responses by message id and ISM address
+    cache: Mutex<HashMap<(H256, H256), CachedResponse>>,
+    requests: IntCounterVec,
+    circuit_open: IntGaugeVec,
+}
+
+#[derive(Debug, Default)]
+struct CircuitBreaker {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+#[derive(Debug)]
+struct CachedResponse {
+    fetched_at: Instant,
+    metadata: Vec<u8>,
+}
+
+#[derive(Debug, Error)]
+enum GatewayError {
+    #[error("Request timed out")]
+    Timeout,
+    #[error("Request failed: {0}")]
+    Request(reqwest::Error),
+    #[error("Gateway rejected the request with status {0}")]
+    Rejected(StatusCode),
+    #[error("Gateway failed with status {0}")]
+    Server(StatusCode),
+    #[error("Invalid gateway response: {0}")]
+    InvalidResponse(String),
+}
+
+impl GatewayError {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Timeout => "timeout",
+            Self::Request(_) => "request_error",
+            Self::Rejected(_) => "rejected",
+            Self::Server(_) => "server_error",
+            Self::InvalidResponse(_) => "invalid_response",
+        }
+    }
+}
+
+impl CcipReadGateways {
+    pub fn new(metrics: &CoreMetrics, conf: CcipReadGatewayConf) -> eyre::Result<Self> {
+        Ok(Self {
+            client: Client::new(),
+            conf,
+            breakers: Default::default(),
+            cache: Default::default(),
+            requests: metrics.new_int_counter(
+                "ccip_read_gateway_requests",
+                "Number of requests to CCIP-read gateways, by result",
+                &["gateway", "result"],
+            )?,
+            circuit_open: metrics.new_int_gauge(
+                "ccip_read_gateway_circuit_open",
+                "Whether a CCIP-read gateway is being skipped after repeated failures",
+                &["gateway"],
+            )?,
+        })
+    }
+
+    /// Fetches the metadata for the message from the gateways in `lookup`, in order.
+    /// Returns `None` if no gateway returned metadata.
+    pub async fn fetch(
+        &self,
+        message_id: H256,
+        ism_address: H256,
+        lookup: &OffchainLookup,
+    ) -> eyre::Result<Option<Vec<u8>>> {
+        if let Some(metadata) = self.cached(message_id, ism_address) {
+            debug!(?message_id, "Using cached CCIP-read gateway response");
+            return Ok(Some(metadata));
+        }
 
-            let json: Result<OffchainResponse, reqwest::Error> = res.json().await;
+        // Need to explicitly convert the sender H160 the hex because the `ToString` implementation
+        // for `H160` truncates the output. (e.g. `0xc66a…7b6f` instead of returning
+        // the full address)
+        let sender = bytes_to_hex(lookup.sender.as_bytes());
+        let data = lookup.call_data.to_string();
+        for url in lookup.urls.iter() {
+            let gateway = gateway_name(url);
+            if !self.allows_request(&gateway) {
+                self.requests
+                    .with_label_values(&[gateway.as_str(), "circuit_open"])
+                    .inc();
+                continue;
+            }
 
-            match json {
-                Ok(result) => {
-                    // remove leading 0x which hex_decode doesn't like
-                    let metadata = hex_decode(&result.data[2..])?;
+            match self.request(url, &sender, &data).await {
+                Ok(metadata) => {
+                    self.requests
+                        .with_label_values(&[gateway.as_str(), "success"])
+                        .inc();
+                    self.record_success(&gateway);
+                    self.cache_response(message_id, ism_address, metadata.clone());
                     return Ok(Some(metadata));
                 }
-                Err(_err) => {
-                    // try the next URL
+                // A 4xx response means the request itself is bad, so EIP-3668 says not to
+                // try the other gateways. The gateway is up, so it isn't a failure.
+                Err(err @ GatewayError::Rejected(_)) => {
+                    self.requests
+                        .with_label_values(&[gateway.as_str(), err.label()])
+                        .inc();
+                    self.record_success(&gateway);
+                    info!(%gateway, %err, "CCIP-read gateway rejected the request");
+                    return Ok(None);
+                }
+                Err(err) => {
+                    self.requests
+                        .with_label_values(&[gateway.as_str(), err.label()])
+                        .inc();
+                    self.record_failure(&gateway);
+                    warn!(%gateway, %err, "CCIP-read gateway request failed, trying the next one");
                 }
             }
         }
@@ -94,4 +218,340 @@ impl MetadataBuilder for CcipReadIsmMetadataBuilder {
         // No metadata endpoints or endpoints down
         Ok(None)
     }
+
+    async fn request(&self, url: &str, sender: &str, data: &str) -> Result<Vec<u8>, GatewayError> {
+        let interpolated_url = url.replace("{sender}", sender).replace("{data}", data);
+        let request = if url.contains("{data}") {
+            self.client.get(interpolated_url)
+        } else {
+            self.client
+                .post(interpolated_url)
+                .json(&json!({ "sender": sender, "data": data }))
+        };
+
+        let res = request
+            .timeout(self.conf.request_timeout)
+            .send()
+            .await
+            .map_err(request_error)?;
+        let status = res.status();
+        if status.is_client_error() {
+            return Err(GatewayError::Rejected(status));
+        }
+        if !status.is_success() {
+            return Err(GatewayError::Server(status));
+        }
+
+        let response: OffchainResponse = res.json().await.map_err(|err| {
+            if err.is_timeout() {
+                GatewayError::Timeout
+            } else {
+                GatewayError::InvalidResponse(err.to_string())
+            }
+        })?;
+        let data = response.data.strip_prefix("0x").unwrap_or(&response.data);
+        hex_decode(data).map_err(|err| GatewayError::InvalidResponse(err.to_string()))
+    }
+
+    fn cached(&self, message_id: H256, ism_address: H256) -> Option<Vec<u8>> {
+        let cache = self
+            .cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        cache
+            .get(&(message_id, ism_address))
+            .filter(|cached| cached.fetched_at.elapsed() < self.conf.cache_ttl)
+            .map(|cached| cached.metadata.clone())
+    }
+
+    fn cache_response(&self, message_id: H256, ism_address: H256, metadata: Vec<u8>) {
+        let mut cache = self
+            .cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        cache.retain(|_, cached| cached.fetched_at.elapsed() < self.conf.cache_ttl);
+        cache.insert(
+            (message_id, ism_address),
+            CachedResponse {
+                fetched_at: Instant::now(),
+                metadata,
+            },
+        );
+    }
+
+    /// Whether the gateway's circuit is closed, or its cooldown has passed and it
+    /// should be tried again
+    fn allows_request(&self, gateway: &str) -> bool {
+        let breakers = self
+            .breakers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        breakers
+            .get(gateway)
+            .and_then(|breaker| breaker.open_until)
+            .map_or(true, |open_until| Instant::now() >= open_until)
+    }
+
+    fn record_success(&self, gateway: &str) {
+        let mut breakers = self
+            .breakers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        breakers.remove(gateway);
+        self.circuit_open.with_label_values(&[gateway]).set(0);
+    }
+
+    fn record_failure(&self, gateway: &str) {
+        let mut breakers = self
+            .breakers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let breaker = breakers.entry(gateway.to_owned()).or_default();
+        breaker.consecutive_failures += 1;
+        if breaker.consecutive_failures >= self.conf.failure_threshold {
+            warn!(
+                gateway,
+                consecutive_failures = breaker.consecutive_failures,
+                cooldown = ?self.conf.cooldown,
+                "Skipping CCIP-read gateway after repeated failures"
+            );
+            breaker.open_until = Some(Instant::now() + self.conf.cooldown);
+            self.circuit_open.with_label_values(&[gateway]).set(1);
+        }
+    }
+}
+
+fn request_error(err: reqwest::Error) -> GatewayError {
+    if err.is_timeout() {
+        GatewayError::Timeout
+    } else {
+        GatewayError::Request(err)
+    }
+}
+
+/// Identifies a gateway by its URL template. The query is left out, as it may hold
+/// credentials that shouldn't end up in logs or metrics.
+fn gateway_name(url: &str) -> String {
+    url.split('?').next().unwrap_or(url).to_owned()
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        net::SocketAddr,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    use axum::{
+        extract::{Path, State},
+        http::StatusCode as AxumStatusCode,
+        routing, Json, Router,
+    };
+    use ethers::{abi::AbiEncode, types::Bytes};
+    use prometheus::Registry;
+    use serde_json::Value;
+
+    use super::*;
+
+    /// Number of requests received by each route of the stand-in gateway
+    #[derive(Default)]
+    struct Hits {
+        ok: AtomicUsize,
+        failing: AtomicUsize,
+    }
+
+    /// Local stand-in for a CCIP-read gateway
+    fn setup_test_gateway() -> (SocketAddr, Arc<Hits>) {
+        async fn get_ok(
+            State(hits): State<Arc<Hits>>,
+            Path((_sender, data)): Path<(String, String)>,
+        ) -> Json<Value> {
+            hits.ok.fetch_add(1, Ordering::SeqCst);
+            Json(json!({ "data": data }))
+        }
+        async fn post_ok(State(hits): State<Arc<Hits>>, Json(body): Json<Value>) -> Json<Value> {
+            hits.ok.fetch_add(1, Ordering::SeqCst);
+            Json(json!({ "data": body["data"] }))
+        }
+        async fn failing(State(hits): State<Arc<Hits>>) -> AxumStatusCode {
+            hits.failing.fetch_add(1, Ordering::SeqCst);
+            AxumStatusCode::INTERNAL_SERVER_ERROR
+        }
+        async fn rejecting() -> AxumStatusCode {
+            AxumStatusCode::BAD_REQUEST
+        }
+        async fn slow() -> Json<Value> {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Json(json!({ "data": "0x" }))
+        }
+
+        let hits = Arc::new(Hits::default());
+        let app = Router::new()
+            .route("/get/:sender/:data", routing::get(get_ok))
+            .route("/post", routing::post(post_ok))
+            .route("/failing/:sender/:data", routing::get(failing))
+            .route("/rejecting/:sender/:data", routing::get(rejecting))
+            .route("/slow/:sender/:data", routing::get(slow))
+            .with_state(hits.clone());
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        (addr, hits)
+    }
+
+    fn gateways() -> CcipReadGateways {
+        let metrics = CoreMetrics::new("dummy_relayer", 37582, Registry::new()).unwrap();
+        CcipReadGateways::new(
+            &metrics,
+            CcipReadGatewayConf {
+                request_timeout: Duration::from_millis(200),
+                failure_threshold: 2,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+    }
+
+    fn lookup(addr: SocketAddr, routes: &[&str]) -> OffchainLookup {
+        OffchainLookup {
+            sender: Default::default(),
+            urls: routes
+                .iter()
+                .map(|route| format!("http://{addr}{route}"))
+                .collect(),
+            call_data: Bytes::from(vec![0x12, 0x34]),
+            callback_function: [0; 4],
+            extra_data: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_and_post_requests() {
+        let (addr, hits) = setup_test_gateway();
+        let gateways = gateways();
+
+        let get = lookup(addr, &["/get/{sender}/{data}"]);
+        let metadata = gateways
+            .fetch(H256::repeat_byte(1), H256::zero(), &get)
+            .await
+            .unwrap();
+        assert_eq!(metadata, Some(vec![0x12, 0x34]));
+
+        // URLs without `{data}` are POSTed to
+        let post = lookup(addr, &["/post"]);
+        let metadata = gateways
+            .fetch(H256::repeat_byte(2), H256::zero(), &post)
+            .await
+            .unwrap();
+        assert_eq!(metadata, Some(vec![0x12, 0x34]));
+        assert_eq!(hits.ok.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_skips_failing_gateway() {
+        let (addr, hits) = setup_test_gateway();
+        let gateways = gateways();
+        let lookup = lookup(addr, &["/failing/{sender}/{data}", "/get/{sender}/{data}"]);
+
+        for i in 1..=4 {
+            let metadata = gateways
+                .fetch(H256::repeat_byte(i), H256::zero(), &lookup)
+                .await
+                .unwrap();
+            assert_eq!(metadata, Some(vec![0x12, 0x34]));
+        }
+        // The circuit opens after two failures
+        assert_eq!(hits.failing.load(Ordering::SeqCst), 2);
+        assert_eq!(hits.ok.load(Ordering::SeqCst), 4);
+        let gateway = format!("http://{addr}/failing/{{sender}}/{{data}}");
+        assert_eq!(
+            gateways
+                .requests
+                .with_label_values(&[gateway.as_str(), "circuit_open"])
+                .get(),
+            2
+        );
+        assert_eq!(
+            gateways
+                .circuit_open
+                .with_label_values(&[gateway.as_str()])
+                .get(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_client_error_stops_lookup() {
+        let (addr, hits) = setup_test_gateway();
+        let lookup = lookup(
+            addr,
+            &["/rejecting/{sender}/{data}", "/get/{sender}/{data}"],
+        );
+
+        let metadata = gateways()
+            .fetch(H256::repeat_byte(1), H256::zero(), &lookup)
+            .await
+            .unwrap();
+        assert_eq!(metadata, None);
+        assert_eq!(hits.ok.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_times_out_slow_gateway() {
+        let (addr, _) = setup_test_gateway();
+        let gateways = gateways();
+        let lookup = lookup(addr, &["/slow/{sender}/{data}", "/get/{sender}/{data}"]);
+
+        let metadata = gateways
+            .fetch(H256::repeat_byte(1), H256::zero(), &lookup)
+            .await
+            .unwrap();
+        assert_eq!(metadata, Some(vec![0x12, 0x34]));
+        assert_eq!(
+            gateways
+                .requests
+                .with_label_values(&[
+                    format!("http://{addr}/slow/{{sender}}/{{data}}").as_str(),
+                    "timeout",
+                ])
+                .get(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_caches_responses_by_message() {
+        let (addr, hits) = setup_test_gateway();
+        let gateways = gateways();
+        let lookup = lookup(addr, &["/get/{sender}/{data}"]);
+
+        for _ in 0..2 {
+            gateways
+                .fetch(H256::repeat_byte(1), H256::zero(), &lookup)
+                .await
+                .unwrap();
+        }
+        assert_eq!(hits.ok.load(Ordering::SeqCst), 1);
+
+        gateways
+            .fetch(H256::repeat_byte(2), H256::zero(), &lookup)
+            .await
+            .unwrap();
+        assert_eq!(hits.ok.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_decode_offchain_lookup() {
+        let expected = lookup(([127, 0, 0, 1], 1).into(), &["/get/{sender}/{data}"]);
+        let revert = format!(
+            "Contract call reverted with data: 0xdeadbeef, custom error: 0x{}",
+            ethers::core::utils::hex::encode(expected.clone().encode())
+        );
+        assert_eq!(decode_offchain_lookup(&revert), Some(expected));
+        assert_eq!(decode_offchain_lookup("execution reverted: 0x1234"), None);
+    }
 }
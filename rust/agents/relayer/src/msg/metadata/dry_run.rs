@@ -0,0 +1,438 @@
+//! Dry runs of metadata building, which explain how the metadata for a message is
+//! built from the recipient's ISM tree, or why it couldn't be built.
+
+use std::sync::Arc;
+
+use eyre::{Context, Result};
+use futures::future::{join_all, BoxFuture, FutureExt};
+use hyperlane_core::{utils::bytes_to_hex, HyperlaneMessage, ModuleType, H160, H256, U256};
+use serde::{Serialize, Serializer};
+
+use super::{
+    aggregation::SubModuleMetadata, AggregationIsmMetadataBuilder, BaseMetadataBuilder,
+    MessageMetadataBuilder, MetadataBuilder,
+};
+
+/// Result of building the metadata for a message without submitting it
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetadataDryRun {
+    pub message_id: H256,
+    /// The recipient's ISM and its sub-modules
+    pub ism: IsmExplanation,
+    /// The hex-encoded metadata the relayer would submit with the message
+    #[serde(serialize_with = "serialize_hex")]
+    pub metadata: Option<Vec<u8>>,
+    /// Why the metadata could not be built
+    pub error: Option<String>,
+}
+
+/// A module of the ISM tree
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IsmExplanation {
+    pub address: H256,
+    pub module_type: Option<ModuleType>,
+    pub details: Option<ModuleDetails>,
+    /// The hex-encoded metadata. Routing and aggregation ISMs have the metadata
+    /// derived from their sub-modules.
+    #[serde(serialize_with = "serialize_hex")]
+    pub metadata: Option<Vec<u8>>,
+    /// Gas used to verify the metadata, for sub-modules of aggregation ISMs
+    pub verification_gas: Option<U256>,
+    /// Why the module couldn't be inspected or its metadata couldn't be built
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ModuleDetails {
+    Routing {
+        /// The module the message is routed to
+        route: Box<IsmExplanation>,
+    },
+    Aggregation {
+        threshold: u8,
+        modules: Vec<IsmExplanation>,
+    },
+    Multisig(MultisigDetails),
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultisigDetails {
+    pub threshold: u8,
+    pub validators: Vec<ValidatorCheckpoints>,
+    /// Index of the message in the origin merkle tree, if the relayer has indexed it
+    pub merkle_leaf_index: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorCheckpoints {
+    pub validator: H160,
+    /// Whether the validator announced a checkpoint syncer the relayer can read
+    pub has_checkpoint_syncer: bool,
+    /// Index of the latest checkpoint the validator signed
+    pub latest_checkpoint_index: Option<u32>,
+}
+
+/// Builds the metadata for `message` the same way the relayer does before
+/// submitting it, explaining each module of the ISM tree along the way
+pub async fn dry_run_metadata(
+    base: Arc<BaseMetadataBuilder>,
+    ism_address: H256,
+    message: &HyperlaneMessage,
+) -> Result<MetadataDryRun> {
+    let builder = MessageMetadataBuilder::new(ism_address, message, base).await?;
+    // The metadata of the root module is the metadata for the message
+    let ism = explain(&builder, ism_address, message, false).await;
+    let error = ism
+        .metadata
+        .is_none()
+        .then(|| "Could not build metadata, see the ISM explanation for why".to_owned());
+    Ok(MetadataDryRun {
+        message_id: message.id(),
+        metadata: ism.metadata.clone(),
+        ism,
+        error,
+    })
+}
+
+/// Explains the module at `ism_address`. Sub-modules of aggregation ISMs are
+/// `verified`, as the aggregation metadata builder only uses the metadata of
+/// sub-modules that pass verification.
+fn explain<'a>(
+    builder: &'a MessageMetadataBuilder,
+    ism_address: H256,
+    message: &'a HyperlaneMessage,
+    verify: bool,
+) -> BoxFuture<'a, IsmExplanation> {
+    async move {
+        let mut explanation = IsmExplanation {
+            address: ism_address,
+            module_type: None,
+            details: None,
+            metadata: None,
+            verification_gas: None,
+            error: None,
+        };
+        if let Err(err) =
+            explain_module(builder, ism_address, message, verify, &mut explanation).await
+        {
+            explanation.error = Some(format!("{err:#}"));
+        }
+        explanation
+    }
+    .boxed()
+}
+
+async fn explain_module(
+    builder: &MessageMetadataBuilder,
+    ism_address: H256,
+    message: &HyperlaneMessage,
+    verify: bool,
+    explanation: &mut IsmExplanation,
+) -> Result<()> {
+    let ism = builder
+        .build_ism(ism_address)
+        .await
+        .context("When building ISM")?;
+    let module_type = ism
+        .module_type()
+        .await
+        .context("When fetching module type")?;
+    explanation.module_type = Some(module_type);
+
+    match module_type {
+        ModuleType::Routing => {
+            let route = builder
+                .build_routing_ism(ism_address)
+                .await?
+                .route(message)
+                .await
+                .context("When routing message")?;
+            let cloned = builder.clone_with_incremented_depth()?;
+            explanation.details = Some(ModuleDetails::Routing {
+                route: Box::new(explain(&cloned, route, message, false).await),
+            });
+        }
+        ModuleType::Aggregation => {
+            let (modules, threshold) = builder
+                .build_aggregation_ism(ism_address)
+                .await?
+                .modules_and_threshold(message)
+                .await
+                .context("When fetching modules and threshold")?;
+            let cloned = builder.clone_with_incremented_depth()?;
+            let modules = join_all(
+                modules
+                    .into_iter()
+                    .map(|module| explain(&cloned, module, message, true)),
+            )
+            .await;
+            explanation.details = Some(ModuleDetails::Aggregation { threshold, modules });
+        }
+        _ => {
+            if matches!(
+                module_type,
+                ModuleType::MerkleRootMultisig | ModuleType::MessageIdMultisig
+            ) {
+                let details = explain_multisig(builder, ism_address, message).await?;
+                explanation.details = Some(ModuleDetails::Multisig(details));
+            }
+            explanation.metadata = builder
+                .metadata_builder_for(module_type)?
+                .build(ism_address, message)
+                .await?;
+        }
+    }
+    resolve_metadata(explanation);
+
+    if let (true, Some(metadata)) = (verify, &explanation.metadata) {
+        match ism
+            .dry_run_verify(message, metadata)
+            .await
+            .context("When dry running verification")?
+        {
+            Some(gas) => explanation.verification_gas = Some(gas),
+            None => explanation.error = Some("Metadata failed verification".to_owned()),
+        }
+    }
+    Ok(())
+}
+
+/// Derives the metadata of a routing or aggregation module from the metadata of its
+/// sub-modules, the same way their metadata builders do, and explains why the module
+/// has no metadata if it doesn't. The sub-modules must have been resolved already.
+fn resolve_metadata(explanation: &mut IsmExplanation) {
+    let derived = match &explanation.details {
+        Some(ModuleDetails::Routing { route }) => Some(route.metadata.clone()),
+        Some(ModuleDetails::Aggregation { threshold, modules }) => {
+            Some(aggregation_metadata(*threshold, modules))
+        }
+        _ => None,
+    };
+    if let Some(metadata) = derived {
+        explanation.metadata = metadata;
+    }
+    if let (None, Some(module_type)) = (&explanation.metadata, explanation.module_type) {
+        explanation.error = Some(missing_metadata_reason(
+            module_type,
+            explanation.details.as_ref(),
+        ));
+    }
+}
+
+/// Formats the metadata of the cheapest `threshold` sub-modules that passed verification
+fn aggregation_metadata(threshold: u8, modules: &[IsmExplanation]) -> Option<Vec<u8>> {
+    let metas_and_gas: Vec<_> = modules
+        .iter()
+        .enumerate()
+        .filter_map(|(index, module)| {
+            Some((
+                SubModuleMetadata::new(index, module.metadata.clone()?),
+                module.verification_gas?,
+            ))
+        })
+        .collect();
+    let threshold = threshold as usize;
+    if metas_and_gas.len() < threshold {
+        return None;
+    }
+    let mut metas = AggregationIsmMetadataBuilder::n_cheapest_metas(metas_and_gas, threshold);
+    Some(AggregationIsmMetadataBuilder::format_metadata(
+        &mut metas,
+        modules.len(),
+    ))
+}
+
+fn serialize_hex<S: Serializer>(bytes: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error> {
+    bytes.as_deref().map(bytes_to_hex).serialize(serializer)
+}
+
+async fn explain_multisig(
+    builder: &MessageMetadataBuilder,
+    ism_address: H256,
+    message: &HyperlaneMessage,
+) -> Result<MultisigDetails> {
+    let (validators, threshold) = builder
+        .build_multisig_ism(ism_address)
+        .await?
+        .validators_and_threshold(message)
+        .await
+        .context("When fetching validators and threshold")?;
+    let checkpoint_syncer = builder
+        .build_checkpoint_syncer(&validators, builder.app_context.clone())
+        .await?;
+    let latest_indices = checkpoint_syncer
+        .get_validator_latest_checkpoints(&validators)
+        .await;
+    let merkle_leaf_index = builder
+        .get_merkle_leaf_id_by_message_id(message.id())
+        .await?;
+
+    Ok(MultisigDetails {
+        threshold,
+        validators: validators
+            .into_iter()
+            .map(|validator| {
+                let validator = H160::from(validator);
+                ValidatorCheckpoints {
+                    validator,
+                    has_checkpoint_syncer: latest_indices.contains_key(&validator),
+                    latest_checkpoint_index: latest_indices.get(&validator).copied().flatten(),
+                }
+            })
+            .collect(),
+        merkle_leaf_index,
+    })
+}
+
+fn missing_metadata_reason(module_type: ModuleType, details: Option<&ModuleDetails>) -> String {
+    match (module_type, details) {
+        (_, Some(ModuleDetails::Routing { .. })) => {
+            "The module the message is routed to has no metadata".to_owned()
+        }
+        (_, Some(ModuleDetails::Aggregation { threshold, modules })) => {
+            let verified = modules
+                .iter()
+                .filter(|module| module.verification_gas.is_some())
+                .count();
+            format!(
+                "Only {verified} of the {threshold} sub-modules required have metadata that passes verification"
+            )
+        }
+        (_, Some(ModuleDetails::Multisig(multisig))) => {
+            if multisig.validators.is_empty() {
+                return "No validator set found for ISM".to_owned();
+            }
+            let Some(leaf_index) = multisig.merkle_leaf_index else {
+                return "Message has not been indexed in the origin merkle tree yet".to_owned();
+            };
+            let signed = multisig
+                .validators
+                .iter()
+                .filter(|validator| {
+                    validator
+                        .latest_checkpoint_index
+                        .map_or(false, |index| index >= leaf_index)
+                })
+                .count();
+            if signed < multisig.threshold as usize {
+                format!(
+                    "Only {signed} of the {} validators required have signed a checkpoint including the message",
+                    multisig.threshold
+                )
+            } else {
+                "Unable to reach quorum, validators signed conflicting checkpoints or their signatures could not be fetched".to_owned()
+            }
+        }
+        (ModuleType::CcipRead, _) => "No CCIP-read gateway returned metadata".to_owned(),
+        _ => "Could not build metadata".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn module(module_type: ModuleType, details: ModuleDetails) -> IsmExplanation {
+        IsmExplanation {
+            address: H256::zero(),
+            module_type: Some(module_type),
+            details: Some(details),
+            metadata: None,
+            verification_gas: None,
+            error: None,
+        }
+    }
+
+    /// A message id multisig ISM that is a sub-module of an aggregation ISM. It has
+    /// verified metadata if `signed` validators of 2 signed the message.
+    fn multisig(signed: u32) -> IsmExplanation {
+        let validators = (0..2)
+            .map(|i| ValidatorCheckpoints {
+                validator: H160::repeat_byte(i as u8),
+                has_checkpoint_syncer: true,
+                latest_checkpoint_index: Some(if i < signed { 10 } else { 9 }),
+            })
+            .collect();
+        let mut explanation = module(
+            ModuleType::MessageIdMultisig,
+            ModuleDetails::Multisig(MultisigDetails {
+                threshold: 2,
+                validators,
+                merkle_leaf_index: Some(10),
+            }),
+        );
+        if signed >= 2 {
+            explanation.metadata = Some(vec![0xaa, 0xbb]);
+            explanation.verification_gas = Some(100_000.into());
+        }
+        resolve_metadata(&mut explanation);
+        explanation
+    }
+
+    /// Routing ISM -> aggregation ISM -> multisig ISMs, resolved bottom up like
+    /// `explain_module` does
+    fn routing_to_aggregation(threshold: u8, modules: Vec<IsmExplanation>) -> IsmExplanation {
+        let mut aggregation = module(
+            ModuleType::Aggregation,
+            ModuleDetails::Aggregation { threshold, modules },
+        );
+        resolve_metadata(&mut aggregation);
+        let mut routing = module(
+            ModuleType::Routing,
+            ModuleDetails::Routing {
+                route: Box::new(aggregation),
+            },
+        );
+        resolve_metadata(&mut routing);
+        routing
+    }
+
+    fn route(routing: &IsmExplanation) -> &IsmExplanation {
+        match &routing.details {
+            Some(ModuleDetails::Routing { route }) => route,
+            _ => panic!("Not a routing ISM"),
+        }
+    }
+
+    #[test]
+    fn test_metadata_is_derived_through_routing_and_aggregation() {
+        let routing = routing_to_aggregation(1, vec![multisig(1), multisig(2)]);
+
+        // Only the second sub-module has metadata. The aggregation metadata starts with
+        // the (start, end) ranges of both sub-modules, the first one being empty.
+        let expected = [vec![0; 8], vec![0, 0, 0, 16, 0, 0, 0, 18], vec![0xaa, 0xbb]].concat();
+        assert_eq!(route(&routing).metadata, Some(expected.clone()));
+        assert_eq!(route(&routing).error, None);
+        assert_eq!(routing.metadata, Some(expected));
+        assert_eq!(routing.error, None);
+    }
+
+    #[test]
+    fn test_missing_metadata_is_explained_through_routing_and_aggregation() {
+        let routing = routing_to_aggregation(2, vec![multisig(1), multisig(2)]);
+
+        assert_eq!(routing.metadata, None);
+        assert_eq!(
+            routing.error.as_deref(),
+            Some("The module the message is routed to has no metadata")
+        );
+        let aggregation = route(&routing);
+        assert_eq!(
+            aggregation.error.as_deref(),
+            Some("Only 1 of the 2 sub-modules required have metadata that passes verification")
+        );
+        let Some(ModuleDetails::Aggregation { modules, .. }) = &aggregation.details else {
+            panic!("Not an aggregation ISM");
+        };
+        assert_eq!(
+            modules[0].error.as_deref(),
+            Some("Only 1 of the 2 validators required have signed a checkpoint including the message")
+        );
+        assert_eq!(modules[1].error, None);
+    }
+}
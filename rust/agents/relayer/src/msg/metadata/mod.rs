@@ -1,6 +1,7 @@
 mod aggregation;
 mod base;
 mod ccip_read;
+mod dry_run;
 mod multisig;
 mod null_metadata;
 mod routing;
@@ -10,6 +11,8 @@ pub(crate) use base::MetadataBuilder;
 pub(crate) use base::{
     AppContextClassifier, BaseMetadataBuilder, IsmAwareAppContextClassifier, MessageMetadataBuilder,
 };
+pub(crate) use ccip_read::CcipReadGateways;
 use ccip_read::CcipReadIsmMetadataBuilder;
+pub(crate) use dry_run::{dry_run_metadata, MetadataDryRun};
 use null_metadata::NullMetadataBuilder;
 use routing::RoutingIsmMetadataBuilder;
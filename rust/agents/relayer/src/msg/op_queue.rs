@@ -1,7 +1,7 @@
 use std::{cmp::Reverse, collections::BinaryHeap, sync::Arc};
 
 use derive_new::new;
-use hyperlane_core::{PendingOperation, PendingOperationStatus, QueueOperation};
+use hyperlane_core::{PendingOperation, PendingOperationStatus, QueueOperation, H256};
 use prometheus::{IntGauge, IntGaugeVec};
 use tokio::sync::{broadcast::Receiver, Mutex};
 use tracing::{debug, info, instrument};
@@ -19,6 +19,10 @@ pub struct OpQueue {
     retry_rx: Arc<Mutex<Receiver<MessageRetryRequest>>>,
     #[new(default)]
     pub queue: OperationPriorityQueue,
+    /// Ids of the operations an operator moved to the front of the queue, most recent last.
+    /// These are popped before any other operation, regardless of their priority.
+    #[new(default)]
+    front: Arc<Mutex<Vec<H256>>>,
 }
 
 impl OpQueue {
@@ -50,15 +54,17 @@ impl OpQueue {
     pub async fn pop_many(&mut self, limit: usize) -> Vec<QueueOperation> {
         self.process_retry_requests().await;
         let mut queue = self.queue.lock().await;
-        let mut popped = vec![];
-        while let Some(Reverse(op)) = queue.pop() {
+        let mut popped = self.pop_front(&mut queue, limit).await;
+        while popped.len() < limit {
+            let Some(Reverse(op)) = queue.pop() else {
+                break;
+            };
+            popped.push(op);
+        }
+        for op in &popped {
             // even if the metric is decremented here, the operation may fail to process and be re-added to the queue.
             // in those cases, the queue length will look like it has spikes whose sizes are at most `limit`
             self.get_operation_metric(op.as_ref()).dec();
-            popped.push(op);
-            if popped.len() >= limit {
-                break;
-            }
         }
         // This function is called very often by the op_submitter tasks, so only log when there are operations to pop
         // to avoid spamming the logs
@@ -104,6 +110,87 @@ impl OpQueue {
         queue.append(&mut reprioritized_queue);
     }
 
+    /// Remove the operation with the given id from the queue, if it is in it, and update metrics
+    #[instrument(skip(self), fields(queue_label=%self.queue_metrics_label), level = "debug")]
+    pub async fn remove(&self, id: H256) -> Option<QueueOperation> {
+        let mut queue = self.queue.lock().await;
+        let (mut removed, remaining): (Vec<_>, Vec<_>) =
+            queue.drain().partition(|Reverse(op)| op.id() == id);
+        queue.extend(remaining);
+        let Reverse(op) = removed.pop()?;
+        self.get_operation_metric(op.as_ref()).dec();
+        info!(
+            operation = %op,
+            queue_label = %self.queue_metrics_label,
+            "Removed OpQueue operation"
+        );
+        Some(op)
+    }
+
+    /// Take up to `limit` of the operations that were moved to the front of the queue out of it,
+    /// the most recently moved one first.
+    async fn pop_front(
+        &self,
+        queue: &mut BinaryHeap<Reverse<QueueOperation>>,
+        limit: usize,
+    ) -> Vec<QueueOperation> {
+        let mut front = self.front.lock().await;
+        if front.is_empty() {
+            return vec![];
+        }
+        let (mut prioritized, remaining): (Vec<_>, Vec<_>) = queue
+            .drain()
+            .map(|Reverse(op)| op)
+            .partition(|op| front.contains(&op.id()));
+        queue.extend(remaining.into_iter().map(Reverse));
+        // Ids of operations that are no longer in the queue are forgotten
+        front.retain(|id| prioritized.iter().any(|op| op.id() == *id));
+        prioritized.sort_by_key(|op| {
+            let position = front.iter().position(|id| *id == op.id());
+            Reverse(position)
+        });
+        if prioritized.len() > limit {
+            queue.extend(prioritized.drain(limit..).map(Reverse));
+        }
+        front.retain(|id| !prioritized.iter().any(|op| op.id() == *id));
+        prioritized
+    }
+
+    /// Reset the attempts of the operation with the given id, moving it to the front of the queue.
+    /// Returns whether the operation was found in the queue.
+    #[instrument(skip(self), fields(queue_label=%self.queue_metrics_label), level = "debug")]
+    pub async fn reprioritize(&self, id: H256) -> bool {
+        let mut queue = self.queue.lock().await;
+        let mut found = false;
+        let mut reprioritized_queue: BinaryHeap<_> = queue
+            .drain()
+            .map(|Reverse(mut op)| {
+                if op.id() == id {
+                    info!(
+                        operation = %op,
+                        queue_label = %self.queue_metrics_label,
+                        "Reprioritizing OpQueue operation"
+                    );
+                    op.reset_attempts();
+                    found = true;
+                }
+                Reverse(op)
+            })
+            .collect();
+        queue.append(&mut reprioritized_queue);
+        if found {
+            let mut front = self.front.lock().await;
+            front.retain(|front_id| *front_id != id);
+            front.push(id);
+        }
+        found
+    }
+
+    /// Label of the queue, as used in metrics
+    pub fn label(&self) -> &str {
+        &self.queue_metrics_label
+    }
+
     /// Get the metric associated with this operation
     fn get_operation_metric(&self, operation: &dyn PendingOperation) -> IntGauge {
         let (destination, app_context) = operation.get_operation_labels();
@@ -205,7 +292,7 @@ pub mod test {
         }
 
         fn get_tx_cost_estimate(&self) -> Option<U256> {
-            todo!()
+            None
         }
 
         /// This will be called after the operation has been submitted and is
@@ -220,7 +307,6 @@ pub mod test {
             _submission_outcome: TxOutcome,
             _submission_estimated_cost: U256,
         ) {
-            todo!()
         }
 
         fn next_attempt_after(&self) -> Option<Instant> {
@@ -231,9 +317,7 @@ pub mod test {
             )
         }
 
-        fn set_next_attempt_after(&mut self, _delay: Duration) {
-            todo!()
-        }
+        fn set_next_attempt_after(&mut self, _delay: Duration) {}
 
         fn set_retries(&mut self, _retries: u32) {
             todo!()
@@ -387,4 +471,54 @@ pub mod test {
         assert_eq!(popped[3], op_ids[0]);
         assert_eq!(popped[4], op_ids[1]);
     }
+
+    #[tokio::test]
+    async fn test_reprioritize_moves_operations_to_the_front() {
+        let (metrics, queue_metrics_label) = dummy_metrics_and_label();
+        let broadcaster = sync::broadcast::Sender::new(100);
+        let mut op_queue = OpQueue::new(
+            metrics,
+            queue_metrics_label,
+            Arc::new(Mutex::new(broadcaster.subscribe())),
+        );
+        let destination_domain: HyperlaneDomain = KnownHyperlaneDomain::Injective.into();
+        let ops = (1..=3)
+            .map(|seconds_to_next_attempt| {
+                Box::new(MockPendingOperation::new(
+                    seconds_to_next_attempt,
+                    destination_domain.clone(),
+                )) as QueueOperation
+            })
+            .collect::<Vec<_>>();
+        let op_ids: Vec<_> = ops.iter().map(|op| op.id()).collect();
+        for op in ops {
+            op_queue.push(op, None).await;
+        }
+
+        assert!(op_queue.reprioritize(op_ids[2]).await);
+        assert!(op_queue.reprioritize(op_ids[1]).await);
+        assert!(!op_queue.reprioritize(H256::random()).await);
+
+        // The most recently reprioritized operation comes first, even if both were reset
+        assert_eq!(
+            op_queue
+                .pop_many(1)
+                .await
+                .iter()
+                .map(|op| op.id())
+                .collect::<Vec<_>>(),
+            vec![op_ids[1]]
+        );
+        let popped = op_queue.pop_many(3).await;
+        assert_eq!(
+            popped.iter().map(|op| op.id()).collect::<Vec<_>>(),
+            vec![op_ids[2], op_ids[0]]
+        );
+
+        // Operations are only at the front until they are popped
+        for op in popped {
+            op_queue.push(op, None).await;
+        }
+        assert!(op_queue.front.lock().await.is_empty());
+    }
 }
@@ -1,5 +1,7 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use derive_new::new;
 use futures::future::join_all;
@@ -19,8 +21,8 @@ use tracing::{info, warn};
 
 use hyperlane_base::CoreMetrics;
 use hyperlane_core::{
-    BatchItem, ChainCommunicationError, ChainResult, HyperlaneDomain, HyperlaneDomainProtocol,
-    HyperlaneMessage, PendingOperationResult, QueueOperation, TxOutcome,
+    BatchItem, BatchResult, ChainCommunicationError, ChainResult, HyperlaneDomain,
+    HyperlaneDomainProtocol, HyperlaneMessage, PendingOperationResult, QueueOperation, H256,
 };
 
 use crate::msg::pending_message::CONFIRM_DELAY;
@@ -76,6 +78,30 @@ use super::op_queue::OperationPriorityQueue;
 /// eligible for submission, we should be working on it within reason. This
 /// must be balanced with the cost of making RPCs that will almost certainly
 /// fail and potentially block new messages from being sent immediately.
+///
+/// Pipelining
+/// ----------
+///
+/// On chains where the single execution slot is the throughput ceiling, the
+/// submitter can opt into having up to `max_submissions_in_flight` operations
+/// submitted concurrently, each using the next sequential signer nonce. The
+/// submissions are started in queue order and the destination mailbox
+/// dispatches their transactions in the order they were started, so nonces
+/// follow the queue order. Since a transaction that never lands leaves a nonce
+/// gap that blocks every transaction submitted after it, the operations
+/// submitted after it in the same window are considered its dependents. If the
+/// operation is reprepared during confirmation, its dependents are re-checked
+/// right away instead of waiting for their own confirmation delay. A dependent
+/// that did not land is only reprepared once its own confirmation delay has
+/// elapsed, since its transaction may still be pending behind the gap.
+///
+/// Operator controls
+/// -----------------
+///
+/// A [`SubmitterHandle`] gives the operator API access to all three queues, so
+/// that operations can be inspected, dropped or reprioritized, and allows
+/// pausing the destination. While paused, no operations are prepared or
+/// submitted, but operations that were already submitted keep being confirmed.
 #[derive(Debug)]
 pub struct SerialSubmitter {
     /// Domain this submitter delivers to.
@@ -86,8 +112,14 @@ pub struct SerialSubmitter {
     metrics: SerialSubmitterMetrics,
     /// Max batch size for submitting messages
     max_batch_size: u32,
+    /// Max number of submissions in flight at once, when not batching
+    max_submissions_in_flight: u32,
+    /// Tracks the dependents of pipelined submissions
+    pipeline_tracker: PipelineTracker,
     /// tokio task monitor
     task_monitor: TaskMonitor,
+    /// Whether preparing and submitting operations is paused by an operator
+    paused: Arc<AtomicBool>,
     prepare_queue: OpQueue,
     submit_queue: OpQueue,
     confirm_queue: OpQueue,
@@ -100,6 +132,7 @@ impl SerialSubmitter {
         retry_op_transmitter: Sender<MessageRetryRequest>,
         metrics: SerialSubmitterMetrics,
         max_batch_size: u32,
+        max_submissions_in_flight: u32,
         task_monitor: TaskMonitor,
     ) -> Self {
         let prepare_queue = OpQueue::new(
@@ -117,13 +150,17 @@ impl SerialSubmitter {
             "confirm_queue".to_string(),
             Arc::new(Mutex::new(retry_op_transmitter.subscribe())),
         );
+        let pipeline_tracker = PipelineTracker::new(retry_op_transmitter);
 
         Self {
             domain,
             rx,
             metrics,
             max_batch_size,
+            max_submissions_in_flight,
+            pipeline_tracker,
             task_monitor,
+            paused: Default::default(),
             prepare_queue,
             submit_queue,
             confirm_queue,
@@ -134,6 +171,15 @@ impl SerialSubmitter {
         self.prepare_queue.queue.clone()
     }
 
+    pub fn handle(&self) -> SubmitterHandle {
+        SubmitterHandle::new(
+            self.paused.clone(),
+            self.prepare_queue.clone(),
+            self.submit_queue.clone(),
+            self.confirm_queue.clone(),
+        )
+    }
+
     pub fn spawn(self) -> Instrumented<JoinHandle<()>> {
         let span = info_span!("SerialSubmitter", destination=%self.domain);
         let task_monitor = self.task_monitor.clone();
@@ -149,7 +195,10 @@ impl SerialSubmitter {
             metrics,
             rx: rx_prepare,
             max_batch_size,
+            max_submissions_in_flight,
+            pipeline_tracker,
             task_monitor,
+            paused,
             prepare_queue,
             submit_queue,
             confirm_queue,
@@ -158,7 +207,12 @@ impl SerialSubmitter {
         let tasks = [
             tokio::spawn(TaskMonitor::instrument(
                 &task_monitor,
-                receive_task(domain.clone(), rx_prepare, prepare_queue.clone()),
+                receive_task(
+                    domain.clone(),
+                    rx_prepare,
+                    prepare_queue.clone(),
+                    confirm_queue.clone(),
+                ),
             )),
             tokio::spawn(TaskMonitor::instrument(
                 &task_monitor,
@@ -168,6 +222,7 @@ impl SerialSubmitter {
                     submit_queue.clone(),
                     confirm_queue.clone(),
                     max_batch_size,
+                    paused.clone(),
                     metrics.clone(),
                 ),
             )),
@@ -178,6 +233,9 @@ impl SerialSubmitter {
                     submit_queue,
                     confirm_queue.clone(),
                     max_batch_size,
+                    max_submissions_in_flight,
+                    pipeline_tracker.clone(),
+                    paused,
                     metrics.clone(),
                 ),
             )),
@@ -188,6 +246,7 @@ impl SerialSubmitter {
                     prepare_queue,
                     confirm_queue,
                     max_batch_size,
+                    pipeline_tracker,
                     metrics,
                 ),
             )),
@@ -208,6 +267,7 @@ async fn receive_task(
     domain: HyperlaneDomain,
     mut rx: mpsc::UnboundedReceiver<QueueOperation>,
     prepare_queue: OpQueue,
+    confirm_queue: OpQueue,
 ) {
     // Pull any messages sent to this submitter
     while let Some(op) = rx.recv().await {
@@ -222,7 +282,20 @@ async fn receive_task(
             );
             PendingOperationStatus::FirstPrepareAttempt
         });
-        prepare_queue.push(op, Some(status)).await;
+        match status {
+            // Operations that were awaiting confirmation before a restart resume from the confirm queue,
+            // rather than being resubmitted. If they turn out not to have landed, they get reprepared.
+            PendingOperationStatus::Confirm(_) => {
+                debug!(?op, "Resuming confirmation of operation");
+                confirm_queue.push(op, Some(status)).await;
+            }
+            PendingOperationStatus::Dropped => {
+                debug!(?op, "Skipping operation dropped by an operator");
+            }
+            _ => {
+                prepare_queue.push(op, Some(status)).await;
+            }
+        }
     }
 }
 
@@ -233,11 +306,16 @@ async fn prepare_task(
     submit_queue: OpQueue,
     confirm_queue: OpQueue,
     max_batch_size: u32,
+    paused: Arc<AtomicBool>,
     metrics: SerialSubmitterMetrics,
 ) {
     // Prepare at most `max_batch_size` ops at a time to avoid getting rate-limited
     let ops_to_prepare = max_batch_size as usize;
     loop {
+        if paused.load(Ordering::Relaxed) {
+            sleep(Duration::from_millis(500)).await;
+            continue;
+        }
         // Pop messages here according to the configured batch.
         let mut batch = prepare_queue.pop_many(ops_to_prepare).await;
         if batch.is_empty() {
@@ -306,10 +384,23 @@ async fn submit_task(
     mut submit_queue: OpQueue,
     mut confirm_queue: OpQueue,
     max_batch_size: u32,
+    max_submissions_in_flight: u32,
+    pipeline_tracker: PipelineTracker,
+    paused: Arc<AtomicBool>,
     metrics: SerialSubmitterMetrics,
 ) {
-    let recv_limit = max_batch_size as usize;
+    // Batching takes precedence over pipelining, since a batch is a single transaction anyway
+    let pipelining = max_batch_size <= 1 && max_submissions_in_flight > 1;
+    let recv_limit = if pipelining {
+        max_submissions_in_flight as usize
+    } else {
+        max_batch_size as usize
+    };
     loop {
+        if paused.load(Ordering::Relaxed) {
+            sleep(Duration::from_millis(500)).await;
+            continue;
+        }
         let mut batch = submit_queue.pop_many(recv_limit).await;
 
         match batch.len().cmp(&1) {
@@ -322,6 +413,10 @@ async fn submit_task(
                 let op = batch.pop().unwrap();
                 submit_single_operation(op, &mut confirm_queue, &metrics).await;
             }
+            std::cmp::Ordering::Greater if pipelining => {
+                submit_pipelined_operations(batch, &mut confirm_queue, &pipeline_tracker, &metrics)
+                    .await;
+            }
             std::cmp::Ordering::Greater => {
                 OperationBatch::new(batch, domain.clone())
                     .submit(&mut confirm_queue, &metrics)
@@ -357,12 +452,38 @@ async fn submit_single_operation(
     }
 }
 
+/// Submits all operations concurrently, so that each of them is sent with the next
+/// sequential signer nonce without waiting for the previous one to be included.
+///
+/// `join_all` polls the submissions in order, so they queue up for the destination
+/// mailbox's dispatch lock in the order of `ops`, and are assigned nonces in that order.
+#[instrument(skip(confirm_queue, pipeline_tracker, metrics), level = "debug")]
+async fn submit_pipelined_operations(
+    mut ops: Vec<QueueOperation>,
+    confirm_queue: &mut OpQueue,
+    pipeline_tracker: &PipelineTracker,
+    metrics: &SerialSubmitterMetrics,
+) {
+    join_all(ops.iter_mut().map(|op| op.submit())).await;
+    let ids = ops.iter().map(|op| op.id()).collect::<Vec<_>>();
+    pipeline_tracker.track(&ids, CONFIRM_DELAY).await;
+    for mut op in ops {
+        debug!(?op, "Pipelined operation submitted");
+        op.set_next_attempt_after(CONFIRM_DELAY);
+        confirm_queue
+            .push(op, Some(PendingOperationStatus::Confirm(SubmittedBySelf)))
+            .await;
+        metrics.ops_submitted.inc();
+    }
+}
+
 #[instrument(skip_all, fields(%domain))]
 async fn confirm_task(
     domain: HyperlaneDomain,
     prepare_queue: OpQueue,
     mut confirm_queue: OpQueue,
     max_batch_size: u32,
+    pipeline_tracker: PipelineTracker,
     metrics: SerialSubmitterMetrics,
 ) {
     let recv_limit = max_batch_size as usize;
@@ -382,6 +503,7 @@ async fn confirm_task(
                 domain.clone(),
                 prepare_queue.clone(),
                 confirm_queue.clone(),
+                pipeline_tracker.clone(),
                 metrics.clone(),
             )
         });
@@ -404,12 +526,28 @@ async fn confirm_operation(
     domain: HyperlaneDomain,
     prepare_queue: OpQueue,
     confirm_queue: OpQueue,
+    pipeline_tracker: PipelineTracker,
     metrics: SerialSubmitterMetrics,
 ) -> PendingOperationResult {
     trace!(?op, "Confirming operation");
     debug_assert_eq!(*op.destination_domain(), domain);
 
-    let operation_result = op.confirm().await;
+    let mut operation_result = op.confirm().await;
+    if let Some(remaining) = pipeline_tracker
+        .hold_until_confirm_due(op.id(), &operation_result)
+        .await
+    {
+        debug!(
+            ?op,
+            ?remaining,
+            "Pipelined operation did not land yet, but may still be pending"
+        );
+        op.set_next_attempt_after(remaining);
+        operation_result = PendingOperationResult::NotReady;
+    }
+    pipeline_tracker
+        .on_confirm_result(op.id(), &operation_result)
+        .await;
     match &operation_result {
         PendingOperationResult::Success => {
             debug!(?op, "Operation confirmed");
@@ -437,6 +575,119 @@ async fn confirm_operation(
     operation_result
 }
 
+/// Handle to the queues of a `SerialSubmitter`, used by operators to inspect and
+/// control the operations of a destination while the relayer is running.
+#[derive(new, Debug, Clone)]
+pub struct SubmitterHandle {
+    paused: Arc<AtomicBool>,
+    prepare_queue: OpQueue,
+    submit_queue: OpQueue,
+    confirm_queue: OpQueue,
+}
+
+impl SubmitterHandle {
+    /// Pause or resume preparing and submitting operations
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Relaxed);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// All queues of the submitter, in the order operations move through them
+    pub fn queues(&self) -> [&OpQueue; 3] {
+        [&self.prepare_queue, &self.submit_queue, &self.confirm_queue]
+    }
+}
+
+/// Keeps track of the operations that were submitted after each pipelined operation,
+/// within the same window. These are the operations whose nonces can't be used until
+/// the operation's own transaction lands.
+#[derive(Debug, Clone)]
+struct PipelineTracker {
+    state: Arc<Mutex<PipelineState>>,
+    retry_transmitter: Sender<MessageRetryRequest>,
+}
+
+#[derive(Debug, Default)]
+struct PipelineState {
+    /// Operations submitted after each pipelined operation, in submission order
+    dependents: HashMap<H256, Vec<H256>>,
+    /// When each pipelined operation is due to be confirmed
+    confirm_due: HashMap<H256, Instant>,
+}
+
+impl PipelineTracker {
+    fn new(retry_transmitter: Sender<MessageRetryRequest>) -> Self {
+        Self {
+            state: Default::default(),
+            retry_transmitter,
+        }
+    }
+
+    /// Record the dependents of each operation in a pipelined window, given in submission
+    /// order, and when they are due to be confirmed.
+    async fn track(&self, ids: &[H256], confirm_delay: Duration) {
+        let confirm_due = Instant::now() + confirm_delay;
+        let mut state = self.state.lock().await;
+        for (index, id) in ids.iter().enumerate() {
+            state.dependents.insert(*id, ids[index + 1..].to_vec());
+            state.confirm_due.insert(*id, confirm_due);
+        }
+    }
+
+    /// If a pipelined operation has to be reprepared before it was due to be confirmed, e.g.
+    /// because it was retried as the dependent of another operation, its transaction may
+    /// still be pending. Returns how long to keep it in the confirm queue in that case, so
+    /// that it isn't resubmitted while the original transaction can still land.
+    async fn hold_until_confirm_due(
+        &self,
+        id: H256,
+        result: &PendingOperationResult,
+    ) -> Option<Duration> {
+        if !matches!(result, PendingOperationResult::Reprepare(_)) {
+            return None;
+        }
+        let confirm_due = *self.state.lock().await.confirm_due.get(&id)?;
+        confirm_due
+            .checked_duration_since(Instant::now())
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    /// Forget about operations that reached a final confirmation result. If an operation has to be
+    /// reprepared, its dependents are retried right away, since they most likely did not land either.
+    async fn on_confirm_result(&self, id: H256, result: &PendingOperationResult) {
+        let dependents = {
+            let mut state = self.state.lock().await;
+            match result {
+                PendingOperationResult::NotReady | PendingOperationResult::Confirm(_) => return,
+                PendingOperationResult::Success | PendingOperationResult::Drop => {
+                    state.dependents.remove(&id);
+                    state.confirm_due.remove(&id);
+                    return;
+                }
+                PendingOperationResult::Reprepare(_) => {
+                    state.confirm_due.remove(&id);
+                    let Some(dependents) = state.dependents.remove(&id) else {
+                        return;
+                    };
+                    dependents
+                }
+            }
+        };
+        for dependent in dependents {
+            debug!(operation_id=?id, ?dependent, "Retrying dependent of pipelined operation that was reprepared");
+            if let Err(err) = self
+                .retry_transmitter
+                .send(MessageRetryRequest::MessageId(dependent))
+            {
+                warn!(error=?err, ?dependent, "Failed to send retry request for dependent operation");
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SerialSubmitterMetrics {
     submitter_queue_length: IntGaugeVec,
@@ -474,37 +725,30 @@ impl SerialSubmitterMetrics {
 #[derive(new, Debug)]
 struct OperationBatch {
     operations: Vec<QueueOperation>,
-    #[allow(dead_code)]
     domain: HyperlaneDomain,
 }
 
 impl OperationBatch {
     async fn submit(self, confirm_queue: &mut OpQueue, metrics: &SerialSubmitterMetrics) {
-        match self.try_submit_as_batch(metrics).await {
-            Ok(outcome) => {
-                info!(outcome=?outcome, batch_size=self.operations.len(), batch=?self.operations, "Submitted transaction batch");
-                let total_estimated_cost = total_estimated_cost(&self.operations);
-                for mut op in self.operations {
-                    op.set_operation_outcome(outcome.clone(), total_estimated_cost);
-                    op.set_next_attempt_after(CONFIRM_DELAY);
-                    confirm_queue
-                        .push(op, Some(PendingOperationStatus::Confirm(SubmittedBySelf)))
-                        .await;
-                }
-                return;
+        let excluded_ops = match self.try_submit_as_batch().await {
+            Ok(batch_result) => {
+                Self::handle_batch_result(self.operations, batch_result, confirm_queue, metrics)
+                    .await
             }
             Err(e) => {
                 warn!(error=?e, batch=?self.operations, "Error when submitting batch. Falling back to serial submission.");
+                self.operations
             }
+        };
+        if !excluded_ops.is_empty() {
+            OperationBatch::new(excluded_ops, self.domain)
+                .submit_serially(confirm_queue, metrics)
+                .await;
         }
-        self.submit_serially(confirm_queue, metrics).await;
     }
 
-    #[instrument(skip(metrics), ret, level = "debug")]
-    async fn try_submit_as_batch(
-        &self,
-        metrics: &SerialSubmitterMetrics,
-    ) -> ChainResult<TxOutcome> {
+    #[instrument(ret, level = "debug")]
+    async fn try_submit_as_batch(&self) -> ChainResult<BatchResult> {
         let batch = self
             .operations
             .iter()
@@ -517,9 +761,50 @@ impl OperationBatch {
             return Err(ChainCommunicationError::BatchIsEmpty);
         };
 
-        let outcome = first_item.mailbox.process_batch(&batch).await?;
-        metrics.ops_submitted.inc_by(self.operations.len() as u64);
-        Ok(outcome)
+        first_item.mailbox.process_batch(&batch).await
+    }
+
+    /// Moves the operations included in the batch transaction to the confirm queue,
+    /// and returns the ones that weren't processed by it.
+    async fn handle_batch_result(
+        operations: Vec<QueueOperation>,
+        batch_result: BatchResult,
+        confirm_queue: &mut OpQueue,
+        metrics: &SerialSubmitterMetrics,
+    ) -> Vec<QueueOperation> {
+        let Some(outcome) = batch_result.outcome else {
+            warn!(batch=?operations, "No transaction was submitted for the batch. Falling back to serial submission.");
+            return operations;
+        };
+        let (included_ops, excluded_ops): (Vec<_>, Vec<_>) = operations
+            .into_iter()
+            .enumerate()
+            .partition(|(index, _)| !batch_result.failed_indexes.contains(index));
+        let included_ops = included_ops
+            .into_iter()
+            .map(|(_, op)| op)
+            .collect::<Vec<_>>();
+        let excluded_ops = excluded_ops
+            .into_iter()
+            .map(|(_, op)| op)
+            .collect::<Vec<_>>();
+
+        info!(outcome=?outcome, batch_size=included_ops.len(), batch=?included_ops, "Submitted transaction batch");
+        if !excluded_ops.is_empty() {
+            warn!(excluded_ops=?excluded_ops, "Operations weren't processed by the batch transaction. Falling back to serial submission for them.");
+        }
+
+        // Only the included operations pay for the transaction
+        let total_estimated_cost = total_estimated_cost(&included_ops);
+        metrics.ops_submitted.inc_by(included_ops.len() as u64);
+        for mut op in included_ops {
+            op.set_operation_outcome(outcome.clone(), total_estimated_cost);
+            op.set_next_attempt_after(CONFIRM_DELAY);
+            confirm_queue
+                .push(op, Some(PendingOperationStatus::Confirm(SubmittedBySelf)))
+                .await;
+        }
+        excluded_ops
     }
 
     async fn submit_serially(self, confirm_queue: &mut OpQueue, metrics: &SerialSubmitterMetrics) {
@@ -528,3 +813,183 @@ impl OperationBatch {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use hyperlane_core::{KnownHyperlaneDomain, ReprepareReason, TxOutcome, H512};
+    use prometheus::Registry;
+    use tokio::sync::broadcast::{self, error::TryRecvError};
+
+    use super::*;
+    use crate::msg::op_queue::test::{dummy_metrics_and_label, MockPendingOperation};
+
+    fn reprepare() -> PendingOperationResult {
+        PendingOperationResult::Reprepare(ReprepareReason::RevertedOrReorged)
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_tracker_retries_dependents_of_reprepared_operation() {
+        let (tx, mut rx) = broadcast::channel(16);
+        let tracker = PipelineTracker::new(tx);
+        let ids = [H256::random(), H256::random(), H256::random()];
+        tracker.track(&ids, Duration::ZERO).await;
+
+        // The second operation only has the third as dependent
+        tracker.on_confirm_result(ids[1], &reprepare()).await;
+        assert_eq!(rx.try_recv(), Ok(MessageRetryRequest::MessageId(ids[2])));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+
+        // The dependents of an operation are only retried once
+        tracker.on_confirm_result(ids[1], &reprepare()).await;
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+
+        // Non-final results don't retry anything
+        tracker
+            .on_confirm_result(ids[0], &PendingOperationResult::NotReady)
+            .await;
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+
+        tracker.on_confirm_result(ids[0], &reprepare()).await;
+        assert_eq!(rx.try_recv(), Ok(MessageRetryRequest::MessageId(ids[1])));
+        assert_eq!(rx.try_recv(), Ok(MessageRetryRequest::MessageId(ids[2])));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_tracker_forgets_confirmed_operations() {
+        let (tx, mut rx) = broadcast::channel(16);
+        let tracker = PipelineTracker::new(tx);
+        let ids = [H256::random(), H256::random()];
+        tracker.track(&ids, Duration::ZERO).await;
+
+        tracker
+            .on_confirm_result(ids[0], &PendingOperationResult::Success)
+            .await;
+        tracker.on_confirm_result(ids[0], &reprepare()).await;
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+        assert!(tracker
+            .state
+            .lock()
+            .await
+            .confirm_due
+            .get(&ids[0])
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_tracker_holds_dependent_retried_while_pending() {
+        let (tx, mut rx) = broadcast::channel(16);
+        let tracker = PipelineTracker::new(tx);
+        let ids = [H256::random(), H256::random()];
+        tracker.track(&ids, Duration::from_secs(60)).await;
+
+        // The first operation is reprepared, so its dependent is retried right away
+        tracker.on_confirm_result(ids[0], &reprepare()).await;
+        assert_eq!(rx.try_recv(), Ok(MessageRetryRequest::MessageId(ids[1])));
+
+        // The dependent did not land yet, but its confirmation isn't due, so it's held
+        // in the confirm queue rather than being resubmitted
+        let remaining = tracker
+            .hold_until_confirm_due(ids[1], &reprepare())
+            .await
+            .unwrap();
+        assert!(remaining > Duration::from_secs(50) && remaining <= Duration::from_secs(60));
+
+        // Landing is never held back
+        assert!(tracker
+            .hold_until_confirm_due(ids[1], &PendingOperationResult::Success)
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_tracker_reprepares_dependent_once_confirmation_is_due() {
+        let (tx, _rx) = broadcast::channel(16);
+        let tracker = PipelineTracker::new(tx);
+        let ids = [H256::random(), H256::random()];
+        tracker.track(&ids, Duration::ZERO).await;
+
+        assert!(tracker
+            .hold_until_confirm_due(ids[1], &reprepare())
+            .await
+            .is_none());
+        // Operations that aren't pipelined are never held
+        assert!(tracker
+            .hold_until_confirm_due(H256::random(), &reprepare())
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_batch_result_only_confirms_included_operations() {
+        let destination: HyperlaneDomain = KnownHyperlaneDomain::Arbitrum.into();
+        let core_metrics = CoreMetrics::new("test", 9090, Registry::new()).unwrap();
+        let metrics = SerialSubmitterMetrics::new(&core_metrics, &destination);
+        let (queue_metrics, queue_metrics_label) = dummy_metrics_and_label();
+        let mut confirm_queue = OpQueue::new(
+            queue_metrics,
+            queue_metrics_label,
+            Arc::new(Mutex::new(broadcast::channel(16).1)),
+        );
+        let ops = (0..3)
+            .map(|_| Box::new(MockPendingOperation::new(0, destination.clone())) as QueueOperation)
+            .collect::<Vec<_>>();
+        let ids = ops.iter().map(|op| op.id()).collect::<Vec<_>>();
+        let outcome = TxOutcome {
+            transaction_id: H512::random(),
+            executed: true,
+            gas_used: 0.into(),
+            gas_price: 0u32.into(),
+        };
+
+        let excluded = OperationBatch::handle_batch_result(
+            ops,
+            BatchResult::new(Some(outcome), vec![1]),
+            &mut confirm_queue,
+            &metrics,
+        )
+        .await;
+
+        assert_eq!(
+            excluded.iter().map(|op| op.id()).collect::<Vec<_>>(),
+            vec![ids[1]]
+        );
+        let mut confirmed = vec![];
+        while let Some(op) = confirm_queue.pop().await {
+            confirmed.push(op.id());
+        }
+        confirmed.sort();
+        let mut expected = vec![ids[0], ids[2]];
+        expected.sort();
+        assert_eq!(confirmed, expected);
+        assert_eq!(metrics.ops_submitted.get(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_batch_result_without_transaction_excludes_all_operations() {
+        let destination: HyperlaneDomain = KnownHyperlaneDomain::Arbitrum.into();
+        let core_metrics = CoreMetrics::new("test", 9090, Registry::new()).unwrap();
+        let metrics = SerialSubmitterMetrics::new(&core_metrics, &destination);
+        let (queue_metrics, queue_metrics_label) = dummy_metrics_and_label();
+        let mut confirm_queue = OpQueue::new(
+            queue_metrics,
+            queue_metrics_label,
+            Arc::new(Mutex::new(broadcast::channel(16).1)),
+        );
+        let ops = (0..2)
+            .map(|_| Box::new(MockPendingOperation::new(0, destination.clone())) as QueueOperation)
+            .collect::<Vec<_>>();
+
+        let excluded = OperationBatch::handle_batch_result(
+            ops,
+            BatchResult::new(None, vec![]),
+            &mut confirm_queue,
+            &metrics,
+        )
+        .await;
+
+        assert_eq!(excluded.len(), 2);
+        assert!(confirm_queue.pop().await.is_none());
+        assert_eq!(metrics.ops_submitted.get(), 0);
+    }
+}
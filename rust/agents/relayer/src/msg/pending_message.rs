@@ -1,26 +1,28 @@
 use std::{
     fmt::{Debug, Formatter},
     sync::Arc,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use async_trait::async_trait;
 use derive_new::new;
 use eyre::Result;
+use futures::future::join_all;
 use hyperlane_base::{db::HyperlaneRocksDB, CoreMetrics};
 use hyperlane_core::{
     gas_used_by_operation, BatchItem, ChainCommunicationError, ChainResult, ConfirmReason,
-    HyperlaneChain, HyperlaneDomain, HyperlaneMessage, Mailbox, MessageSubmissionData,
-    PendingOperation, PendingOperationResult, PendingOperationStatus, ReprepareReason, TryBatchAs,
-    TxOutcome, H256, U256,
+    FixedPointNumber, HyperlaneChain, HyperlaneDomain, HyperlaneMessage, Mailbox,
+    MessageSubmissionData, PendingOperation, PendingOperationQueueState, PendingOperationResult,
+    PendingOperationStatus, ReprepareReason, TryBatchAs, TxOutcome, TxnInfo, H256, H512, U256,
 };
 use prometheus::{IntCounter, IntGauge};
 use serde::Serialize;
 use tracing::{debug, error, info, info_span, instrument, trace, warn, Instrument};
 
 use super::{
-    gas_payment::GasPaymentEnforcer,
+    gas_payment::{ledger::ProfitabilityLedger, GasPaymentEnforcer},
     metadata::{BaseMetadataBuilder, MessageMetadataBuilder, MetadataBuilder},
+    policies::PolicyStore,
 };
 
 pub const CONFIRM_DELAY: Duration = if cfg!(any(test, feature = "test-utils")) {
@@ -31,6 +33,10 @@ pub const CONFIRM_DELAY: Duration = if cfg!(any(test, feature = "test-utils")) {
     Duration::from_secs(60)
 };
 
+/// How long a message that isn't allowed by the relayer policies waits before they are
+/// checked again
+const POLICY_RECHECK_DELAY: Duration = Duration::from_secs(60);
+
 /// The message context contains the links needed to submit a message. Each
 /// instance is for a unique origin -> destination pairing.
 pub struct MessageContext {
@@ -44,9 +50,13 @@ pub struct MessageContext {
     /// Used to determine if messages from the origin have made sufficient gas
     /// payments.
     pub origin_gas_payment_enforcer: Arc<GasPaymentEnforcer>,
-    /// Hard limit on transaction gas when submitting a transaction to the
-    /// destination.
-    pub transaction_gas_limit: Option<U256>,
+    /// Totals of payments and delivery costs of processed messages
+    pub ledger: Arc<ProfitabilityLedger>,
+    /// Relayer policies, including the hard limit on transaction gas when
+    /// submitting a transaction to the destination.
+    pub policies: PolicyStore,
+    /// Whether the transaction gas limit from the policies is ignored for the destination.
+    pub skip_transaction_gas_limit: bool,
     pub metrics: MessageSubmissionMetrics,
 }
 
@@ -74,6 +84,14 @@ pub struct PendingMessage {
     #[new(default)]
     #[serde(skip_serializing)]
     submission_outcome: Option<TxOutcome>,
+    /// Hash of the last transaction submitted for this message. Unlike `submission_outcome`,
+    /// this survives restarts, so confirmations can be resumed.
+    #[new(default)]
+    submitted_tx_id: Option<H512>,
+    /// Hashes of the transactions that `submitted_tx_id` replaced with bumped fees. Any of
+    /// them may be the one that lands, so confirmation checks all of them.
+    #[new(default)]
+    replaced_tx_ids: Vec<H512>,
 }
 
 impl Debug for PendingMessage {
@@ -134,6 +152,7 @@ impl PendingOperation for PendingMessage {
     }
 
     fn set_status(&mut self, status: PendingOperationStatus) {
+        self.status = status;
         if let Err(e) = self
             .ctx
             .origin_db
@@ -141,7 +160,6 @@ impl PendingOperation for PendingMessage {
         {
             warn!(message_id = ?self.message.id(), err = %e, status = %self.status, "Persisting `status` failed for message");
         }
-        self.status = status;
     }
 
     fn priority(&self) -> u32 {
@@ -177,6 +195,22 @@ impl PendingOperation for PendingMessage {
             return PendingOperationResult::NotReady;
         }
 
+        // The policies may have changed since the message was queued, e.g. due to an emergency blacklist
+        if let Some(reason) = self
+            .ctx
+            .policies
+            .current()
+            .await
+            .rejection_reason(&self.message)
+        {
+            // Hold the message rather than dropping it, so that it's relayed if the
+            // policies are lifted
+            warn!(%reason, "Holding message that is no longer allowed by the relayer policies");
+            self.next_attempt_after = Some(Instant::now() + POLICY_RECHECK_DELAY);
+            self.persist_queue_state();
+            return PendingOperationResult::Reprepare(ReprepareReason::NotAllowedByPolicies);
+        }
+
         // If the message has already been processed, e.g. due to another relayer having
         // already processed, then mark it as already-processed, and move on to
         // the next tick.
@@ -297,7 +331,12 @@ impl PendingOperation for PendingMessage {
             "Gas payment requirement met, ready to process message"
         );
 
-        if let Some(max_limit) = self.ctx.transaction_gas_limit {
+        let transaction_gas_limit = if self.ctx.skip_transaction_gas_limit {
+            None
+        } else {
+            self.ctx.policies.current().await.conf.transaction_gas_limit
+        };
+        if let Some(max_limit) = transaction_gas_limit {
             if gas_limit > max_limit {
                 // TODO: consider dropping instead of repreparing in this case
                 return self.on_reprepare::<String>(None, ReprepareReason::ExceedsMaxGasLimit);
@@ -308,6 +347,7 @@ impl PendingOperation for PendingMessage {
             metadata,
             gas_limit,
         }));
+        self.persist_queue_state();
         PendingOperationResult::Success
     }
 
@@ -334,6 +374,15 @@ impl PendingOperation for PendingMessage {
             Ok(outcome) => {
                 self.set_operation_outcome(outcome, state.gas_limit);
             }
+            Err(ChainCommunicationError::TransactionsNotIncluded(mut tx_ids)) => {
+                warn!(?tx_ids, "Submitted transactions were not included yet");
+                // Any earlier outcome is stale, and would stop `confirm` from
+                // looking for these transactions
+                self.submission_outcome = None;
+                self.submitted_tx_id = tx_ids.pop();
+                self.replaced_tx_ids = tx_ids;
+                self.persist_queue_state();
+            }
             Err(e) => {
                 error!(error=?e, "Error when processing message");
             }
@@ -341,7 +390,10 @@ impl PendingOperation for PendingMessage {
     }
 
     fn set_submission_outcome(&mut self, outcome: TxOutcome) {
+        self.submitted_tx_id = Some(outcome.transaction_id);
+        self.replaced_tx_ids.clear();
         self.submission_outcome = Some(outcome);
+        self.persist_queue_state();
     }
 
     fn get_tx_cost_estimate(&self) -> Option<U256> {
@@ -365,20 +417,35 @@ impl PendingOperation for PendingMessage {
             }
         };
 
+        // If the submission timed out, the transactions it dispatched may still land
+        let submitted_txs = if self.submission_outcome.is_none() {
+            self.submitted_txs().await
+        } else {
+            vec![]
+        };
+
         if is_delivered {
-            if let Err(err) = self.record_message_process_success() {
+            let landed = submitted_txs.iter().find_map(landed_tx_outcome);
+            if let (Some(outcome), Some(gas_limit)) = (landed, self.get_tx_cost_estimate()) {
+                self.set_operation_outcome(outcome, gas_limit);
+            }
+            if let Err(err) = self.record_message_process_success().await {
                 return self
                     .on_reconfirm(Some(err), "Error when recording message process success");
             }
             info!(
                 submission=?self.submission_outcome,
+                submitted_tx_id=?self.submitted_tx_id,
                 "Message successfully processed"
             );
             PendingOperationResult::Success
+        } else if submitted_txs.iter().any(|txn| txn.receipt.is_none()) {
+            self.on_reconfirm::<String>(None, "Submitted transaction is still pending")
         } else {
             let span = info_span!(
                 "Error: Transaction attempting to process message either reverted or was reorged",
                 tx_outcome=?self.submission_outcome,
+                submitted_tx_id=?self.submitted_tx_id,
                 message_id=?self.message.id()
             );
             self.on_reprepare::<String>(None, ReprepareReason::RevertedOrReorged)
@@ -394,6 +461,8 @@ impl PendingOperation for PendingMessage {
     ) {
         let Some(operation_estimate) = self.get_tx_cost_estimate() else {
             warn!("Cannot set operation outcome without a cost estimate set previously");
+            // Still keep the outcome, so that the delivery is attributed to this relayer
+            self.set_submission_outcome(submission_outcome);
             return;
         };
         // calculate the gas used by the operation
@@ -437,6 +506,7 @@ impl PendingOperation for PendingMessage {
 
     fn set_next_attempt_after(&mut self, delay: Duration) {
         self.next_attempt_after = Some(Instant::now() + delay);
+        self.persist_queue_state();
     }
 
     fn reset_attempts(&mut self) {
@@ -449,9 +519,11 @@ impl PendingOperation for PendingMessage {
 }
 
 impl PendingMessage {
-    /// Constructor that tries reading the retry count from the HyperlaneDB in order to recompute the `next_attempt_after`.
+    /// Constructor that tries reading the retry count, status and queue state from the HyperlaneDB,
+    /// so that an operation that was in the submitter queues before a restart resumes where it left off.
+    /// If no queue state was persisted, `next_attempt_after` is recomputed from the retry count.
     /// In case of failure, behaves like `Self::new(...)`.
-    pub fn from_persisted_retries(
+    pub fn from_persisted_state(
         message: HyperlaneMessage,
         ctx: Arc<MessageContext>,
         app_context: Option<String>,
@@ -459,10 +531,12 @@ impl PendingMessage {
         let mut pm = Self::new(
             message,
             ctx,
-            // Since we don't persist the message status for now, assume it's the first attempt
             PendingOperationStatus::FirstPrepareAttempt,
             app_context,
         );
+        if let Some(status) = pm.retrieve_status_from_db() {
+            pm.status = status;
+        }
         match pm
             .ctx
             .origin_db
@@ -478,6 +552,28 @@ impl PendingMessage {
                 trace!(message_id = ?pm.message.id(), result = ?r, "Failed to read retry count from HyperlaneDB for message.")
             }
         }
+        match pm
+            .ctx
+            .origin_db
+            .retrieve_pending_operation_queue_state_by_message_id(&pm.message.id())
+        {
+            Ok(Some(queue_state)) => {
+                // The persisted schedule takes precedence over the one derived from the retry count,
+                // since it also covers delays that aren't retry backoffs (e.g. awaiting confirmation).
+                if let Some(next_attempt_after) = queue_state.next_attempt_after {
+                    pm.next_attempt_after = Some(instant_from_unix_timestamp(next_attempt_after));
+                }
+                pm.submitted_tx_id = queue_state.submitted_tx_id;
+                pm.replaced_tx_ids = queue_state.replaced_tx_ids;
+                pm.submission_data = queue_state.submission_data.map(Box::new);
+                if matches!(pm.status, PendingOperationStatus::Confirm(_)) {
+                    pm.submitted = true;
+                }
+            }
+            r => {
+                trace!(message_id = ?pm.message.id(), result = ?r, "Failed to read queue state from HyperlaneDB for message.")
+            }
+        }
         pm
     }
 
@@ -488,6 +584,12 @@ impl PendingMessage {
     ) -> PendingOperationResult {
         self.inc_attempts();
         self.submitted = false;
+        // The message will be submitted in a new transaction, so the previous
+        // ones mustn't be confirmed against it
+        self.submitted_tx_id = None;
+        self.replaced_tx_ids.clear();
+        self.submission_outcome = None;
+        self.persist_queue_state();
         if let Some(e) = err {
             warn!(error = ?e, "Repreparing message: {}", reason.clone());
         } else {
@@ -506,6 +608,25 @@ impl PendingMessage {
         PendingOperationResult::NotReady
     }
 
+    /// Looks up every transaction submitted for this message that is known to the
+    /// destination. Since replacements share a nonce, at most one of them lands, and the
+    /// others are pending until then.
+    async fn submitted_txs(&self) -> Vec<TxnInfo> {
+        let provider = self.ctx.destination_mailbox.provider();
+        let tx_ids = self.replaced_tx_ids.iter().chain(&self.submitted_tx_id);
+        join_all(tx_ids.map(|tx_id| provider.get_txn_by_hash(tx_id)))
+            .await
+            .into_iter()
+            .filter_map(|txn| match txn {
+                Ok(txn) => Some(txn),
+                Err(err) => {
+                    trace!(error=?err, "Submitted transaction not found");
+                    None
+                }
+            })
+            .collect()
+    }
+
     fn is_ready(&self) -> bool {
         self.next_attempt_after
             .map(|a| Instant::now() >= a)
@@ -519,18 +640,41 @@ impl PendingMessage {
     /// `return Ok(())`, then without a wiped HyperlaneDB, we will never
     /// re-attempt processing for this message again, even after the relayer
     /// restarts.
-    fn record_message_process_success(&mut self) -> Result<()> {
+    async fn record_message_process_success(&mut self) -> Result<()> {
         self.ctx
             .origin_db
             .store_processed_by_nonce(&self.message.nonce, &true)?;
         self.ctx.metrics.update_nonce(&self.message);
         self.ctx.metrics.messages_processed.inc();
+        self.record_in_ledger().await;
         Ok(())
     }
 
+    /// Records the delivery in the profitability ledger if it was made by this relayer,
+    /// i.e. if a transaction it submitted delivered the message
+    async fn record_in_ledger(&self) {
+        let delivered_by_relayer = self
+            .submission_outcome
+            .as_ref()
+            .map(|outcome| outcome.executed)
+            .unwrap_or(false);
+        if !delivered_by_relayer {
+            return;
+        }
+        if let Err(err) = self
+            .ctx
+            .ledger
+            .record_delivery(&self.ctx.origin_db, &self.message, self.app_context.clone())
+            .await
+        {
+            warn!(error=?err, "Failed to record delivery in the profitability ledger");
+        }
+    }
+
     fn reset_attempts(&mut self) {
         self.next_attempt_after = None;
         self.last_attempted_at = Instant::now();
+        self.persist_queue_state();
     }
 
     fn inc_attempts(&mut self) {
@@ -538,6 +682,7 @@ impl PendingMessage {
         self.last_attempted_at = Instant::now();
         self.next_attempt_after = PendingMessage::calculate_msg_backoff(self.num_retries)
             .map(|dur| self.last_attempted_at + dur);
+        self.persist_queue_state();
     }
 
     fn set_retries(&mut self, retries: u32) {
@@ -555,6 +700,22 @@ impl PendingMessage {
         }
     }
 
+    fn persist_queue_state(&self) {
+        let queue_state = PendingOperationQueueState {
+            next_attempt_after: self.next_attempt_after.map(unix_timestamp_from_instant),
+            submitted_tx_id: self.submitted_tx_id,
+            replaced_tx_ids: self.replaced_tx_ids.clone(),
+            submission_data: self.submission_data.as_deref().cloned(),
+        };
+        if let Err(e) = self
+            .ctx
+            .origin_db
+            .store_pending_operation_queue_state_by_message_id(&self.message.id(), &queue_state)
+        {
+            warn!(message_id = ?self.message.id(), err = %e, "Persisting the queue state failed for message");
+        }
+    }
+
     /// Get duration we should wait before re-attempting to deliver a message
     /// given the number of retries.
     /// `pub(crate)` for testing purposes
@@ -584,6 +745,44 @@ impl PendingMessage {
     }
 }
 
+/// The outcome of a submitted transaction, if it landed
+fn landed_tx_outcome(txn: &TxnInfo) -> Option<TxOutcome> {
+    let receipt = txn.receipt.as_ref()?;
+    Some(TxOutcome {
+        transaction_id: txn.hash,
+        executed: true,
+        gas_used: receipt.gas_used,
+        gas_price: receipt
+            .effective_gas_price
+            .or(txn.gas_price)
+            .and_then(|price| FixedPointNumber::try_from(price).ok())
+            .unwrap_or(FixedPointNumber::zero()),
+    })
+}
+
+/// `Instant`s are opaque and can't be persisted, so they are converted to unix timestamps first.
+fn unix_timestamp_from_instant(instant: Instant) -> u64 {
+    let now = Instant::now();
+    let system_time = if instant >= now {
+        SystemTime::now() + instant.duration_since(now)
+    } else {
+        SystemTime::now() - now.duration_since(instant)
+    };
+    system_time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Inverse of `unix_timestamp_from_instant`. Timestamps in the past map to `Instant::now()`.
+fn instant_from_unix_timestamp(timestamp: u64) -> Instant {
+    let system_time = UNIX_EPOCH + Duration::from_secs(timestamp);
+    let remaining = system_time
+        .duration_since(SystemTime::now())
+        .unwrap_or_default();
+    Instant::now() + remaining
+}
+
 #[derive(Debug)]
 pub struct MessageSubmissionMetrics {
     // Fields are public for testing purposes
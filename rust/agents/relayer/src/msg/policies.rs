@@ -0,0 +1,335 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime},
+};
+
+use ethers::utils::hex;
+use eyre::{Context, Result};
+use hyperlane_core::HyperlaneMessage;
+use serde_json::Value;
+use tokio::{
+    sync::{Mutex, RwLock},
+    task::JoinHandle,
+    time::sleep,
+};
+use tracing::{info, info_span, instrument::Instrumented, warn, Instrument};
+
+use super::{blacklist::AddressBlacklist, gas_payment::GasPaymentEnforcer};
+use crate::settings::RelayerPolicyConf;
+
+/// How often the policy file is checked for changes
+const POLICY_FILE_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The relayer policies currently in effect
+#[derive(Debug)]
+pub struct RelayerPolicies {
+    pub conf: RelayerPolicyConf,
+    pub address_blacklist: AddressBlacklist,
+}
+
+impl From<RelayerPolicyConf> for RelayerPolicies {
+    fn from(conf: RelayerPolicyConf) -> Self {
+        Self {
+            address_blacklist: AddressBlacklist::new(conf.address_blacklist.clone()),
+            conf,
+        }
+    }
+}
+
+impl RelayerPolicies {
+    /// Returns the reason why the message must not be relayed, if it's filtered out
+    /// by the whitelist, the blacklist or the address blacklist.
+    pub fn rejection_reason(&self, message: &HyperlaneMessage) -> Option<String> {
+        if !self.conf.whitelist.msg_matches(message, true) {
+            return Some("Message not whitelisted".to_owned());
+        }
+        if self.conf.blacklist.msg_matches(message, false) {
+            return Some("Message blacklisted".to_owned());
+        }
+        self.address_blacklist
+            .find_blacklisted_address(message)
+            .map(|address| {
+                format!(
+                    "Message involves blacklisted address {}",
+                    hex::encode(address)
+                )
+            })
+    }
+}
+
+/// Policy changes made while the relayer is running, applied on top of the policies from
+/// the relayer config in order: first the policy file, then the updates from the API.
+#[derive(Debug, Default)]
+struct PolicyOverrides {
+    /// Contents of the policy file when it was last loaded
+    file: Option<Value>,
+    /// Updates from the API, in the order they were made
+    updates: Vec<Value>,
+}
+
+impl PolicyOverrides {
+    fn apply(&self, startup_conf: &RelayerPolicyConf) -> Result<RelayerPolicyConf> {
+        let mut conf = startup_conf.clone();
+        for value in self.file.iter().chain(&self.updates) {
+            conf = RelayerPolicyConf::from_json(value.clone(), &conf)?;
+        }
+        Ok(conf)
+    }
+}
+
+/// Shared handle to the relayer policies. Updating the policies atomically swaps them for
+/// all message processors, pending messages and gas payment enforcers holding the store.
+///
+/// Updates from the API take precedence over the policy file: reloading the file keeps
+/// them in effect until the relayer restarts.
+#[derive(Debug, Clone)]
+pub struct PolicyStore {
+    current: Arc<RwLock<Arc<RelayerPolicies>>>,
+    /// Incremented every time the policies are replaced
+    version: Arc<AtomicU64>,
+    /// Policies from the relayer config, which the overrides are applied on top of
+    startup_conf: Arc<RelayerPolicyConf>,
+    overrides: Arc<Mutex<PolicyOverrides>>,
+    gas_payment_enforcers: Vec<Arc<GasPaymentEnforcer>>,
+}
+
+impl Default for PolicyStore {
+    fn default() -> Self {
+        Self::new(Default::default(), vec![])
+    }
+}
+
+impl PolicyStore {
+    pub fn new(
+        conf: RelayerPolicyConf,
+        gas_payment_enforcers: Vec<Arc<GasPaymentEnforcer>>,
+    ) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(Arc::new(conf.clone().into()))),
+            version: Default::default(),
+            startup_conf: Arc::new(conf),
+            overrides: Default::default(),
+            gas_payment_enforcers,
+        }
+    }
+
+    /// Snapshot of the policies currently in effect
+    pub async fn current(&self) -> Arc<RelayerPolicies> {
+        self.current.read().await.clone()
+    }
+
+    /// Changes whenever the policies are replaced, so that decisions made with older
+    /// policies can be revisited
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::Acquire)
+    }
+
+    /// Replace the policies currently in effect
+    async fn update(&self, conf: RelayerPolicyConf) {
+        info!(
+            whitelist=%conf.whitelist,
+            blacklist=%conf.blacklist,
+            address_blacklist=?conf.address_blacklist,
+            transaction_gas_limit=?conf.transaction_gas_limit,
+            gas_enforcement_policies=?conf.gas_payment_enforcement,
+            "Updating relayer policies"
+        );
+        for enforcer in &self.gas_payment_enforcers {
+            enforcer
+                .set_policies(conf.gas_payment_enforcement.clone())
+                .await;
+        }
+        *self.current.write().await = Arc::new(conf.into());
+        self.version.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Apply the policies set in `value` on top of the ones currently in effect. They stay
+    /// in effect across policy file reloads.
+    pub async fn update_from_json(&self, value: Value) -> Result<()> {
+        let mut overrides = self.overrides.lock().await;
+        overrides.updates.push(value);
+        match overrides.apply(&self.startup_conf) {
+            Ok(conf) => {
+                self.update(conf).await;
+                Ok(())
+            }
+            Err(err) => {
+                overrides.updates.pop();
+                Err(err)
+            }
+        }
+    }
+
+    /// Drop the updates made through the API, going back to the policies from the
+    /// relayer config and the policy file.
+    pub async fn reset_updates(&self) -> Result<()> {
+        let mut overrides = self.overrides.lock().await;
+        let conf = PolicyOverrides {
+            file: overrides.file.clone(),
+            updates: vec![],
+        }
+        .apply(&self.startup_conf)?;
+        overrides.updates.clear();
+        self.update(conf).await;
+        Ok(())
+    }
+
+    /// Watch a policy file and apply it on top of the policies from the relayer config
+    /// whenever it changes, followed by any updates from the API. Invalid files are
+    /// ignored, keeping the policies in effect.
+    pub fn watch_file(self, path: PathBuf) -> Instrumented<JoinHandle<()>> {
+        let span = info_span!("PolicyFileWatcher", path=%path.display());
+        tokio::spawn(async move {
+            let mut last_modified: Option<SystemTime> = None;
+            loop {
+                let modified = tokio::fs::metadata(&path)
+                    .await
+                    .and_then(|metadata| metadata.modified());
+                match modified {
+                    Ok(modified) if last_modified != Some(modified) => {
+                        last_modified = Some(modified);
+                        if let Err(err) = self.reload_file(&path).await {
+                            warn!(error=?err, "Failed to reload relayer policies from file");
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(err) => warn!(error=?err, "Failed to read policy file metadata"),
+                }
+                sleep(POLICY_FILE_POLL_INTERVAL).await;
+            }
+        })
+        .instrument(span)
+    }
+
+    async fn reload_file(&self, path: &Path) -> Result<()> {
+        let contents = tokio::fs::read_to_string(path)
+            .await
+            .context("Reading policy file")?;
+        let value: Value = serde_json::from_str(&contents).context("Parsing policy file")?;
+        self.apply_file(value).await
+    }
+
+    async fn apply_file(&self, value: Value) -> Result<()> {
+        let mut overrides = self.overrides.lock().await;
+        let previous = overrides.file.replace(value);
+        match overrides.apply(&self.startup_conf) {
+            Ok(conf) => {
+                self.update(conf).await;
+                Ok(())
+            }
+            Err(err) => {
+                overrides.file = previous;
+                Err(err)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_core::{HyperlaneMessage, H256};
+    use serde_json::json;
+
+    use super::{PolicyStore, RelayerPolicies};
+    use crate::settings::{matching_list::MatchingList, RelayerPolicyConf};
+
+    fn message(origin: u32) -> HyperlaneMessage {
+        HyperlaneMessage {
+            origin,
+            destination: 2,
+            sender: H256::random(),
+            recipient: H256::random(),
+            ..Default::default()
+        }
+    }
+
+    fn matching_origin(origin: u32) -> MatchingList {
+        serde_json::from_str(&format!(r#"[{{"origindomain": {origin}}}]"#)).unwrap()
+    }
+
+    #[test]
+    fn test_rejection_reason_checks_blacklist() {
+        let policies: RelayerPolicies = RelayerPolicyConf {
+            whitelist: matching_origin(1),
+            blacklist: matching_origin(1),
+            ..Default::default()
+        }
+        .into();
+        assert_eq!(
+            policies.rejection_reason(&message(1)),
+            Some("Message blacklisted".to_owned())
+        );
+
+        // A whitelisted message that isn't blacklisted must be relayed
+        let policies: RelayerPolicies = RelayerPolicyConf {
+            whitelist: matching_origin(1),
+            blacklist: matching_origin(3),
+            ..Default::default()
+        }
+        .into();
+        assert_eq!(policies.rejection_reason(&message(1)), None);
+        assert_eq!(
+            policies.rejection_reason(&message(3)),
+            Some("Message not whitelisted".to_owned())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_api_updates_take_precedence_over_policy_file() {
+        let store = PolicyStore::new(
+            RelayerPolicyConf {
+                blacklist: matching_origin(1),
+                ..Default::default()
+            },
+            vec![],
+        );
+        assert!(store
+            .current()
+            .await
+            .rejection_reason(&message(1))
+            .is_some());
+
+        store
+            .update_from_json(json!({ "whitelist": [{ "origindomain": 3 }] }))
+            .await
+            .unwrap();
+        store
+            .apply_file(json!({
+                "blacklist": [{ "origindomain": 4 }],
+                "whitelist": [{ "origindomain": 1 }],
+            }))
+            .await
+            .unwrap();
+
+        // The file replaces the blacklist from the config, but the whitelist set through
+        // the API wins over the one in the file
+        let policies = store.current().await;
+        assert_eq!(policies.rejection_reason(&message(3)), None);
+        assert_eq!(
+            policies.rejection_reason(&message(1)),
+            Some("Message not whitelisted".to_owned())
+        );
+
+        // Invalid updates are rejected without dropping the ones in effect
+        assert!(store
+            .update_from_json(json!({ "whitelist": [{ "origindomain": "not a domain" }] }))
+            .await
+            .is_err());
+        assert_eq!(store.current().await.rejection_reason(&message(3)), None);
+
+        // Resetting drops the updates from the API, but keeps the policy file
+        let version = store.version();
+        store.reset_updates().await.unwrap();
+        assert!(store.version() > version);
+        let policies = store.current().await;
+        assert_eq!(policies.rejection_reason(&message(1)), None);
+        assert_eq!(
+            policies.rejection_reason(&message(3)),
+            Some("Message not whitelisted".to_owned())
+        );
+    }
+}
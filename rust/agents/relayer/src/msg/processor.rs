@@ -8,7 +8,6 @@ use std::{
 
 use async_trait::async_trait;
 use derive_new::new;
-use ethers::utils::hex;
 use eyre::Result;
 use hyperlane_base::{
     db::{HyperlaneRocksDB, ProcessMessage},
@@ -17,28 +16,33 @@ use hyperlane_base::{
 use hyperlane_core::{HyperlaneDomain, HyperlaneMessage, QueueOperation};
 use prometheus::IntGauge;
 use tokio::sync::mpsc::UnboundedSender;
-use tracing::{debug, instrument, trace};
+use tracing::{debug, info, instrument, trace};
 
-use super::{blacklist::AddressBlacklist, metadata::AppContextClassifier, pending_message::*};
-use crate::{processor::ProcessorExt, settings::matching_list::MatchingList};
+use super::{
+    metadata::AppContextClassifier,
+    pending_message::*,
+    policies::{PolicyStore, RelayerPolicies},
+};
+use crate::processor::ProcessorExt;
 
 /// Finds unprocessed messages from an origin and submits then through a channel
 /// for to the appropriate destination.
 #[allow(clippy::too_many_arguments)]
 pub struct MessageProcessor {
-    /// A matching list of messages that should be whitelisted.
-    message_whitelist: Arc<MatchingList>,
-    /// A matching list of messages that should be blacklisted.
-    message_blacklist: Arc<MatchingList>,
-    /// Addresses that messages may not interact with.
-    address_blacklist: Arc<AddressBlacklist>,
+    /// Policies deciding which messages should be relayed, such as the
+    /// message whitelist and blacklists, and the metric app contexts.
+    policies: PolicyStore,
+    /// Version of the policies the skipped messages were last checked against
+    policies_version: u64,
+    /// Nonces of the messages skipped because of the policies, which are checked
+    /// again whenever the policies change
+    skipped_by_policies: Vec<u32>,
     metrics: MessageProcessorMetrics,
     /// channel for each destination chain to send operations (i.e. message
     /// submissions) to
     send_channels: HashMap<u32, UnboundedSender<QueueOperation>>,
     /// Needed context to send a message for each destination chain
     destination_ctxs: HashMap<u32, Arc<MessageContext>>,
-    metric_app_contexts: Vec<(MatchingList, String)>,
     nonce_iterator: ForwardBackwardIterator,
 }
 
@@ -222,8 +226,8 @@ impl Debug for MessageProcessor {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "MessageProcessor {{ message_whitelist: {:?}, message_blacklist: {:?}, address_blacklist: {:?}, nonce_iterator: {:?}}}",
-            self.message_whitelist, self.message_blacklist, self.address_blacklist, self.nonce_iterator
+            "MessageProcessor {{ policies: {:?}, nonce_iterator: {:?}}}",
+            self.policies, self.nonce_iterator
         )
     }
 }
@@ -238,6 +242,8 @@ impl ProcessorExt for MessageProcessor {
     /// One round of processing, extracted from infinite work loop for
     /// testing purposes.
     async fn tick(&mut self) -> Result<()> {
+        self.send_messages_allowed_by_new_policies().await?;
+
         // Forever, scan HyperlaneRocksDB looking for new messages to send. When criteria are
         // satisfied or the message is disqualified, push the message onto
         // self.tx_msg and then continue the scan at the next highest
@@ -249,56 +255,16 @@ impl ProcessorExt for MessageProcessor {
                 cursor = ?self.nonce_iterator,
                 "Processor working on message"
             );
-            let destination = msg.destination;
-
-            // Skip if not whitelisted.
-            if !self.message_whitelist.msg_matches(&msg, true) {
-                debug!(?msg, whitelist=?self.message_whitelist, "Message not whitelisted, skipping");
-                return Ok(());
-            }
-
-            // Skip if the message is blacklisted
-            if self.message_whitelist.msg_matches(&msg, false) {
-                debug!(?msg, blacklist=?self.message_whitelist, "Message blacklisted, skipping");
-                return Ok(());
-            }
-
-            // Skip if the message involves a blacklisted address
-            if let Some(blacklisted_address) = self.address_blacklist.find_blacklisted_address(&msg)
-            {
-                debug!(
-                    ?msg,
-                    blacklisted_address = hex::encode(blacklisted_address),
-                    "Message involves blacklisted address, skipping"
-                );
-                return Ok(());
-            }
-
-            // Skip if the message is intended for this origin
-            if destination == self.domain().id() {
-                debug!(?msg, "Message destined for self, skipping");
-                return Ok(());
-            }
 
-            // Skip if the message is intended for a destination we do not service
-            if !self.send_channels.contains_key(&destination) {
-                debug!(?msg, "Message destined for unknown domain, skipping");
+            // Skip if not whitelisted, blacklisted or involving a blacklisted address.
+            let policies = self.policies.current().await;
+            if let Some(reason) = policies.rejection_reason(&msg) {
+                debug!(?msg, %reason, "Message not allowed by the relayer policies, skipping");
+                self.skipped_by_policies.push(msg.nonce);
                 return Ok(());
             }
 
-            debug!(%msg, "Sending message to submitter");
-
-            let app_context_classifier =
-                AppContextClassifier::new(self.metric_app_contexts.clone());
-
-            let app_context = app_context_classifier.get_app_context(&msg).await?;
-            // Finally, build the submit arg and dispatch it to the submitter.
-            let pending_msg = PendingMessage::from_persisted_retries(
-                msg,
-                self.destination_ctxs[&destination].clone(),
-                app_context,
-            );
-            self.send_channels[&destination].send(Box::new(pending_msg) as QueueOperation)?;
+            self.send_message(msg, &policies).await?;
         } else {
             tokio::time::sleep(Duration::from_secs(1)).await;
         }
@@ -307,29 +273,84 @@ impl ProcessorExt for MessageProcessor {
 }
 
 impl MessageProcessor {
-    #[allow(clippy::too_many_arguments)]
     pub fn new(
         db: HyperlaneRocksDB,
-        message_whitelist: Arc<MatchingList>,
-        message_blacklist: Arc<MatchingList>,
-        address_blacklist: Arc<AddressBlacklist>,
+        policies: PolicyStore,
         metrics: MessageProcessorMetrics,
         send_channels: HashMap<u32, UnboundedSender<QueueOperation>>,
         destination_ctxs: HashMap<u32, Arc<MessageContext>>,
-        metric_app_contexts: Vec<(MatchingList, String)>,
     ) -> Self {
         Self {
-            message_whitelist,
-            message_blacklist,
-            address_blacklist,
+            policies_version: policies.version(),
+            policies,
+            skipped_by_policies: vec![],
             metrics,
             send_channels,
             destination_ctxs,
-            metric_app_contexts,
             nonce_iterator: ForwardBackwardIterator::new(Arc::new(db) as Arc<dyn ProcessMessage>),
         }
     }
 
+    async fn send_message(&self, msg: HyperlaneMessage, policies: &RelayerPolicies) -> Result<()> {
+        let destination = msg.destination;
+
+        // Skip if the message is intended for this origin
+        if destination == self.domain().id() {
+            debug!(?msg, "Message destined for self, skipping");
+            return Ok(());
+        }
+
+        // Skip if the message is intended for a destination we do not service
+        if !self.send_channels.contains_key(&destination) {
+            debug!(?msg, "Message destined for unknown domain, skipping");
+            return Ok(());
+        }
+
+        debug!(%msg, "Sending message to submitter");
+
+        let app_context_classifier =
+            AppContextClassifier::new(policies.conf.metric_app_contexts.clone());
+
+        let app_context = app_context_classifier.get_app_context(&msg).await?;
+        // Finally, build the submit arg and dispatch it to the submitter.
+        let pending_msg = PendingMessage::from_persisted_state(
+            msg,
+            self.destination_ctxs[&destination].clone(),
+            app_context,
+        );
+        self.send_channels[&destination].send(Box::new(pending_msg) as QueueOperation)?;
+        Ok(())
+    }
+
+    /// Checks the messages skipped because of the policies again if the policies
+    /// changed since, and sends the ones they now allow to the submitters.
+    async fn send_messages_allowed_by_new_policies(&mut self) -> Result<()> {
+        let version = self.policies.version();
+        if version == self.policies_version {
+            return Ok(());
+        }
+        self.policies_version = version;
+        let policies = self.policies.current().await;
+        let db = self.nonce_iterator.high_nonce_iter.db.clone();
+        let mut still_skipped = vec![];
+        for nonce in std::mem::take(&mut self.skipped_by_policies) {
+            let Some(msg) = db.retrieve_message_by_nonce(nonce)? else {
+                continue;
+            };
+            if db.retrieve_processed_by_nonce(nonce)?.unwrap_or(false) {
+                continue;
+            }
+            if policies.rejection_reason(&msg).is_some() {
+                still_skipped.push(nonce);
+                continue;
+            }
+            info!(?msg, "Message allowed by the updated relayer policies");
+            self.send_message(msg, &policies).await?;
+        }
+        self.skipped_by_policies = still_skipped;
+        Ok(())
+    }
+
     async fn try_get_unprocessed_message(&mut self) -> Result<Option<HyperlaneMessage>> {
         trace!(nonce_iterator=?self.nonce_iterator, "Trying to get the next processor message");
         let next_message = self
@@ -381,15 +402,16 @@ impl MessageProcessorMetrics {
 
 #[cfg(test)]
 mod test {
-    use std::time::Instant;
+    use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
     use crate::{
         merkle_tree::builder::MerkleTreeBuilder,
         msg::{
-            gas_payment::GasPaymentEnforcer,
-            metadata::{BaseMetadataBuilder, IsmAwareAppContextClassifier},
+            gas_payment::{ledger::ProfitabilityLedger, GasPaymentEnforcer},
+            metadata::{BaseMetadataBuilder, CcipReadGateways, IsmAwareAppContextClassifier},
         },
         processor::Processor,
+        settings::RelayerPolicyConf,
     };
 
     use super::*;
@@ -397,6 +419,10 @@ mod test {
         db::{test_utils, DbResult, HyperlaneRocksDB},
         settings::{ChainConf, ChainConnectionConf, Settings},
     };
+    use hyperlane_core::{
+        ConfirmReason, MessageSubmissionData, PendingOperationQueueState, PendingOperationStatus,
+        H512, U256,
+    };
     use hyperlane_test::mocks::{MockMailboxContract, MockValidatorAnnounceContract};
     use prometheus::{IntCounter, Registry};
     use tokio::{
@@ -463,6 +489,8 @@ mod test {
         );
         let destination_chain_conf = settings.chain_setup(destination_domain).unwrap();
         let core_metrics = CoreMetrics::new("dummy_relayer", 37582, Registry::new()).unwrap();
+        let ccip_read_gateways =
+            Arc::new(CcipReadGateways::new(&core_metrics, Default::default()).unwrap());
         BaseMetadataBuilder::new(
             origin_domain.clone(),
             destination_chain_conf.clone(),
@@ -471,7 +499,11 @@ mod test {
             false,
             Arc::new(core_metrics),
             db.clone(),
-            IsmAwareAppContextClassifier::new(Arc::new(MockMailboxContract::default()), vec![]),
+            IsmAwareAppContextClassifier::new(
+                Arc::new(MockMailboxContract::default()),
+                Default::default(),
+            ),
+            ccip_read_gateways,
         )
     }
 
@@ -486,7 +518,16 @@ mod test {
             origin_db: db.clone(),
             metadata_builder: Arc::new(base_metadata_builder),
             origin_gas_payment_enforcer: Arc::new(GasPaymentEnforcer::new([], db.clone())),
-            transaction_gas_limit: Default::default(),
+            ledger: Arc::new(
+                ProfitabilityLedger::new(
+                    &CoreMetrics::new("dummy_relayer", 37582, Registry::new()).unwrap(),
+                    Default::default(),
+                    [origin_domain, destination_domain],
+                )
+                .unwrap(),
+            ),
+            policies: Default::default(),
+            skip_transaction_gas_limit: false,
             metrics: dummy_submission_metrics(),
         });
 
@@ -495,12 +536,9 @@ mod test {
             MessageProcessor::new(
                 db.clone(),
                 Default::default(),
-                Default::default(),
-                Default::default(),
                 dummy_processor_metrics(origin_domain.id()),
                 HashMap::from([(destination_domain.id(), send_channel)]),
                 HashMap::from([(destination_domain.id(), message_context)]),
-                vec![],
             ),
             receive_channel,
         )
@@ -654,6 +692,89 @@ mod test {
         .await;
     }
 
+    #[tokio::test]
+    async fn test_messages_skipped_by_policies_are_sent_once_allowed() {
+        test_utils::run_test_db(|db| async move {
+            let origin_domain = dummy_domain(0, "dummy_origin_domain");
+            let destination_domain = dummy_domain(1, "dummy_destination_domain");
+            let db = HyperlaneRocksDB::new(&origin_domain, db);
+            let message = dummy_hyperlane_message(&destination_domain, 0);
+            add_db_entry(&db, &message, 0);
+
+            let policies = PolicyStore::new(
+                RelayerPolicyConf {
+                    blacklist: serde_json::from_str(r#"[{"destinationdomain": 1}]"#).unwrap(),
+                    ..Default::default()
+                },
+                vec![],
+            );
+            let (mut processor, mut receive_channel) =
+                dummy_message_processor(&origin_domain, &destination_domain, &db);
+            processor.policies_version = policies.version();
+            processor.policies = policies.clone();
+
+            processor.tick().await.unwrap();
+            assert!(receive_channel.try_recv().is_err());
+
+            policies
+                .update_from_json(serde_json::json!({ "blacklist": null }))
+                .await
+                .unwrap();
+            processor.tick().await.unwrap();
+            assert_eq!(receive_channel.try_recv().unwrap().id(), message.id());
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_queue_state_is_rehydrated_from_db() {
+        test_utils::run_test_db(|db| async move {
+            let origin_domain = dummy_domain(0, "dummy_origin_domain");
+            let destination_domain = dummy_domain(1, "dummy_destination_domain");
+            let db = HyperlaneRocksDB::new(&origin_domain, db);
+
+            // A message that was awaiting confirmation when the relayer stopped
+            let message = dummy_hyperlane_message(&destination_domain, 0);
+            add_db_entry(&db, &message, 2);
+            let status = PendingOperationStatus::Confirm(ConfirmReason::SubmittedBySelf);
+            db.store_status_by_message_id(&message.id(), &status)
+                .unwrap();
+            let next_attempt_after = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+                + 30;
+            db.store_pending_operation_queue_state_by_message_id(
+                &message.id(),
+                &PendingOperationQueueState {
+                    next_attempt_after: Some(next_attempt_after),
+                    submitted_tx_id: Some(H512::from_low_u64_be(1)),
+                    replaced_tx_ids: vec![],
+                    submission_data: Some(MessageSubmissionData {
+                        metadata: vec![],
+                        gas_limit: U256::from(100_000u64),
+                    }),
+                },
+            )
+            .unwrap();
+
+            let pending_messages =
+                get_first_n_operations_from_processor(&origin_domain, &destination_domain, &db, 1)
+                    .await;
+
+            // The persisted schedule takes precedence over the backoff derived from the retry count
+            let pm = &pending_messages[0];
+            assert_eq!(pm.status(), status);
+            let actual_delay = pm
+                .next_attempt_after()
+                .map(|instant| instant.duration_since(Instant::now()).as_secs_f32().round());
+            assert!(matches!(actual_delay, Some(d) if (29.0..=30.0).contains(&d)));
+            // The estimate the submitted transaction was built from is restored as well
+            assert_eq!(pm.get_tx_cost_estimate(), Some(U256::from(100_000u64)));
+        })
+        .await;
+    }
+
     #[tokio::test]
     async fn test_forward_backward_iterator() {
         let mut mock_db = MockDb::new();
@@ -1,6 +1,7 @@
 use std::{
     collections::{HashMap, HashSet},
     fmt::{Debug, Formatter},
+    path::PathBuf,
     sync::Arc,
 };
 
@@ -16,8 +17,8 @@ use hyperlane_base::{
     SyncOptions,
 };
 use hyperlane_core::{
-    HyperlaneDomain, HyperlaneMessage, InterchainGasPayment, MerkleTreeInsertion, QueueOperation,
-    H512, U256,
+    HyperlaneDomain, HyperlaneDomainProtocol, HyperlaneMessage, InterchainGasPayment,
+    MerkleTreeInsertion, QueueOperation, H512,
 };
 use tokio::{
     sync::{
@@ -33,15 +34,15 @@ use tracing::{error, info, info_span, instrument::Instrumented, warn, Instrument
 use crate::{
     merkle_tree::builder::MerkleTreeBuilder,
     msg::{
-        blacklist::AddressBlacklist,
-        gas_payment::GasPaymentEnforcer,
-        metadata::{BaseMetadataBuilder, IsmAwareAppContextClassifier},
+        gas_payment::{ledger::ProfitabilityLedger, token_prices::TokenPrices, GasPaymentEnforcer},
+        metadata::{BaseMetadataBuilder, CcipReadGateways, IsmAwareAppContextClassifier},
         op_submitter::{SerialSubmitter, SerialSubmitterMetrics},
         pending_message::{MessageContext, MessageSubmissionMetrics},
+        policies::PolicyStore,
         processor::{MessageProcessor, MessageProcessorMetrics},
     },
     server::{self as relayer_server, MessageRetryRequest},
-    settings::{matching_list::MatchingList, RelayerSettings},
+    settings::RelayerSettings,
 };
 use crate::{
     merkle_tree::processor::{MerkleTreeProcessor, MerkleTreeProcessorMetrics},
@@ -71,13 +72,12 @@ pub struct Relayer {
     prover_syncs: HashMap<HyperlaneDomain, Arc<RwLock<MerkleTreeBuilder>>>,
     merkle_tree_hook_syncs: HashMap<HyperlaneDomain, Arc<dyn ContractSyncer<MerkleTreeInsertion>>>,
     dbs: HashMap<HyperlaneDomain, HyperlaneRocksDB>,
-    message_whitelist: Arc<MatchingList>,
-    message_blacklist: Arc<MatchingList>,
-    address_blacklist: Arc<AddressBlacklist>,
-    transaction_gas_limit: Option<U256>,
+    policies: PolicyStore,
+    policy_config_path: Option<PathBuf>,
+    ledger: Arc<ProfitabilityLedger>,
     skip_transaction_gas_limit_for: HashSet<u32>,
     allow_local_checkpoint_syncers: bool,
-    metric_app_contexts: Vec<(MatchingList, String)>,
+    operator_api_token: Option<String>,
     core_metrics: Arc<CoreMetrics>,
     // TODO: decide whether to consolidate `agent_metrics` and `chain_metrics` into a single struct
     // or move them in `core_metrics`, like the validator metrics
@@ -91,13 +91,11 @@ impl Debug for Relayer {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Relayer {{ origin_chains: {:?}, destination_chains: {:?}, message_whitelist: {:?}, message_blacklist: {:?}, address_blacklist: {:?}, transaction_gas_limit: {:?}, skip_transaction_gas_limit_for: {:?}, allow_local_checkpoint_syncers: {:?} }}",
+            "Relayer {{ origin_chains: {:?}, destination_chains: {:?}, policies: {:?}, policy_config_path: {:?}, skip_transaction_gas_limit_for: {:?}, allow_local_checkpoint_syncers: {:?} }}",
             self.origin_chains,
             self.destination_chains,
-            self.message_whitelist,
-            self.message_blacklist,
-            self.address_blacklist,
-            self.transaction_gas_limit,
+            self.policies,
+            self.policy_config_path,
             self.skip_transaction_gas_limit_for,
             self.allow_local_checkpoint_syncers
         )
@@ -146,6 +144,7 @@ impl BaseAgent for Relayer {
                 dbs.iter()
                     .map(|(d, db)| (d.clone(), Arc::new(db.clone())))
                     .collect(),
+                false,
             )
             .await?
             .into_iter()
@@ -160,6 +159,7 @@ impl BaseAgent for Relayer {
                 dbs.iter()
                     .map(|(d, db)| (d.clone(), Arc::new(db.clone())))
                     .collect(),
+                false,
             )
             .await?
             .into_iter()
@@ -174,23 +174,20 @@ impl BaseAgent for Relayer {
                 dbs.iter()
                     .map(|(d, db)| (d.clone(), Arc::new(db.clone())))
                     .collect(),
+                false,
             )
             .await?
             .into_iter()
             .map(|(k, v)| (k, v as _))
             .collect();
 
-        let message_whitelist = Arc::new(settings.whitelist);
-        let message_blacklist = Arc::new(settings.blacklist);
-        let address_blacklist = Arc::new(AddressBlacklist::new(settings.address_blacklist));
         let skip_transaction_gas_limit_for = settings.skip_transaction_gas_limit_for;
-        let transaction_gas_limit = settings.transaction_gas_limit;
 
         info!(
-            %message_whitelist,
-            %message_blacklist,
-            ?address_blacklist,
-            ?transaction_gas_limit,
+            message_whitelist=%settings.policies.whitelist,
+            message_blacklist=%settings.policies.blacklist,
+            address_blacklist=?settings.policies.address_blacklist,
+            transaction_gas_limit=?settings.policies.transaction_gas_limit,
             ?skip_transaction_gas_limit_for,
             "Whitelist configuration"
         );
@@ -207,7 +204,7 @@ impl BaseAgent for Relayer {
             })
             .collect::<HashMap<_, _>>();
 
-        info!(gas_enforcement_policies=?settings.gas_payment_enforcement, "Gas enforcement configuration");
+        info!(gas_enforcement_policies=?settings.policies.gas_payment_enforcement, "Gas enforcement configuration");
 
         // need one of these per origin chain due to the database scoping even though
         // the config itself is the same
@@ -218,24 +215,38 @@ impl BaseAgent for Relayer {
                 (
                     domain.clone(),
                     Arc::new(GasPaymentEnforcer::new(
-                        settings.gas_payment_enforcement.clone(),
+                        settings.policies.gas_payment_enforcement.clone(),
                         dbs.get(domain).unwrap().clone(),
                     )),
                 )
             })
             .collect();
 
+        let policies = PolicyStore::new(
+            settings.policies,
+            gas_payment_enforcers.values().cloned().collect(),
+        );
+
+        let ledger = Arc::new(ProfitabilityLedger::new(
+            &core_metrics,
+            TokenPrices::new(settings.token_prices, settings.token_prices_path),
+            settings
+                .origin_chains
+                .iter()
+                .chain(&settings.destination_chains),
+        )?);
+        let ccip_read_gateways = Arc::new(CcipReadGateways::new(
+            &core_metrics,
+            settings.ccip_read_gateways,
+        )?);
+
         let mut msg_ctxs = HashMap::new();
         let mut destination_chains = HashMap::new();
         for destination in &settings.destination_chains {
             let destination_chain_setup = core.settings.chain_setup(destination).unwrap().clone();
             destination_chains.insert(destination.clone(), destination_chain_setup.clone());
-            let transaction_gas_limit: Option<U256> =
-                if skip_transaction_gas_limit_for.contains(&destination.id()) {
-                    None
-                } else {
-                    transaction_gas_limit
-                };
+            let skip_transaction_gas_limit =
+                skip_transaction_gas_limit_for.contains(&destination.id());
 
             for origin in &settings.origin_chains {
                 let db = dbs.get(origin).unwrap().clone();
@@ -249,8 +260,9 @@ impl BaseAgent for Relayer {
                     db,
                     IsmAwareAppContextClassifier::new(
                         mailboxes[destination].clone(),
-                        settings.metric_app_contexts.clone(),
+                        policies.clone(),
                     ),
+                    ccip_read_gateways.clone(),
                 );
 
                 msg_ctxs.insert(
@@ -263,7 +275,9 @@ impl BaseAgent for Relayer {
                         origin_db: dbs.get(origin).unwrap().clone(),
                         metadata_builder: Arc::new(metadata_builder),
                         origin_gas_payment_enforcer: gas_payment_enforcers[origin].clone(),
-                        transaction_gas_limit,
+                        ledger: ledger.clone(),
+                        policies: policies.clone(),
+                        skip_transaction_gas_limit,
                         metrics: MessageSubmissionMetrics::new(&core_metrics, origin, destination),
                     }),
                 );
@@ -280,13 +294,12 @@ impl BaseAgent for Relayer {
             interchain_gas_payment_syncs,
             prover_syncs,
             merkle_tree_hook_syncs,
-            message_whitelist,
-            message_blacklist,
-            address_blacklist,
-            transaction_gas_limit,
+            policies,
+            policy_config_path: settings.policy_config_path,
+            ledger,
             skip_transaction_gas_limit_for,
             allow_local_checkpoint_syncers: settings.allow_local_checkpoint_syncers,
-            metric_app_contexts: settings.metric_app_contexts,
+            operator_api_token: settings.operator_api_token,
             core_metrics,
             agent_metrics,
             chain_metrics,
@@ -313,23 +326,32 @@ impl BaseAgent for Relayer {
         // send channels by destination chain
         let mut send_channels = HashMap::with_capacity(self.destination_chains.len());
         let mut prep_queues = HashMap::with_capacity(self.destination_chains.len());
+        let mut submitter_handles = HashMap::with_capacity(self.destination_chains.len());
         for (dest_domain, dest_conf) in &self.destination_chains {
             let (send_channel, receive_channel) = mpsc::unbounded_channel::<QueueOperation>();
             send_channels.insert(dest_domain.id(), send_channel);
+            let operation_batch_config = self.core.settings.chains[dest_domain.name()]
+                .connection
+                .operation_batch_config();
             let serial_submitter = SerialSubmitter::new(
                 dest_domain.clone(),
                 receive_channel,
                 sender.clone(),
                 SerialSubmitterMetrics::new(&self.core.metrics, dest_domain),
                 // Default to submitting one message at a time if there is no batch config
-                self.core.settings.chains[dest_domain.name()]
-                    .connection
-                    .operation_batch_config()
+                operation_batch_config
                     .map(|c| c.max_batch_size)
                     .unwrap_or(1),
+                // Only EVM signers hand out sequential nonces locally, so pipelining is
+                // limited to those chains
+                operation_batch_config
+                    .filter(|_| dest_domain.domain_protocol() == HyperlaneDomainProtocol::Ethereum)
+                    .map(|c| c.max_submissions_in_flight.max(1))
+                    .unwrap_or(1),
                 task_monitor.clone(),
             );
             prep_queues.insert(dest_domain.id(), serial_submitter.prepare_queue().await);
+            submitter_handles.insert(dest_domain.id(), serial_submitter.handle());
 
             tasks.push(self.run_destination_submitter(
                 dest_domain,
@@ -373,10 +395,16 @@ impl BaseAgent for Relayer {
             );
         }
         // run server
-        let custom_routes = relayer_server::Server::new()
+        let mut relayer_server = relayer_server::Server::new()
             .with_op_retry(sender.clone())
             .with_message_queue(prep_queues)
-            .routes();
+            .with_profitability_ledger(self.ledger.clone());
+        if let Some(token) = self.operator_api_token.clone() {
+            relayer_server = relayer_server
+                .with_operator_api(token.clone(), submitter_handles, self.policies.clone())
+                .with_metadata_dry_run(token, self.msg_ctxs.values().cloned().collect());
+        }
+        let custom_routes = relayer_server.routes();
 
         let server = self
             .core
@@ -388,6 +416,12 @@ impl BaseAgent for Relayer {
             .instrument(info_span!("Relayer server"));
         tasks.push(server_task);
 
+        if let Some(path) = self.policy_config_path.clone() {
+            tasks.push(self.policies.clone().watch_file(path));
+        }
+
+        tasks.push(self.run_ledger_load());
+
         // each message process attempts to send messages from a chain
         for origin in &self.origin_chains {
             tasks.push(self.run_message_processor(
@@ -500,13 +534,10 @@ impl Relayer {
 
         let message_processor = MessageProcessor::new(
             self.dbs.get(origin).unwrap().clone(),
-            self.message_whitelist.clone(),
-            self.message_blacklist.clone(),
-            self.address_blacklist.clone(),
+            self.policies.clone(),
             metrics,
             send_channels,
             destination_ctxs,
-            self.metric_app_contexts.clone(),
         );
 
         let span = info_span!("MessageProcessor", origin=%message_processor.domain());
@@ -532,6 +563,30 @@ impl Relayer {
         processor.spawn().instrument(span)
     }
 
+    /// Loads the persisted deliveries into the profitability ledger in the background,
+    /// as this reads the whole ledger from each origin's DB
+    fn run_ledger_load(&self) -> Instrumented<JoinHandle<()>> {
+        let ledger = self.ledger.clone();
+        let dbs: Vec<_> = self.dbs.values().cloned().collect();
+        tokio::task::spawn_blocking(move || {
+            for db in dbs {
+                match ledger.load(&db) {
+                    Ok(loaded) => info!(
+                        origin=%db.domain(),
+                        loaded,
+                        "Loaded profitability ledger deliveries"
+                    ),
+                    Err(err) => error!(
+                        origin=%db.domain(),
+                        ?err,
+                        "Failed to load profitability ledger deliveries"
+                    ),
+                }
+            }
+        })
+        .instrument(info_span!("ProfitabilityLedgerLoad"))
+    }
+
     #[allow(clippy::too_many_arguments)]
     #[tracing::instrument(skip(self, serial_submitter))]
     fn run_destination_submitter(
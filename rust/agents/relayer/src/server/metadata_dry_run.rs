@@ -0,0 +1,278 @@
+//! Builds the ISM metadata for a message without submitting it, explaining each
+//! module of the recipient's ISM tree and why metadata could not be built.
+//!
+//! Base URL /metadata_dry_run
+//! Routes
+//! - GET /{message_id} - Dry run for a message indexed by the relayer
+//! - POST / - Dry run for a message given as raw bytes
+//!   eg. body {"message":"0x03000000..."}
+//!
+//! Requests must set the `Authorization: Bearer <token>` header with the operator API
+//! token. Dry runs make many RPC calls, so only a few of them run at a time and
+//! requests beyond that are rejected with 429 Too Many Requests.
+
+use std::{str::FromStr, sync::Arc};
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing, Json, Router,
+};
+use derive_new::new;
+use ethers::core::utils::hex::decode as hex_decode;
+use hyperlane_base::server::require_bearer_token;
+use hyperlane_core::{Decode, HyperlaneMessage, H256};
+use serde::Deserialize;
+use tokio::sync::Semaphore;
+use tracing::warn;
+
+use crate::msg::{
+    metadata::{dry_run_metadata, MetadataDryRun},
+    pending_message::MessageContext,
+};
+
+const METADATA_DRY_RUN_API_BASE: &str = "/metadata_dry_run";
+
+/// Maximum number of dry runs in progress at the same time
+const MAX_CONCURRENT_DRY_RUNS: usize = 2;
+
+type DryRunResponse = Result<Json<MetadataDryRun>, (StatusCode, String)>;
+
+#[derive(new, Clone)]
+pub struct MetadataDryRunApi {
+    token: String,
+    /// Contexts of the (origin, destination) pairs the relayer delivers messages between
+    contexts: Vec<Arc<MessageContext>>,
+    #[new(value = "Arc::new(Semaphore::new(MAX_CONCURRENT_DRY_RUNS))")]
+    in_progress: Arc<Semaphore>,
+}
+
+#[derive(Deserialize)]
+struct RawMessageDryRunRequest {
+    /// Hex-encoded message bytes
+    message: String,
+}
+
+impl MetadataDryRunApi {
+    pub fn router(&self) -> Router {
+        let router = Router::new()
+            .route("/", routing::post(Self::dry_run_raw_message))
+            .route("/:message_id", routing::get(Self::dry_run_message_id))
+            .with_state(self.clone());
+        require_bearer_token(router, self.token.clone())
+    }
+
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (METADATA_DRY_RUN_API_BASE, self.router())
+    }
+
+    async fn dry_run_message_id(
+        State(api): State<Self>,
+        Path(message_id): Path<String>,
+    ) -> DryRunResponse {
+        let message_id = H256::from_str(&message_id).map_err(|err| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Invalid message id: {err}"),
+            )
+        })?;
+        let message = api.find_message(message_id)?.ok_or((
+            StatusCode::NOT_FOUND,
+            format!("Message {message_id:?} has not been indexed by the relayer"),
+        ))?;
+        api.dry_run(message).await
+    }
+
+    async fn dry_run_raw_message(
+        State(api): State<Self>,
+        Json(request): Json<RawMessageDryRunRequest>,
+    ) -> DryRunResponse {
+        let bytes = hex_decode(request.message.trim_start_matches("0x")).map_err(|err| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Invalid message hex: {err}"),
+            )
+        })?;
+        let message = HyperlaneMessage::read_from(&mut bytes.as_slice())
+            .map_err(|err| (StatusCode::BAD_REQUEST, format!("Invalid message: {err}")))?;
+        api.dry_run(message).await
+    }
+
+    fn find_message(
+        &self,
+        message_id: H256,
+    ) -> Result<Option<HyperlaneMessage>, (StatusCode, String)> {
+        for context in &self.contexts {
+            if let Some(message) = context
+                .origin_db
+                .retrieve_message_by_id(&message_id)
+                .map_err(internal_error)?
+            {
+                return Ok(Some(message));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn dry_run(&self, message: HyperlaneMessage) -> DryRunResponse {
+        let _permit = self.in_progress.try_acquire().map_err(|_| {
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                "Too many metadata dry runs in progress, try again later".to_owned(),
+            )
+        })?;
+        let context = self
+            .contexts
+            .iter()
+            .find(|context| {
+                context.metadata_builder.origin_domain().id() == message.origin
+                    && context.metadata_builder.destination_domain().id() == message.destination
+            })
+            .ok_or((
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "Not relaying messages from domain {} to domain {}",
+                    message.origin, message.destination
+                ),
+            ))?;
+
+        let ism_address = context
+            .destination_mailbox
+            .recipient_ism(message.recipient)
+            .await
+            .map_err(internal_error)?;
+        dry_run_metadata(context.metadata_builder.clone(), ism_address, &message)
+            .await
+            .map(Json)
+            .map_err(internal_error)
+    }
+}
+
+fn internal_error(err: impl std::fmt::Display) -> (StatusCode, String) {
+    warn!(%err, "Metadata dry run failed");
+    (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use hyperlane_core::{utils::bytes_to_hex, Encode};
+    use serde_json::json;
+
+    use super::*;
+
+    const TOKEN: &str = "secret";
+
+    fn setup_test_server(api: MetadataDryRunApi) -> SocketAddr {
+        let app = api.router();
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        addr
+    }
+
+    fn raw_message_request(
+        addr: SocketAddr,
+        message: &HyperlaneMessage,
+    ) -> reqwest::RequestBuilder {
+        reqwest::Client::new()
+            .post(format!("http://{addr}/"))
+            .json(&json!({ "message": bytes_to_hex(&message.to_vec()) }))
+    }
+
+    fn message() -> HyperlaneMessage {
+        HyperlaneMessage {
+            origin: 1,
+            destination: 2,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_requires_token() {
+        let addr = setup_test_server(MetadataDryRunApi::new(TOKEN.to_owned(), vec![]));
+        let client = reqwest::Client::new();
+
+        let res = client
+            .get(format!("http://{addr}/{:?}", H256::repeat_byte(1)))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        let res = raw_message_request(addr, &message())
+            .bearer_auth("wrong")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_message_id() {
+        let addr = setup_test_server(MetadataDryRunApi::new(TOKEN.to_owned(), vec![]));
+        let client = reqwest::Client::new();
+
+        let res = client
+            .get(format!("http://{addr}/{:?}", H256::repeat_byte(1)))
+            .bearer_auth(TOKEN)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        let res = client
+            .get(format!("http://{addr}/not_a_message_id"))
+            .bearer_auth(TOKEN)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_raw_message() {
+        let addr = setup_test_server(MetadataDryRunApi::new(TOKEN.to_owned(), vec![]));
+
+        let res = raw_message_request(addr, &message())
+            .bearer_auth(TOKEN)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            res.text().await.unwrap(),
+            "Not relaying messages from domain 1 to domain 2"
+        );
+
+        let res = reqwest::Client::new()
+            .post(format!("http://{addr}/"))
+            .bearer_auth(TOKEN)
+            .json(&json!({ "message": "0x0300" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_dry_runs_beyond_limit() {
+        let api = MetadataDryRunApi::new(TOKEN.to_owned(), vec![]);
+        // Dry runs that are still in progress
+        let _permits = api
+            .in_progress
+            .clone()
+            .try_acquire_many_owned(MAX_CONCURRENT_DRY_RUNS as u32)
+            .unwrap();
+        let addr = setup_test_server(api);
+
+        let res = raw_message_request(addr, &message())
+            .bearer_auth(TOKEN)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+}
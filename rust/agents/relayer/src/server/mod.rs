@@ -1,17 +1,26 @@
 use axum::Router;
 use derive_new::new;
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 use tokio::sync::broadcast::Sender;
 
-use crate::msg::op_queue::OperationPriorityQueue;
+use crate::msg::{
+    gas_payment::ledger::ProfitabilityLedger, op_queue::OperationPriorityQueue,
+    op_submitter::SubmitterHandle, pending_message::MessageContext, policies::PolicyStore,
+};
 
 pub const ENDPOINT_MESSAGES_QUEUE_SIZE: usize = 1_000;
 
 pub use list_messages::*;
 pub use message_retry::*;
+pub use metadata_dry_run::*;
+pub use operator::*;
+pub use profitability::*;
 
 mod list_messages;
 mod message_retry;
+mod metadata_dry_run;
+mod operator;
+mod profitability;
 
 #[derive(new)]
 pub struct Server {
@@ -19,6 +28,12 @@ pub struct Server {
     retry_transmitter: Option<Sender<MessageRetryRequest>>,
     #[new(default)]
     op_queues: Option<HashMap<u32, OperationPriorityQueue>>,
+    #[new(default)]
+    operator: Option<OperatorApi>,
+    #[new(default)]
+    ledger: Option<Arc<ProfitabilityLedger>>,
+    #[new(default)]
+    metadata_dry_run: Option<MetadataDryRunApi>,
 }
 
 impl Server {
@@ -32,6 +47,33 @@ impl Server {
         self
     }
 
+    pub fn with_profitability_ledger(mut self, ledger: Arc<ProfitabilityLedger>) -> Self {
+        self.ledger = Some(ledger);
+        self
+    }
+
+    /// Enables the metadata dry run API. Requests must be authenticated with the operator
+    /// API token.
+    pub fn with_metadata_dry_run(
+        mut self,
+        token: String,
+        message_contexts: Vec<Arc<MessageContext>>,
+    ) -> Self {
+        self.metadata_dry_run = Some(MetadataDryRunApi::new(token, message_contexts));
+        self
+    }
+
+    /// Enables the operator API. Requests must be authenticated with the given bearer token.
+    pub fn with_operator_api(
+        mut self,
+        token: String,
+        submitters: HashMap<u32, SubmitterHandle>,
+        policies: PolicyStore,
+    ) -> Self {
+        self.operator = Some(OperatorApi::new(token, submitters, policies));
+        self
+    }
+
     /// Returns a vector of agent-specific endpoint routes to be served.
     /// Can be extended with additional routes and feature flags to enable/disable individually.
     pub fn routes(self) -> Vec<(&'static str, Router)> {
@@ -42,6 +84,15 @@ impl Server {
         if let Some(op_queues) = self.op_queues {
             routes.push(ListOperationsApi::new(op_queues).get_route());
         }
+        if let Some(operator_api) = self.operator {
+            routes.push(operator_api.get_route());
+        }
+        if let Some(ledger) = self.ledger {
+            routes.push(ProfitabilityApi::new(ledger).get_route());
+        }
+        if let Some(metadata_dry_run) = self.metadata_dry_run {
+            routes.push(metadata_dry_run.get_route());
+        }
 
         routes
     }
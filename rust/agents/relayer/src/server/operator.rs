@@ -0,0 +1,504 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing, Json, Router,
+};
+use derive_new::new;
+use ethers::utils::hex;
+use hyperlane_base::server::require_bearer_token;
+use hyperlane_core::{PendingOperationStatus, H256, U256};
+use serde::Serialize;
+use serde_json::Value;
+use std::{collections::HashMap, str::FromStr};
+
+use crate::msg::{
+    op_submitter::SubmitterHandle,
+    policies::{PolicyStore, RelayerPolicies},
+};
+
+const OPERATOR_API_BASE: &str = "/operator";
+
+/// Authenticated endpoints that allow operators to control the relayer's submitters
+/// without restarting it:
+///  - `POST /destinations/:domain/pause` and `POST /destinations/:domain/resume`
+///  - `GET /messages/:message_id` - the operation, its status and the queue it is in
+///  - `POST /messages/:message_id/drop` - removes the operation from the queues for good
+///  - `POST /messages/:message_id/prioritize` - resets the operation's backoff and moves it to the
+///    front of its queue
+///  - `GET /policies` - the whitelist, blacklists, gas payment enforcement and gas limit in effect
+///  - `PUT /policies` - applies the policies in the JSON body on top of the ones in effect. Each
+///    policy that is set replaces the one in effect, and `null` resets it to its default.
+///  - `DELETE /policies` - reverts the updates made through `PUT /policies`
+///
+/// Requests must set the `Authorization: Bearer <token>` header.
+#[derive(new, Clone)]
+pub struct OperatorApi {
+    token: String,
+    submitters: HashMap<u32, SubmitterHandle>,
+    policies: PolicyStore,
+}
+
+#[derive(Debug, Serialize)]
+struct DestinationStatus {
+    destination_domain: u32,
+    paused: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct OperationStatus {
+    destination_domain: u32,
+    queue: String,
+    destination_paused: bool,
+    /// The serialized operation. Its status includes the reason of the last failure, if any.
+    operation: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct GasPaymentEnforcementStatus {
+    policy: String,
+    matching_list: String,
+}
+
+#[derive(Debug, Serialize)]
+struct MetricAppContextStatus {
+    name: String,
+    matching_list: String,
+}
+
+/// The relayer policies in effect. Matching lists are in the same format as in the logs.
+#[derive(Debug, Serialize)]
+struct PoliciesStatus {
+    whitelist: String,
+    blacklist: String,
+    address_blacklist: Vec<String>,
+    transaction_gas_limit: Option<U256>,
+    gas_payment_enforcement: Vec<GasPaymentEnforcementStatus>,
+    metric_app_contexts: Vec<MetricAppContextStatus>,
+}
+
+impl From<&RelayerPolicies> for PoliciesStatus {
+    fn from(policies: &RelayerPolicies) -> Self {
+        let conf = &policies.conf;
+        Self {
+            whitelist: conf.whitelist.to_string(),
+            blacklist: conf.blacklist.to_string(),
+            address_blacklist: conf
+                .address_blacklist
+                .iter()
+                .map(|address| format!("0x{}", hex::encode(address)))
+                .collect(),
+            transaction_gas_limit: conf.transaction_gas_limit,
+            gas_payment_enforcement: conf
+                .gas_payment_enforcement
+                .iter()
+                .map(|enforcement| GasPaymentEnforcementStatus {
+                    policy: format!("{:?}", enforcement.policy),
+                    matching_list: enforcement.matching_list.to_string(),
+                })
+                .collect(),
+            metric_app_contexts: conf
+                .metric_app_contexts
+                .iter()
+                .map(|(matching_list, name)| MetricAppContextStatus {
+                    name: name.clone(),
+                    matching_list: matching_list.to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, (StatusCode, String)>;
+
+fn parse_message_id(message_id: &str) -> Result<H256, (StatusCode, String)> {
+    H256::from_str(message_id).map_err(|err| {
+        (
+            StatusCode::BAD_REQUEST,
+            format!("Invalid message id {message_id}: {err}"),
+        )
+    })
+}
+
+fn not_found(message_id: H256) -> (StatusCode, String) {
+    (
+        StatusCode::NOT_FOUND,
+        // operations that are being prepared, submitted or confirmed are temporarily out of the queues
+        format!("Operation {message_id:?} not found in any queue. It may be in flight, try again shortly."),
+    )
+}
+
+fn set_destination_paused(
+    submitters: &HashMap<u32, SubmitterHandle>,
+    domain: u32,
+    paused: bool,
+) -> ApiResult<DestinationStatus> {
+    let Some(submitter) = submitters.get(&domain) else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            format!("No submitter found for domain {domain}"),
+        ));
+    };
+    submitter.set_paused(paused);
+    tracing::info!(domain, paused, "Operator changed destination pause state");
+    Ok(Json(DestinationStatus {
+        destination_domain: domain,
+        paused,
+    }))
+}
+
+async fn pause_destination(
+    State(submitters): State<HashMap<u32, SubmitterHandle>>,
+    Path(domain): Path<u32>,
+) -> ApiResult<DestinationStatus> {
+    set_destination_paused(&submitters, domain, true)
+}
+
+async fn resume_destination(
+    State(submitters): State<HashMap<u32, SubmitterHandle>>,
+    Path(domain): Path<u32>,
+) -> ApiResult<DestinationStatus> {
+    set_destination_paused(&submitters, domain, false)
+}
+
+async fn message_status(
+    State(submitters): State<HashMap<u32, SubmitterHandle>>,
+    Path(message_id): Path<String>,
+) -> ApiResult<OperationStatus> {
+    let message_id = parse_message_id(&message_id)?;
+    for (domain, submitter) in submitters.iter() {
+        for queue in submitter.queues() {
+            let operation = queue
+                .queue
+                .lock()
+                .await
+                .iter()
+                .find(|op| op.0.id() == message_id)
+                .map(|op| serde_json::to_value(&op.0));
+            let Some(operation) = operation else {
+                continue;
+            };
+            let operation = operation.map_err(|err| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Error formatting operation: {err}"),
+                )
+            })?;
+            return Ok(Json(OperationStatus {
+                destination_domain: *domain,
+                queue: queue.label().to_owned(),
+                destination_paused: submitter.is_paused(),
+                operation,
+            }));
+        }
+    }
+    Err(not_found(message_id))
+}
+
+async fn drop_message(
+    State(submitters): State<HashMap<u32, SubmitterHandle>>,
+    Path(message_id): Path<String>,
+) -> Result<String, (StatusCode, String)> {
+    let message_id = parse_message_id(&message_id)?;
+    for submitter in submitters.values() {
+        for queue in submitter.queues() {
+            if let Some(mut op) = queue.remove(message_id).await {
+                // Persisting the status makes sure the operation isn't picked up again after a restart
+                op.set_status(PendingOperationStatus::Dropped);
+                tracing::info!(operation = %op, queue = queue.label(), "Operator dropped operation");
+                return Ok(format!("Dropped operation {message_id:?}"));
+            }
+        }
+    }
+    Err(not_found(message_id))
+}
+
+async fn prioritize_message(
+    State(submitters): State<HashMap<u32, SubmitterHandle>>,
+    Path(message_id): Path<String>,
+) -> Result<String, (StatusCode, String)> {
+    let message_id = parse_message_id(&message_id)?;
+    for submitter in submitters.values() {
+        for queue in submitter.queues() {
+            if queue.reprioritize(message_id).await {
+                return Ok(format!(
+                    "Moved operation {message_id:?} to the front of the {}",
+                    queue.label()
+                ));
+            }
+        }
+    }
+    Err(not_found(message_id))
+}
+
+async fn get_policies(State(policies): State<PolicyStore>) -> Json<PoliciesStatus> {
+    Json(policies.current().await.as_ref().into())
+}
+
+async fn update_policies(
+    State(policies): State<PolicyStore>,
+    Json(value): Json<Value>,
+) -> ApiResult<PoliciesStatus> {
+    policies.update_from_json(value).await.map_err(|err| {
+        (
+            StatusCode::BAD_REQUEST,
+            format!("Invalid policies: {err:?}"),
+        )
+    })?;
+    tracing::info!("Operator updated relayer policies");
+    Ok(Json(policies.current().await.as_ref().into()))
+}
+
+async fn reset_policies(State(policies): State<PolicyStore>) -> ApiResult<PoliciesStatus> {
+    policies.reset_updates().await.map_err(|err| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Error resetting policies: {err:?}"),
+        )
+    })?;
+    tracing::info!("Operator reset relayer policies");
+    Ok(Json(policies.current().await.as_ref().into()))
+}
+
+impl OperatorApi {
+    pub fn router(&self) -> Router {
+        let policies = Router::new()
+            .route(
+                "/policies",
+                routing::get(get_policies)
+                    .put(update_policies)
+                    .delete(reset_policies),
+            )
+            .with_state(self.policies.clone());
+        let router = Router::new()
+            .route(
+                "/destinations/:domain/pause",
+                routing::post(pause_destination),
+            )
+            .route(
+                "/destinations/:domain/resume",
+                routing::post(resume_destination),
+            )
+            .route("/messages/:message_id", routing::get(message_status))
+            .route("/messages/:message_id/drop", routing::post(drop_message))
+            .route(
+                "/messages/:message_id/prioritize",
+                routing::post(prioritize_message),
+            )
+            .with_state(self.submitters.clone())
+            .merge(policies);
+        require_bearer_token(router, self.token.clone())
+    }
+
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (OPERATOR_API_BASE, self.router())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::msg::op_queue::{
+        test::{dummy_metrics_and_label, MockPendingOperation},
+        OpQueue,
+    };
+
+    use super::*;
+    use hyperlane_core::{KnownHyperlaneDomain, QueueOperation};
+    use std::{
+        net::SocketAddr,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+    };
+    use tokio::sync::{self, Mutex};
+
+    const DUMMY_DOMAIN: KnownHyperlaneDomain = KnownHyperlaneDomain::Arbitrum;
+    const TOKEN: &str = "secret";
+
+    struct TestSetup {
+        addr: SocketAddr,
+        paused: Arc<AtomicBool>,
+        prepare_queue: OpQueue,
+        confirm_queue: OpQueue,
+    }
+
+    fn setup_test_server() -> TestSetup {
+        let (metrics, queue_metrics_label) = dummy_metrics_and_label();
+        let broadcaster = sync::broadcast::Sender::new(100);
+        let new_queue = || {
+            OpQueue::new(
+                metrics.clone(),
+                queue_metrics_label.clone(),
+                Arc::new(Mutex::new(broadcaster.subscribe())),
+            )
+        };
+        let paused = Arc::new(AtomicBool::new(false));
+        let (prepare_queue, submit_queue, confirm_queue) = (new_queue(), new_queue(), new_queue());
+        let handle = SubmitterHandle::new(
+            paused.clone(),
+            prepare_queue.clone(),
+            submit_queue,
+            confirm_queue.clone(),
+        );
+        let mut submitters = HashMap::new();
+        submitters.insert(DUMMY_DOMAIN as u32, handle);
+
+        let operator_api = OperatorApi::new(TOKEN.to_owned(), submitters, PolicyStore::default());
+        let (path, router) = operator_api.get_route();
+        let app = Router::new().nest(path, router);
+
+        // Running the app in the background using a test server
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+
+        TestSetup {
+            addr,
+            paused,
+            prepare_queue,
+            confirm_queue,
+        }
+    }
+
+    fn dummy_operation() -> QueueOperation {
+        Box::new(MockPendingOperation::new(10, DUMMY_DOMAIN.into()))
+    }
+
+    #[tokio::test]
+    async fn test_requests_without_token_are_rejected() {
+        let setup = setup_test_server();
+        let client = reqwest::Client::new();
+        let url = format!(
+            "http://{}{}/destinations/{}/pause",
+            setup.addr, OPERATOR_API_BASE, DUMMY_DOMAIN as u32
+        );
+
+        let response = client.post(&url).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = client.post(&url).bearer_auth("wrong").send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(!setup.paused.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn test_pause_and_resume_destination() {
+        let setup = setup_test_server();
+        let client = reqwest::Client::new();
+
+        for (action, expected) in [("pause", true), ("resume", false)] {
+            let response = client
+                .post(format!(
+                    "http://{}{}/destinations/{}/{}",
+                    setup.addr, OPERATOR_API_BASE, DUMMY_DOMAIN as u32, action
+                ))
+                .bearer_auth(TOKEN)
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(setup.paused.load(Ordering::Relaxed), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_message_status_and_drop() {
+        let setup = setup_test_server();
+        let client = reqwest::Client::new();
+        let operation = dummy_operation();
+        let message_id = operation.id();
+        setup.prepare_queue.push(dummy_operation(), None).await;
+        setup.confirm_queue.push(operation, None).await;
+
+        let response = client
+            .get(format!(
+                "http://{}{}/messages/{:?}",
+                setup.addr, OPERATOR_API_BASE, message_id
+            ))
+            .bearer_auth(TOKEN)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let status: Value = response.json().await.unwrap();
+        assert_eq!(status["destination_domain"], DUMMY_DOMAIN as u32);
+        assert_eq!(status["queue"], setup.confirm_queue.label());
+
+        let response = client
+            .post(format!(
+                "http://{}{}/messages/{:?}/drop",
+                setup.addr, OPERATOR_API_BASE, message_id
+            ))
+            .bearer_auth(TOKEN)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(setup.confirm_queue.queue.lock().await.is_empty());
+        assert_eq!(setup.prepare_queue.queue.lock().await.len(), 1);
+
+        let response = client
+            .get(format!(
+                "http://{}{}/messages/{:?}",
+                setup.addr, OPERATOR_API_BASE, message_id
+            ))
+            .bearer_auth(TOKEN)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_prioritize_message() {
+        let setup = setup_test_server();
+        let client = reqwest::Client::new();
+        let operation = dummy_operation();
+        let message_id = operation.id();
+        setup.prepare_queue.push(dummy_operation(), None).await;
+        setup.prepare_queue.push(operation, None).await;
+
+        let response = client
+            .post(format!(
+                "http://{}{}/messages/{:?}/prioritize",
+                setup.addr, OPERATOR_API_BASE, message_id
+            ))
+            .bearer_auth(TOKEN)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let mut prepare_queue = setup.prepare_queue.clone();
+        assert_eq!(prepare_queue.pop().await.unwrap().id(), message_id);
+    }
+
+    #[tokio::test]
+    async fn test_update_and_reset_policies() {
+        let setup = setup_test_server();
+        let client = reqwest::Client::new();
+        let url = format!("http://{}{}/policies", setup.addr, OPERATOR_API_BASE);
+
+        let response = client
+            .put(&url)
+            .bearer_auth(TOKEN)
+            .json(&serde_json::json!({ "addressBlacklist": "0xaa", "transactionGasLimit": "5" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let policies: Value = response.json().await.unwrap();
+        assert_eq!(policies["address_blacklist"], serde_json::json!(["0xaa"]));
+        assert!(!policies["transaction_gas_limit"].is_null());
+
+        let response = client.delete(&url).bearer_auth(TOKEN).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = client.get(&url).bearer_auth(TOKEN).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let policies: Value = response.json().await.unwrap();
+        assert_eq!(policies["address_blacklist"], serde_json::json!([]));
+        assert!(policies["transaction_gas_limit"].is_null());
+    }
+}
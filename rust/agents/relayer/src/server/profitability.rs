@@ -0,0 +1,144 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::header,
+    response::IntoResponse,
+    routing, Json, Router,
+};
+use derive_new::new;
+
+use crate::msg::gas_payment::ledger::{ProfitabilityLedger, ProfitabilityReport};
+
+const PROFITABILITY_API_BASE: &str = "/profitability";
+
+/// Serves the profitability ledger totals as JSON on `/profitability` and the
+/// per-route totals as CSV on `/profitability/csv`
+#[derive(new, Clone)]
+pub struct ProfitabilityApi {
+    ledger: Arc<ProfitabilityLedger>,
+}
+
+async fn get_report(State(ledger): State<Arc<ProfitabilityLedger>>) -> Json<ProfitabilityReport> {
+    Json(ledger.report())
+}
+
+async fn get_report_csv(State(ledger): State<Arc<ProfitabilityLedger>>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/csv")],
+        ledger.report_csv(),
+    )
+}
+
+impl ProfitabilityApi {
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/", routing::get(get_report))
+            .route("/csv", routing::get(get_report_csv))
+            .with_state(self.ledger.clone())
+    }
+
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (PROFITABILITY_API_BASE, self.router())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use axum::http::StatusCode;
+    use hyperlane_base::{
+        db::{test_utils, HyperlaneRocksDB},
+        CoreMetrics,
+    };
+    use hyperlane_core::{
+        HyperlaneDomain, HyperlaneMessage, InterchainGasExpenditure, InterchainGasPayment,
+        KnownHyperlaneDomain,
+    };
+    use prometheus::Registry;
+
+    use super::*;
+
+    fn setup_test_server() -> (SocketAddr, Arc<ProfitabilityLedger>) {
+        let metrics = CoreMetrics::new("dummy_relayer", 37582, Registry::new()).unwrap();
+        let ledger = Arc::new(ProfitabilityLedger::new(&metrics, Default::default(), []).unwrap());
+        let app = ProfitabilityApi::new(ledger.clone()).router();
+
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        (addr, ledger)
+    }
+
+    #[tokio::test]
+    async fn test_profitability_report() {
+        test_utils::run_test_db(|db| async move {
+            let (addr, ledger) = setup_test_server();
+            let origin = HyperlaneDomain::Known(KnownHyperlaneDomain::Arbitrum);
+            let db = HyperlaneRocksDB::new(&origin, db);
+            let message = HyperlaneMessage {
+                origin: origin.id(),
+                destination: KnownHyperlaneDomain::Ethereum as u32,
+                ..Default::default()
+            };
+            db.process_gas_payment(
+                InterchainGasPayment {
+                    message_id: message.id(),
+                    destination: message.destination,
+                    payment: 10.into(),
+                    gas_amount: 0.into(),
+                },
+                &Default::default(),
+            )
+            .unwrap();
+            db.process_gas_expenditure(InterchainGasExpenditure {
+                message_id: message.id(),
+                gas_used: 2.into(),
+                tokens_used: 4.into(),
+            })
+            .unwrap();
+            ledger
+                .record_delivery(&db, &message, Some("app".to_owned()))
+                .unwrap();
+            check_report(addr, &message).await;
+        })
+        .await;
+    }
+
+    async fn check_report(addr: SocketAddr, message: &HyperlaneMessage) {
+        let client = reqwest::Client::new();
+        let res = client
+            .get(format!("http://{addr}/"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let report: serde_json::Value = res.json().await.unwrap();
+        assert_eq!(report["routes"][0]["appContext"], "app");
+        assert_eq!(report["routes"][0]["payment"], "10");
+        // Unpriced messages aren't valued in USD
+        assert_eq!(report["routes"][0]["unpricedMessages"], 1);
+        assert_eq!(report["lossMakingSenders"], serde_json::json!([]));
+
+        let res = client
+            .get(format!("http://{addr}/csv"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()[header::CONTENT_TYPE], "text/csv");
+        let csv = res.text().await.unwrap();
+        assert_eq!(
+            csv.lines().nth(1),
+            Some(
+                format!(
+                    "{},{},app,1,1,2,10,4,0,0,0",
+                    message.origin, message.destination
+                )
+                .as_str()
+            )
+        );
+    }
+}
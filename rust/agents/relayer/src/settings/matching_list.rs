@@ -9,6 +9,7 @@ use std::{
 };
 
 use hyperlane_core::{config::StrOrInt, utils::hex_or_base58_to_h256, HyperlaneMessage, H256};
+use regex::Regex;
 use serde::{
     de::{Error, SeqAccess, Visitor},
     Deserialize, Deserializer,
@@ -22,6 +23,12 @@ use serde::{
 /// - wildcard "*"
 /// - single value in decimal or hex (must start with `0x`) format
 /// - list of values in decimal or hex format
+///
+/// In addition, each element can restrict
+/// - the message nonce and body length, to an inclusive `{min, max}` range
+/// - the message body, to start with one of a list of hex prefixes (e.g. a function selector)
+/// - the sender address, to match a regex against its 0x-prefixed 32 byte hex encoding
+/// - the message to not match a nested matching list, via a `not` clause
 #[derive(Debug, Default, Clone)]
 pub struct MatchingList(Option<Vec<ListElement>>);
 
@@ -46,6 +53,15 @@ impl<T: PartialEq> Filter<T> {
     }
 }
 
+impl Filter<Vec<u8>> {
+    fn matches_prefix(&self, body: &[u8]) -> bool {
+        match self {
+            Filter::Wildcard => true,
+            Filter::Enumerated(prefixes) => prefixes.iter().any(|p| body.starts_with(p)),
+        }
+    }
+}
+
 impl<T: Debug> Display for Filter<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
@@ -62,6 +78,47 @@ impl<T: Debug> Display for Filter<T> {
     }
 }
 
+/// Inclusive range of values, where either bound can be omitted
+#[derive(Debug, Default, Clone, PartialEq)]
+struct InclusiveRange {
+    min: Option<u32>,
+    max: Option<u32>,
+}
+
+impl InclusiveRange {
+    fn matches(&self, v: u32) -> bool {
+        self.min.map_or(true, |min| v >= min) && self.max.map_or(true, |max| v <= max)
+    }
+}
+
+impl Display for InclusiveRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match (self.min, self.max) {
+            (None, None) => write!(f, "*"),
+            (min, max) => {
+                if let Some(min) = min {
+                    write!(f, "{min}")?;
+                }
+                write!(f, "..=")?;
+                if let Some(max) = max {
+                    write!(f, "{max}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Regex matched against the 0x-prefixed, 32 byte, lowercase hex encoding of an address
+#[derive(Debug, Clone)]
+struct AddressRegex(Regex);
+
+impl AddressRegex {
+    fn matches(&self, addr: &H256) -> bool {
+        self.0.is_match(&format!("{addr:?}"))
+    }
+}
+
 struct MatchingListVisitor;
 impl<'de> Visitor<'de> for MatchingListVisitor {
     type Value = MatchingList;
@@ -193,6 +250,39 @@ impl<'de> Visitor<'de> for FilterVisitor<H256> {
     }
 }
 
+impl<'de> Visitor<'de> for FilterVisitor<Vec<u8>> {
+    type Value = Filter<Vec<u8>>;
+
+    fn expecting(&self, fmt: &mut Formatter) -> fmt::Result {
+        write!(
+            fmt,
+            "Expecting either a wildcard \"*\", hex bytes string, or list of hex bytes strings"
+        )
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(if v == "*" {
+            Self::Value::Wildcard
+        } else {
+            Self::Value::Enumerated(vec![parse_bytes(v)?])
+        })
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = Vec::new();
+        while let Some(i) = seq.next_element::<String>()? {
+            values.push(parse_bytes(&i)?)
+        }
+        Ok(Self::Value::Enumerated(values))
+    }
+}
+
 impl<'de> Deserialize<'de> for MatchingList {
     fn deserialize<D>(d: D) -> Result<Self, D::Error>
     where
@@ -220,6 +310,52 @@ impl<'de> Deserialize<'de> for Filter<H256> {
     }
 }
 
+impl<'de> Deserialize<'de> for Filter<Vec<u8>> {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        d.deserialize_any(FilterVisitor::<Vec<u8>>(Default::default()))
+    }
+}
+
+impl<'de> Deserialize<'de> for InclusiveRange {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct RawRange {
+            min: Option<StrOrInt>,
+            max: Option<StrOrInt>,
+        }
+
+        let raw = RawRange::deserialize(d)?;
+        Ok(Self {
+            min: raw
+                .min
+                .map(TryInto::try_into)
+                .transpose()
+                .map_err(to_serde_err)?,
+            max: raw
+                .max
+                .map(TryInto::try_into)
+                .transpose()
+                .map_err(to_serde_err)?,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for AddressRegex {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let regex = String::deserialize(d)?;
+        Regex::new(&regex).map(Self).map_err(to_serde_err)
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 #[serde(tag = "type")]
 struct ListElement {
@@ -231,18 +367,39 @@ struct ListElement {
     destination_domain: Filter<u32>,
     #[serde(default, rename = "recipientaddress")]
     recipient_address: Filter<H256>,
+    #[serde(default, rename = "senderaddressregex")]
+    sender_address_regex: Option<AddressRegex>,
+    #[serde(default)]
+    nonce: InclusiveRange,
+    #[serde(default, rename = "bodyprefix")]
+    body_prefix: Filter<Vec<u8>>,
+    #[serde(default, rename = "bodylength")]
+    body_length: InclusiveRange,
+    /// Messages that match this list are excluded, even if they match the rest of the element
+    #[serde(default)]
+    not: MatchingList,
 }
 
 impl Display for ListElement {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{originDomain: {}, senderAddress: {}, destinationDomain: {}, recipientAddress: {}}}",
+            "{{originDomain: {}, senderAddress: {}, destinationDomain: {}, recipientAddress: {}, nonce: {}, bodyPrefix: {}, bodyLength: {}",
             self.origin_domain,
             self.sender_address,
             self.destination_domain,
-            self.recipient_address
-        )
+            self.recipient_address,
+            self.nonce,
+            self.body_prefix,
+            self.body_length,
+        )?;
+        if let Some(regex) = &self.sender_address_regex {
+            write!(f, ", senderAddressRegex: {}", regex.0)?;
+        }
+        if self.not.0.is_some() {
+            write!(f, ", not: {}", self.not)?;
+        }
+        write!(f, "}}")
     }
 }
 
@@ -252,6 +409,8 @@ struct MatchInfo<'a> {
     src_addr: &'a H256,
     dst_domain: u32,
     dst_addr: &'a H256,
+    nonce: u32,
+    body: &'a [u8],
 }
 
 impl<'a> From<&'a HyperlaneMessage> for MatchInfo<'a> {
@@ -261,6 +420,8 @@ impl<'a> From<&'a HyperlaneMessage> for MatchInfo<'a> {
             src_addr: &msg.sender,
             dst_domain: msg.destination,
             dst_addr: &msg.recipient,
+            nonce: msg.nonce,
+            body: &msg.body,
         }
     }
 }
@@ -289,6 +450,14 @@ fn matches_any_rule<'a>(mut rules: impl Iterator<Item = &'a ListElement>, info:
             && rule.sender_address.matches(info.src_addr)
             && rule.destination_domain.matches(&info.dst_domain)
             && rule.recipient_address.matches(info.dst_addr)
+            && rule
+                .sender_address_regex
+                .as_ref()
+                .map_or(true, |regex| regex.matches(info.src_addr))
+            && rule.nonce.matches(info.nonce)
+            && rule.body_prefix.matches_prefix(info.body)
+            && rule.body_length.matches(info.body.len() as u32)
+            && !rule.not.matches(info, false)
     })
 }
 
@@ -314,11 +483,16 @@ fn parse_addr<E: Error>(addr_str: &str) -> Result<H256, E> {
     hex_or_base58_to_h256(addr_str).map_err(to_serde_err)
 }
 
+fn parse_bytes<E: Error>(bytes_str: &str) -> Result<Vec<u8>, E> {
+    let bytes_str = bytes_str.strip_prefix("0x").unwrap_or(bytes_str);
+    ethers::utils::hex::decode(bytes_str).map_err(to_serde_err)
+}
+
 #[cfg(test)]
 mod test {
     use hyperlane_core::{H160, H256};
 
-    use super::{Filter::*, MatchingList};
+    use super::{Filter::*, InclusiveRange, MatchingList};
     use crate::settings::matching_list::MatchInfo;
 
     #[test]
@@ -343,7 +517,9 @@ mod test {
                 src_domain: 0,
                 src_addr: &H256::default(),
                 dst_domain: 0,
-                dst_addr: &H256::default()
+                dst_addr: &H256::default(),
+                nonce: 0,
+                body: &[],
             },
             false
         ));
@@ -356,7 +532,9 @@ mod test {
                     .unwrap()
                     .into(),
                 dst_domain: 5456,
-                dst_addr: &H256::default()
+                dst_addr: &H256::default(),
+                nonce: 0,
+                body: &[],
             },
             false
         ))
@@ -396,7 +574,9 @@ mod test {
                 dst_addr: &"9d4454B023096f34B160D6B654540c56A1F81688"
                     .parse::<H160>()
                     .unwrap()
-                    .into()
+                    .into(),
+                nonce: 0,
+                body: &[],
             },
             false
         ));
@@ -409,7 +589,9 @@ mod test {
                     .unwrap()
                     .into(),
                 dst_domain: 5456,
-                dst_addr: &H256::default()
+                dst_addr: &H256::default(),
+                nonce: 0,
+                body: &[],
             },
             false
         ));
@@ -441,6 +623,8 @@ mod test {
             src_addr: &H256::default(),
             dst_domain: 0,
             dst_addr: &H256::default(),
+            nonce: 0,
+            body: &[],
         };
         // whitelist use
         assert!(MatchingList(None).matches(info, true));
@@ -448,6 +632,86 @@ mod test {
         assert!(!MatchingList(None).matches(info, false));
     }
 
+    fn match_info<'a>(
+        src_addr: &'a H256,
+        dst_addr: &'a H256,
+        nonce: u32,
+        body: &'a [u8],
+    ) -> MatchInfo<'a> {
+        MatchInfo {
+            src_domain: 34,
+            src_addr,
+            dst_domain: 5456,
+            dst_addr,
+            nonce,
+            body,
+        }
+    }
+
+    #[test]
+    fn config_with_body_and_nonce_predicates() {
+        let list: MatchingList = serde_json::from_str(
+            r#"[{"nonce": {"min": 10, "max": "20"}, "bodyprefix": ["0xdeadbeef", "cafe"], "bodylength": {"max": 8}}]"#,
+        )
+        .unwrap();
+        let elem = &list.0.as_ref().unwrap()[0];
+        assert_eq!(
+            elem.nonce,
+            InclusiveRange {
+                min: Some(10),
+                max: Some(20)
+            }
+        );
+        assert_eq!(
+            elem.body_prefix,
+            Enumerated(vec![vec![0xde, 0xad, 0xbe, 0xef], vec![0xca, 0xfe]])
+        );
+        assert_eq!(
+            elem.body_length,
+            InclusiveRange {
+                min: None,
+                max: Some(8)
+            }
+        );
+
+        let zero = H256::zero();
+        let info = |nonce, body: &'static [u8]| match_info(&zero, &zero, nonce, body);
+        assert!(list.matches(info(10, &[0xde, 0xad, 0xbe, 0xef, 0x01]), false));
+        assert!(list.matches(info(20, &[0xca, 0xfe]), false));
+        // nonce out of range
+        assert!(!list.matches(info(21, &[0xca, 0xfe]), false));
+        // prefix doesn't match
+        assert!(!list.matches(info(15, &[0xca, 0xff]), false));
+        // body too long
+        assert!(!list.matches(info(15, &[0xca, 0xfe, 0, 0, 0, 0, 0, 0, 0]), false));
+    }
+
+    #[test]
+    fn config_with_sender_regex_and_not_clause() {
+        let list: MatchingList = serde_json::from_str(
+            r#"[{"destinationdomain": 5456, "senderaddressregex": "^0x0{24}", "not": [{"bodyprefix": "0x01"}]}]"#,
+        )
+        .unwrap();
+        let evm_sender: H256 = "0x9d4454B023096f34B160D6B654540c56A1F81688"
+            .parse::<H160>()
+            .unwrap()
+            .into();
+        let non_evm_sender = H256::repeat_byte(0xab);
+        let zero = H256::zero();
+
+        assert!(list.matches(match_info(&evm_sender, &zero, 0, &[0x02]), false));
+        assert!(!list.matches(match_info(&non_evm_sender, &zero, 0, &[0x02]), false));
+        // excluded by the `not` clause
+        assert!(!list.matches(match_info(&evm_sender, &zero, 0, &[0x01, 0x02]), false));
+    }
+
+    #[test]
+    fn invalid_predicates_are_rejected() {
+        assert!(serde_json::from_str::<MatchingList>(r#"[{"bodyprefix": "0xnothex"}]"#).is_err());
+        assert!(serde_json::from_str::<MatchingList>(r#"[{"senderaddressregex": "("}]"#).is_err());
+        assert!(serde_json::from_str::<MatchingList>(r#"[{"nonce": {"min": -1}}]"#).is_err());
+    }
+
     #[test]
     fn supports_base58() {
         serde_json::from_str::<MatchingList>(
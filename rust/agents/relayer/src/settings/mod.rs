@@ -4,7 +4,11 @@
 //! and validations it defines are not applied here, we should mirror them.
 //! ANY CHANGES HERE NEED TO BE REFLECTED IN THE TYPESCRIPT SDK.
 
-use std::{collections::HashSet, path::PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    time::Duration,
+};
 
 use convert_case::Case;
 use derive_more::{AsMut, AsRef, Deref, DerefMut};
@@ -17,7 +21,7 @@ use hyperlane_base::{
         Settings,
     },
 };
-use hyperlane_core::{cfg_unwrap_all, config::*, HyperlaneDomain, U256};
+use hyperlane_core::{cfg_unwrap_all, config::*, FixedPointNumber, HyperlaneDomain, U256};
 use itertools::Itertools;
 use serde::Deserialize;
 use serde_json::Value;
@@ -41,6 +45,32 @@ pub struct RelayerSettings {
     pub origin_chains: HashSet<HyperlaneDomain>,
     /// Chains to relay messages to
     pub destination_chains: HashSet<HyperlaneDomain>,
+    /// Policies deciding which messages to relay, which can be reloaded at runtime.
+    pub policies: RelayerPolicyConf,
+    /// Path to a JSON file with policies that override `policies`. The file is watched
+    /// for changes while the relayer is running.
+    pub policy_config_path: Option<PathBuf>,
+    /// List of domain ids to skip transaction gas for.
+    pub skip_transaction_gas_limit_for: HashSet<u32>,
+    /// If true, allows local storage based checkpoint syncers.
+    /// Not intended for production use.
+    pub allow_local_checkpoint_syncers: bool,
+    /// Bearer token for the operator and metadata dry run APIs. They are disabled if not
+    /// specified.
+    pub operator_api_token: Option<String>,
+    /// Native token prices by domain id, used to value payments and delivery costs
+    /// in the profitability ledger.
+    pub token_prices: HashMap<u32, TokenPriceConf>,
+    /// Path to a JSON file with native token prices by domain id, e.g. written by a
+    /// price feed. Prices in the file take precedence over `token_prices`.
+    pub token_prices_path: Option<PathBuf>,
+    /// Settings for querying the gateways of CCIP-read ISMs
+    pub ccip_read_gateways: CcipReadGatewayConf,
+}
+
+/// Relayer policies that can be changed while the relayer is running
+#[derive(Debug, Clone, Default)]
+pub struct RelayerPolicyConf {
     /// The gas payment enforcement policies
     pub gas_payment_enforcement: Vec<GasPaymentEnforcementConf>,
     /// Filter for what messages to relay.
@@ -54,15 +84,23 @@ pub struct RelayerSettings {
     /// This is optional. If not specified, any amount of gas will be valid, otherwise this
     /// is the max allowed gas in wei to relay a transaction.
     pub transaction_gas_limit: Option<U256>,
-    /// List of domain ids to skip transaction gas for.
-    pub skip_transaction_gas_limit_for: HashSet<u32>,
-    /// If true, allows local storage based checkpoint syncers.
-    /// Not intended for production use.
-    pub allow_local_checkpoint_syncers: bool,
     /// App contexts used for metrics.
     pub metric_app_contexts: Vec<(MatchingList, String)>,
 }
 
+impl RelayerPolicyConf {
+    /// Parse policies from a JSON object using the same keys as the relayer config, such as
+    /// the contents of a policy file. Policies that are set replace their value in `base`,
+    /// `null` resets them to their default and policies that aren't set keep their value.
+    pub fn from_json(value: Value, base: &RelayerPolicyConf) -> ConfigResult<Self> {
+        let mut err = ConfigParsingError::default();
+        let value = recase_json_value(value, Case::Flat);
+        let p = ValueParser::new(ConfigPath::default(), &value);
+        let policies = parse_policies(&p, &mut err, base.clone());
+        err.into_result(policies)
+    }
+}
+
 /// Config for gas payment enforcement
 #[derive(Debug, Clone, Default)]
 pub struct GasPaymentEnforcementConf {
@@ -87,6 +125,65 @@ pub enum GasPaymentEnforcementPolicy {
         gas_fraction_numerator: u64,
         gas_fraction_denominator: u64,
     },
+    /// The payment, converted to USD using the origin token price, covers the
+    /// estimated cost of delivery, converted using the destination token price,
+    /// times a margin.
+    UsdCost {
+        /// The payment must be at least the cost times this value
+        margin: FixedPointNumber,
+        /// Native token prices by domain id
+        token_prices: HashMap<u32, TokenPriceConf>,
+        /// Path to a JSON file with native token prices by domain id, e.g. written by
+        /// a price feed. Prices in the file take precedence over `token_prices`.
+        token_prices_path: Option<PathBuf>,
+    },
+}
+
+/// Settings for querying CCIP-read gateways
+#[derive(Debug, Clone)]
+pub struct CcipReadGatewayConf {
+    /// Timeout for a single request to a gateway
+    pub request_timeout: Duration,
+    /// Number of consecutive failures after which a gateway is skipped
+    pub failure_threshold: u32,
+    /// How long a gateway is skipped for before it is tried again
+    pub cooldown: Duration,
+    /// How long gateway responses are reused for
+    pub cache_ttl: Duration,
+}
+
+impl Default for CcipReadGatewayConf {
+    fn default() -> Self {
+        Self {
+            request_timeout: Duration::from_secs(10),
+            failure_threshold: 3,
+            cooldown: Duration::from_secs(60),
+            cache_ttl: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+/// The highest number of decimals whose scale, 10^decimals, fits in a U256
+const MAX_TOKEN_DECIMALS: u32 = 77;
+
+/// The price of a chain's native token
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenPriceConf {
+    /// Price of one whole token in USD
+    pub usd: FixedPointNumber,
+    /// Number of decimals of the token's smallest unit
+    pub decimals: u32,
+}
+
+impl TokenPriceConf {
+    /// Converts an amount of the token's smallest unit to USD
+    pub fn to_usd(&self, amount: U256) -> eyre::Result<FixedPointNumber> {
+        let scale = U256::from(10)
+            .checked_pow(self.decimals.into())
+            .ok_or_else(|| eyre!("Token decimals {} are too large", self.decimals))?;
+        let scale = FixedPointNumber::try_from(scale)?;
+        Ok(FixedPointNumber::try_from(amount)? * self.usd.clone() / scale)
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -125,89 +222,12 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
             .parse_from_str("Expected database path")
             .unwrap_or_else(|| std::env::current_dir().unwrap().join("hyperlane_db"));
 
-        let (raw_gas_payment_enforcement_path, raw_gas_payment_enforcement) = p
-            .get_opt_key("gasPaymentEnforcement")
-            .take_config_err_flat(&mut err)
-            .and_then(parse_json_array)
-            .unwrap_or_else(|| (&p.cwp + "gas_payment_enforcement", Value::Array(vec![])));
-
-        let gas_payment_enforcement_parser = ValueParser::new(
-            raw_gas_payment_enforcement_path,
-            &raw_gas_payment_enforcement,
-        );
-        let mut gas_payment_enforcement = gas_payment_enforcement_parser.into_array_iter().map(|itr| {
-            itr.filter_map(|policy| {
-                let policy_type = policy.chain(&mut err).get_opt_key("type").parse_string().end();
-                let minimum_is_defined = matches!(policy.get_opt_key("minimum"), Ok(Some(_)));
-
-                let matching_list = policy.chain(&mut err).get_opt_key("matchingList").and_then(parse_matching_list).unwrap_or_default();
-
-                let parse_minimum = |p| GasPaymentEnforcementPolicy::Minimum { payment: p };
-                match policy_type {
-                    Some("minimum") => policy.chain(&mut err).get_opt_key("payment").parse_u256().end().map(parse_minimum),
-                    None if minimum_is_defined => policy.chain(&mut err).get_opt_key("payment").parse_u256().end().map(parse_minimum),
-                    Some("none") | None => Some(GasPaymentEnforcementPolicy::None),
-                    Some("onChainFeeQuoting") => {
-                        let gas_fraction = policy.chain(&mut err)
-                            .get_opt_key("gasFraction")
-                            .parse_string()
-                            .map(|v| v.replace(' ', ""))
-                            .unwrap_or_else(|| "1/2".to_owned());
-                        let (numerator, denominator) = gas_fraction
-                            .split_once('/')
-                            .ok_or_else(|| eyre!("Invalid `gas_fraction` for OnChainFeeQuoting gas payment enforcement policy; expected `numerator / denominator`"))
-                            .take_err(&mut err, || &policy.cwp + "gas_fraction")
-                            .unwrap_or(("1", "1"));
-
-                        Some(GasPaymentEnforcementPolicy::OnChainFeeQuoting {
-                            gas_fraction_numerator: numerator
-                                .parse()
-                                .context("Error parsing gas fraction numerator")
-                                .take_err(&mut err, || &policy.cwp + "gas_fraction")
-                                .unwrap_or(1),
-                            gas_fraction_denominator: denominator
-                                .parse()
-                                .context("Error parsing gas fraction denominator")
-                                .take_err(&mut err, || &policy.cwp + "gas_fraction")
-                                .unwrap_or(1),
-                        })
-                    }
-                    Some(pt) => Err(eyre!("Unknown gas payment enforcement policy type `{pt}`"))
-                        .take_err(&mut err, || cwp + "type"),
-                }.map(|policy| GasPaymentEnforcementConf {
-                    policy,
-                    matching_list,
-                })
-            }).collect_vec()
-        }).unwrap_or_default();
-
-        if gas_payment_enforcement.is_empty() {
-            gas_payment_enforcement.push(GasPaymentEnforcementConf::default());
-        }
-
-        let whitelist = p
-            .chain(&mut err)
-            .get_opt_key("whitelist")
-            .and_then(parse_matching_list)
-            .unwrap_or_default();
-        let blacklist = p
-            .chain(&mut err)
-            .get_opt_key("blacklist")
-            .and_then(parse_matching_list)
-            .unwrap_or_default();
-
-        let address_blacklist = p
-            .chain(&mut err)
-            .get_opt_key("addressBlacklist")
-            .parse_string()
-            .end()
-            .map(|str| parse_address_list(str, &mut err, || &p.cwp + "address_blacklist"))
-            .unwrap_or_default();
+        let policies = parse_policies(&p, &mut err, RelayerPolicyConf::default());
 
-        let transaction_gas_limit = p
+        let policy_config_path = p
             .chain(&mut err)
-            .get_opt_key("transactionGasLimit")
-            .parse_u256()
+            .get_opt_key("policyConfigPath")
+            .parse_from_str("Expected policy config path")
             .end();
 
         let skip_transaction_gas_limit_for_names: HashSet<&str> = p
@@ -223,6 +243,33 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
             .parse_bool()
             .unwrap_or(false);
 
+        let operator_api_token = p
+            .chain(&mut err)
+            .get_opt_key("operatorApiToken")
+            .parse_string()
+            .end()
+            .map(str::to_owned);
+
+        let token_prices = p
+            .chain(&mut err)
+            .get_opt_key("tokenPrices")
+            .end()
+            .map(|p| parse_token_prices(p, &mut err))
+            .unwrap_or_default();
+
+        let token_prices_path = p
+            .chain(&mut err)
+            .get_opt_key("tokenPricesPath")
+            .parse_from_str("Expected token prices file path")
+            .end();
+
+        let ccip_read_gateways = p
+            .chain(&mut err)
+            .get_opt_key("ccipReadGateways")
+            .end()
+            .map(|p| parse_ccip_read_gateways(p, &mut err))
+            .unwrap_or_default();
+
         cfg_unwrap_all!(cwp, err: [base]);
 
         let skip_transaction_gas_limit_for = skip_transaction_gas_limit_for_names
@@ -247,49 +294,295 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
             })
             .collect();
 
-        let (raw_metric_app_contexts_path, raw_metric_app_contexts) = p
-            .get_opt_key("metricAppContexts")
-            .take_config_err_flat(&mut err)
-            .and_then(parse_json_array)
-            .unwrap_or_else(|| (&p.cwp + "metric_app_contexts", Value::Array(vec![])));
-
-        let metric_app_contexts_parser =
-            ValueParser::new(raw_metric_app_contexts_path, &raw_metric_app_contexts);
-        let metric_app_contexts = metric_app_contexts_parser
-            .into_array_iter()
-            .map(|itr| {
-                itr.filter_map(|policy| {
-                    let name = policy.chain(&mut err).get_key("name").parse_string().end();
-
-                    let matching_list = policy
-                        .chain(&mut err)
-                        .get_key("matchingList")
-                        .and_then(parse_matching_list)
-                        .unwrap_or_default();
-
-                    name.map(|name| (matching_list, name.to_owned()))
-                })
-                .collect_vec()
-            })
-            .unwrap_or_default();
-
         err.into_result(RelayerSettings {
             base,
             db,
             origin_chains: relay_chains.clone(),
             destination_chains: relay_chains,
-            gas_payment_enforcement,
-            whitelist,
-            blacklist,
-            address_blacklist,
-            transaction_gas_limit,
+            policies,
+            policy_config_path,
             skip_transaction_gas_limit_for,
             allow_local_checkpoint_syncers,
-            metric_app_contexts,
+            operator_api_token,
+            token_prices,
+            token_prices_path,
+            ccip_read_gateways,
+        })
+    }
+}
+
+fn parse_policies(
+    p: &ValueParser,
+    err: &mut ConfigParsingError,
+    base: RelayerPolicyConf,
+) -> RelayerPolicyConf {
+    let mut gas_payment_enforcement = parse_policy(
+        p,
+        err,
+        "gasPaymentEnforcement",
+        base.gas_payment_enforcement,
+        |v, err| {
+            parse_json_array(v)
+                .map(|(path, raw)| parse_gas_payment_enforcement(ValueParser::new(path, &raw), err))
+        },
+    );
+
+    if gas_payment_enforcement.is_empty() {
+        gas_payment_enforcement.push(GasPaymentEnforcementConf::default());
+    }
+
+    let whitelist = parse_policy(p, err, "whitelist", base.whitelist, |v, err| {
+        parse_matching_list(v).take_config_err(err)
+    });
+    let blacklist = parse_policy(p, err, "blacklist", base.blacklist, |v, err| {
+        parse_matching_list(v).take_config_err(err)
+    });
+
+    let address_blacklist = parse_policy(
+        p,
+        err,
+        "addressBlacklist",
+        base.address_blacklist,
+        |v, err| {
+            v.parse_string()
+                .take_config_err(err)
+                .map(|str| parse_address_list(str, err, || &p.cwp + "address_blacklist"))
+        },
+    );
+
+    let transaction_gas_limit = parse_policy(
+        p,
+        err,
+        "transactionGasLimit",
+        base.transaction_gas_limit,
+        |v, err| v.parse_u256().take_config_err(err).map(Some),
+    );
+
+    let metric_app_contexts = parse_policy(
+        p,
+        err,
+        "metricAppContexts",
+        base.metric_app_contexts,
+        |v, err| {
+            parse_json_array(v)
+                .map(|(path, raw)| parse_metric_app_contexts(ValueParser::new(path, &raw), err))
+        },
+    );
+
+    RelayerPolicyConf {
+        gas_payment_enforcement,
+        whitelist,
+        blacklist,
+        address_blacklist,
+        transaction_gas_limit,
+        metric_app_contexts,
+    }
+}
+
+/// Parses the policy at `key`, which replaces the one in `base` as a whole if it's set,
+/// e.g. an empty list clears it. Setting it to `null` resets it to its default.
+fn parse_policy<'v, T: Default>(
+    p: &ValueParser<'v>,
+    err: &mut ConfigParsingError,
+    key: &str,
+    base: T,
+    parse: impl FnOnce(ValueParser<'v>, &mut ConfigParsingError) -> Option<T>,
+) -> T {
+    match p.get_opt_key(key).take_config_err_flat(err) {
+        None => base,
+        Some(ValueParser {
+            val: Value::Null, ..
+        }) => T::default(),
+        Some(v) => parse(v, err).unwrap_or(base),
+    }
+}
+
+fn parse_gas_payment_enforcement(
+    p: ValueParser,
+    err: &mut ConfigParsingError,
+) -> Vec<GasPaymentEnforcementConf> {
+    p.into_array_iter().map(|itr| {
+        itr.filter_map(|policy| {
+            let policy_type = policy.chain(err).get_opt_key("type").parse_string().end();
+            let minimum_is_defined = matches!(policy.get_opt_key("minimum"), Ok(Some(_)));
+
+            let matching_list = policy.chain(err).get_opt_key("matchingList").and_then(parse_matching_list).unwrap_or_default();
+
+            let parse_minimum = |p| GasPaymentEnforcementPolicy::Minimum { payment: p };
+            match policy_type {
+                Some("minimum") => policy.chain(err).get_opt_key("payment").parse_u256().end().map(parse_minimum),
+                None if minimum_is_defined => policy.chain(err).get_opt_key("payment").parse_u256().end().map(parse_minimum),
+                Some("none") | None => Some(GasPaymentEnforcementPolicy::None),
+                Some("onChainFeeQuoting") => {
+                    let gas_fraction = policy.chain(err)
+                        .get_opt_key("gasFraction")
+                        .parse_string()
+                        .map(|v| v.replace(' ', ""))
+                        .unwrap_or_else(|| "1/2".to_owned());
+                    let (numerator, denominator) = gas_fraction
+                        .split_once('/')
+                        .ok_or_else(|| eyre!("Invalid `gas_fraction` for OnChainFeeQuoting gas payment enforcement policy; expected `numerator / denominator`"))
+                        .take_err(err, || &policy.cwp + "gas_fraction")
+                        .unwrap_or(("1", "1"));
+
+                    Some(GasPaymentEnforcementPolicy::OnChainFeeQuoting {
+                        gas_fraction_numerator: numerator
+                            .parse()
+                            .context("Error parsing gas fraction numerator")
+                            .take_err(err, || &policy.cwp + "gas_fraction")
+                            .unwrap_or(1),
+                        gas_fraction_denominator: denominator
+                            .parse()
+                            .context("Error parsing gas fraction denominator")
+                            .take_err(err, || &policy.cwp + "gas_fraction")
+                            .unwrap_or(1),
+                    })
+                }
+                Some("usdCost") => {
+                    let margin = policy.chain(err)
+                        .get_opt_key("margin")
+                        .and_then(parse_fixed_point)
+                        .unwrap_or_else(|| FixedPointNumber::from(1u32));
+                    let token_prices = policy.chain(err)
+                        .get_opt_key("tokenPrices")
+                        .end()
+                        .map(|p| parse_token_prices(p, err))
+                        .unwrap_or_default();
+                    let token_prices_path = policy.chain(err)
+                        .get_opt_key("tokenPricesPath")
+                        .parse_from_str("Expected token prices file path")
+                        .end();
+                    if token_prices.is_empty() && token_prices_path.is_none() {
+                        err.push(
+                            &policy.cwp + "token_prices",
+                            eyre!("Expected `tokenPrices` or `tokenPricesPath` for UsdCost gas payment enforcement policy"),
+                        );
+                    }
+
+                    Some(GasPaymentEnforcementPolicy::UsdCost {
+                        margin,
+                        token_prices,
+                        token_prices_path,
+                    })
+                }
+                Some(pt) => Err(eyre!("Unknown gas payment enforcement policy type `{pt}`"))
+                    .take_err(err, || &policy.cwp + "type"),
+            }.map(|policy| GasPaymentEnforcementConf {
+                policy,
+                matching_list,
+            })
+        }).collect_vec()
+    }).unwrap_or_default()
+}
+
+/// Parses native token prices keyed by domain id, e.g.
+/// `{"1": {"usd": "3000.5", "decimals": 18}}`
+pub(crate) fn parse_token_prices(
+    p: ValueParser,
+    err: &mut ConfigParsingError,
+) -> HashMap<u32, TokenPriceConf> {
+    p.chain(err)
+        .into_obj_iter()
+        .map(|itr| {
+            itr.filter_map(|(domain, price)| {
+                let domain = domain
+                    .parse::<u32>()
+                    .context("Expected token prices to be keyed by domain id")
+                    .take_err(err, || price.cwp.clone());
+                let usd = price
+                    .chain(err)
+                    .get_key("usd")
+                    .and_then(parse_fixed_point)
+                    .end();
+                let decimals = price
+                    .chain(err)
+                    .get_key("decimals")
+                    .parse_u32()
+                    .end()
+                    .and_then(|decimals| {
+                        if decimals > MAX_TOKEN_DECIMALS {
+                            err.push(
+                                &price.cwp + "decimals",
+                                eyre!("Expected at most {MAX_TOKEN_DECIMALS} decimals"),
+                            );
+                            return None;
+                        }
+                        Some(decimals)
+                    });
+                Some((
+                    domain?,
+                    TokenPriceConf {
+                        usd: usd?,
+                        decimals: decimals?,
+                    },
+                ))
+            })
+            .collect()
         })
+        .unwrap_or_default()
+}
+
+/// Parses the CCIP-read gateway settings, with durations in seconds. Unset values keep
+/// their default.
+fn parse_ccip_read_gateways(p: ValueParser, err: &mut ConfigParsingError) -> CcipReadGatewayConf {
+    let default = CcipReadGatewayConf::default();
+    let mut duration = |key: &str| {
+        p.chain(err)
+            .get_opt_key(key)
+            .parse_u64()
+            .end()
+            .map(Duration::from_secs)
+    };
+    let request_timeout = duration("requestTimeout").unwrap_or(default.request_timeout);
+    let cooldown = duration("cooldown").unwrap_or(default.cooldown);
+    let cache_ttl = duration("cacheTtl").unwrap_or(default.cache_ttl);
+    let failure_threshold = p
+        .chain(err)
+        .get_opt_key("failureThreshold")
+        .parse_u32()
+        .unwrap_or(default.failure_threshold);
+    CcipReadGatewayConf {
+        request_timeout,
+        failure_threshold,
+        cooldown,
+        cache_ttl,
+    }
+}
+
+/// Parses a decimal number from a JSON number or string without losing precision
+fn parse_fixed_point(p: ValueParser) -> ConfigResult<FixedPointNumber> {
+    match p.val {
+        Value::Number(n) => n
+            .to_string()
+            .parse()
+            .context("Expected decimal number")
+            .into_config_result(|| p.cwp.clone()),
+        _ => p.parse_from_str("Expected decimal number"),
     }
 }
 
+fn parse_metric_app_contexts(
+    p: ValueParser,
+    err: &mut ConfigParsingError,
+) -> Vec<(MatchingList, String)> {
+    p.into_array_iter()
+        .map(|itr| {
+            itr.filter_map(|policy| {
+                let name = policy.chain(err).get_key("name").parse_string().end();
+
+                let matching_list = policy
+                    .chain(err)
+                    .get_key("matchingList")
+                    .and_then(parse_matching_list)
+                    .unwrap_or_default();
+
+                name.map(|name| (matching_list, name.to_owned()))
+            })
+            .collect_vec()
+        })
+        .unwrap_or_default()
+}
+
 fn parse_json_array(p: ValueParser) -> Option<(ConfigPath, Value)> {
     let mut err = ConfigParsingError::default();
 
@@ -332,6 +625,7 @@ fn parse_address_list(
     err_path: impl Fn() -> ConfigPath,
 ) -> Vec<Vec<u8>> {
     str.split(',')
+        .filter(|s| !s.trim().is_empty())
         .filter_map(|s| {
             let mut s = s.trim().to_owned();
             if let Some(stripped) = s.strip_prefix("0x") {
@@ -374,4 +668,115 @@ mod test {
         assert_eq!(res, vec![valid_address1, valid_address2]);
         assert!(!err.is_ok());
     }
+
+    #[test]
+    fn test_policies_from_json_apply_on_top_of_base() {
+        let base = RelayerPolicyConf {
+            address_blacklist: vec![vec![0xaa]],
+            transaction_gas_limit: Some(100.into()),
+            ..Default::default()
+        };
+
+        let value = serde_json::json!({
+            "addressBlacklist": "0xbb,0xcc",
+            "gasPaymentEnforcement": [{ "type": "minimum", "payment": "5" }],
+        });
+        let policies = RelayerPolicyConf::from_json(value, &base).unwrap();
+        assert_eq!(policies.address_blacklist, vec![vec![0xbb], vec![0xcc]]);
+        assert_eq!(policies.transaction_gas_limit, Some(100.into()));
+        assert_eq!(policies.gas_payment_enforcement.len(), 1);
+        assert!(matches!(
+            policies.gas_payment_enforcement[0].policy,
+            GasPaymentEnforcementPolicy::Minimum { payment } if payment == 5.into()
+        ));
+
+        // Invalid values are rejected rather than silently ignored
+        let value = serde_json::json!({ "transactionGasLimit": "not a number" });
+        assert!(RelayerPolicyConf::from_json(value, &base).is_err());
+
+        // Policies that are set can clear the ones in the base, and `null` resets them
+        let value = serde_json::json!({
+            "addressBlacklist": "",
+            "transactionGasLimit": null,
+        });
+        let policies = RelayerPolicyConf::from_json(value, &base).unwrap();
+        assert!(policies.address_blacklist.is_empty());
+        assert_eq!(policies.transaction_gas_limit, None);
+    }
+
+    #[test]
+    fn test_parse_usd_cost_gas_payment_enforcement() {
+        let value = serde_json::json!({
+            "gasPaymentEnforcement": [{
+                "type": "usdCost",
+                "margin": "1.25",
+                "tokenPrices": {
+                    "1": { "usd": "3000.5", "decimals": 18 },
+                    "1399811149": { "usd": 150, "decimals": 9 },
+                },
+            }],
+        });
+        let policies = RelayerPolicyConf::from_json(value, &Default::default()).unwrap();
+        let GasPaymentEnforcementPolicy::UsdCost {
+            margin,
+            token_prices,
+            token_prices_path,
+        } = &policies.gas_payment_enforcement[0].policy
+        else {
+            panic!("Expected UsdCost gas payment enforcement policy");
+        };
+        assert_eq!(*margin, "1.25".parse().unwrap());
+        assert_eq!(
+            token_prices[&1],
+            TokenPriceConf {
+                usd: "3000.5".parse().unwrap(),
+                decimals: 18,
+            }
+        );
+        assert_eq!(token_prices[&1399811149].usd, 150u32.into());
+        assert_eq!(*token_prices_path, None);
+
+        // Prices are required
+        let value = serde_json::json!({
+            "gasPaymentEnforcement": [{ "type": "usdCost" }],
+        });
+        assert!(RelayerPolicyConf::from_json(value, &Default::default()).is_err());
+
+        // Scales beyond U256 are rejected
+        let value = serde_json::json!({
+            "gasPaymentEnforcement": [{
+                "type": "usdCost",
+                "tokenPrices": { "1": { "usd": "1", "decimals": 78 } },
+            }],
+        });
+        assert!(RelayerPolicyConf::from_json(value, &Default::default()).is_err());
+    }
+
+    #[test]
+    fn test_parse_ccip_read_gateways() {
+        let value = serde_json::json!({ "requestTimeout": 2, "failureThreshold": 5 });
+        let mut err = ConfigParsingError::default();
+        let conf =
+            parse_ccip_read_gateways(ValueParser::new(ConfigPath::default(), &value), &mut err);
+        assert!(err.is_ok());
+        assert_eq!(conf.request_timeout, Duration::from_secs(2));
+        assert_eq!(conf.failure_threshold, 5);
+        // Unset values keep their default
+        assert_eq!(conf.cooldown, CcipReadGatewayConf::default().cooldown);
+    }
+
+    #[test]
+    fn test_token_price_to_usd() {
+        let price = TokenPriceConf {
+            usd: "2".parse().unwrap(),
+            decimals: 6,
+        };
+        assert_eq!(price.to_usd(3_000_000.into()).unwrap(), 6u32.into());
+
+        let price = TokenPriceConf {
+            usd: "2".parse().unwrap(),
+            decimals: 100,
+        };
+        assert!(price.to_usd(1.into()).is_err());
+    }
 }
@@ -13,6 +13,8 @@ mod m20230309_000003_create_table_transaction;
 mod m20230309_000004_create_table_delivered_message;
 mod m20230309_000004_create_table_gas_payment;
 mod m20230309_000005_create_table_message;
+mod m20261018_000006_alter_tables_add_sequence;
+mod m20261018_000007_create_table_message_status;
 
 pub struct Migrator;
 
@@ -30,6 +32,8 @@ impl MigratorTrait for Migrator {
             Box::new(m20230309_000004_create_table_gas_payment::Migration),
             Box::new(m20230309_000004_create_table_delivered_message::Migration),
             Box::new(m20230309_000005_create_table_message::Migration),
+            Box::new(m20261018_000006_alter_tables_add_sequence::Migration),
+            Box::new(m20261018_000007_create_table_message_status::Migration),
         ]
     }
 }
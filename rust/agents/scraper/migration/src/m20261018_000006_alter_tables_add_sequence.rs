@@ -0,0 +1,114 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DeliveredMessage::Table)
+                    .add_column(ColumnDef::new(DeliveredMessage::Sequence).big_integer())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .table(DeliveredMessage::Table)
+                    .name("delivered_message_sequence_idx")
+                    .col(DeliveredMessage::Domain)
+                    .col(DeliveredMessage::DestinationMailbox)
+                    .col(DeliveredMessage::Sequence)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GasPayment::Table)
+                    .add_column(ColumnDef::new(GasPayment::Destination).unsigned())
+                    .add_column(ColumnDef::new(GasPayment::Sequence).big_integer())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .table(GasPayment::Table)
+                    .name("gas_payment_sequence_idx")
+                    .col(GasPayment::Domain)
+                    .col(GasPayment::Sequence)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .table(GasPayment::Table)
+                    .name("gas_payment_sequence_idx")
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GasPayment::Table)
+                    .drop_column(GasPayment::Destination)
+                    .drop_column(GasPayment::Sequence)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .table(DeliveredMessage::Table)
+                    .name("delivered_message_sequence_idx")
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DeliveredMessage::Table)
+                    .drop_column(DeliveredMessage::Sequence)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum DeliveredMessage {
+    Table,
+    /// Domain the message was received on
+    Domain,
+    /// Address of the mailbox contract the message was received by
+    DestinationMailbox,
+    /// Sequence of the delivery on chains which index deliveries by sequence
+    /// rather than by block
+    Sequence,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum GasPayment {
+    Table,
+    /// Domain ID of the chain the payment was made on
+    Domain,
+    /// Domain ID of the chain the message is being sent to
+    Destination,
+    /// Sequence of the payment on chains which index payments by sequence
+    /// rather than by block
+    Sequence,
+}
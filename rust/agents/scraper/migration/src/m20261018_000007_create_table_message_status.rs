@@ -0,0 +1,262 @@
+use sea_orm::ConnectionTrait;
+use sea_orm_migration::prelude::*;
+
+use crate::l20230309_types::*;
+use crate::m20230309_000002_create_table_block::Block;
+use crate::m20230309_000003_create_table_transaction::Transaction;
+use crate::m20230309_000004_create_table_delivered_message::DeliveredMessage;
+use crate::m20230309_000004_create_table_gas_payment::TotalGasPayment;
+use crate::m20230309_000005_create_table_message::Message;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(MessageStatus::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MessageStatus::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(MessageStatus::TimeCreated)
+                            .timestamp()
+                            .not_null()
+                            .default("NOW()"),
+                    )
+                    .col(
+                        ColumnDef::new(MessageStatus::TimeUpdated)
+                            .timestamp()
+                            .not_null()
+                            .default("NOW()"),
+                    )
+                    .col(
+                        ColumnDef::new_with_type(MessageStatus::MsgId, Hash)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(MessageStatus::Origin).unsigned())
+                    .col(ColumnDef::new(MessageStatus::Destination).unsigned())
+                    .col(ColumnDef::new(MessageStatus::Nonce).unsigned())
+                    .col(ColumnDef::new(MessageStatus::OriginTxId).big_integer())
+                    .col(ColumnDef::new(MessageStatus::DispatchedAt).timestamp())
+                    .col(
+                        ColumnDef::new(MessageStatus::NumPayments)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new_with_type(MessageStatus::TotalPayment, Wei).not_null())
+                    .col(ColumnDef::new_with_type(MessageStatus::TotalGasAmount, Wei).not_null())
+                    .col(ColumnDef::new(MessageStatus::DestinationTxId).big_integer())
+                    .col(ColumnDef::new(MessageStatus::DeliveredAt).timestamp())
+                    .col(ColumnDef::new(MessageStatus::DeliveryLatency).big_integer())
+                    .col(ColumnDef::new_with_type(
+                        MessageStatus::DestinationTxGasUsed,
+                        Wei,
+                    ))
+                    .col(ColumnDef::new_with_type(MessageStatus::DeliveryCost, Wei))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from_col(MessageStatus::OriginTxId)
+                            .to(Transaction::Table, Transaction::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from_col(MessageStatus::DestinationTxId)
+                            .to(Transaction::Table, Transaction::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .table(MessageStatus::Table)
+                    .name("message_status_msg_id_idx")
+                    .col(MessageStatus::MsgId)
+                    .index_type(IndexType::Hash)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .table(MessageStatus::Table)
+                    .name("message_status_origin_dispatched_at_idx")
+                    .col(MessageStatus::Origin)
+                    .col(MessageStatus::DispatchedAt)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .table(MessageStatus::Table)
+                    .name("message_status_destination_delivered_at_idx")
+                    .col(MessageStatus::Destination)
+                    .col(MessageStatus::DeliveredAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Backfill the statuses of the messages dispatched or delivered before
+        // this table existed, the same way the scraper derives them
+        manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                r#"
+            INSERT INTO "{ms_table}" (
+                "{ms_mid}", "{ms_origin}", "{ms_dest}", "{ms_nonce}", "{ms_oti}", "{ms_dispatched_at}",
+                "{ms_num_payments}", "{ms_payment}", "{ms_gas_amount}", "{ms_dti}", "{ms_delivered_at}",
+                "{ms_latency}", "{ms_gas_used}", "{ms_delivery_cost}"
+            )
+            SELECT
+                "ids"."msg_id",
+                "msg"."{msg_origin}",
+                COALESCE("msg"."{msg_dest}", "dmsg"."{dmsg_domain}"),
+                "msg"."{msg_nonce}",
+                "msg"."{msg_oti}",
+                "origin_block"."{block_timestamp}",
+                COALESCE("tgp"."{tgp_num_payments}", 0),
+                COALESCE("tgp"."{tgp_payment}", 0),
+                COALESCE("tgp"."{tgp_gas_amount}", 0),
+                "dmsg"."{dmsg_dti}",
+                "dest_block"."{block_timestamp}",
+                TRUNC(EXTRACT(EPOCH FROM "dest_block"."{block_timestamp}" - "origin_block"."{block_timestamp}"))::BIGINT,
+                "dest_tx"."{tx_gas_used}",
+                DIV("dest_tx"."{tx_gas_used}" * "dest_tx"."{tx_egp}", "dest_tx_deliveries"."deliveries")
+            FROM (
+                SELECT "{msg_mid}" AS "msg_id" FROM "{msg_table}"
+                UNION
+                SELECT "{dmsg_mid}" AS "msg_id" FROM "{dmsg_table}"
+            ) AS "ids"
+                LEFT JOIN "{msg_table}"
+                    AS "msg"
+                    ON "msg"."{msg_mid}" = "ids"."msg_id"
+                LEFT JOIN "{dmsg_table}"
+                    AS "dmsg"
+                    ON "dmsg"."{dmsg_mid}" = "ids"."msg_id"
+                    AND ("msg"."{msg_id}" IS NULL OR "dmsg"."{dmsg_domain}" = "msg"."{msg_dest}")
+                LEFT JOIN "{tgp_table}"
+                    AS "tgp"
+                    ON "tgp"."{tgp_mid}" = "ids"."msg_id"
+                LEFT JOIN "{tx_table}"
+                    AS "origin_tx"
+                    ON "origin_tx"."{tx_id}" = "msg"."{msg_oti}"
+                LEFT JOIN "{block_table}"
+                    AS "origin_block"
+                    ON "origin_block"."{block_id}" = "origin_tx"."{tx_block_id}"
+                LEFT JOIN "{tx_table}"
+                    AS "dest_tx"
+                    ON "dest_tx"."{tx_id}" = "dmsg"."{dmsg_dti}"
+                LEFT JOIN "{block_table}"
+                    AS "dest_block"
+                    ON "dest_block"."{block_id}" = "dest_tx"."{tx_block_id}"
+                LEFT JOIN (
+                    SELECT "{dmsg_dti}", COUNT(*) AS "deliveries"
+                    FROM "{dmsg_table}"
+                    GROUP BY "{dmsg_dti}"
+                ) AS "dest_tx_deliveries"
+                    ON "dest_tx_deliveries"."{dmsg_dti}" = "dmsg"."{dmsg_dti}"
+            ON CONFLICT ("{ms_mid}") DO NOTHING
+            "#,
+                ms_table = MessageStatus::Table.to_string(),
+                ms_mid = MessageStatus::MsgId.to_string(),
+                ms_origin = MessageStatus::Origin.to_string(),
+                ms_dest = MessageStatus::Destination.to_string(),
+                ms_nonce = MessageStatus::Nonce.to_string(),
+                ms_oti = MessageStatus::OriginTxId.to_string(),
+                ms_dispatched_at = MessageStatus::DispatchedAt.to_string(),
+                ms_num_payments = MessageStatus::NumPayments.to_string(),
+                ms_payment = MessageStatus::TotalPayment.to_string(),
+                ms_gas_amount = MessageStatus::TotalGasAmount.to_string(),
+                ms_dti = MessageStatus::DestinationTxId.to_string(),
+                ms_delivered_at = MessageStatus::DeliveredAt.to_string(),
+                ms_latency = MessageStatus::DeliveryLatency.to_string(),
+                ms_gas_used = MessageStatus::DestinationTxGasUsed.to_string(),
+                ms_delivery_cost = MessageStatus::DeliveryCost.to_string(),
+                msg_table = Message::Table.to_string(),
+                msg_id = Message::Id.to_string(),
+                msg_mid = Message::MsgId.to_string(),
+                msg_origin = Message::Origin.to_string(),
+                msg_dest = Message::Destination.to_string(),
+                msg_nonce = Message::Nonce.to_string(),
+                msg_oti = Message::OriginTxId.to_string(),
+                dmsg_table = DeliveredMessage::Table.to_string(),
+                dmsg_mid = DeliveredMessage::MsgId.to_string(),
+                dmsg_domain = DeliveredMessage::Domain.to_string(),
+                dmsg_dti = DeliveredMessage::DestinationTxId.to_string(),
+                tgp_table = TotalGasPayment::Table.to_string(),
+                tgp_mid = TotalGasPayment::MsgId.to_string(),
+                tgp_num_payments = TotalGasPayment::NumPayments.to_string(),
+                tgp_payment = TotalGasPayment::TotalPayment.to_string(),
+                tgp_gas_amount = TotalGasPayment::TotalGasAmount.to_string(),
+                tx_table = Transaction::Table.to_string(),
+                tx_id = Transaction::Id.to_string(),
+                tx_block_id = Transaction::BlockId.to_string(),
+                tx_gas_used = Transaction::GasUsed.to_string(),
+                tx_egp = Transaction::EffectiveGasPrice.to_string(),
+                block_table = Block::Table.to_string(),
+                block_id = Block::Id.to_string(),
+                block_timestamp = Block::Timestamp.to_string(),
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MessageStatus::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum MessageStatus {
+    Table,
+    /// Unique database ID
+    Id,
+    /// Time of record creation
+    TimeCreated,
+    /// Time the record was last brought up to date
+    TimeUpdated,
+    /// Unique id of the message on the blockchain
+    MsgId,
+    /// Domain ID of the origin chain, once the dispatch has been scraped
+    Origin,
+    /// Domain ID of the destination chain, once the dispatch has been scraped
+    Destination,
+    /// Nonce of the message, once the dispatch has been scraped
+    Nonce,
+    /// Transaction the message was dispatched in
+    OriginTxId,
+    /// Timestamp of the block the message was dispatched in
+    DispatchedAt,
+    /// Number of gas payments made for the message
+    NumPayments,
+    /// Total amount of native tokens paid for the message
+    TotalPayment,
+    /// Total amount of destination gas paid for
+    TotalGasAmount,
+    /// Transaction the message was delivered in
+    DestinationTxId,
+    /// Timestamp of the block the message was delivered in
+    DeliveredAt,
+    /// Seconds between the dispatch and delivery blocks
+    DeliveryLatency,
+    /// Gas used by the delivery transaction
+    DestinationTxGasUsed,
+    /// The message's share of the native tokens spent on the delivery
+    /// transaction, which is split evenly between the messages it delivered
+    DeliveryCost,
+}
@@ -14,7 +14,9 @@ use tokio::{
 };
 use tracing::{info_span, instrument::Instrumented, trace, Instrument};
 
-use crate::{chain_scraper::HyperlaneSqlDb, db::ScraperDb, settings::ScraperSettings};
+use crate::{
+    chain_scraper::HyperlaneSqlDb, db::ScraperDb, server::QueryApi, settings::ScraperSettings,
+};
 
 /// A message explorer scraper agent
 #[derive(Debug, AsRef)]
@@ -24,6 +26,7 @@ pub struct Scraper {
     core: HyperlaneAgentCore,
     contract_sync_metrics: Arc<ContractSyncMetrics>,
     scrapers: HashMap<u32, ChainScraper>,
+    db: ScraperDb,
     settings: ScraperSettings,
     core_metrics: Arc<CoreMetrics>,
     agent_metrics: AgentMetrics,
@@ -87,6 +90,7 @@ impl BaseAgent for Scraper {
             core,
             contract_sync_metrics,
             scrapers,
+            db,
             settings,
             core_metrics: metrics,
             agent_metrics,
@@ -104,7 +108,13 @@ impl BaseAgent for Scraper {
             .settings
             .server(self.core_metrics.clone())
             .expect("Failed to create server");
-        let server_task = server.run().instrument(info_span!("Relayer server"));
+        let mut custom_routes = vec![];
+        if self.settings.query_api {
+            custom_routes.push(QueryApi::new(self.db.clone()).get_route());
+        }
+        let server_task = server
+            .run_with_custom_routes(custom_routes)
+            .instrument(info_span!("Scraper server"));
         tasks.push(server_task);
 
         for (domain, scraper) in self.scrapers.iter() {
@@ -196,6 +206,8 @@ impl Scraper {
                 &metrics.clone(),
                 &contract_sync_metrics.clone(),
                 db.into(),
+                // The scraper stores the transaction of every event
+                true,
             )
             .await
             .unwrap();
@@ -220,11 +232,12 @@ impl Scraper {
         let sync = self
             .as_ref()
             .settings
-            .watermark_contract_sync::<Delivery, _>(
+            .contract_sync::<Delivery, _>(
                 &domain,
                 &metrics.clone(),
                 &contract_sync_metrics.clone(),
-                Arc::new(db.clone()) as _,
+                Arc::new(db.clone()),
+                true,
             )
             .await
             .unwrap();
@@ -250,11 +263,12 @@ impl Scraper {
         let sync = self
             .as_ref()
             .settings
-            .watermark_contract_sync::<InterchainGasPayment, _>(
+            .contract_sync::<InterchainGasPayment, _>(
                 &domain,
                 &metrics.clone(),
                 &contract_sync_metrics.clone(),
                 Arc::new(db.clone()),
+                true,
             )
             .await
             .unwrap();
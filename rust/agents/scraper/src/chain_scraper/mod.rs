@@ -4,12 +4,12 @@
 use std::{collections::HashMap, sync::Arc};
 
 use async_trait::async_trait;
-use eyre::Result;
+use eyre::{bail, Result};
 use hyperlane_base::settings::IndexSettings;
 use hyperlane_core::{
     unwrap_or_none_result, BlockInfo, Delivery, HyperlaneDomain, HyperlaneLogStore,
     HyperlaneMessage, HyperlaneProvider, HyperlaneSequenceAwareIndexerStoreReader,
-    HyperlaneWatermarkedLogStore, Indexed, InterchainGasPayment, LogMeta, H256,
+    HyperlaneWatermarkedLogStore, Indexed, InterchainGasPayment, LogMeta, H256, H512,
 };
 use itertools::Itertools;
 use tracing::trace;
@@ -76,13 +76,14 @@ impl HyperlaneSqlDb {
         &self,
         log_meta: impl Iterator<Item = &LogMeta>,
     ) -> Result<impl Iterator<Item = TxnWithId>> {
-        let block_hash_by_txn_hash: HashMap<H256, H256> = log_meta
+        let block_by_txn_hash: HashMap<H512, BlockHashNumber> = log_meta
             .map(|meta| {
                 (
-                    meta.transaction_id
-                        .try_into()
-                        .expect("256-bit transaction ids are the maximum supported at this time"),
-                    meta.block_hash,
+                    meta.transaction_id,
+                    BlockHashNumber {
+                        hash: meta.block_hash,
+                        number: meta.block_number,
+                    },
                 )
             })
             .collect();
@@ -90,23 +91,21 @@ impl HyperlaneSqlDb {
         // all blocks we care about
         // hash of block maps to the block id and timestamp
         let blocks: HashMap<_, _> = self
-            .ensure_blocks(block_hash_by_txn_hash.values().copied())
+            .ensure_blocks(block_by_txn_hash.values().copied())
             .await?
             .map(|block| (block.hash, block))
             .collect();
         trace!(?blocks, "Ensured blocks");
 
         // all txns we care about
-        let txns_with_ids =
-            self.ensure_txns(block_hash_by_txn_hash.into_iter().map(
-                move |(txn_hash, block_hash)| {
-                    let block_info = *blocks.get(&block_hash).as_ref().unwrap();
-                    TxnWithBlockId {
-                        txn_hash,
-                        block_id: block_info.id,
-                    }
-                },
-            ))
+        let txns_with_ids = self
+            .ensure_txns(block_by_txn_hash.into_iter().map(move |(txn_hash, block)| {
+                let block_info = *blocks.get(&block.hash).as_ref().unwrap();
+                TxnWithBlockId {
+                    txn_hash,
+                    block_id: block_info.id,
+                }
+            }))
             .await?;
 
         Ok(txns_with_ids.map(move |TxnWithId { hash, id: txn_id }| TxnWithId { hash, id: txn_id }))
@@ -123,7 +122,7 @@ impl HyperlaneSqlDb {
         txns: impl Iterator<Item = TxnWithBlockId>,
     ) -> Result<impl Iterator<Item = TxnWithId>> {
         // mapping of txn hash to (txn_id, block_id).
-        let mut txns: HashMap<H256, (Option<i64>, i64)> = txns
+        let mut txns: HashMap<H512, (Option<i64>, i64)> = txns
             .map(|TxnWithBlockId { txn_hash, block_id }| (txn_hash, (None, block_id)))
             .collect();
 
@@ -147,9 +146,9 @@ impl HyperlaneSqlDb {
         let mut txns_to_fetch = txns.iter_mut().filter(|(_, id)| id.0.is_none());
 
         let mut txns_to_insert: Vec<StorableTxn> = Vec::with_capacity(CHUNK_SIZE);
-        let mut hashes_to_insert: Vec<&H256> = Vec::with_capacity(CHUNK_SIZE);
+        let mut hashes_to_insert: Vec<&H512> = Vec::with_capacity(CHUNK_SIZE);
 
-        for mut chunk in as_chunks::<(&H256, &mut (Option<i64>, i64))>(txns_to_fetch, CHUNK_SIZE) {
+        for mut chunk in as_chunks::<(&H512, &mut (Option<i64>, i64))>(txns_to_fetch, CHUNK_SIZE) {
             for (hash, (_, block_id)) in chunk.iter() {
                 let info = self.provider.get_txn_by_hash(hash).await?;
                 hashes_to_insert.push(*hash);
@@ -175,21 +174,25 @@ impl HyperlaneSqlDb {
             }))
     }
 
-    /// Takes a list of block hashes for each block
+    /// Takes a list of block hashes and numbers for each block
     /// if it is in the database already:
     ///     Fetches its associated database id
     /// if it is not in the database already:
-    ///     Looks up its data with ethers and then returns the database id after
+    ///     Looks up its data by number and then returns the database id after
     ///     inserting it into the database.
     async fn ensure_blocks(
         &self,
-        block_hashes: impl Iterator<Item = H256>,
+        blocks: impl Iterator<Item = BlockHashNumber>,
     ) -> Result<impl Iterator<Item = BasicBlock>> {
+        // Not every chain can look blocks up by hash, so the number is kept
+        // around to fetch any blocks which are not in the database yet
+        let block_numbers: HashMap<H256, u64> = blocks.map(|b| (b.hash, b.number)).collect();
+
         // mapping of block hash to the database id and block timestamp. Optionals are
         // in place because we will find the timestamp first if the block was not
         // already in the db.
         let mut blocks: HashMap<H256, Option<BasicBlock>> =
-            block_hashes.map(|b| (b, None)).collect();
+            block_numbers.keys().map(|hash| (*hash, None)).collect();
 
         let db_blocks: Vec<BasicBlock> = if !blocks.is_empty() {
             // check database to see which blocks we already know and fetch their IDs
@@ -220,7 +223,18 @@ impl HyperlaneSqlDb {
         for chunk in as_chunks(blocks_to_fetch, CHUNK_SIZE) {
             debug_assert!(!chunk.is_empty());
             for (hash, block_info) in chunk {
-                let info = self.provider.get_block_by_hash(hash).await?;
+                let info = self
+                    .provider
+                    .get_block_by_height(block_numbers[hash])
+                    .await?;
+                if info.hash != *hash {
+                    bail!(
+                        "Block {} has hash {:?} rather than the expected {:?}",
+                        info.number,
+                        info.hash,
+                        hash
+                    );
+                }
                 let basic_info_ref = block_info.insert(BasicBlock {
                     id: -1,
                     hash: *hash,
@@ -273,19 +287,13 @@ impl HyperlaneLogStore<HyperlaneMessage> for HyperlaneSqlDb {
         if messages.is_empty() {
             return Ok(0);
         }
-        let txns: HashMap<H256, TxnWithId> = self
+        let txns: HashMap<H512, TxnWithId> = self
             .ensure_blocks_and_txns(messages.iter().map(|r| &r.1))
             .await?
             .map(|t| (t.hash, t))
             .collect();
         let storable = messages.iter().map(|m| {
-            let txn = txns
-                .get(
-                    &m.1.transaction_id
-                        .try_into()
-                        .expect("256-bit transaction ids are the maximum supported at this time"),
-                )
-                .unwrap();
+            let txn = txns.get(&m.1.transaction_id).unwrap();
             StorableMessage {
                 msg: m.0.inner().clone(),
                 meta: &m.1,
@@ -296,6 +304,9 @@ impl HyperlaneLogStore<HyperlaneMessage> for HyperlaneSqlDb {
             .db
             .store_dispatched_messages(self.domain().id(), &self.mailbox_address, storable)
             .await?;
+        self.db
+            .update_message_statuses(messages.iter().map(|m| m.0.inner().id()))
+            .await?;
         Ok(stored as u32)
     }
 }
@@ -306,23 +317,16 @@ impl HyperlaneLogStore<Delivery> for HyperlaneSqlDb {
         if deliveries.is_empty() {
             return Ok(0);
         }
-        let txns: HashMap<Delivery, TxnWithId> = self
+        let txns: HashMap<H512, TxnWithId> = self
             .ensure_blocks_and_txns(deliveries.iter().map(|r| &r.1))
             .await?
             .map(|t| (t.hash, t))
             .collect();
         let storable = deliveries.iter().map(|(message_id, meta)| {
-            let txn_id = txns
-                .get(
-                    &meta
-                        .transaction_id
-                        .try_into()
-                        .expect("256-bit transaction ids are the maximum supported at this time"),
-                )
-                .unwrap()
-                .id;
+            let txn_id = txns.get(&meta.transaction_id).unwrap().id;
             StorableDelivery {
                 message_id: *message_id.inner(),
+                sequence: message_id.sequence.map(i64::from),
                 meta,
                 txn_id,
             }
@@ -332,6 +336,9 @@ impl HyperlaneLogStore<Delivery> for HyperlaneSqlDb {
             .db
             .store_deliveries(self.domain().id(), self.mailbox_address, storable)
             .await?;
+        self.db
+            .update_message_statuses(deliveries.iter().map(|d| *d.0.inner()))
+            .await?;
         Ok(stored as u32)
     }
 }
@@ -345,29 +352,25 @@ impl HyperlaneLogStore<InterchainGasPayment> for HyperlaneSqlDb {
         if payments.is_empty() {
             return Ok(0);
         }
-        let txns: HashMap<H256, TxnWithId> = self
+        let txns: HashMap<H512, TxnWithId> = self
             .ensure_blocks_and_txns(payments.iter().map(|r| &r.1))
             .await?
             .map(|t| (t.hash, t))
             .collect();
         let storable = payments.iter().map(|(payment, meta)| {
-            let txn_id = txns
-                .get(
-                    &meta
-                        .transaction_id
-                        .try_into()
-                        .expect("256-bit transaction ids are the maximum supported at this time"),
-                )
-                .unwrap()
-                .id;
+            let txn_id = txns.get(&meta.transaction_id).unwrap().id;
             StorablePayment {
                 payment: payment.inner(),
+                sequence: payment.sequence.map(i64::from),
                 meta,
                 txn_id,
             }
         });
 
         let stored = self.db.store_payments(self.domain().id(), storable).await?;
+        self.db
+            .update_message_statuses(payments.iter().map(|p| p.0.inner().message_id))
+            .await?;
         Ok(stored as u32)
     }
 }
@@ -395,6 +398,56 @@ impl HyperlaneSequenceAwareIndexerStoreReader<HyperlaneMessage> for HyperlaneSql
     }
 }
 
+#[async_trait]
+impl HyperlaneSequenceAwareIndexerStoreReader<Delivery> for HyperlaneSqlDb {
+    /// Gets a delivered message id by its sequence.
+    async fn retrieve_by_sequence(&self, sequence: u32) -> Result<Option<Delivery>> {
+        let delivery = self
+            .db
+            .retrieve_delivery_by_sequence(self.domain().id(), &self.mailbox_address, sequence)
+            .await?;
+        Ok(delivery)
+    }
+
+    /// Gets the block number at which the log occurred.
+    async fn retrieve_log_block_number_by_sequence(&self, sequence: u32) -> Result<Option<u64>> {
+        let tx_id = unwrap_or_none_result!(
+            self.db
+                .retrieve_delivered_message_tx_id(
+                    self.domain().id(),
+                    &self.mailbox_address,
+                    sequence
+                )
+                .await?
+        );
+        let block_id = unwrap_or_none_result!(self.db.retrieve_block_id(tx_id).await?);
+        Ok(self.db.retrieve_block_number(block_id).await?)
+    }
+}
+
+#[async_trait]
+impl HyperlaneSequenceAwareIndexerStoreReader<InterchainGasPayment> for HyperlaneSqlDb {
+    /// Gets a gas payment by its sequence.
+    async fn retrieve_by_sequence(&self, sequence: u32) -> Result<Option<InterchainGasPayment>> {
+        let payment = self
+            .db
+            .retrieve_payment_by_sequence(self.domain().id(), sequence)
+            .await?;
+        Ok(payment)
+    }
+
+    /// Gets the block number at which the log occurred.
+    async fn retrieve_log_block_number_by_sequence(&self, sequence: u32) -> Result<Option<u64>> {
+        let tx_id = unwrap_or_none_result!(
+            self.db
+                .retrieve_payment_tx_id(self.domain().id(), sequence)
+                .await?
+        );
+        let block_id = unwrap_or_none_result!(self.db.retrieve_block_id(tx_id).await?);
+        Ok(self.db.retrieve_block_number(block_id).await?)
+    }
+}
+
 #[async_trait]
 impl<T> HyperlaneWatermarkedLogStore<T> for HyperlaneSqlDb
 where
@@ -413,16 +466,22 @@ where
 
 #[derive(Debug, Clone)]
 struct TxnWithId {
-    hash: H256,
+    hash: H512,
     id: i64,
 }
 
 #[derive(Debug, Clone)]
 struct TxnWithBlockId {
-    txn_hash: H256,
+    txn_hash: H512,
     block_id: i64,
 }
 
+#[derive(Debug, Clone, Copy)]
+struct BlockHashNumber {
+    hash: H256,
+    number: u64,
+}
+
 fn as_chunks<T>(iter: impl Iterator<Item = T>, chunk_size: usize) -> impl Iterator<Item = Vec<T>> {
     // the itertools chunks function uses refcell which cannot be used across an
     // await so this stabilizes the result by putting it into a vec of vecs and
@@ -1,7 +1,7 @@
 use num_bigint::{BigInt, Sign};
 use sea_orm::prelude::BigDecimal;
 
-use hyperlane_core::{H256, U256};
+use hyperlane_core::{H256, H512, U256};
 
 // Creates a big-endian hex representation of the address
 pub fn address_to_bytes(data: &H256) -> Vec<u8> {
@@ -32,8 +32,36 @@ pub fn h256_to_bytes(data: &H256) -> Vec<u8> {
     data.as_fixed_bytes().as_slice().into()
 }
 
+// Creates a big-endian hex representation of the transaction hash. Hashes
+// which fit in 256 bits are stored as such so they match existing rows.
+pub fn h512_to_bytes(data: &H512) -> Vec<u8> {
+    if data.as_fixed_bytes()[..32] == [0; 32] {
+        h256_to_bytes(&H256::from(*data))
+    } else {
+        data.as_fixed_bytes().as_slice().into()
+    }
+}
+
+// Parses a transaction hash stored by `h512_to_bytes`
+pub fn bytes_to_h512(data: &[u8]) -> eyre::Result<H512> {
+    match data.len() {
+        32 => Ok(H256::from_slice(data).into()),
+        64 => Ok(H512::from_slice(data)),
+        _ => Err(eyre::eyre!("Invalid transaction hash length")),
+    }
+}
+
 pub fn u256_to_decimal(v: U256) -> BigDecimal {
     let mut buf = [0u8; 32];
     v.to_little_endian(&mut buf);
     BigDecimal::from(BigInt::from_bytes_le(Sign::Plus, &buf as &[u8]))
 }
+
+pub fn decimal_to_u256(v: BigDecimal) -> eyre::Result<U256> {
+    let (int, _) = v.with_scale(0).into_bigint_and_exponent();
+    let (sign, buf) = int.to_bytes_le();
+    if sign == Sign::Minus || buf.len() > 32 {
+        return Err(eyre::eyre!("Decimal does not fit in a U256"));
+    }
+    Ok(U256::from_little_endian(&buf))
+}
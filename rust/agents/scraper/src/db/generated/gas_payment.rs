@@ -21,6 +21,8 @@ pub struct Model {
     pub gas_amount: BigDecimal,
     pub tx_id: i64,
     pub log_index: i64,
+    pub destination: Option<i32>,
+    pub sequence: Option<i64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
@@ -33,6 +35,8 @@ pub enum Column {
     GasAmount,
     TxId,
     LogIndex,
+    Destination,
+    Sequence,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DerivePrimaryKey)]
@@ -65,6 +69,8 @@ impl ColumnTrait for Column {
             Self::GasAmount => ColumnType::Decimal(Some((78u32, 0u32))).def(),
             Self::TxId => ColumnType::BigInteger.def(),
             Self::LogIndex => ColumnType::BigInteger.def(),
+            Self::Destination => ColumnType::Integer.def().null(),
+            Self::Sequence => ColumnType::BigInteger.def().null(),
         }
     }
 }
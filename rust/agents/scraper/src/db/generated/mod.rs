@@ -8,4 +8,5 @@ pub mod delivered_message;
 pub mod domain;
 pub mod gas_payment;
 pub mod message;
+pub mod message_status;
 pub mod transaction;
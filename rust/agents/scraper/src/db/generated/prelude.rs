@@ -4,5 +4,5 @@ pub use super::{
     block::Entity as Block, cursor::Entity as Cursor,
     delivered_message::Entity as DeliveredMessage, domain::Entity as Domain,
     gas_payment::Entity as GasPayment, message::Entity as Message,
-    transaction::Entity as Transaction,
+    message_status::Entity as MessageStatus, transaction::Entity as Transaction,
 };
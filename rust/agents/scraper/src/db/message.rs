@@ -15,6 +15,9 @@ use super::generated::{delivered_message, message};
 #[derive(Debug, Clone)]
 pub struct StorableDelivery<'a> {
     pub message_id: H256,
+    /// The sequence of the delivery, for chains which index deliveries by
+    /// sequence
+    pub sequence: Option<i64>,
     pub meta: &'a LogMeta,
     /// The database id of the transaction the delivery event occurred in
     pub txn_id: i64,
@@ -118,6 +121,62 @@ impl ScraperDb {
         Ok(tx_id)
     }
 
+    /// Get the id of the message delivered with a sequence.
+    #[instrument(skip(self))]
+    pub async fn retrieve_delivery_by_sequence(
+        &self,
+        destination_domain: u32,
+        destination_mailbox: &H256,
+        sequence: u32,
+    ) -> Result<Option<H256>> {
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
+        enum QueryAs {
+            MsgId,
+        }
+
+        let msg_id = delivered_message::Entity::find()
+            .filter(delivered_message::Column::Domain.eq(destination_domain))
+            .filter(
+                delivered_message::Column::DestinationMailbox
+                    .eq(address_to_bytes(destination_mailbox)),
+            )
+            .filter(delivered_message::Column::Sequence.eq(sequence))
+            .select_only()
+            .column_as(delivered_message::Column::MsgId, QueryAs::MsgId)
+            .into_values::<Vec<u8>, QueryAs>()
+            .one(&self.0)
+            .await?;
+        Ok(msg_id.map(|msg_id| H256::from_slice(&msg_id)))
+    }
+
+    /// Get the tx id associated with a delivery sequence.
+    #[instrument(skip(self))]
+    pub async fn retrieve_delivered_message_tx_id(
+        &self,
+        destination_domain: u32,
+        destination_mailbox: &H256,
+        sequence: u32,
+    ) -> Result<Option<i64>> {
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
+        enum QueryAs {
+            TxId,
+        }
+
+        let tx_id = delivered_message::Entity::find()
+            .filter(delivered_message::Column::Domain.eq(destination_domain))
+            .filter(
+                delivered_message::Column::DestinationMailbox
+                    .eq(address_to_bytes(destination_mailbox)),
+            )
+            .filter(delivered_message::Column::Sequence.eq(sequence))
+            .select_only()
+            .column_as(delivered_message::Column::DestinationTxId, QueryAs::TxId)
+            .into_values::<i64, QueryAs>()
+            .one(&self.0)
+            .await?;
+        Ok(tx_id)
+    }
+
     async fn latest_deliveries_id(&self, domain: u32, destination_mailbox: Vec<u8>) -> Result<i64> {
         let result = delivered_message::Entity::find()
             .select_only()
@@ -174,6 +233,7 @@ impl ScraperDb {
                 domain: Unchanged(domain as i32),
                 destination_mailbox: Unchanged(destination_mailbox.clone()),
                 destination_tx_id: Set(delivery.txn_id),
+                sequence: Set(delivery.sequence),
             })
             .collect_vec();
 
@@ -186,6 +246,7 @@ impl ScraperDb {
                     .update_columns([
                         delivered_message::Column::TimeCreated,
                         delivered_message::Column::DestinationTxId,
+                        delivered_message::Column::Sequence,
                     ])
                     .to_owned(),
             )
@@ -0,0 +1,402 @@
+use std::collections::HashMap;
+
+use eyre::Result;
+use itertools::Itertools;
+use sea_orm::{prelude::*, ActiveValue::*, FromQueryResult, Insert, QuerySelect};
+use tracing::{debug, instrument, trace};
+
+use hyperlane_core::H256;
+use migration::OnConflict;
+
+use crate::conversions::h256_to_bytes;
+use crate::date_time;
+use crate::db::ScraperDb;
+
+use super::generated::{
+    block, delivered_message, gas_payment, message, message_status, transaction,
+};
+
+/// The parts of a transaction the lifecycle of a message is derived from.
+#[derive(Debug)]
+struct TxnSummary {
+    timestamp: TimeDateTime,
+    gas_used: BigDecimal,
+    cost: Option<BigDecimal>,
+    /// Number of messages delivered in the transaction
+    deliveries: i64,
+}
+
+impl TxnSummary {
+    /// The share of the transaction's cost of each message it delivered. A
+    /// relayer may deliver several messages in a single transaction, so its
+    /// cost is split evenly between them.
+    fn delivery_cost(&self) -> Option<BigDecimal> {
+        let deliveries = BigDecimal::from(self.deliveries.max(1));
+        self.cost
+            .as_ref()
+            .map(|cost| (cost / deliveries).with_scale(0))
+    }
+}
+
+#[derive(Debug, FromQueryResult)]
+struct TxnDeliveries {
+    destination_tx_id: i64,
+    deliveries: i64,
+}
+
+impl ScraperDb {
+    /// Recompute the `message_status` rows of the given messages from the
+    /// dispatches, deliveries and gas payments currently in the database.
+    ///
+    /// Each part of the lifecycle may be scraped in any order and by
+    /// different chain scrapers, so rows are always rebuilt from scratch
+    /// rather than patched incrementally.
+    #[instrument(skip_all)]
+    pub async fn update_message_statuses(&self, msg_ids: impl Iterator<Item = H256>) -> Result<()> {
+        let msg_ids = msg_ids.unique().map(|id| h256_to_bytes(&id)).collect_vec();
+        if msg_ids.is_empty() {
+            return Ok(());
+        }
+
+        let messages: HashMap<Vec<u8>, message::Model> = message::Entity::find()
+            .filter(message::Column::MsgId.is_in(msg_ids.clone()))
+            .all(&self.0)
+            .await?
+            .into_iter()
+            .map(|m| (m.msg_id.clone(), m))
+            .collect();
+        // Keyed by destination domain as well, so that a delivery is only matched
+        // with a message that was sent to the domain it was delivered on
+        let deliveries: HashMap<(i32, Vec<u8>), delivered_message::Model> =
+            delivered_message::Entity::find()
+                .filter(delivered_message::Column::MsgId.is_in(msg_ids.clone()))
+                .all(&self.0)
+                .await?
+                .into_iter()
+                .map(|d| ((d.domain, d.msg_id.clone()), d))
+                .collect();
+        let payments = gas_payment::Entity::find()
+            .filter(gas_payment::Column::MsgId.is_in(msg_ids.clone()))
+            .all(&self.0)
+            .await?
+            .into_iter()
+            .into_group_map_by(|p| p.msg_id.clone());
+
+        let txn_ids = messages
+            .values()
+            .map(|m| m.origin_tx_id)
+            .chain(deliveries.values().map(|d| d.destination_tx_id))
+            .unique()
+            .collect_vec();
+        let txns = self.retrieve_txn_summaries(txn_ids).await?;
+
+        let now = date_time::now();
+        let models = msg_ids
+            .into_iter()
+            .map(|msg_id| {
+                let message = messages.get(&msg_id);
+                let delivery = find_delivery(&deliveries, &msg_id, message);
+                let payments = payments.get(&msg_id).map(Vec::as_slice).unwrap_or_default();
+                message_status_model(msg_id, message, delivery, payments, &txns, now)
+            })
+            .collect_vec();
+
+        trace!(?models, "Writing message statuses to database");
+        let count = models.len();
+
+        Insert::many(models)
+            .on_conflict(
+                OnConflict::column(message_status::Column::MsgId)
+                    .update_columns([
+                        message_status::Column::TimeUpdated,
+                        message_status::Column::Origin,
+                        message_status::Column::Destination,
+                        message_status::Column::Nonce,
+                        message_status::Column::OriginTxId,
+                        message_status::Column::DispatchedAt,
+                        message_status::Column::NumPayments,
+                        message_status::Column::TotalPayment,
+                        message_status::Column::TotalGasAmount,
+                        message_status::Column::DestinationTxId,
+                        message_status::Column::DeliveredAt,
+                        message_status::Column::DeliveryLatency,
+                        message_status::Column::DestinationTxGasUsed,
+                        message_status::Column::DeliveryCost,
+                    ])
+                    .to_owned(),
+            )
+            .exec(&self.0)
+            .await?;
+
+        debug!(messages = count, "Updated message statuses in database");
+        Ok(())
+    }
+
+    /// Get the block timestamp, gas used, cost and number of deliveries of the
+    /// given transactions, keyed by their database id.
+    async fn retrieve_txn_summaries(&self, txn_ids: Vec<i64>) -> Result<HashMap<i64, TxnSummary>> {
+        if txn_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let txns = transaction::Entity::find()
+            .filter(transaction::Column::Id.is_in(txn_ids.clone()))
+            .all(&self.0)
+            .await?;
+        let block_ids = txns.iter().map(|t| t.block_id).unique().collect_vec();
+        let block_timestamps: HashMap<i64, TimeDateTime> = block::Entity::find()
+            .filter(block::Column::Id.is_in(block_ids))
+            .all(&self.0)
+            .await?
+            .into_iter()
+            .map(|b| (b.id, b.timestamp))
+            .collect();
+        let deliveries: HashMap<i64, i64> = delivered_message::Entity::find()
+            .select_only()
+            .column(delivered_message::Column::DestinationTxId)
+            .column_as(delivered_message::Column::Id.count(), "deliveries")
+            .filter(delivered_message::Column::DestinationTxId.is_in(txn_ids))
+            .group_by(delivered_message::Column::DestinationTxId)
+            .into_model::<TxnDeliveries>()
+            .all(&self.0)
+            .await?
+            .into_iter()
+            .map(|d| (d.destination_tx_id, d.deliveries))
+            .collect();
+
+        Ok(txns
+            .into_iter()
+            .filter_map(|txn| {
+                let timestamp = *block_timestamps.get(&txn.block_id)?;
+                let cost = txn
+                    .effective_gas_price
+                    .as_ref()
+                    .map(|price| &txn.gas_used * price);
+                Some((
+                    txn.id,
+                    TxnSummary {
+                        timestamp,
+                        gas_used: txn.gas_used,
+                        cost,
+                        deliveries: deliveries.get(&txn.id).copied().unwrap_or_default(),
+                    },
+                ))
+            })
+            .collect())
+    }
+}
+
+/// Finds the delivery of a message on its destination domain. Until the dispatch
+/// is scraped the destination is unknown, so a delivery is only used if the
+/// message was delivered on a single domain.
+fn find_delivery<'a>(
+    deliveries: &'a HashMap<(i32, Vec<u8>), delivered_message::Model>,
+    msg_id: &[u8],
+    message: Option<&message::Model>,
+) -> Option<&'a delivered_message::Model> {
+    match message {
+        Some(message) => deliveries.get(&(message.destination, msg_id.to_vec())),
+        None => deliveries
+            .iter()
+            .filter(|((_, id), _)| id == msg_id)
+            .map(|(_, delivery)| delivery)
+            .exactly_one()
+            .ok(),
+    }
+}
+
+/// Derives the status of a message from its dispatch, delivery and gas payments,
+/// any of which may not have been scraped yet.
+fn message_status_model(
+    msg_id: Vec<u8>,
+    message: Option<&message::Model>,
+    delivery: Option<&delivered_message::Model>,
+    payments: &[gas_payment::Model],
+    txns: &HashMap<i64, TxnSummary>,
+    now: TimeDateTime,
+) -> message_status::ActiveModel {
+    let origin_txn = message.and_then(|m| txns.get(&m.origin_tx_id));
+    let destination_txn = delivery.and_then(|d| txns.get(&d.destination_tx_id));
+
+    let dispatched_at = origin_txn.map(|t| t.timestamp);
+    let delivered_at = destination_txn.map(|t| t.timestamp);
+    let delivery_latency = dispatched_at
+        .zip(delivered_at)
+        .map(|(dispatched, delivered)| (delivered - dispatched).whole_seconds());
+
+    message_status::ActiveModel {
+        id: NotSet,
+        time_created: Set(now),
+        time_updated: Set(now),
+        msg_id: Unchanged(msg_id),
+        origin: Set(message.map(|m| m.origin)),
+        destination: Set(message
+            .map(|m| m.destination)
+            .or_else(|| delivery.map(|d| d.domain))),
+        nonce: Set(message.map(|m| m.nonce)),
+        origin_tx_id: Set(message.map(|m| m.origin_tx_id)),
+        dispatched_at: Set(dispatched_at),
+        num_payments: Set(payments.len() as i32),
+        total_payment: Set(payments
+            .iter()
+            .fold(BigDecimal::from(0), |sum, p| sum + &p.payment)),
+        total_gas_amount: Set(payments
+            .iter()
+            .fold(BigDecimal::from(0), |sum, p| sum + &p.gas_amount)),
+        destination_tx_id: Set(delivery.map(|d| d.destination_tx_id)),
+        delivered_at: Set(delivered_at),
+        delivery_latency: Set(delivery_latency),
+        destination_tx_gas_used: Set(destination_txn.map(|t| t.gas_used.clone())),
+        delivery_cost: Set(destination_txn.and_then(TxnSummary::delivery_cost)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::Duration;
+
+    use super::*;
+
+    const MSG_ID: [u8; 32] = [1; 32];
+
+    fn message(destination: i32) -> message::Model {
+        message::Model {
+            id: 1,
+            time_created: date_time::now(),
+            msg_id: MSG_ID.to_vec(),
+            origin: 1,
+            destination,
+            nonce: 7,
+            sender: vec![],
+            recipient: vec![],
+            msg_body: None,
+            origin_mailbox: vec![],
+            origin_tx_id: 10,
+        }
+    }
+
+    fn delivery(id: i64, domain: i32) -> delivered_message::Model {
+        delivered_message::Model {
+            id,
+            time_created: date_time::now(),
+            msg_id: MSG_ID.to_vec(),
+            domain,
+            destination_mailbox: vec![],
+            destination_tx_id: 20,
+            sequence: None,
+        }
+    }
+
+    fn payment(payment: u32, gas_amount: u32) -> gas_payment::Model {
+        gas_payment::Model {
+            id: 1,
+            time_created: date_time::now(),
+            domain: 1,
+            msg_id: MSG_ID.to_vec(),
+            payment: payment.into(),
+            gas_amount: gas_amount.into(),
+            tx_id: 10,
+            log_index: 0,
+            destination: Some(2),
+            sequence: None,
+        }
+    }
+
+    fn deliveries(
+        deliveries: impl IntoIterator<Item = delivered_message::Model>,
+    ) -> HashMap<(i32, Vec<u8>), delivered_message::Model> {
+        deliveries
+            .into_iter()
+            .map(|d| ((d.domain, d.msg_id.clone()), d))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_delivery_on_destination_domain() {
+        let deliveries = deliveries([delivery(1, 2), delivery(2, 3)]);
+
+        assert_eq!(
+            find_delivery(&deliveries, &MSG_ID, Some(&message(3))).map(|d| d.id),
+            Some(2)
+        );
+        assert_eq!(find_delivery(&deliveries, &MSG_ID, Some(&message(4))), None);
+        // Ambiguous until the dispatch is scraped
+        assert_eq!(find_delivery(&deliveries, &MSG_ID, None), None);
+
+        let deliveries = self::deliveries([delivery(1, 2)]);
+        assert_eq!(
+            find_delivery(&deliveries, &MSG_ID, None).map(|d| d.id),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_delivery_cost_is_split_between_messages() {
+        let dispatched_at = date_time::from_unix_timestamp_s(1_000);
+        let txns = HashMap::from([
+            (
+                10,
+                TxnSummary {
+                    timestamp: dispatched_at,
+                    gas_used: 50.into(),
+                    cost: Some(500.into()),
+                    deliveries: 0,
+                },
+            ),
+            (
+                20,
+                TxnSummary {
+                    timestamp: dispatched_at + Duration::seconds(30),
+                    gas_used: 100.into(),
+                    cost: Some(1_000.into()),
+                    deliveries: 3,
+                },
+            ),
+        ]);
+        let message = message(2);
+        let delivery = delivery(1, 2);
+
+        let model = message_status_model(
+            MSG_ID.to_vec(),
+            Some(&message),
+            Some(&delivery),
+            &[payment(10, 1), payment(20, 2)],
+            &txns,
+            date_time::now(),
+        );
+
+        assert_eq!(model.destination.as_ref(), &Some(2));
+        assert_eq!(model.dispatched_at.as_ref(), &Some(dispatched_at));
+        assert_eq!(model.delivery_latency.as_ref(), &Some(30));
+        assert_eq!(model.num_payments.as_ref(), &2);
+        assert_eq!(model.total_payment.as_ref(), &BigDecimal::from(30));
+        assert_eq!(model.total_gas_amount.as_ref(), &BigDecimal::from(3));
+        assert_eq!(
+            model.destination_tx_gas_used.as_ref(),
+            &Some(BigDecimal::from(100))
+        );
+        // The transaction delivered 3 messages, the remainder is rounded down
+        assert_eq!(model.delivery_cost.as_ref(), &Some(BigDecimal::from(333)));
+    }
+
+    #[test]
+    fn test_status_of_delivery_without_dispatch() {
+        let delivery = delivery(1, 2);
+
+        let model = message_status_model(
+            MSG_ID.to_vec(),
+            None,
+            Some(&delivery),
+            &[],
+            &HashMap::new(),
+            date_time::now(),
+        );
+
+        assert_eq!(model.origin.as_ref(), &None);
+        assert_eq!(model.destination.as_ref(), &Some(2));
+        assert_eq!(model.destination_tx_id.as_ref(), &Some(20));
+        assert_eq!(model.delivered_at.as_ref(), &None);
+        assert_eq!(model.delivery_cost.as_ref(), &None);
+        assert_eq!(model.num_payments.as_ref(), &0);
+    }
+}
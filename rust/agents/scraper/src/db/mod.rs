@@ -2,7 +2,9 @@ pub use block::*;
 pub use block_cursor::BlockCursor;
 use eyre::Result;
 pub use message::*;
+pub use message_status::*;
 pub use payment::*;
+pub use query::*;
 use sea_orm::{Database, DbConn};
 use tracing::instrument;
 pub use txn::*;
@@ -14,11 +16,13 @@ mod generated;
 mod block;
 mod block_cursor;
 mod message;
+mod message_status;
 mod payment;
+mod query;
 mod txn;
 
 /// Database interface to the message explorer database for the scraper. This is
-/// focused on writing data to the database.
+/// mostly focused on writing data to the database.
 #[derive(Clone, Debug)]
 pub struct ScraperDb(DbConn);
 
@@ -28,4 +32,9 @@ impl ScraperDb {
         let db = Database::connect(url).await?;
         Ok(Self(db))
     }
+
+    /// Wrap an existing database connection
+    pub fn from_connection(db: DbConn) -> Self {
+        Self(db)
+    }
 }
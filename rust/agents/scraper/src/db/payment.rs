@@ -1,12 +1,12 @@
 use eyre::{eyre, Result};
 use itertools::Itertools;
-use sea_orm::{prelude::*, ActiveValue::*, Insert, QuerySelect};
+use sea_orm::{prelude::*, ActiveValue::*, DeriveColumn, EnumIter, Insert, QuerySelect};
 use tracing::{debug, instrument, trace};
 
-use hyperlane_core::{InterchainGasPayment, LogMeta};
+use hyperlane_core::{InterchainGasPayment, LogMeta, H256};
 use migration::OnConflict;
 
-use crate::conversions::{h256_to_bytes, u256_to_decimal};
+use crate::conversions::{decimal_to_u256, h256_to_bytes, u256_to_decimal};
 use crate::date_time;
 use crate::db::ScraperDb;
 
@@ -14,6 +14,9 @@ use super::generated::gas_payment;
 
 pub struct StorablePayment<'a> {
     pub payment: &'a InterchainGasPayment,
+    /// The sequence of the payment, for chains which index payments by
+    /// sequence
+    pub sequence: Option<i64>,
     pub meta: &'a LogMeta,
     /// The database id of the transaction the payment was made in
     pub txn_id: i64,
@@ -39,6 +42,8 @@ impl ScraperDb {
                 gas_amount: Set(u256_to_decimal(storable.payment.gas_amount)),
                 tx_id: Unchanged(storable.txn_id),
                 log_index: Unchanged(storable.meta.log_index.as_u64() as i64),
+                destination: Set(Some(storable.payment.destination as i32)),
+                sequence: Set(storable.sequence),
             })
             .collect_vec();
 
@@ -57,6 +62,8 @@ impl ScraperDb {
                     gas_payment::Column::TimeCreated,
                     gas_payment::Column::Payment,
                     gas_payment::Column::GasAmount,
+                    gas_payment::Column::Destination,
+                    gas_payment::Column::Sequence,
                 ])
                 .to_owned(),
             )
@@ -76,6 +83,52 @@ impl ScraperDb {
         Ok(new_payments_count)
     }
 
+    /// Get the gas payment made with a sequence.
+    #[instrument(skip(self))]
+    pub async fn retrieve_payment_by_sequence(
+        &self,
+        domain: u32,
+        sequence: u32,
+    ) -> Result<Option<InterchainGasPayment>> {
+        if let Some(payment) = gas_payment::Entity::find()
+            .filter(gas_payment::Column::Domain.eq(domain))
+            .filter(gas_payment::Column::Sequence.eq(sequence))
+            .one(&self.0)
+            .await?
+        {
+            Ok(Some(InterchainGasPayment {
+                message_id: H256::from_slice(&payment.msg_id),
+                destination: payment
+                    .destination
+                    .ok_or_else(|| eyre!("Gas payment is missing its destination"))?
+                    as u32,
+                payment: decimal_to_u256(payment.payment)?,
+                gas_amount: decimal_to_u256(payment.gas_amount)?,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Get the tx id associated with a gas payment sequence.
+    #[instrument(skip(self))]
+    pub async fn retrieve_payment_tx_id(&self, domain: u32, sequence: u32) -> Result<Option<i64>> {
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
+        enum QueryAs {
+            TxId,
+        }
+
+        let tx_id = gas_payment::Entity::find()
+            .filter(gas_payment::Column::Domain.eq(domain))
+            .filter(gas_payment::Column::Sequence.eq(sequence))
+            .select_only()
+            .column_as(gas_payment::Column::TxId, QueryAs::TxId)
+            .into_values::<i64, QueryAs>()
+            .one(&self.0)
+            .await?;
+        Ok(tx_id)
+    }
+
     async fn latest_payment_id(&self, domain: u32) -> Result<i64> {
         let result = gas_payment::Entity::find()
             .select_only()
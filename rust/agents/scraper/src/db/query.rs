@@ -0,0 +1,367 @@
+//! Read-side queries used to serve the scraper's query API.
+
+use std::collections::HashMap;
+
+use eyre::Result;
+use itertools::Itertools;
+use sea_orm::{prelude::*, QueryOrder, QuerySelect};
+use serde::Serialize;
+
+use hyperlane_core::{utils::bytes_to_hex, H256, H512, U256};
+
+use crate::conversions::{
+    address_to_bytes, bytes_to_address, bytes_to_h512, decimal_to_u256, h256_to_bytes,
+    h512_to_bytes,
+};
+use crate::db::ScraperDb;
+
+use super::generated::{gas_payment, message, message_status, transaction};
+
+/// Maximum number of items returned in a single page
+pub const MAX_PAGE_SIZE: u64 = 500;
+
+/// Position and size of a page of results. Results are ordered from the most
+/// to the least recently stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageRequest {
+    /// Only return items stored before the item with this cursor
+    pub before: Option<i64>,
+    /// Maximum number of items to return, capped to `MAX_PAGE_SIZE`
+    pub limit: u64,
+}
+
+/// A page of results
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Cursor to pass as `before` to fetch the next page, if there may be one
+    pub next_cursor: Option<i64>,
+}
+
+/// Filters for dispatched messages. Unset fields match every message.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageFilter {
+    pub msg_id: Option<H256>,
+    pub sender: Option<H256>,
+    pub recipient: Option<H256>,
+    pub origin_tx_hash: Option<H512>,
+    pub origin: Option<u32>,
+    pub destination: Option<u32>,
+}
+
+/// A dispatched message along with its lifecycle status
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MessageRecord {
+    pub cursor: i64,
+    pub msg_id: H256,
+    pub origin: u32,
+    pub destination: u32,
+    pub nonce: u32,
+    pub sender: H256,
+    pub recipient: H256,
+    /// Hex-encoded message body
+    pub body: String,
+    pub origin_mailbox: H256,
+    pub origin_tx_hash: Option<H512>,
+    /// Unix timestamp of the block the message was dispatched in
+    pub dispatched_at: Option<i64>,
+    pub delivered: bool,
+    pub destination_tx_hash: Option<H512>,
+    /// Unix timestamp of the block the message was delivered in
+    pub delivered_at: Option<i64>,
+    /// Seconds between the dispatch and the delivery
+    pub delivery_latency: Option<i64>,
+    /// The message's share of the cost of the transaction it was delivered in
+    pub delivery_cost: Option<U256>,
+    pub num_payments: u32,
+    pub total_payment: U256,
+    pub total_gas_amount: U256,
+}
+
+/// A gas payment made for a message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PaymentRecord {
+    pub cursor: i64,
+    pub msg_id: H256,
+    /// Domain the payment was made on
+    pub domain: u32,
+    pub destination: Option<u32>,
+    pub payment: U256,
+    pub gas_amount: U256,
+    pub tx_hash: Option<H512>,
+    pub log_index: u64,
+}
+
+impl PageRequest {
+    fn limit(&self) -> u64 {
+        self.limit.clamp(1, MAX_PAGE_SIZE)
+    }
+
+    fn page<T>(&self, items: Vec<T>, cursor: impl Fn(&T) -> i64) -> Page<T> {
+        let next_cursor = if items.len() as u64 == self.limit() {
+            items.last().map(cursor)
+        } else {
+            None
+        };
+        Page { items, next_cursor }
+    }
+}
+
+impl ScraperDb {
+    /// Get a page of dispatched messages matching the filter.
+    pub async fn query_messages(
+        &self,
+        filter: &MessageFilter,
+        page: PageRequest,
+    ) -> Result<Page<MessageRecord>> {
+        let mut query = message::Entity::find();
+        if let Some(msg_id) = &filter.msg_id {
+            query = query.filter(message::Column::MsgId.eq(h256_to_bytes(msg_id)));
+        }
+        if let Some(sender) = &filter.sender {
+            query = query.filter(message::Column::Sender.eq(address_to_bytes(sender)));
+        }
+        if let Some(recipient) = &filter.recipient {
+            query = query.filter(message::Column::Recipient.eq(address_to_bytes(recipient)));
+        }
+        if let Some(origin) = filter.origin {
+            query = query.filter(message::Column::Origin.eq(origin));
+        }
+        if let Some(destination) = filter.destination {
+            query = query.filter(message::Column::Destination.eq(destination));
+        }
+        if let Some(tx_hash) = &filter.origin_tx_hash {
+            let Some(tx_id) = self.retrieve_txn_id_by_hash(tx_hash).await? else {
+                return Ok(page.page(vec![], |m: &MessageRecord| m.cursor));
+            };
+            query = query.filter(message::Column::OriginTxId.eq(tx_id));
+        }
+        if let Some(before) = page.before {
+            query = query.filter(message::Column::Id.lt(before));
+        }
+
+        let messages = query
+            .order_by_desc(message::Column::Id)
+            .limit(page.limit())
+            .all(&self.0)
+            .await?;
+        if messages.is_empty() {
+            return Ok(page.page(vec![], |m: &MessageRecord| m.cursor));
+        }
+
+        let statuses: HashMap<Vec<u8>, message_status::Model> = message_status::Entity::find()
+            .filter(
+                message_status::Column::MsgId
+                    .is_in(messages.iter().map(|m| m.msg_id.clone()).collect_vec()),
+            )
+            .all(&self.0)
+            .await?
+            .into_iter()
+            .map(|s| (s.msg_id.clone(), s))
+            .collect();
+        let tx_hashes = self
+            .retrieve_txn_hashes(
+                messages
+                    .iter()
+                    .map(|m| m.origin_tx_id)
+                    .chain(statuses.values().filter_map(|s| s.destination_tx_id)),
+            )
+            .await?;
+
+        let items = messages
+            .into_iter()
+            .map(|message| {
+                let status = statuses.get(&message.msg_id);
+                let destination_tx_id = status.and_then(|s| s.destination_tx_id);
+                Ok(MessageRecord {
+                    cursor: message.id,
+                    msg_id: H256::from_slice(&message.msg_id),
+                    origin: message.origin as u32,
+                    destination: message.destination as u32,
+                    nonce: message.nonce as u32,
+                    sender: bytes_to_address(message.sender)?,
+                    recipient: bytes_to_address(message.recipient)?,
+                    body: bytes_to_hex(&message.msg_body.unwrap_or_default()),
+                    origin_mailbox: bytes_to_address(message.origin_mailbox)?,
+                    origin_tx_hash: tx_hashes.get(&message.origin_tx_id).copied(),
+                    dispatched_at: status
+                        .and_then(|s| s.dispatched_at)
+                        .map(|t| t.assume_utc().unix_timestamp()),
+                    delivered: destination_tx_id.is_some(),
+                    destination_tx_hash: destination_tx_id
+                        .and_then(|id| tx_hashes.get(&id).copied()),
+                    delivered_at: status
+                        .and_then(|s| s.delivered_at)
+                        .map(|t| t.assume_utc().unix_timestamp()),
+                    delivery_latency: status.and_then(|s| s.delivery_latency),
+                    delivery_cost: status
+                        .and_then(|s| s.delivery_cost.clone())
+                        .map(decimal_to_u256)
+                        .transpose()?,
+                    num_payments: status.map(|s| s.num_payments as u32).unwrap_or_default(),
+                    total_payment: status
+                        .map(|s| decimal_to_u256(s.total_payment.clone()))
+                        .transpose()?
+                        .unwrap_or_default(),
+                    total_gas_amount: status
+                        .map(|s| decimal_to_u256(s.total_gas_amount.clone()))
+                        .transpose()?
+                        .unwrap_or_default(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(page.page(items, |m| m.cursor))
+    }
+
+    /// Get a page of the gas payments made for a message.
+    pub async fn query_payments(
+        &self,
+        msg_id: &H256,
+        page: PageRequest,
+    ) -> Result<Page<PaymentRecord>> {
+        let mut query = gas_payment::Entity::find()
+            .filter(gas_payment::Column::MsgId.eq(h256_to_bytes(msg_id)));
+        if let Some(before) = page.before {
+            query = query.filter(gas_payment::Column::Id.lt(before));
+        }
+        let payments = query
+            .order_by_desc(gas_payment::Column::Id)
+            .limit(page.limit())
+            .all(&self.0)
+            .await?;
+
+        let tx_hashes = self
+            .retrieve_txn_hashes(payments.iter().map(|p| p.tx_id))
+            .await?;
+
+        let items = payments
+            .into_iter()
+            .map(|payment| {
+                Ok(PaymentRecord {
+                    cursor: payment.id,
+                    msg_id: H256::from_slice(&payment.msg_id),
+                    domain: payment.domain as u32,
+                    destination: payment.destination.map(|d| d as u32),
+                    payment: decimal_to_u256(payment.payment)?,
+                    gas_amount: decimal_to_u256(payment.gas_amount)?,
+                    tx_hash: tx_hashes.get(&payment.tx_id).copied(),
+                    log_index: payment.log_index as u64,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(page.page(items, |p| p.cursor))
+    }
+
+    async fn retrieve_txn_id_by_hash(&self, hash: &H512) -> Result<Option<i64>> {
+        Ok(transaction::Entity::find()
+            .filter(transaction::Column::Hash.eq(h512_to_bytes(hash)))
+            .one(&self.0)
+            .await?
+            .map(|txn| txn.id))
+    }
+
+    async fn retrieve_txn_hashes(
+        &self,
+        txn_ids: impl Iterator<Item = i64>,
+    ) -> Result<HashMap<i64, H512>> {
+        let txn_ids = txn_ids.unique().collect_vec();
+        if txn_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        transaction::Entity::find()
+            .filter(transaction::Column::Id.is_in(txn_ids))
+            .all(&self.0)
+            .await?
+            .into_iter()
+            .map(|txn| Ok((txn.id, bytes_to_h512(&txn.hash)?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{DatabaseBackend, MockDatabase};
+
+    use crate::date_time;
+
+    use super::*;
+
+    fn message_model(id: i64, msg_id: H256) -> message::Model {
+        message::Model {
+            id,
+            time_created: date_time::now(),
+            msg_id: h256_to_bytes(&msg_id),
+            origin: 1,
+            destination: 2,
+            nonce: id as i32,
+            sender: address_to_bytes(&H256::from_low_u64_be(3)),
+            recipient: address_to_bytes(&H256::from_low_u64_be(4)),
+            msg_body: Some(vec![0xab]),
+            origin_mailbox: address_to_bytes(&H256::from_low_u64_be(5)),
+            origin_tx_id: 10 + id,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_messages_paginates() {
+        let first = H256::repeat_byte(1);
+        let second = H256::repeat_byte(2);
+        let conn = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![
+                message_model(2, second),
+                message_model(1, first),
+            ]])
+            .append_query_results(vec![Vec::<message_status::Model>::new()])
+            .append_query_results(vec![Vec::<transaction::Model>::new()])
+            .into_connection();
+        let db = ScraperDb(conn);
+
+        let page = db
+            .query_messages(
+                &MessageFilter::default(),
+                PageRequest {
+                    before: None,
+                    limit: 2,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            page.items.iter().map(|m| m.msg_id).collect_vec(),
+            vec![second, first]
+        );
+        assert_eq!(page.next_cursor, Some(1));
+        assert_eq!(page.items[0].sender, H256::from_low_u64_be(3));
+        assert_eq!(page.items[0].body, "0xab");
+        assert!(!page.items[0].delivered);
+        assert_eq!(page.items[0].total_payment, U256::zero());
+    }
+
+    #[tokio::test]
+    async fn test_query_messages_with_unknown_origin_tx() {
+        let conn = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![Vec::<transaction::Model>::new()])
+            .into_connection();
+        let db = ScraperDb(conn);
+
+        let page = db
+            .query_messages(
+                &MessageFilter {
+                    origin_tx_hash: Some(H512::repeat_byte(1)),
+                    ..Default::default()
+                },
+                PageRequest {
+                    before: None,
+                    limit: 10,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert!(page.items.is_empty());
+        assert_eq!(page.next_cursor, None);
+    }
+}
@@ -23,6 +23,7 @@ mod agent;
 mod chain_scraper;
 mod conversions;
 mod date_time;
+mod server;
 mod settings;
 
 #[tokio::main(flavor = "current_thread")]
@@ -0,0 +1,3 @@
+pub use query_api::*;
+
+mod query_api;
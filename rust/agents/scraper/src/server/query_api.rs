@@ -0,0 +1,564 @@
+//! Read-only API over the data stored by the scraper.
+//!
+//! Base URL /query
+//! Routes
+//! - GET /messages - Messages, optionally filtered by `msg_id`, `sender`,
+//!   `recipient`, `origin_tx_hash`, `origin` and `destination`
+//! - GET /messages/{message_id} - A single message
+//! - GET /messages/{message_id}/gas_payments - Gas payments made for a message
+//!
+//! List routes are paginated from the most to the least recently scraped item.
+//! They accept a `limit` and a `before` cursor, which is the `next_cursor` of
+//! the previous page.
+
+use std::str::FromStr;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing, Json, Router,
+};
+use ethers::utils::hex;
+use hyperlane_core::{H256, H512};
+use serde::Deserialize;
+use tracing::warn;
+
+use crate::conversions::bytes_to_address;
+use crate::db::{MessageFilter, MessageRecord, Page, PageRequest, PaymentRecord, ScraperDb};
+
+const QUERY_API_BASE: &str = "/query";
+
+/// Number of items returned when a request doesn't specify a limit
+const DEFAULT_PAGE_SIZE: u64 = 50;
+
+type ApiResult<T> = Result<Json<T>, (StatusCode, String)>;
+
+#[derive(Clone)]
+pub struct QueryApi {
+    db: ScraperDb,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PageParams {
+    before: Option<i64>,
+    limit: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct MessagesParams {
+    msg_id: Option<String>,
+    sender: Option<String>,
+    recipient: Option<String>,
+    origin_tx_hash: Option<String>,
+    origin: Option<u32>,
+    destination: Option<u32>,
+    before: Option<i64>,
+    limit: Option<u64>,
+}
+
+impl From<PageParams> for PageRequest {
+    fn from(params: PageParams) -> Self {
+        PageRequest {
+            before: params.before,
+            limit: params.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+        }
+    }
+}
+
+impl MessagesParams {
+    fn page(&self) -> PageRequest {
+        PageParams {
+            before: self.before,
+            limit: self.limit,
+        }
+        .into()
+    }
+
+    fn filter(&self) -> Result<MessageFilter, (StatusCode, String)> {
+        Ok(MessageFilter {
+            msg_id: self.msg_id.as_deref().map(parse_h256).transpose()?,
+            sender: self.sender.as_deref().map(parse_address).transpose()?,
+            recipient: self.recipient.as_deref().map(parse_address).transpose()?,
+            origin_tx_hash: self
+                .origin_tx_hash
+                .as_deref()
+                .map(parse_tx_hash)
+                .transpose()?,
+            origin: self.origin,
+            destination: self.destination,
+        })
+    }
+}
+
+impl QueryApi {
+    pub fn new(db: ScraperDb) -> Self {
+        Self { db }
+    }
+
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/messages", routing::get(Self::messages))
+            .route("/messages/:message_id", routing::get(Self::message))
+            .route(
+                "/messages/:message_id/gas_payments",
+                routing::get(Self::gas_payments),
+            )
+            .with_state(self.clone())
+    }
+
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (QUERY_API_BASE, self.router())
+    }
+
+    async fn messages(
+        State(api): State<Self>,
+        Query(params): Query<MessagesParams>,
+    ) -> ApiResult<Page<MessageRecord>> {
+        let filter = params.filter()?;
+        api.db
+            .query_messages(&filter, params.page())
+            .await
+            .map(Json)
+            .map_err(internal_error)
+    }
+
+    async fn message(
+        State(api): State<Self>,
+        Path(message_id): Path<String>,
+    ) -> ApiResult<MessageRecord> {
+        let filter = MessageFilter {
+            msg_id: Some(parse_h256(&message_id)?),
+            ..Default::default()
+        };
+        let page = PageRequest {
+            before: None,
+            limit: 1,
+        };
+        let message = api
+            .db
+            .query_messages(&filter, page)
+            .await
+            .map_err(internal_error)?
+            .items
+            .pop()
+            .ok_or((
+                StatusCode::NOT_FOUND,
+                format!("Message {message_id} has not been scraped"),
+            ))?;
+        Ok(Json(message))
+    }
+
+    async fn gas_payments(
+        State(api): State<Self>,
+        Path(message_id): Path<String>,
+        Query(params): Query<PageParams>,
+    ) -> ApiResult<Page<PaymentRecord>> {
+        let message_id = parse_h256(&message_id)?;
+        api.db
+            .query_payments(&message_id, params.into())
+            .await
+            .map(Json)
+            .map_err(internal_error)
+    }
+}
+
+fn parse_h256(value: &str) -> Result<H256, (StatusCode, String)> {
+    H256::from_str(value).map_err(|err| bad_request(value, err))
+}
+
+/// Parses both 20 byte and 32 byte addresses
+fn parse_address(value: &str) -> Result<H256, (StatusCode, String)> {
+    hex::decode(value.trim_start_matches("0x"))
+        .map_err(eyre::Report::from)
+        .and_then(bytes_to_address)
+        .map_err(|err| bad_request(value, err))
+}
+
+/// Parses both 32 byte and 64 byte transaction hashes
+fn parse_tx_hash(value: &str) -> Result<H512, (StatusCode, String)> {
+    let value = value.trim_start_matches("0x");
+    if value.len() == H256::len_bytes() * 2 {
+        parse_h256(value).map(Into::into)
+    } else {
+        H512::from_str(value).map_err(|err| bad_request(value, err))
+    }
+}
+
+fn bad_request(value: &str, err: impl std::fmt::Display) -> (StatusCode, String) {
+    (
+        StatusCode::BAD_REQUEST,
+        format!("Invalid value {value}: {err}"),
+    )
+}
+
+fn internal_error(err: impl std::fmt::Display) -> (StatusCode, String) {
+    warn!(%err, "Query API request failed");
+    (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, net::SocketAddr};
+
+    use hyperlane_core::{
+        BlockInfo, HyperlaneMessage, InterchainGasPayment, LogMeta, TxnInfo, TxnReceiptInfo, U256,
+    };
+    use migration::{Migrator, MigratorTrait};
+    use sea_orm::{
+        ConnectOptions, ConnectionTrait, Database, DatabaseBackend, DbConn, MockDatabase, Value,
+    };
+    use serde_json::Value as JsonValue;
+
+    use crate::db::{StorableDelivery, StorableMessage, StorablePayment, StorableTxn};
+
+    use super::*;
+
+    /// Postgres database the integration tests run against. Each test creates
+    /// its own schema in it and drops it when done.
+    const TEST_DATABASE_URL_ENV: &str = "SCRAPER_TEST_DATABASE_URL";
+
+    const ORIGIN: u32 = 1;
+    const DESTINATION: u32 = 42161;
+
+    fn setup_test_server(db: ScraperDb) -> SocketAddr {
+        let api = QueryApi::new(db);
+        let (path, router) = api.get_route();
+        let app = Router::new().nest(path, router);
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_invalid_params() {
+        let addr = setup_test_server(ScraperDb::from_connection(
+            MockDatabase::new(DatabaseBackend::Postgres).into_connection(),
+        ));
+
+        let res = reqwest::get(format!("http://{addr}/query/messages/not_a_message_id"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+        let res = reqwest::get(format!("http://{addr}/query/messages?sender=0x1234"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_message_id() {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![Vec::<BTreeMap<String, Value>>::new()]);
+        let addr = setup_test_server(ScraperDb::from_connection(db.into_connection()));
+
+        let res = reqwest::get(format!(
+            "http://{addr}/query/messages/{:?}",
+            H256::repeat_byte(1)
+        ))
+        .await
+        .unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_parse_tx_hash() {
+        let hash = H256::repeat_byte(1);
+        assert_eq!(parse_tx_hash(&format!("{hash:?}")).unwrap(), hash.into());
+
+        let hash = H512::repeat_byte(1);
+        assert_eq!(parse_tx_hash(&format!("{hash:?}")).unwrap(), hash);
+    }
+
+    /// A throwaway schema in the test database, migrated to the latest version.
+    struct TestSchema {
+        name: String,
+        admin: DbConn,
+        conn: DbConn,
+    }
+
+    impl TestSchema {
+        async fn create() -> Self {
+            let url = std::env::var(TEST_DATABASE_URL_ENV)
+                .unwrap_or_else(|_| panic!("{TEST_DATABASE_URL_ENV} must be set"));
+            let nanos = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos();
+            let name = format!("scraper_test_{}_{nanos}", std::process::id());
+
+            let admin = Database::connect(&url).await.unwrap();
+            admin
+                .execute_unprepared(&format!("CREATE SCHEMA \"{name}\""))
+                .await
+                .unwrap();
+            let mut options = ConnectOptions::new(url);
+            options.set_schema_search_path(name.clone());
+            let conn = Database::connect(options).await.unwrap();
+            Migrator::up(&conn, None).await.unwrap();
+            Self { name, admin, conn }
+        }
+
+        async fn remove(self) {
+            self.admin
+                .execute_unprepared(&format!("DROP SCHEMA \"{}\" CASCADE", self.name))
+                .await
+                .unwrap();
+        }
+    }
+
+    fn block(domain: u32, timestamp: u64) -> BlockInfo {
+        BlockInfo {
+            hash: H256::from_low_u64_be(domain as u64),
+            timestamp,
+            number: 1,
+        }
+    }
+
+    fn txn(hash: H256, block_id: i64, gas_used: u32, effective_gas_price: u32) -> StorableTxn {
+        StorableTxn {
+            info: TxnInfo {
+                hash: hash.into(),
+                gas_limit: U256::from(1_000_000),
+                max_priority_fee_per_gas: None,
+                max_fee_per_gas: None,
+                gas_price: None,
+                nonce: 0,
+                sender: H256::from_low_u64_be(1),
+                recipient: None,
+                receipt: Some(TxnReceiptInfo {
+                    gas_used: gas_used.into(),
+                    cumulative_gas_used: gas_used.into(),
+                    effective_gas_price: Some(effective_gas_price.into()),
+                }),
+            },
+            block_id,
+        }
+    }
+
+    fn message(nonce: u32) -> HyperlaneMessage {
+        HyperlaneMessage {
+            nonce,
+            origin: ORIGIN,
+            destination: DESTINATION,
+            sender: H256::from_low_u64_be(3),
+            recipient: H256::from_low_u64_be(4),
+            body: vec![nonce as u8],
+            ..Default::default()
+        }
+    }
+
+    async fn get_json(url: String) -> JsonValue {
+        let res = reqwest::get(url).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        res.json().await.unwrap()
+    }
+
+    fn json(value: impl serde::Serialize) -> JsonValue {
+        serde_json::to_value(value).unwrap()
+    }
+
+    /// Three messages are dispatched in one transaction, and the first two are
+    /// delivered together in another one.
+    async fn store_messages(db: &ScraperDb) -> (Vec<HyperlaneMessage>, H256, H256) {
+        let origin_tx = H256::repeat_byte(0xa1);
+        let destination_tx = H256::repeat_byte(0xb2);
+        let mailbox = H256::from_low_u64_be(5);
+
+        db.store_blocks(ORIGIN, [block(ORIGIN, 1_000)].into_iter())
+            .await
+            .unwrap();
+        db.store_blocks(DESTINATION, [block(DESTINATION, 1_060)].into_iter())
+            .await
+            .unwrap();
+        let blocks = db
+            .get_block_basic(
+                [
+                    H256::from_low_u64_be(ORIGIN as u64),
+                    H256::from_low_u64_be(DESTINATION as u64),
+                ]
+                .iter(),
+            )
+            .await
+            .unwrap();
+        let block_id = |domain: u32| {
+            blocks
+                .iter()
+                .find(|b| b.hash == H256::from_low_u64_be(domain as u64))
+                .unwrap()
+                .id
+        };
+        db.store_txns(
+            [
+                txn(origin_tx, block_id(ORIGIN), 50, 1),
+                txn(destination_tx, block_id(DESTINATION), 100, 10),
+            ]
+            .into_iter(),
+        )
+        .await
+        .unwrap();
+        let txn_ids = db
+            .get_txn_ids([H512::from(origin_tx), H512::from(destination_tx)].iter())
+            .await
+            .unwrap();
+        let origin_tx_id = txn_ids[&H512::from(origin_tx)];
+        let destination_tx_id = txn_ids[&H512::from(destination_tx)];
+
+        let messages = (0..3).map(message).collect::<Vec<_>>();
+        let meta = LogMeta::default();
+        db.store_dispatched_messages(
+            ORIGIN,
+            &mailbox,
+            messages.iter().map(|msg| StorableMessage {
+                msg: msg.clone(),
+                meta: &meta,
+                txn_id: origin_tx_id,
+            }),
+        )
+        .await
+        .unwrap();
+        db.store_deliveries(
+            DESTINATION,
+            mailbox,
+            messages[..2].iter().map(|msg| StorableDelivery {
+                message_id: msg.id(),
+                sequence: None,
+                meta: &meta,
+                txn_id: destination_tx_id,
+            }),
+        )
+        .await
+        .unwrap();
+        let payments = [10u32, 20].map(|payment| InterchainGasPayment {
+            message_id: messages[0].id(),
+            destination: DESTINATION,
+            payment: payment.into(),
+            gas_amount: 1u32.into(),
+        });
+        let metas = [0u64, 1].map(|log_index| LogMeta {
+            log_index: log_index.into(),
+            ..Default::default()
+        });
+        db.store_payments(
+            ORIGIN,
+            payments
+                .iter()
+                .zip(&metas)
+                .map(|(payment, meta)| StorablePayment {
+                    payment,
+                    sequence: None,
+                    meta,
+                    txn_id: origin_tx_id,
+                }),
+        )
+        .await
+        .unwrap();
+
+        db.update_message_statuses(messages.iter().map(|msg| msg.id()))
+            .await
+            .unwrap();
+        (messages, origin_tx, destination_tx)
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a Postgres database at SCRAPER_TEST_DATABASE_URL"]
+    async fn test_query_migrated_db() {
+        let schema = TestSchema::create().await;
+        let conn = schema.conn.clone();
+        let db = ScraperDb::from_connection(conn.clone());
+        let (messages, origin_tx, destination_tx) = store_messages(&db).await;
+        let addr = setup_test_server(db);
+        let msg_ids = |page: &JsonValue| {
+            page["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|item| item["msg_id"].clone())
+                .collect::<Vec<_>>()
+        };
+
+        // Pages go from the most to the least recently dispatched message
+        let page = get_json(format!("http://{addr}/query/messages?limit=2")).await;
+        assert_eq!(
+            msg_ids(&page),
+            vec![json(messages[2].id()), json(messages[1].id())]
+        );
+        let page = get_json(format!(
+            "http://{addr}/query/messages?limit=2&before={}",
+            page["next_cursor"]
+        ))
+        .await;
+        assert_eq!(msg_ids(&page), vec![json(messages[0].id())]);
+        assert_eq!(page["next_cursor"], JsonValue::Null);
+
+        let page = get_json(format!(
+            "http://{addr}/query/messages?origin_tx_hash={origin_tx:?}&destination={DESTINATION}"
+        ))
+        .await;
+        assert_eq!(msg_ids(&page).len(), 3);
+        let page = get_json(format!(
+            "http://{addr}/query/messages?origin_tx_hash={destination_tx:?}"
+        ))
+        .await;
+        assert_eq!(msg_ids(&page).len(), 0);
+
+        // The lifecycle is joined from the dispatch, delivery and payments
+        let delivered = get_json(format!(
+            "http://{addr}/query/messages/{:?}",
+            messages[0].id()
+        ))
+        .await;
+        assert_eq!(delivered["origin_tx_hash"], json(H512::from(origin_tx)));
+        assert_eq!(delivered["dispatched_at"], json(1_000));
+        assert_eq!(delivered["delivered"], json(true));
+        assert_eq!(
+            delivered["destination_tx_hash"],
+            json(H512::from(destination_tx))
+        );
+        assert_eq!(delivered["delivered_at"], json(1_060));
+        assert_eq!(delivered["delivery_latency"], json(60));
+        // Half of the 100 * 10 spent on the transaction that delivered two messages
+        assert_eq!(delivered["delivery_cost"], json(U256::from(500)));
+        assert_eq!(delivered["num_payments"], json(2));
+        assert_eq!(delivered["total_payment"], json(U256::from(30)));
+        assert_eq!(delivered["total_gas_amount"], json(U256::from(2)));
+
+        let undelivered = get_json(format!(
+            "http://{addr}/query/messages/{:?}",
+            messages[2].id()
+        ))
+        .await;
+        assert_eq!(undelivered["delivered"], json(false));
+        assert_eq!(undelivered["delivery_cost"], JsonValue::Null);
+        assert_eq!(undelivered["num_payments"], json(0));
+
+        let page = get_json(format!(
+            "http://{addr}/query/messages/{:?}/gas_payments?limit=1",
+            messages[0].id()
+        ))
+        .await;
+        assert_eq!(page["items"][0]["payment"], json(U256::from(20)));
+        assert_eq!(page["items"][0]["tx_hash"], json(H512::from(origin_tx)));
+        let page = get_json(format!(
+            "http://{addr}/query/messages/{:?}/gas_payments?limit=1&before={}",
+            messages[0].id(),
+            page["next_cursor"]
+        ))
+        .await;
+        assert_eq!(page["items"][0]["payment"], json(U256::from(10)));
+
+        // Recreating the statuses from the existing rows gives the same result
+        Migrator::down(&conn, Some(1)).await.unwrap();
+        Migrator::up(&conn, None).await.unwrap();
+        let backfilled = get_json(format!(
+            "http://{addr}/query/messages/{:?}",
+            messages[0].id()
+        ))
+        .await;
+        assert_eq!(backfilled, delivered);
+
+        schema.remove().await;
+    }
+}
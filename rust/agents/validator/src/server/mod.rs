@@ -1,19 +1,32 @@
 pub mod eigen_node;
+pub mod slashing_protection;
 use std::{sync::Arc, vec};
 
 use axum::Router;
 pub use eigen_node::EigenNodeApi;
+pub use slashing_protection::SlashingProtectionApi;
 
 use hyperlane_base::CoreMetrics;
-use hyperlane_core::HyperlaneDomain;
+use hyperlane_core::{HyperlaneDomain, H160};
+
+use crate::slashing_protection::SigningJournal;
 
 /// Returns a vector of validator-specific endpoint routes to be served.
 /// Can be extended with additional routes and feature flags to enable/disable individually.
 pub fn routes(
     origin_chain: HyperlaneDomain,
     metrics: Arc<CoreMetrics>,
+    signing_journal: SigningJournal,
+    validator: H160,
+    operator_api_token: Option<String>,
 ) -> Vec<(&'static str, Router)> {
     let eigen_node_api = EigenNodeApi::new(origin_chain, metrics);
+    let mut routes = vec![eigen_node_api.get_route()];
 
-    vec![eigen_node_api.get_route()]
+    // The slashing protection export is only served to operators
+    if let Some(token) = operator_api_token {
+        let slashing_protection_api = SlashingProtectionApi::new(signing_journal, validator);
+        routes.push(slashing_protection_api.get_route(token));
+    }
+    routes
 }
@@ -0,0 +1,184 @@
+//! Serves the validator's slashing protection data, so it can be imported into a new
+//! instance when migrating the validator key. Requires the operator API token.
+//!
+//! Base URL /slashing_protection
+//! Routes
+//! - /?from=<index>&limit=<count> - Page of the checkpoints signed by this validator,
+//!   starting at index `from` (default 0), with at most `limit` indices (default and max
+//!   10000). `nextIndex` is set if there are more checkpoints to export.
+//!   eg. response {"validator":"0x...","mailboxDomain":1,"checkpoints":[...],"nextIndex":10000}
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    routing::get,
+    Json, Router,
+};
+use derive_new::new;
+use hyperlane_base::server::require_bearer_token;
+use hyperlane_core::H160;
+use serde::{Deserialize, Serialize};
+use tokio::task::spawn_blocking;
+use tracing::error;
+
+use crate::slashing_protection::{SigningJournal, SlashingProtectionInterchange};
+
+const SLASHING_PROTECTION_API_BASE: &str = "/slashing_protection";
+
+/// Maximum number of indices exported per request
+const MAX_EXPORT_PAGE_SIZE: u32 = 10_000;
+
+#[derive(new, Clone)]
+pub struct SlashingProtectionApi {
+    journal: SigningJournal,
+    validator: H160,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExportQuery {
+    from: Option<u32>,
+    limit: Option<u32>,
+}
+
+/// A page of the export, which can be imported like a complete export
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportPage {
+    #[serde(flatten)]
+    interchange: SlashingProtectionInterchange,
+    /// Index to start the next page at, if any checkpoints are left
+    next_index: Option<u32>,
+}
+
+impl SlashingProtectionApi {
+    pub fn get_route(&self, token: String) -> (&'static str, Router) {
+        (
+            SLASHING_PROTECTION_API_BASE,
+            require_bearer_token(self.router(), token),
+        )
+    }
+
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/", get(Self::export_handler))
+            .with_state(self.clone())
+    }
+
+    async fn export_handler(
+        State(api): State<Self>,
+        Query(query): Query<ExportQuery>,
+    ) -> Result<Json<ExportPage>, StatusCode> {
+        let from = query.from.unwrap_or_default();
+        let limit = query
+            .limit
+            .unwrap_or(MAX_EXPORT_PAGE_SIZE)
+            .clamp(1, MAX_EXPORT_PAGE_SIZE);
+        // Reading the DB blocks, so keep it off the async runtime
+        spawn_blocking(move || api.export_page(from, limit))
+            .await
+            .map_err(eyre::Report::from)
+            .and_then(|page| page)
+            .map(Json)
+            .map_err(|err| {
+                error!(?err, "Failed to export slashing protection data");
+                StatusCode::INTERNAL_SERVER_ERROR
+            })
+    }
+
+    fn export_page(&self, from: u32, limit: u32) -> eyre::Result<ExportPage> {
+        let highest_index = self.journal.highest_signed_index()?;
+        let last = from.saturating_add(limit - 1);
+        let indices = match highest_index {
+            Some(highest_index) if from <= highest_index => from..=last.min(highest_index),
+            // Nothing to export
+            _ => 1..=0,
+        };
+        let next_index = highest_index
+            .filter(|&highest_index| last < highest_index)
+            .map(|_| last + 1);
+        Ok(ExportPage {
+            interchange: self.journal.export(self.validator, indices)?,
+            next_index,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperlane_base::{
+        db::{test_utils, HyperlaneRocksDB},
+        CoreMetrics,
+    };
+    use hyperlane_core::{Checkpoint, CheckpointWithMessageId, HyperlaneDomain, H256};
+    use prometheus::Registry;
+
+    use super::*;
+
+    const TOKEN: &str = "secret";
+
+    #[tokio::test]
+    async fn test_export_slashing_protection_data() {
+        test_utils::run_test_db(|db| async move {
+            let domain = HyperlaneDomain::new_test_domain("ethereum");
+            let metrics = CoreMetrics::new("dummy_validator", 37582, Registry::new()).unwrap();
+            let journal =
+                SigningJournal::new(HyperlaneRocksDB::new(&domain, db), &metrics, &domain);
+            let checkpoints: Vec<_> = (0..3)
+                .map(|index| CheckpointWithMessageId {
+                    checkpoint: Checkpoint {
+                        merkle_tree_hook_address: H256::repeat_byte(1),
+                        mailbox_domain: domain.id(),
+                        root: H256::repeat_byte(2),
+                        index,
+                    },
+                    message_id: H256::repeat_byte(3),
+                })
+                .collect();
+            for checkpoint in &checkpoints {
+                journal.check_and_record(checkpoint).unwrap();
+            }
+
+            let validator = H160::repeat_byte(4);
+            let (path, router) =
+                SlashingProtectionApi::new(journal, validator).get_route(TOKEN.to_owned());
+            let app = Router::new().nest(path, router);
+            let server =
+                axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+            let addr = server.local_addr();
+            tokio::spawn(server);
+
+            let client = reqwest::Client::new();
+            let url = format!("http://{addr}{SLASHING_PROTECTION_API_BASE}");
+            let res = client
+                .get(&url)
+                .send()
+                .await
+                .expect("Failed to send request");
+            assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+            let get_page = |from: u32| {
+                client
+                    .get(&url)
+                    .query(&[("from", from), ("limit", 2)])
+                    .bearer_auth(TOKEN)
+                    .send()
+            };
+            let res = get_page(0).await.expect("Failed to send request");
+            assert_eq!(res.status(), StatusCode::OK);
+            let page: ExportPage = res.json().await.unwrap();
+            assert_eq!(page.interchange.validator, validator);
+            assert_eq!(page.interchange.mailbox_domain, domain.id());
+            assert_eq!(page.interchange.checkpoints, checkpoints[..2]);
+            assert_eq!(page.next_index, Some(2));
+
+            let page: ExportPage = get_page(2).await.unwrap().json().await.unwrap();
+            assert_eq!(page.interchange.checkpoints, checkpoints[2..]);
+            assert_eq!(page.next_index, None);
+
+            let page: ExportPage = get_page(5).await.unwrap().json().await.unwrap();
+            assert!(page.interchange.checkpoints.is_empty());
+            assert_eq!(page.next_index, None);
+        })
+        .await;
+    }
+}
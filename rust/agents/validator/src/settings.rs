@@ -36,10 +36,18 @@ pub struct ValidatorSettings {
     pub validator: SignerConf,
     /// The checkpoint syncer configuration
     pub checkpoint_syncer: CheckpointSyncerConf,
+    /// Additional checkpoint syncers that checkpoints are mirrored to
+    pub checkpoint_syncer_mirrors: Vec<CheckpointSyncerConf>,
     /// The reorg_period in blocks
     pub reorg_period: u64,
     /// How frequently to check for new checkpoints
     pub interval: Duration,
+    /// Slashing protection data exported from another instance of this validator,
+    /// imported on startup
+    pub slashing_protection_import_path: Option<PathBuf>,
+    /// Bearer token required by the operator endpoints, such as the slashing protection
+    /// export. They are disabled if not set.
+    pub operator_api_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -107,6 +115,17 @@ impl FromRawConf<RawValidatorSettings> for ValidatorSettings {
             .and_then(parse_checkpoint_syncer)
             .end();
 
+        let checkpoint_syncer_mirrors = p
+            .chain(&mut err)
+            .get_opt_key("checkpointSyncerMirrors")
+            .into_array_iter()
+            .map(|syncers| {
+                syncers
+                    .filter_map(|syncer| parse_checkpoint_syncer(syncer).take_config_err(&mut err))
+                    .collect()
+            })
+            .unwrap_or_default();
+
         let interval = p
             .chain(&mut err)
             .get_opt_key("interval")
@@ -114,6 +133,19 @@ impl FromRawConf<RawValidatorSettings> for ValidatorSettings {
             .map(Duration::from_secs)
             .unwrap_or(Duration::from_secs(5));
 
+        let slashing_protection_import_path = p
+            .chain(&mut err)
+            .get_opt_key("slashingProtectionImportPath")
+            .parse_from_str("Expected slashing protection import path")
+            .end();
+
+        let operator_api_token = p
+            .chain(&mut err)
+            .get_opt_key("operatorApiToken")
+            .parse_string()
+            .end()
+            .map(str::to_owned);
+
         cfg_unwrap_all!(cwp, err: [origin_chain_name]);
 
         let reorg_period = p
@@ -129,7 +161,10 @@ impl FromRawConf<RawValidatorSettings> for ValidatorSettings {
 
         let mut base: Settings = base;
         // If the origin chain is an EVM chain, then we can use the validator as the signer if needed.
-        if origin_chain.domain_protocol() == HyperlaneDomainProtocol::Ethereum {
+        // Remote signers can't sign transactions, so they can't be used as the chain signer.
+        if origin_chain.domain_protocol() == HyperlaneDomainProtocol::Ethereum
+            && !matches!(validator, SignerConf::Remote { .. })
+        {
             if let Some(origin) = base.chains.get_mut(origin_chain.name()) {
                 origin.signer.get_or_insert_with(|| validator.clone());
             }
@@ -141,8 +176,11 @@ impl FromRawConf<RawValidatorSettings> for ValidatorSettings {
             origin_chain,
             validator,
             checkpoint_syncer,
+            checkpoint_syncer_mirrors,
             reorg_period,
             interval,
+            slashing_protection_import_path,
+            operator_api_token,
         })
     }
 }
@@ -188,6 +226,22 @@ fn parse_checkpoint_syncer(syncer: ValueParser) -> ConfigResult<CheckpointSyncer
                 folder,
             })
         }
+        Some("http") => {
+            let url = syncer
+                .chain(&mut err)
+                .get_key("url")
+                .parse_from_str("Expected checkpoint syncer url")
+                .end();
+            let auth_token = syncer
+                .chain(&mut err)
+                .get_opt_key("authToken")
+                .parse_string()
+                .end()
+                .map(str::to_owned);
+
+            cfg_unwrap_all!(&syncer.cwp, err: [url]);
+            err.into_result(CheckpointSyncerConf::Http { url, auth_token })
+        }
         Some(_) => {
             Err(eyre!("Unknown checkpoint syncer type")).into_config_result(|| &syncer.cwp + "type")
         }
@@ -0,0 +1,273 @@
+//! Slashing protection for checkpoint signing.
+//!
+//! Every checkpoint is recorded in the validator DB before it is signed. Signing a
+//! checkpoint that conflicts with one already signed at the same index, e.g. after a
+//! reorg or when a second instance with the same key is misconfigured, is refused.
+//!
+//! The journal can be exported from a running validator and imported into a new one
+//! when migrating the key, so the new instance doesn't sign conflicting checkpoints.
+
+use std::{
+    ops::RangeInclusive,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use eyre::{bail, Context, Result};
+use hyperlane_base::{db::HyperlaneRocksDB, CoreMetrics};
+use hyperlane_core::{CheckpointWithMessageId, HyperlaneDomain, H160};
+use prometheus::IntCounter;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{error, info};
+
+/// Signing journal that can be exported and imported into another validator
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlashingProtectionInterchange {
+    /// The validator that signed the checkpoints
+    pub validator: H160,
+    /// The domain of the mailbox the checkpoints are for
+    pub mailbox_domain: u32,
+    /// The signed checkpoints, in order of index
+    pub checkpoints: Vec<CheckpointWithMessageId>,
+}
+
+#[derive(Debug, Error)]
+pub enum SlashingProtectionError {
+    #[error("Refusing to sign checkpoint {attempted:?}, which conflicts with already signed checkpoint {signed:?}")]
+    ConflictingCheckpoint {
+        signed: CheckpointWithMessageId,
+        attempted: CheckpointWithMessageId,
+    },
+    #[error(transparent)]
+    Db(#[from] hyperlane_base::db::DbError),
+}
+
+/// Persistent record of the checkpoints signed by the validator
+#[derive(Debug, Clone)]
+pub struct SigningJournal {
+    db: HyperlaneRocksDB,
+    /// Makes checking and recording a checkpoint atomic across submitter tasks
+    lock: Arc<Mutex<()>>,
+    refusals: IntCounter,
+}
+
+impl SigningJournal {
+    pub fn new(db: HyperlaneRocksDB, metrics: &CoreMetrics, origin: &HyperlaneDomain) -> Self {
+        let refusals = metrics
+            .new_int_counter(
+                "validator_checkpoint_signing_refusals",
+                "Number of checkpoints the validator refused to sign because they conflict with an already signed checkpoint",
+                &["origin"],
+            )
+            .expect("failed to register validator_checkpoint_signing_refusals metric")
+            .with_label_values(&[origin.name()]);
+        Self {
+            db,
+            lock: Default::default(),
+            refusals,
+        }
+    }
+
+    /// Records that the checkpoint is about to be signed. Fails if a different checkpoint
+    /// was already signed at the same index. Re-signing the same checkpoint is allowed.
+    pub fn check_and_record(
+        &self,
+        checkpoint: &CheckpointWithMessageId,
+    ) -> Result<(), SlashingProtectionError> {
+        let _guard = self
+            .lock
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match self.db.retrieve_signed_checkpoint(checkpoint.index)? {
+            Some(signed) if signed == *checkpoint => Ok(()),
+            Some(signed) => {
+                self.refusals.inc();
+                let err = SlashingProtectionError::ConflictingCheckpoint {
+                    signed,
+                    attempted: *checkpoint,
+                };
+                error!(error = %err, "Slashing protection refused to sign checkpoint");
+                Err(err)
+            }
+            None => Ok(self.db.store_signed_checkpoint(checkpoint)?),
+        }
+    }
+
+    /// The index of the highest checkpoint signed by the validator
+    pub fn highest_signed_index(&self) -> Result<Option<u32>> {
+        Ok(self.db.retrieve_highest_signed_checkpoint_index()?)
+    }
+
+    /// Exports the checkpoints signed by the validator with an index in `indices`
+    pub fn export(
+        &self,
+        validator: H160,
+        indices: RangeInclusive<u32>,
+    ) -> Result<SlashingProtectionInterchange> {
+        let mut checkpoints = vec![];
+        for index in indices {
+            if let Some(checkpoint) = self.db.retrieve_signed_checkpoint(index)? {
+                checkpoints.push(checkpoint);
+            }
+        }
+        Ok(SlashingProtectionInterchange {
+            validator,
+            mailbox_domain: self.db.domain().id(),
+            checkpoints,
+        })
+    }
+
+    /// Imports checkpoints signed with the same key by another validator instance.
+    /// Fails without importing anything if any checkpoint conflicts with the journal.
+    pub fn import(
+        &self,
+        interchange: &SlashingProtectionInterchange,
+        validator: H160,
+    ) -> Result<usize> {
+        if interchange.validator != validator {
+            bail!(
+                "Slashing protection data is for validator {:?}, not {:?}",
+                interchange.validator,
+                validator
+            );
+        }
+        if interchange.mailbox_domain != self.db.domain().id() {
+            bail!(
+                "Slashing protection data is for domain {}, not {}",
+                interchange.mailbox_domain,
+                self.db.domain().id()
+            );
+        }
+
+        let _guard = self
+            .lock
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        for checkpoint in &interchange.checkpoints {
+            match self.db.retrieve_signed_checkpoint(checkpoint.index)? {
+                Some(signed) if signed != *checkpoint => bail!(
+                    "Imported checkpoint {checkpoint:?} conflicts with already signed checkpoint {signed:?}"
+                ),
+                _ => {}
+            }
+        }
+        for checkpoint in &interchange.checkpoints {
+            self.db.store_signed_checkpoint(checkpoint)?;
+        }
+        Ok(interchange.checkpoints.len())
+    }
+
+    /// Imports the journal at `path`, see [`SigningJournal::import`]
+    pub fn import_file(&self, path: &Path, validator: H160) -> Result<()> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Reading slashing protection data from {path:?}"))?;
+        let interchange: SlashingProtectionInterchange = serde_json::from_str(&contents)
+            .with_context(|| format!("Parsing slashing protection data from {path:?}"))?;
+        let imported = self.import(&interchange, validator)?;
+        info!(?path, imported, "Imported slashing protection data");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_base::db::test_utils;
+    use hyperlane_core::{Checkpoint, KnownHyperlaneDomain, H256};
+    use prometheus::Registry;
+
+    use super::*;
+
+    fn dummy_checkpoint(index: u32, root: H256) -> CheckpointWithMessageId {
+        CheckpointWithMessageId {
+            checkpoint: Checkpoint {
+                merkle_tree_hook_address: H256::repeat_byte(1),
+                mailbox_domain: KnownHyperlaneDomain::Arbitrum as u32,
+                root,
+                index,
+            },
+            message_id: H256::repeat_byte(2),
+        }
+    }
+
+    fn test_journal(db: hyperlane_base::db::DB) -> SigningJournal {
+        let domain = HyperlaneDomain::Known(KnownHyperlaneDomain::Arbitrum);
+        let metrics = CoreMetrics::new("dummy_validator", 37582, Registry::new()).unwrap();
+        SigningJournal::new(HyperlaneRocksDB::new(&domain, db), &metrics, &domain)
+    }
+
+    #[tokio::test]
+    async fn test_refuses_conflicting_checkpoints() {
+        test_utils::run_test_db(|db| async move {
+            let journal = test_journal(db);
+            let checkpoint = dummy_checkpoint(3, H256::repeat_byte(3));
+            journal.check_and_record(&checkpoint).unwrap();
+            // Signing the same checkpoint again is fine
+            journal.check_and_record(&checkpoint).unwrap();
+
+            let conflicting = dummy_checkpoint(3, H256::repeat_byte(4));
+            assert!(matches!(
+                journal.check_and_record(&conflicting),
+                Err(SlashingProtectionError::ConflictingCheckpoint { signed, .. }) if signed == checkpoint
+            ));
+            assert_eq!(journal.refusals.get(), 1);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_export_and_import() {
+        let validator = H160::repeat_byte(9);
+        let checkpoints = vec![
+            dummy_checkpoint(0, H256::repeat_byte(1)),
+            dummy_checkpoint(2, H256::repeat_byte(2)),
+        ];
+
+        test_utils::run_test_db(|db| async move {
+            let journal = test_journal(db);
+            for checkpoint in &checkpoints {
+                journal.check_and_record(checkpoint).unwrap();
+            }
+            let interchange = journal.export(validator, 0..=2).unwrap();
+            assert_eq!(interchange.checkpoints, checkpoints);
+
+            let (migrated_interchange, migrated_checkpoints) =
+                (interchange.clone(), checkpoints.clone());
+            test_utils::run_test_db(|db| async move {
+                let migrated = test_journal(db);
+                assert!(migrated
+                    .import(&migrated_interchange, H160::zero())
+                    .is_err());
+                assert_eq!(
+                    migrated.import(&migrated_interchange, validator).unwrap(),
+                    2
+                );
+                assert!(migrated.check_and_record(&migrated_checkpoints[1]).is_ok());
+                assert!(migrated
+                    .check_and_record(&dummy_checkpoint(2, H256::repeat_byte(5)))
+                    .is_err());
+            })
+            .await;
+
+            // A conflicting checkpoint prevents importing anything
+            test_utils::run_test_db(|db| async move {
+                let conflicting = test_journal(db);
+                conflicting
+                    .check_and_record(&dummy_checkpoint(2, H256::repeat_byte(5)))
+                    .unwrap();
+                assert!(conflicting.import(&interchange, validator).is_err());
+                assert_eq!(
+                    conflicting
+                        .export(validator, 0..=1)
+                        .unwrap()
+                        .checkpoints
+                        .len(),
+                    1
+                );
+            })
+            .await;
+        })
+        .await;
+    }
+}
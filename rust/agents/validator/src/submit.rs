@@ -16,6 +16,8 @@ use hyperlane_core::{
 };
 use hyperlane_ethereum::SingletonSignerHandle;
 
+use crate::slashing_protection::{SigningJournal, SlashingProtectionError};
+
 #[derive(Clone)]
 pub(crate) struct ValidatorSubmitter {
     interval: Duration,
@@ -24,6 +26,7 @@ pub(crate) struct ValidatorSubmitter {
     merkle_tree_hook: Arc<dyn MerkleTreeHook>,
     checkpoint_syncer: Arc<dyn CheckpointSyncer>,
     message_db: HyperlaneRocksDB,
+    signing_journal: SigningJournal,
     metrics: ValidatorSubmitterMetrics,
 }
 
@@ -35,6 +38,7 @@ impl ValidatorSubmitter {
         signer: SingletonSignerHandle,
         checkpoint_syncer: Arc<dyn CheckpointSyncer>,
         message_db: HyperlaneRocksDB,
+        signing_journal: SigningJournal,
         metrics: ValidatorSubmitterMetrics,
     ) -> Self {
         Self {
@@ -44,6 +48,7 @@ impl ValidatorSubmitter {
             signer,
             checkpoint_syncer,
             message_db,
+            signing_journal,
             metrics,
         }
     }
@@ -240,6 +245,17 @@ impl ValidatorSubmitter {
             debug!(index = checkpoint.index, "Checkpoint already submitted");
             return Ok(());
         }
+        match self.signing_journal.check_and_record(&checkpoint) {
+            Ok(()) => {}
+            // Already logged and counted by the journal. Retrying won't help, and skipping
+            // the checkpoint would announce a latest index whose checkpoint was never
+            // written, so submission stops loudly, like for an incorrect tree root, until
+            // the operator resolves the conflict.
+            Err(err @ SlashingProtectionError::ConflictingCheckpoint { .. }) => {
+                panic!("Stopping checkpoint submission: {err}");
+            }
+            Err(SlashingProtectionError::Db(err)) => return Err(err.into()),
+        }
         let signed_checkpoint = self.signer.sign(checkpoint).await?;
         self.checkpoint_syncer
             .write_checkpoint(&signed_checkpoint)
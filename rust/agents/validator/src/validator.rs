@@ -14,7 +14,7 @@ use hyperlane_base::{
     metrics::AgentMetrics,
     settings::ChainConf,
     BaseAgent, ChainMetrics, CheckpointSyncer, ContractSyncMetrics, ContractSyncer, CoreMetrics,
-    HyperlaneAgentCore, MetricsUpdater, SequencedDataContractSync,
+    HyperlaneAgentCore, MetricsUpdater, MirroredCheckpointSyncer, SequencedDataContractSync,
 };
 
 use hyperlane_core::{
@@ -26,6 +26,7 @@ use hyperlane_ethereum::{SingletonSigner, SingletonSignerHandle};
 
 use crate::{
     settings::ValidatorSettings,
+    slashing_protection::SigningJournal,
     submit::{ValidatorSubmitter, ValidatorSubmitterMetrics},
 };
 
@@ -37,6 +38,8 @@ pub struct Validator {
     #[as_ref]
     core: HyperlaneAgentCore,
     db: HyperlaneRocksDB,
+    signing_journal: SigningJournal,
+    operator_api_token: Option<String>,
     merkle_tree_hook_sync: Arc<SequencedDataContractSync<MerkleTreeInsertion>>,
     mailbox: Arc<dyn Mailbox>,
     merkle_tree_hook: Arc<dyn MerkleTreeHook>,
@@ -46,7 +49,7 @@ pub struct Validator {
     signer_instance: Option<Box<SingletonSigner>>,
     reorg_period: u64,
     interval: Duration,
-    checkpoint_syncer: Arc<dyn CheckpointSyncer>,
+    checkpoint_syncer: Arc<MirroredCheckpointSyncer>,
     core_metrics: Arc<CoreMetrics>,
     agent_metrics: AgentMetrics,
     chain_metrics: ChainMetrics,
@@ -74,8 +77,18 @@ impl BaseAgent for Validator {
         // Intentionally using hyperlane_ethereum for the validator's signer
         let (signer_instance, signer) = SingletonSigner::new(settings.validator.build().await?);
 
+        let signing_journal = SigningJournal::new(msg_db.clone(), &metrics, &settings.origin_chain);
+        if let Some(path) = &settings.slashing_protection_import_path {
+            signing_journal.import_file(path, signer.eth_address())?;
+        }
+
         let core = settings.build_hyperlane_core(metrics.clone());
-        let checkpoint_syncer = settings.checkpoint_syncer.build(None).await?.into();
+        let mut checkpoint_syncers: Vec<Arc<dyn CheckpointSyncer>> =
+            vec![settings.checkpoint_syncer.build(None).await?.into()];
+        for mirror in &settings.checkpoint_syncer_mirrors {
+            checkpoint_syncers.push(mirror.build(None).await?.into());
+        }
+        let checkpoint_syncer = Arc::new(MirroredCheckpointSyncer::new(checkpoint_syncers)?);
 
         let mailbox = settings
             .build_mailbox(&settings.origin_chain, &metrics)
@@ -103,6 +116,7 @@ impl BaseAgent for Validator {
                 &metrics,
                 &contract_sync_metrics,
                 msg_db.clone().into(),
+                false,
             )
             .await?;
 
@@ -111,6 +125,8 @@ impl BaseAgent for Validator {
             origin_chain_conf,
             core,
             db: msg_db,
+            signing_journal,
+            operator_api_token: settings.operator_api_token,
             mailbox: mailbox.into(),
             merkle_tree_hook: merkle_tree_hook.into(),
             merkle_tree_hook_sync,
@@ -131,8 +147,13 @@ impl BaseAgent for Validator {
         let mut tasks = vec![];
 
         // run server
-        let custom_routes =
-            validator_server::routes(self.origin_chain.clone(), self.core.metrics.clone());
+        let custom_routes = validator_server::routes(
+            self.origin_chain.clone(),
+            self.core.metrics.clone(),
+            self.signing_journal.clone(),
+            self.signer.eth_address(),
+            self.operator_api_token.clone(),
+        );
         let server = self
             .core
             .settings
@@ -226,6 +247,7 @@ impl Validator {
             self.signer.clone(),
             self.checkpoint_syncer.clone(),
             self.db.clone(),
+            self.signing_journal.clone(),
             ValidatorSubmitterMetrics::new(&self.core.metrics, &self.origin_chain),
         );
 
@@ -290,21 +312,33 @@ impl Validator {
         }
     }
 
+    /// Announces the location of every store, the primary store first, so that relayers
+    /// can fail over to the mirrors. Each store holds the announcement of its own location.
     async fn announce(&self) -> Result<()> {
         let address = self.signer.eth_address();
-        let announcement_location = self.checkpoint_syncer.announcement_location();
-
-        // Sign and post the validator announcement
-        let announcement = Announcement {
-            validator: address,
-            mailbox_address: self.mailbox.address(),
-            mailbox_domain: self.mailbox.domain().id(),
-            storage_location: announcement_location.clone(),
-        };
-        let signed_announcement = self.signer.sign(announcement.clone()).await?;
-        self.checkpoint_syncer
-            .write_announcement(&signed_announcement)
-            .await?;
+
+        // Sign the announcement of each store and write it to the store
+        let mut signed_announcements = vec![];
+        for (i, store) in self.checkpoint_syncer.stores().iter().enumerate() {
+            let announcement = Announcement {
+                validator: address,
+                mailbox_address: self.mailbox.address(),
+                mailbox_domain: self.mailbox.domain().id(),
+                storage_location: store.announcement_location(),
+            };
+            let signed_announcement = self.signer.sign(announcement).await?;
+            match store.write_announcement(&signed_announcement).await {
+                Ok(()) => {}
+                // The primary store is required, mirrors may be temporarily unavailable
+                Err(err) if i == 0 => return Err(err),
+                Err(err) => warn!(
+                    storage_location = signed_announcement.value.storage_location,
+                    error = ?err,
+                    "Failed to write announcement to checkpoint store mirror"
+                ),
+            }
+            signed_announcements.push(signed_announcement);
+        }
 
         // Ensure that the validator has announced themselves before we enter
         // the main validator submit loop. This is to avoid a situation in
@@ -313,31 +347,43 @@ impl Validator {
         let validators: [H256; 1] = [address.into()];
         loop {
             info!("Checking for validator announcement");
-            if let Some(locations) = self
+            let announced_locations = self
                 .validator_announce
                 .get_announced_storage_locations(&validators)
                 .await?
-                .first()
-            {
-                if locations.contains(&announcement_location) {
-                    info!(
-                        ?locations,
-                        ?announcement_location,
-                        "Validator has announced signature storage location"
-                    );
-                    break;
-                }
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            let unannounced: Vec<_> = signed_announcements
+                .iter()
+                .filter(|signed_announcement| {
+                    !announced_locations.contains(&signed_announcement.value.storage_location)
+                })
+                .collect();
+            if unannounced.is_empty() {
                 info!(
-                    announced_locations=?locations,
-                    "Validator has not announced signature storage location"
+                    ?announced_locations,
+                    "Validator has announced all signature storage locations"
                 );
+                break;
+            }
+            info!(
+                ?announced_locations,
+                unannounced_locations = ?unannounced
+                    .iter()
+                    .map(|signed_announcement| &signed_announcement.value.storage_location)
+                    .collect::<Vec<_>>(),
+                "Validator has not announced all signature storage locations"
+            );
 
-                if let Some(chain_signer) = self.core.settings.chains[self.origin_chain.name()]
-                    .chain_signer()
-                    .await?
-                {
-                    let chain_signer = chain_signer.address_string();
-                    info!(eth_validator_address=?announcement.validator, ?chain_signer, "Attempting self announce");
+            if let Some(chain_signer) = self.core.settings.chains[self.origin_chain.name()]
+                .chain_signer()
+                .await?
+            {
+                let chain_signer = chain_signer.address_string();
+                for signed_announcement in unannounced {
+                    let storage_location = &signed_announcement.value.storage_location;
+                    info!(eth_validator_address=?address, ?chain_signer, ?storage_location, "Attempting self announce");
                     let balance_delta = self
                         .validator_announce
                         .announce_tokens_needed(signed_announcement.clone())
@@ -346,23 +392,23 @@ impl Validator {
                     if balance_delta > U256::zero() {
                         warn!(
                             tokens_needed=%balance_delta,
-                            eth_validator_address=?announcement.validator,
+                            eth_validator_address=?address,
                             ?chain_signer,
                             "Please send tokens to your chain signer address to announce",
                         );
-                    } else {
-                        let result = self
-                            .validator_announce
-                            .announce(signed_announcement.clone())
-                            .await;
-                        Self::log_on_announce_failure(result, &chain_signer);
+                        break;
                     }
-                } else {
-                    warn!(origin_chain=%self.origin_chain, "Cannot announce validator without a signer; make sure a signer is set for the origin chain");
+                    let result = self
+                        .validator_announce
+                        .announce(signed_announcement.clone())
+                        .await;
+                    Self::log_on_announce_failure(result, &chain_signer);
                 }
-
-                sleep(self.interval).await;
+            } else {
+                warn!(origin_chain=%self.origin_chain, "Cannot announce validator without a signer; make sure a signer is set for the origin chain");
             }
+
+            sleep(self.interval).await;
         }
         Ok(())
     }
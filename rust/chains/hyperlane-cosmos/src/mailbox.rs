@@ -24,9 +24,9 @@ use tendermint::abci::EventAttribute;
 
 use crate::utils::{CONTRACT_ADDRESS_ATTRIBUTE_KEY, CONTRACT_ADDRESS_ATTRIBUTE_KEY_BASE64};
 use hyperlane_core::{
-    utils::bytes_to_hex, ChainResult, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
-    HyperlaneMessage, HyperlaneProvider, Indexed, Indexer, LogMeta, Mailbox, TxCostEstimate,
-    TxOutcome, H256, U256,
+    utils::bytes_to_hex, BatchItem, BatchResult, ChainResult, HyperlaneChain, HyperlaneContract,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, Indexed, Indexer, LogMeta, Mailbox,
+    TxCostEstimate, TxOutcome, H256, U256,
 };
 use hyperlane_core::{
     ChainCommunicationError, ContractLocator, Decode, RawHyperlaneMessage, SequenceAwareIndexer,
@@ -93,7 +93,7 @@ impl HyperlaneChain for CosmosMailbox {
 
 impl Debug for CosmosMailbox {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        todo!()
+        write!(f, "{:?}", self as &dyn HyperlaneContract)
     }
 }
 
@@ -188,12 +188,7 @@ impl Mailbox for CosmosMailbox {
         metadata: &[u8],
         tx_gas_limit: Option<U256>,
     ) -> ChainResult<TxOutcome> {
-        let process_message = ProcessMessageRequest {
-            process: ProcessMessageRequestInner {
-                message: hex::encode(RawHyperlaneMessage::from(message)),
-                metadata: hex::encode(metadata),
-            },
-        };
+        let process_message = process_message_request(message, metadata);
 
         let response: TxResponse = self
             .provider
@@ -204,18 +199,68 @@ impl Mailbox for CosmosMailbox {
         Ok(tx_response_to_outcome(response)?)
     }
 
+    #[instrument(err, ret, skip(self, messages), fields(size=%messages.len()))]
+    async fn process_batch(
+        &self,
+        messages: &[BatchItem<HyperlaneMessage>],
+    ) -> ChainResult<BatchResult> {
+        let grpc = self.provider.grpc();
+        let response: TxResponse = grpc
+            // Simulate the combined tx rather than adding up the gas limits of
+            // the individual messages
+            .wasm_send_batch(process_message_requests(messages), None)
+            .await?;
+        // The tx failed its checks and wasn't broadcast, so none of the
+        // messages were processed
+        if response.code != 0 {
+            warn!(
+                txhash = %response.txhash,
+                code = response.code,
+                log = %response.raw_log,
+                "Batch tx was rejected"
+            );
+            return Ok(BatchResult::new(None, (0..messages.len()).collect()));
+        }
+
+        // Broadcasting only checks the tx, so wait for it to be included
+        // before checking which messages it delivered
+        let Some(included) = grpc.wait_for_tx(response.txhash.clone()).await? else {
+            warn!(
+                txhash = %response.txhash,
+                "Batch tx not included yet, leaving its messages to be confirmed"
+            );
+            return Ok(BatchResult::new(
+                Some(tx_response_to_outcome(response)?),
+                vec![],
+            ));
+        };
+
+        let mut failed_indexes = vec![];
+        for (index, item) in messages.iter().enumerate() {
+            if !self.delivered(item.data.id()).await? {
+                failed_indexes.push(index);
+            }
+        }
+        if !failed_indexes.is_empty() {
+            warn!(
+                txhash = %included.txhash,
+                ?failed_indexes,
+                "Batch tx did not deliver every message"
+            );
+        }
+        Ok(BatchResult::new(
+            Some(tx_response_to_outcome(included)?),
+            failed_indexes,
+        ))
+    }
+
     #[instrument(err, ret, skip(self), fields(msg=%message, metadata=%bytes_to_hex(metadata)))]
     async fn process_estimate_costs(
         &self,
         message: &HyperlaneMessage,
         metadata: &[u8],
     ) -> ChainResult<TxCostEstimate> {
-        let process_message = ProcessMessageRequest {
-            process: ProcessMessageRequestInner {
-                message: hex::encode(RawHyperlaneMessage::from(message)),
-                metadata: hex::encode(metadata),
-            },
-        };
+        let process_message = process_message_request(message, metadata);
 
         let gas_limit = self
             .provider
@@ -237,6 +282,24 @@ impl Mailbox for CosmosMailbox {
     }
 }
 
+fn process_message_requests(
+    messages: &[BatchItem<HyperlaneMessage>],
+) -> Vec<ProcessMessageRequest> {
+    messages
+        .iter()
+        .map(|item| process_message_request(&item.data, &item.submission_data.metadata))
+        .collect()
+}
+
+fn process_message_request(message: &HyperlaneMessage, metadata: &[u8]) -> ProcessMessageRequest {
+    ProcessMessageRequest {
+        process: ProcessMessageRequestInner {
+            message: hex::encode(RawHyperlaneMessage::from(message)),
+            metadata: hex::encode(metadata),
+        },
+    }
+}
+
 impl CosmosMailbox {
     #[instrument(level = "debug", err, ret, skip(self))]
     async fn nonce_at_block(&self, block_height: Option<u64>) -> ChainResult<u32> {
@@ -380,13 +443,129 @@ impl Indexer<HyperlaneMessage> for CosmosMailboxIndexer {
 }
 
 #[async_trait]
-impl Indexer<H256> for CosmosMailboxIndexer {
+impl SequenceAwareIndexer<HyperlaneMessage> for CosmosMailboxIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = Indexer::<HyperlaneMessage>::get_finalized_block_number(&self).await?;
+
+        let sequence = self.mailbox.nonce_at_block(Some(tip.into())).await?;
+
+        Ok((Some(sequence), tip))
+    }
+}
+
+const MESSAGE_ID_ATTRIBUTE_KEY: &str = "id";
+static MESSAGE_ID_ATTRIBUTE_KEY_BASE64: Lazy<String> =
+    Lazy::new(|| BASE64.encode(MESSAGE_ID_ATTRIBUTE_KEY));
+
+/// Struct that retrieves delivery event data for a Cosmos Mailbox contract
+#[derive(Debug, Clone)]
+pub struct CosmosMailboxDeliveryIndexer {
+    indexer: Box<CosmosWasmIndexer>,
+}
+
+impl CosmosMailboxDeliveryIndexer {
+    /// The message process event type from the CW contract.
+    const MESSAGE_DELIVERY_EVENT_TYPE: &str = "mailbox_process_id";
+
+    /// Create a reference to a mailbox at a specific Cosmos address on some
+    /// chain
+    pub fn new(
+        conf: ConnectionConf,
+        locator: ContractLocator,
+        reorg_period: u32,
+    ) -> ChainResult<Self> {
+        let indexer = CosmosWasmIndexer::new(
+            conf,
+            locator,
+            Self::MESSAGE_DELIVERY_EVENT_TYPE.into(),
+            reorg_period,
+        )?;
+
+        Ok(Self {
+            indexer: Box::new(indexer),
+        })
+    }
+
+    #[instrument(err)]
+    fn hyperlane_delivery_parser(attrs: &Vec<EventAttribute>) -> ChainResult<ParsedEvent<H256>> {
+        let mut contract_address: Option<String> = None;
+        let mut message_id: Option<H256> = None;
+
+        for attr in attrs {
+            let key = attr.key.as_str();
+            let value = attr.value.as_str();
+
+            match key {
+                CONTRACT_ADDRESS_ATTRIBUTE_KEY => {
+                    contract_address = Some(value.to_string());
+                }
+                v if *CONTRACT_ADDRESS_ATTRIBUTE_KEY_BASE64 == v => {
+                    contract_address = Some(String::from_utf8(
+                        BASE64
+                            .decode(value)
+                            .map_err(Into::<HyperlaneCosmosError>::into)?,
+                    )?);
+                }
+
+                MESSAGE_ID_ATTRIBUTE_KEY => {
+                    message_id = Some(message_id_from_hex(value)?);
+                }
+                v if *MESSAGE_ID_ATTRIBUTE_KEY_BASE64 == v => {
+                    let value = String::from_utf8(
+                        BASE64
+                            .decode(value)
+                            .map_err(Into::<HyperlaneCosmosError>::into)?,
+                    )?;
+                    message_id = Some(message_id_from_hex(&value)?);
+                }
+
+                _ => {}
+            }
+        }
+
+        let contract_address = contract_address
+            .ok_or_else(|| ChainCommunicationError::from_other_str("missing contract_address"))?;
+        let message_id = message_id
+            .ok_or_else(|| ChainCommunicationError::from_other_str("missing message_id"))?;
+
+        Ok(ParsedEvent::new(contract_address, message_id))
+    }
+}
+
+fn message_id_from_hex(value: &str) -> ChainResult<H256> {
+    let bytes = hex::decode(value)?;
+    if bytes.len() != H256::len_bytes() {
+        return Err(ChainCommunicationError::from_other_str(
+            "invalid message_id length",
+        ));
+    }
+    Ok(H256::from_slice(&bytes))
+}
+
+#[async_trait]
+impl Indexer<H256> for CosmosMailboxDeliveryIndexer {
     async fn fetch_logs_in_range(
         &self,
         range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<H256>, LogMeta)>> {
-        // TODO: implement when implementing Cosmos scraping
-        todo!()
+        let logs_futures: Vec<_> = range
+            .map(|block_number| {
+                let self_clone = self.clone();
+                tokio::spawn(async move {
+                    let logs = self_clone
+                        .indexer
+                        .get_logs_in_block(
+                            block_number,
+                            Self::hyperlane_delivery_parser,
+                            "DeliveryCursor",
+                        )
+                        .await;
+                    (logs, block_number)
+                })
+            })
+            .collect();
+
+        execute_and_parse_log_futures(logs_futures).await
     }
 
     async fn get_finalized_block_number(&self) -> ChainResult<u32> {
@@ -395,7 +574,7 @@ impl Indexer<H256> for CosmosMailboxIndexer {
 }
 
 #[async_trait]
-impl SequenceAwareIndexer<H256> for CosmosMailboxIndexer {
+impl SequenceAwareIndexer<H256> for CosmosMailboxDeliveryIndexer {
     async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
         let tip = Indexer::<H256>::get_finalized_block_number(&self).await?;
 
@@ -404,17 +583,6 @@ impl SequenceAwareIndexer<H256> for CosmosMailboxIndexer {
     }
 }
 
-#[async_trait]
-impl SequenceAwareIndexer<HyperlaneMessage> for CosmosMailboxIndexer {
-    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
-        let tip = Indexer::<HyperlaneMessage>::get_finalized_block_number(&self).await?;
-
-        let sequence = self.mailbox.nonce_at_block(Some(tip.into())).await?;
-
-        Ok((Some(sequence), tip))
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use hyperlane_core::HyperlaneMessage;
@@ -450,4 +618,55 @@ mod tests {
         );
         assert_parsed_event(&base64_attrs);
     }
+
+    #[test]
+    fn test_process_message_request() {
+        let message = HyperlaneMessage {
+            nonce: 1,
+            body: vec![0xab],
+            ..Default::default()
+        };
+
+        let request = serde_json::to_value(process_message_request(&message, &[1, 2])).unwrap();
+
+        assert_eq!(
+            request,
+            serde_json::json!({
+                "process": {
+                    "message": hex::encode(RawHyperlaneMessage::from(&message)),
+                    "metadata": "0102",
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_hyperlane_delivery_parser() {
+        let expected = ParsedEvent::new(
+            "neutron1sjzzd4gwkggy6hrrs8kxxatexzcuz3jecsxm3wqgregkulzj8r7qlnuef4".into(),
+            H256::from_slice(
+                &hex::decode("e7f0d7b0a8a25ad1b7e2a4a5ac43e4b01ea0b1ca7b2c1c4f0cc37c1c0ef3a0d2")
+                    .unwrap(),
+            ),
+        );
+
+        let assert_parsed_event = |attrs: &Vec<EventAttribute>| {
+            let parsed_event =
+                CosmosMailboxDeliveryIndexer::hyperlane_delivery_parser(attrs).unwrap();
+
+            assert_eq!(parsed_event, expected);
+        };
+
+        // Non-base64 version
+        let non_base64_attrs = event_attributes_from_str(
+            r#"[{"key":"_contract_address","value":"neutron1sjzzd4gwkggy6hrrs8kxxatexzcuz3jecsxm3wqgregkulzj8r7qlnuef4","index":true},{"key":"id","value":"e7f0d7b0a8a25ad1b7e2a4a5ac43e4b01ea0b1ca7b2c1c4f0cc37c1c0ef3a0d2","index":true}]"#,
+        );
+        assert_parsed_event(&non_base64_attrs);
+
+        // Base64 version
+        let base64_attrs = event_attributes_from_str(
+            r#"[{"key":"X2NvbnRyYWN0X2FkZHJlc3M=","value":"bmV1dHJvbjFzanp6ZDRnd2tnZ3k2aHJyczhreHhhdGV4emN1ejNqZWNzeG0zd3FncmVna3Vsemo4cjdxbG51ZWY0","index":true},{"key":"aWQ=","value":"ZTdmMGQ3YjBhOGEyNWFkMWI3ZTJhNGE1YWM0M2U0YjAxZWEwYjFjYTdiMmMxYzRmMGNjMzdjMWMwZWYzYTBkMg==","index":true}]"#,
+        );
+        assert_parsed_event(&base64_attrs);
+    }
 }
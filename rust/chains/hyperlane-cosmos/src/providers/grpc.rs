@@ -12,7 +12,7 @@ use cosmrs::{
             },
             tx::v1beta1::{
                 service_client::ServiceClient as TxServiceClient, BroadcastMode,
-                BroadcastTxRequest, SimulateRequest, TxRaw,
+                BroadcastTxRequest, GetTxRequest, SimulateRequest, TxRaw,
             },
         },
         cosmwasm::wasm::v1::{
@@ -31,7 +31,7 @@ use hyperlane_core::{
 };
 use protobuf::Message as _;
 use serde::Serialize;
-use std::fmt::Debug;
+use std::{fmt::Debug, time::Duration};
 use tonic::{
     transport::{Channel, Endpoint},
     GrpcMethod, IntoRequest,
@@ -49,6 +49,11 @@ const GAS_ESTIMATE_MULTIPLIER: f64 = 1.25;
 /// The number of blocks in the future in which a transaction will
 /// be valid for.
 const TIMEOUT_BLOCKS: u64 = 1000;
+/// The number of times to poll for a broadcast transaction to be included
+/// in a block before giving up.
+const TX_INCLUSION_POLL_ATTEMPTS: u32 = 30;
+/// The interval between polls for a broadcast transaction to be included.
+const TX_INCLUSION_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
 #[derive(Debug, Clone, new)]
 struct CosmosChannel {
@@ -112,6 +117,14 @@ pub trait WasmProvider: Send + Sync {
         gas_limit: Option<U256>,
     ) -> ChainResult<TxResponse>;
 
+    /// Send a single wasm tx executing each of the payloads in order. The tx
+    /// is atomic, so either all of the payloads are executed or none are.
+    async fn wasm_send_batch<T: Serialize + Sync + Send + Clone + Debug>(
+        &self,
+        payloads: Vec<T>,
+        gas_limit: Option<U256>,
+    ) -> ChainResult<TxResponse>;
+
     /// Estimate gas for a wasm tx.
     async fn wasm_estimate_gas<T: Serialize + Sync + Send + Clone + Debug>(
         &self,
@@ -318,6 +331,37 @@ impl WasmGrpcProvider {
         Ok(gas_estimate)
     }
 
+    /// Waits for a broadcast transaction to be included in a block, returning
+    /// its result. Returns `None` if the transaction wasn't found after
+    /// polling for a while.
+    #[instrument(skip(self))]
+    pub async fn wait_for_tx(&self, tx_hash: String) -> ChainResult<Option<TxResponse>> {
+        for _ in 0..TX_INCLUSION_POLL_ATTEMPTS {
+            tokio::time::sleep(TX_INCLUSION_POLL_INTERVAL).await;
+            let tx_hash = tx_hash.clone();
+            let response = self
+                .provider
+                .call(move |provider| {
+                    let hash = tx_hash.clone();
+                    let future = async move {
+                        let mut client = TxServiceClient::new(provider.channel.clone());
+                        client
+                            .get_tx(tonic::Request::new(GetTxRequest { hash }))
+                            .await
+                            .map_err(ChainCommunicationError::from_other)
+                    };
+                    Box::pin(future)
+                })
+                .await;
+            match response.map(|r| r.into_inner().tx_response) {
+                Ok(Some(tx_response)) => return Ok(Some(tx_response)),
+                // The tx is most likely not included yet
+                Ok(None) | Err(_) => continue,
+            }
+        }
+        Ok(None)
+    }
+
     /// Fetches balance for a given `address` and `denom`
     pub async fn get_balance(&self, address: String, denom: String) -> ChainResult<U256> {
         let response = self
@@ -536,6 +580,18 @@ impl WasmProvider for WasmGrpcProvider {
 
     #[instrument(skip(self))]
     async fn wasm_send<T>(&self, payload: T, gas_limit: Option<U256>) -> ChainResult<TxResponse>
+    where
+        T: Serialize + Send + Sync + Clone + Debug,
+    {
+        self.wasm_send_batch(vec![payload], gas_limit).await
+    }
+
+    #[instrument(skip(self), fields(size=%payloads.len()))]
+    async fn wasm_send_batch<T>(
+        &self,
+        payloads: Vec<T>,
+        gas_limit: Option<U256>,
+    ) -> ChainResult<TxResponse>
     where
         T: Serialize + Send + Sync + Clone + Debug,
     {
@@ -543,14 +599,7 @@ impl WasmProvider for WasmGrpcProvider {
         let contract_address = self.contract_address.as_ref().ok_or_else(|| {
             ChainCommunicationError::from_other_str("No contract address available")
         })?;
-        let msgs = vec![MsgExecuteContract {
-            sender: signer.address.clone(),
-            contract: contract_address.address(),
-            msg: serde_json::to_string(&payload)?.as_bytes().to_vec(),
-            funds: vec![],
-        }
-        .to_any()
-        .map_err(ChainCommunicationError::from_other)?];
+        let msgs = execute_contract_msgs(&signer.address, &contract_address.address(), &payloads)?;
         let gas_limit: Option<u64> = gas_limit.and_then(|limit| match limit.try_into() {
             Ok(limit) => Some(limit),
             Err(err) => {
@@ -599,7 +648,7 @@ impl WasmProvider for WasmGrpcProvider {
                 Box::pin(future)
             })
             .await?;
-        debug!(tx_result=?tx_res, domain=?self.domain, ?payload, "Wasm transaction sent");
+        debug!(tx_result=?tx_res, domain=?self.domain, ?payloads, "Wasm transaction sent");
         Ok(tx_res)
     }
 
@@ -613,26 +662,68 @@ impl WasmProvider for WasmGrpcProvider {
         let contract_address = self.contract_address.as_ref().ok_or_else(|| {
             ChainCommunicationError::from_other_str("No contract address available")
         })?;
-        let msg = MsgExecuteContract {
-            sender: signer.address.clone(),
-            contract: contract_address.address(),
-            msg: serde_json::to_string(&payload)?.as_bytes().to_vec(),
-            funds: vec![],
-        };
+        let msgs = execute_contract_msgs(&signer.address, &contract_address.address(), &[payload])?;
 
-        let response = self
-            .estimate_gas(vec![msg
-                .to_any()
-                .map_err(ChainCommunicationError::from_other)?])
-            .await?;
+        let response = self.estimate_gas(msgs).await?;
 
         Ok(response)
     }
 }
 
+/// Builds a message executing the contract for each of the payloads, in order.
+fn execute_contract_msgs<T: Serialize>(
+    sender: &str,
+    contract: &str,
+    payloads: &[T],
+) -> ChainResult<Vec<Any>> {
+    payloads
+        .iter()
+        .map(|payload| {
+            MsgExecuteContract {
+                sender: sender.to_owned(),
+                contract: contract.to_owned(),
+                msg: serde_json::to_string(payload)?.as_bytes().to_vec(),
+                funds: vec![],
+            }
+            .to_any()
+            .map_err(ChainCommunicationError::from_other)
+        })
+        .collect()
+}
+
 #[async_trait]
 impl BlockNumberGetter for WasmGrpcProvider {
     async fn get_block_number(&self) -> Result<u64, ChainCommunicationError> {
         self.latest_block_height().await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_execute_contract_msgs() {
+        let payloads = [
+            json!({"process": {"message": "01"}}),
+            json!({"process": {"message": "02"}}),
+        ];
+
+        let msgs = execute_contract_msgs("neutron1sender", "neutron1mailbox", &payloads).unwrap();
+
+        assert_eq!(msgs.len(), payloads.len());
+        for (msg, payload) in msgs.iter().zip(&payloads) {
+            assert_eq!(msg.type_url, "/cosmwasm.wasm.v1.MsgExecuteContract");
+            let msg = MsgExecuteContract::decode(msg.value.as_slice()).unwrap();
+            assert_eq!(msg.sender, "neutron1sender");
+            assert_eq!(msg.contract, "neutron1mailbox");
+            assert_eq!(
+                serde_json::from_slice::<serde_json::Value>(&msg.msg).unwrap(),
+                *payload
+            );
+            assert!(msg.funds.is_empty());
+        }
+    }
+}
@@ -1,11 +1,21 @@
+use std::str::FromStr;
+
 use async_trait::async_trait;
+use cosmrs::{
+    cosmwasm::MsgExecuteContract,
+    tx::{Msg, SignerPublicKey},
+    Tx,
+};
 use hyperlane_core::{
-    BlockInfo, ChainInfo, ChainResult, ContractLocator, HyperlaneChain, HyperlaneDomain,
-    HyperlaneProvider, TxnInfo, H256, U256,
+    BlockInfo, ChainCommunicationError, ChainInfo, ChainResult, ContractLocator, FixedPointNumber,
+    HyperlaneChain, HyperlaneDomain, HyperlaneProvider, HyperlaneProviderError, TxnInfo,
+    TxnReceiptInfo, H256, H512, U256,
 };
-use tendermint_rpc::{client::CompatMode, HttpClient};
+use tendermint::{block::Height, hash::Algorithm, Hash};
+use tendermint_rpc::{client::CompatMode, Client, HttpClient};
+use tracing::warn;
 
-use crate::{ConnectionConf, CosmosAmount, HyperlaneCosmosError, Signer};
+use crate::{address::CosmosAddress, ConnectionConf, CosmosAmount, HyperlaneCosmosError, Signer};
 
 use self::grpc::WasmGrpcProvider;
 
@@ -18,6 +28,7 @@ pub mod rpc;
 #[derive(Debug, Clone)]
 pub struct CosmosProvider {
     domain: HyperlaneDomain,
+    connection_conf: ConnectionConf,
     canonical_asset: String,
     grpc_client: WasmGrpcProvider,
     rpc_client: HttpClient,
@@ -54,6 +65,7 @@ impl CosmosProvider {
             rpc_client,
             grpc_client,
             canonical_asset: conf.get_canonical_asset(),
+            connection_conf: conf,
         })
     }
 
@@ -66,6 +78,39 @@ impl CosmosProvider {
     pub fn rpc(&self) -> &HttpClient {
         &self.rpc_client
     }
+
+    /// The sender and the contract called by a transaction. Hyperlane
+    /// transactions are contract executions, anything else is attributed to
+    /// the account which signed it.
+    fn sender_and_contract(&self, tx: &Tx) -> ChainResult<(H256, Option<H256>)> {
+        if let Some(execution) = tx
+            .body
+            .messages
+            .iter()
+            .find_map(|msg| MsgExecuteContract::from_any(msg).ok())
+        {
+            let sender = CosmosAddress::from_str(execution.sender.as_ref())?.digest();
+            let contract = CosmosAddress::from_str(execution.contract.as_ref())?.digest();
+            return Ok((sender, Some(contract)));
+        }
+
+        let signer = tx
+            .auth_info
+            .signer_infos
+            .first()
+            .and_then(|signer_info| signer_info.public_key.clone());
+        match signer {
+            Some(SignerPublicKey::Single(public_key)) => {
+                let prefix = self.connection_conf.get_bech32_prefix();
+                let sender = CosmosAddress::from_pubkey(public_key, &prefix)?.digest();
+                Ok((sender, None))
+            }
+            _ => {
+                warn!(?tx, "Could not determine the sender of transaction");
+                Ok((H256::zero(), None))
+            }
+        }
+    }
 }
 
 impl HyperlaneChain for CosmosProvider {
@@ -80,12 +125,84 @@ impl HyperlaneChain for CosmosProvider {
 
 #[async_trait]
 impl HyperlaneProvider for CosmosProvider {
-    async fn get_block_by_hash(&self, _hash: &H256) -> ChainResult<BlockInfo> {
-        todo!() // FIXME
+    async fn get_block_by_height(&self, height: u64) -> ChainResult<BlockInfo> {
+        let tendermint_height =
+            Height::try_from(height).map_err(Into::<HyperlaneCosmosError>::into)?;
+        let response = self
+            .rpc_client
+            .block(tendermint_height)
+            .await
+            .map_err(Into::<HyperlaneCosmosError>::into)?;
+
+        Ok(BlockInfo {
+            hash: H256::from_slice(response.block_id.hash.as_bytes()),
+            timestamp: response
+                .block
+                .header
+                .time
+                .unix_timestamp()
+                .try_into()
+                .map_err(ChainCommunicationError::from_other)?,
+            number: height,
+        })
     }
 
-    async fn get_txn_by_hash(&self, _hash: &H256) -> ChainResult<TxnInfo> {
-        todo!() // FIXME
+    async fn get_txn_by_hash(&self, hash: &H512) -> ChainResult<TxnInfo> {
+        // Cosmos transaction hashes are 256 bits
+        let tx_hash = H256::from(*hash);
+        let tendermint_hash = Hash::from_bytes(Algorithm::Sha256, tx_hash.as_bytes())
+            .map_err(Into::<HyperlaneCosmosError>::into)?;
+        let response = self
+            .rpc_client
+            .tx(tendermint_hash, false)
+            .await
+            .map_err(Into::<HyperlaneCosmosError>::into)?;
+        if response.tx_result.code.is_err() {
+            return Err(HyperlaneProviderError::CouldNotFindTransactionByHash(*hash).into());
+        }
+
+        let tx = Tx::from_bytes(&response.tx).map_err(Into::<HyperlaneCosmosError>::into)?;
+        let (sender, recipient) = self.sender_and_contract(&tx)?;
+        let nonce = tx
+            .auth_info
+            .signer_infos
+            .first()
+            .map(|signer_info| signer_info.sequence)
+            .unwrap_or_default();
+
+        let fee = &tx.auth_info.fee;
+        let gas_limit = fee.gas_limit;
+        let fee_amount: u128 = fee
+            .amount
+            .iter()
+            .filter(|coin| coin.denom.as_ref() == self.canonical_asset)
+            .map(|coin| coin.amount)
+            .sum();
+        // The whole fee is charged regardless of the gas used. Prices below one
+        // base unit per gas are rounded up, as only whole units can be reported.
+        let gas_price: Option<U256> = if gas_limit == 0 {
+            None
+        } else {
+            let fee_amount = FixedPointNumber::try_from(U256::from(fee_amount))?;
+            Some((fee_amount / gas_limit).ceil_to_integer().try_into()?)
+        };
+        let gas_used = U256::from(response.tx_result.gas_used.max(0) as u64);
+
+        Ok(TxnInfo {
+            hash: *hash,
+            gas_limit: gas_limit.into(),
+            max_priority_fee_per_gas: None,
+            max_fee_per_gas: None,
+            gas_price,
+            nonce,
+            sender,
+            recipient,
+            receipt: Some(TxnReceiptInfo {
+                gas_used,
+                cumulative_gas_used: gas_used,
+                effective_gas_price: gas_price,
+            }),
+        })
     }
 
     async fn is_contract(&self, _address: &H256) -> ChainResult<bool> {
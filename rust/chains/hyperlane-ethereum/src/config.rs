@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use hyperlane_core::{config::OperationBatchConfig, U256};
 use url::Url;
 
@@ -50,4 +52,33 @@ pub struct TransactionOverrides {
     pub max_fee_per_gas: Option<U256>,
     /// Max priority fee per gas to use for EIP-1559 transactions.
     pub max_priority_fee_per_gas: Option<U256>,
+    /// Policy for replacing transactions that are stuck in the mempool.
+    /// If not specified, transactions are sent once and awaited until they time out.
+    pub gas_escalation: Option<GasEscalationPolicy>,
+}
+
+/// Policy for re-broadcasting a pending transaction with the same nonce and bumped fees.
+#[derive(Debug, Clone)]
+pub struct GasEscalationPolicy {
+    /// How long to wait for a transaction to be included before replacing it.
+    pub interval: Duration,
+    /// Percentage by which fees are increased on each replacement.
+    /// Most nodes reject replacements that bump fees by less than 10%.
+    pub bump_percent: u32,
+    /// Max number of times a transaction is replaced.
+    pub max_replacements: u32,
+    /// Fees are never bumped above this value. Applies to the max fee per gas for
+    /// EIP-1559 transactions and to the gas price otherwise.
+    pub max_fee_per_gas_cap: Option<U256>,
+}
+
+impl Default for GasEscalationPolicy {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(30),
+            bump_percent: 12,
+            max_replacements: 3,
+            max_fee_per_gas_cap: None,
+        }
+    }
 }
@@ -12,13 +12,15 @@ use ethers::prelude::Middleware;
 use ethers_contract::builders::ContractCall;
 use futures_util::future::join_all;
 use hyperlane_core::H512;
+use tokio::sync::Mutex;
 use tracing::instrument;
 
 use hyperlane_core::{
-    utils::bytes_to_hex, BatchItem, ChainCommunicationError, ChainResult, ContractLocator,
-    HyperlaneAbi, HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneMessage,
-    HyperlaneProtocolError, HyperlaneProvider, Indexed, Indexer, LogMeta, Mailbox,
-    RawHyperlaneMessage, SequenceAwareIndexer, TxCostEstimate, TxOutcome, H160, H256, U256,
+    utils::bytes_to_hex, BatchItem, BatchResult, ChainCommunicationError, ChainResult,
+    ContractLocator, HyperlaneAbi, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
+    HyperlaneMessage, HyperlaneProtocolError, HyperlaneProvider, Indexed, Indexer, LogMeta,
+    Mailbox, RawHyperlaneMessage, SequenceAwareIndexer, TxCostEstimate, TxOutcome, H160, H256,
+    U256,
 };
 
 use crate::error::HyperlaneEthereumError;
@@ -27,7 +29,7 @@ use crate::interfaces::i_mailbox::{
     IMailbox as EthereumMailboxInternal, ProcessCall, IMAILBOX_ABI,
 };
 use crate::interfaces::mailbox::DispatchFilter;
-use crate::tx::{call_with_lag, fill_tx_gas_params, report_tx};
+use crate::tx::{call_with_lag, fill_tx_gas_params, report_tx_with_escalation};
 use crate::{BuildableWithProvider, ConnectionConf, EthereumProvider, TransactionOverrides};
 
 use super::multicall::{self, build_multicall};
@@ -264,6 +266,9 @@ where
     provider: Arc<M>,
     arbitrum_node_interface: Option<Arc<ArbitrumNodeInterface<M>>>,
     conn: ConnectionConf,
+    /// Held from the start of a submission until its transaction is dispatched, so that
+    /// concurrent submissions are assigned sequential nonces in the order they started.
+    dispatch_lock: Mutex<()>,
 }
 
 impl<M> EthereumMailbox<M>
@@ -293,6 +298,7 @@ where
             provider,
             arbitrum_node_interface,
             conn: conn.clone(),
+            dispatch_lock: Mutex::new(()),
         }
     }
 
@@ -394,10 +400,20 @@ where
         metadata: &[u8],
         tx_gas_limit: Option<U256>,
     ) -> ChainResult<TxOutcome> {
+        // Taken before anything else is awaited, so that the lock is queued for in the
+        // order the submissions were started. The lock is fair, so that's also the order
+        // in which the transactions are dispatched.
+        let dispatch_turn = self.dispatch_lock.lock().await;
         let contract_call = self
             .process_contract_call(message, metadata, tx_gas_limit)
             .await?;
-        let receipt = report_tx(contract_call).await?;
+        let receipt = report_tx_with_escalation(
+            contract_call,
+            self.provider.clone(),
+            self.conn.transaction_overrides.gas_escalation.as_ref(),
+            dispatch_turn,
+        )
+        .await?;
         Ok(receipt.into())
     }
 
@@ -405,7 +421,8 @@ where
     async fn process_batch(
         &self,
         messages: &[BatchItem<HyperlaneMessage>],
-    ) -> ChainResult<TxOutcome> {
+    ) -> ChainResult<BatchResult> {
+        let dispatch_turn = self.dispatch_lock.lock().await;
         let mut multicall = build_multicall(self.provider.clone(), &self.conn, self.domain.clone())
             .await
             .map_err(|e| HyperlaneEthereumError::MulticallError(e.to_string()))?;
@@ -428,8 +445,14 @@ where
         let batch_call = multicall::batch::<_, ()>(&mut multicall, contract_calls);
         let call = self.add_gas_overrides(batch_call, None).await?;
 
-        let receipt = report_tx(call).await?;
-        Ok(receipt.into())
+        let receipt = report_tx_with_escalation(
+            call,
+            self.provider.clone(),
+            self.conn.transaction_overrides.gas_escalation.as_ref(),
+            dispatch_turn,
+        )
+        .await?;
+        Ok(BatchResult::new(Some(receipt.into()), vec![]))
     }
 
     #[instrument(skip(self), fields(msg=%message, metadata=%bytes_to_hex(metadata)))]
@@ -14,6 +14,7 @@ use tracing::instrument;
 use hyperlane_core::{
     BlockInfo, ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain,
     HyperlaneDomain, HyperlaneProvider, HyperlaneProviderError, TxnInfo, TxnReceiptInfo, H256,
+    H512,
 };
 
 use crate::{BuildableWithProvider, ConnectionConf};
@@ -48,28 +49,38 @@ where
     M: Middleware + 'static,
 {
     #[instrument(err, skip(self))]
-    async fn get_block_by_hash(&self, hash: &H256) -> ChainResult<BlockInfo> {
-        let block = get_with_retry_on_none(hash, |h| {
-            let eth_h256: ethers_core_types::H256 = h.into();
-            self.provider.get_block(eth_h256)
-        })
+    async fn get_block_by_height(&self, height: u64) -> ChainResult<BlockInfo> {
+        let block = get_with_retry_on_none(
+            || self.provider.get_block(height),
+            HyperlaneProviderError::CouldNotFindBlockByHeight(height),
+        )
         .await?;
+        let hash: H256 = block
+            .hash
+            .ok_or(HyperlaneProviderError::CouldNotFindBlockByHeight(height))?
+            .into();
         Ok(BlockInfo {
-            hash: *hash,
+            hash,
             timestamp: block.timestamp.as_u64(),
             number: block
                 .number
-                .ok_or(HyperlaneProviderError::BlockIsNotPartOfChainYet(*hash))?
+                .ok_or(HyperlaneProviderError::BlockIsNotPartOfChainYet(hash))?
                 .as_u64(),
         })
     }
 
     #[instrument(err, skip(self))]
-    async fn get_txn_by_hash(&self, hash: &H256) -> ChainResult<TxnInfo> {
-        let txn = get_with_retry_on_none(hash, |h| self.provider.get_transaction(*h)).await?;
+    async fn get_txn_by_hash(&self, hash: &H512) -> ChainResult<TxnInfo> {
+        // EVM transaction hashes are 256 bits
+        let eth_hash: ethers_core_types::H256 = H256::from(*hash).into();
+        let txn = get_with_retry_on_none(
+            || self.provider.get_transaction(eth_hash),
+            HyperlaneProviderError::CouldNotFindTransactionByHash(*hash),
+        )
+        .await?;
         let receipt = self
             .provider
-            .get_transaction_receipt(*hash)
+            .get_transaction_receipt(eth_hash)
             .await
             .map_err(ChainCommunicationError::from_other)?
             .map(|r| -> Result<_, HyperlaneProviderError> {
@@ -187,22 +198,22 @@ impl BuildableWithProvider for HyperlaneProviderBuilder {
 /// Call a get function that returns a Result<Option<T>> and retry if the inner
 /// option is None. This can happen because the provider has not discovered the
 /// object we are looking for yet.
-async fn get_with_retry_on_none<T, F, O, E>(hash: &H256, get: F) -> ChainResult<T>
+async fn get_with_retry_on_none<T, F, O, E>(
+    get: F,
+    not_found: HyperlaneProviderError,
+) -> ChainResult<T>
 where
-    F: Fn(&H256) -> O,
+    F: Fn() -> O,
     O: Future<Output = Result<Option<T>, E>>,
     E: std::error::Error + Send + Sync + 'static,
 {
     for _ in 0..3 {
-        if let Some(t) = get(hash)
-            .await
-            .map_err(ChainCommunicationError::from_other)?
-        {
+        if let Some(t) = get().await.map_err(ChainCommunicationError::from_other)? {
             return Ok(t);
         } else {
             sleep(Duration::from_secs(5)).await;
             continue;
         };
     }
-    Err(HyperlaneProviderError::CouldNotFindObjectByHash(*hash).into())
+    Err(not_found.into())
 }
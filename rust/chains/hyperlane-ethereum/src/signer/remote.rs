@@ -0,0 +1,237 @@
+use std::time::Duration;
+
+use ethers::prelude::{Address, Signature};
+use ethers::types::SignatureError;
+use ethers::utils::hash_message;
+use serde::Deserialize;
+use serde_json::json;
+use thiserror::Error;
+use url::Url;
+
+/// The timeout for requests to the remote signer
+const REMOTE_SIGNER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A signer that delegates signing to an external service implementing the
+/// `eth_sign` JSON-RPC method, such as Web3Signer. This allows keeping the key
+/// in an existing, e.g. HSM-backed, signing service.
+///
+/// Only messages can be signed, which is sufficient for checkpoints and
+/// announcements. Transactions and typed data are not supported.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    client: reqwest::Client,
+    url: Url,
+    address: Address,
+    chain_id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse {
+    result: Option<String>,
+    error: Option<JsonRpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+impl RemoteSigner {
+    /// Create a signer for the key with `address` held by the signing service at `url`
+    pub fn new(url: Url, address: Address) -> Result<Self, RemoteSignerError> {
+        let client = reqwest::Client::builder()
+            .timeout(REMOTE_SIGNER_TIMEOUT)
+            .build()?;
+        Ok(Self {
+            client,
+            url,
+            address,
+            chain_id: 1,
+        })
+    }
+
+    /// Signs an EIP-191 message with the remote key. The returned signature is
+    /// checked to be valid for the signer's address.
+    pub async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: S,
+    ) -> Result<Signature, RemoteSignerError> {
+        let message = message.as_ref();
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_sign",
+            "params": [self.address, format!("0x{}", hex::encode(message))],
+        });
+        let response: JsonRpcResponse = self
+            .client
+            .post(self.url.clone())
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let result = match response {
+            JsonRpcResponse {
+                result: Some(result),
+                ..
+            } => result,
+            JsonRpcResponse {
+                error: Some(JsonRpcError { code, message }),
+                ..
+            } => return Err(RemoteSignerError::Rpc { code, message }),
+            _ => return Err(RemoteSignerError::EmptyResponse),
+        };
+
+        let signature: Signature = result.parse()?;
+        let signer = signature.recover(hash_message(message))?;
+        if signer != self.address {
+            return Err(RemoteSignerError::SignerMismatch {
+                expected: self.address,
+                actual: signer,
+            });
+        }
+        Ok(signature)
+    }
+
+    /// The address of the remote key
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// The chain id used for EIP-155 transactions
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// Set the chain id used for EIP-155 transactions
+    pub fn with_chain_id<T: Into<u64>>(mut self, chain_id: T) -> Self {
+        self.chain_id = chain_id.into();
+        self
+    }
+}
+
+/// Error types for the remote signer
+#[derive(Debug, Error)]
+pub enum RemoteSignerError {
+    /// Error sending the request to the signing service
+    #[error("Error requesting signature from remote signer: {0}")]
+    Http(#[from] reqwest::Error),
+    /// The signing service returned an error
+    #[error("Remote signer returned error {code}: {message}")]
+    Rpc {
+        /// JSON-RPC error code
+        code: i64,
+        /// JSON-RPC error message
+        message: String,
+    },
+    /// The signing service returned neither a result nor an error
+    #[error("Remote signer returned an empty response")]
+    EmptyResponse,
+    /// The returned signature could not be parsed or recovered
+    #[error("Invalid signature returned by remote signer: {0}")]
+    InvalidSignature(#[from] SignatureError),
+    /// The returned signature was made by a different key
+    #[error("Remote signer signed with {actual:?} instead of {expected:?}")]
+    SignerMismatch {
+        /// The configured address
+        expected: Address,
+        /// The address that actually signed
+        actual: Address,
+    },
+    /// The operation isn't supported by remote signers
+    #[error("Remote signer does not support signing {0}")]
+    Unsupported(&'static str),
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::SocketAddr;
+
+    use axum::{extract::State, routing, Json, Router};
+    use ethers::signers::{LocalWallet, Signer};
+    use hyperlane_core::{
+        Checkpoint, CheckpointWithMessageId, HyperlaneSigner, HyperlaneSignerExt, H256,
+    };
+    use serde_json::Value;
+
+    use super::*;
+    use crate::Signers;
+
+    const KEY: &str = "1111111111111111111111111111111111111111111111111111111111111111";
+
+    /// Local stand-in for Web3Signer's `eth_sign`
+    async fn eth_sign(
+        State(wallet): State<LocalWallet>,
+        Json(request): Json<Value>,
+    ) -> Json<Value> {
+        let address: Address = serde_json::from_value(request["params"][0].clone()).unwrap();
+        if address != wallet.address() {
+            return Json(json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "error": { "code": -32000, "message": "Signer not found" },
+            }));
+        }
+        let data = request["params"][1].as_str().unwrap();
+        let data = hex::decode(data.trim_start_matches("0x")).unwrap();
+        let signature = wallet.sign_message(data).await.unwrap();
+        Json(json!({
+            "jsonrpc": "2.0",
+            "id": request["id"],
+            "result": format!("0x{}", hex::encode(signature.to_vec())),
+        }))
+    }
+
+    fn setup_mock_signer(wallet: LocalWallet) -> SocketAddr {
+        let app = Router::new()
+            .route("/", routing::post(eth_sign))
+            .with_state(wallet);
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        addr
+    }
+
+    fn dummy_checkpoint() -> CheckpointWithMessageId {
+        CheckpointWithMessageId {
+            checkpoint: Checkpoint {
+                merkle_tree_hook_address: H256::repeat_byte(2),
+                mailbox_domain: 5,
+                root: H256::repeat_byte(1),
+                index: 123,
+            },
+            message_id: H256::repeat_byte(3),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sign_checkpoint_remotely() {
+        let wallet: LocalWallet = KEY.parse().unwrap();
+        let addr = setup_mock_signer(wallet.clone());
+        let url = format!("http://{addr}/").parse().unwrap();
+        let signer: Signers = RemoteSigner::new(url, wallet.address()).unwrap().into();
+
+        let checkpoint = dummy_checkpoint();
+        let signed = signer.sign(checkpoint).await.unwrap();
+        signed.verify(signer.eth_address()).unwrap();
+
+        // Signatures match the ones made with the key locally
+        let expected = Signers::from(wallet).sign(checkpoint).await.unwrap();
+        assert_eq!(signed.signature, expected.signature);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unknown_key() {
+        let wallet: LocalWallet = KEY.parse().unwrap();
+        let addr = setup_mock_signer(wallet);
+        let url = format!("http://{addr}/").parse().unwrap();
+        let signer = RemoteSigner::new(url, Address::repeat_byte(1)).unwrap();
+
+        let err = signer.sign_message(H256::zero()).await.unwrap_err();
+        assert!(matches!(err, RemoteSignerError::Rpc { code: -32000, .. }));
+    }
+}
@@ -1,12 +1,12 @@
 use std::num::NonZeroU64;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use ethers::{
     abi::Detokenize,
     prelude::{NameOrAddress, TransactionReceipt},
     providers::{JsonRpcClient, PendingTransaction, ProviderError},
-    types::Eip1559TransactionRequest,
+    types::{transaction::eip2718::TypedTransaction, Eip1559TransactionRequest, TxHash},
 };
 use ethers_contract::builders::ContractCall;
 use ethers_core::{
@@ -16,18 +16,37 @@ use ethers_core::{
         EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE,
     },
 };
-use hyperlane_core::{utils::bytes_to_hex, ChainCommunicationError, ChainResult, H256, U256};
-use tracing::{error, info};
+use hyperlane_core::{utils::bytes_to_hex, ChainCommunicationError, ChainResult, H256, H512, U256};
+use tokio::sync::MutexGuard;
+use tracing::{error, info, warn};
 
-use crate::{Middleware, TransactionOverrides};
+use crate::{GasEscalationPolicy, Middleware, TransactionOverrides};
 
 /// An amount of gas to add to the estimated gas
 pub const GAS_ESTIMATE_BUFFER: u32 = 75_000;
 
 const PENDING_TRANSACTION_POLLING_INTERVAL: Duration = Duration::from_secs(2);
 
+const PENDING_TRANSACTION_TIMEOUT: Duration = Duration::from_secs(150);
+
+/// The min percentage by which most nodes require both fees of a replacement transaction
+/// to be bumped for it to be accepted.
+const MIN_REPLACEMENT_BUMP_PERCENT: u32 = 10;
+
 /// Dispatches a transaction, logs the tx id, and returns the result
 pub(crate) async fn report_tx<M, D>(tx: ContractCall<M, D>) -> ChainResult<TransactionReceipt>
+where
+    M: Middleware + 'static,
+    D: Detokenize,
+{
+    let dispatched = dispatch_tx(&tx).await?;
+    track_pending_tx(dispatched).await
+}
+
+/// Dispatches a transaction and logs its data, without waiting for it to be included
+async fn dispatch_tx<'a, M, D>(
+    tx: &'a ContractCall<M, D>,
+) -> ChainResult<PendingTransaction<'a, M::Provider>>
 where
     M: Middleware + 'static,
     D: Detokenize,
@@ -46,11 +65,8 @@ where
 
     info!(?to, %data, "Dispatching transaction");
     // We can set the gas higher here!
-    let dispatch_fut = tx.send();
-    let dispatched = dispatch_fut
-        .await?
-        .interval(PENDING_TRANSACTION_POLLING_INTERVAL);
-    track_pending_tx(dispatched).await
+    let dispatched = tx.send().await?;
+    Ok(dispatched.interval(PENDING_TRANSACTION_POLLING_INTERVAL))
 }
 
 pub(crate) async fn track_pending_tx<P: JsonRpcClient>(
@@ -60,7 +76,7 @@ pub(crate) async fn track_pending_tx<P: JsonRpcClient>(
 
     info!(?tx_hash, "Dispatched tx");
 
-    match tokio::time::timeout(Duration::from_secs(150), pending_tx).await {
+    match tokio::time::timeout(PENDING_TRANSACTION_TIMEOUT, pending_tx).await {
         // all good
         Ok(Ok(Some(receipt))) => {
             info!(?tx_hash, "confirmed transaction");
@@ -82,6 +98,165 @@ pub(crate) async fn track_pending_tx<P: JsonRpcClient>(
     }
 }
 
+/// Dispatches a transaction and, if a gas escalation policy is provided, re-broadcasts it
+/// with the same nonce and bumped fees every time it isn't included within the policy's
+/// interval. Returns the receipt of whichever of the broadcasted transactions lands first.
+/// If none of them lands before the timeout, the hashes of all of them are returned in a
+/// `TransactionsNotIncluded` error, so that the caller can keep checking on them.
+///
+/// `dispatch_turn` is released as soon as the first transaction is dispatched, so that the
+/// next submission can be assigned the following nonce without waiting for this one to land.
+pub(crate) async fn report_tx_with_escalation<M, D>(
+    mut tx: ContractCall<M, D>,
+    provider: Arc<M>,
+    gas_escalation: Option<&GasEscalationPolicy>,
+    dispatch_turn: MutexGuard<'_, ()>,
+) -> ChainResult<TransactionReceipt>
+where
+    M: Middleware + 'static,
+    D: Detokenize,
+{
+    let Some(policy) = gas_escalation else {
+        let dispatched = dispatch_tx(&tx).await;
+        drop(dispatch_turn);
+        let dispatched = dispatched?;
+        let tx_hash: H512 = (*dispatched).into();
+        return track_pending_tx(dispatched).await.map_err(|err| match err {
+            ChainCommunicationError::TransactionTimeout() => {
+                ChainCommunicationError::TransactionsNotIncluded(vec![tx_hash])
+            }
+            err => err,
+        });
+    };
+
+    // Pin the nonce, so that every replacement reuses it
+    provider
+        .fill_transaction(&mut tx.tx, None)
+        .await
+        .map_err(ChainCommunicationError::from_other)?;
+
+    let mut dispatch_turn = Some(dispatch_turn);
+    let mut tx_hashes: Vec<TxHash> = vec![];
+    let mut replacements = 0;
+    loop {
+        let sent = tx.send().await;
+        dispatch_turn.take();
+        match sent {
+            Ok(pending_tx) => {
+                let tx_hash: H256 = (*pending_tx).into();
+                info!(?tx_hash, replacements, nonce=?tx.tx.nonce(), "Dispatched tx");
+                tx_hashes.push(*pending_tx);
+            }
+            // A replacement may be rejected, e.g. because one of the previous transactions
+            // was included in the meantime, so keep waiting on the ones already dispatched
+            Err(err) if !tx_hashes.is_empty() => {
+                warn!(error=?err, replacements, "Failed to dispatch replacement tx");
+            }
+            Err(err) => return Err(err.into()),
+        }
+
+        let mut replacement = tx.tx.clone();
+        let can_replace =
+            replacements < policy.max_replacements && bump_tx_fees(&mut replacement, policy);
+        let wait = if can_replace {
+            policy.interval
+        } else {
+            PENDING_TRANSACTION_TIMEOUT
+        };
+        if let Some(receipt) = wait_for_any_receipt(provider.as_ref(), &tx_hashes, wait).await {
+            return Ok(receipt);
+        }
+        if !can_replace {
+            error!(?tx_hashes, "waiting for receipt timed out");
+            return Err(ChainCommunicationError::TransactionsNotIncluded(
+                tx_hashes.iter().map(|tx_hash| (*tx_hash).into()).collect(),
+            ));
+        }
+        tx.tx = replacement;
+        replacements += 1;
+    }
+}
+
+/// Polls for the receipt of any of the given transactions until the timeout elapses.
+async fn wait_for_any_receipt<M: Middleware>(
+    provider: &M,
+    tx_hashes: &[TxHash],
+    timeout: Duration,
+) -> Option<TransactionReceipt> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        for tx_hash in tx_hashes {
+            match provider.get_transaction_receipt(*tx_hash).await {
+                Ok(Some(receipt)) => {
+                    let tx_hash: H256 = (*tx_hash).into();
+                    info!(?tx_hash, "confirmed transaction");
+                    return Some(receipt);
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    let tx_hash: H256 = (*tx_hash).into();
+                    warn!(?tx_hash, error=?err, "Error when fetching tx receipt");
+                }
+            }
+        }
+        if Instant::now() >= deadline {
+            return None;
+        }
+        tokio::time::sleep(PENDING_TRANSACTION_POLLING_INTERVAL).await;
+    }
+}
+
+/// Bumps the fees of a transaction by the policy's percentage, without exceeding its cap.
+/// Returns false if the fees can't be increased any further. Once the cap doesn't leave
+/// room for bumping the fees by the min replacement percentage, escalation stops, since
+/// nodes would reject the replacement as underpriced.
+fn bump_tx_fees(tx: &mut TypedTransaction, policy: &GasEscalationPolicy) -> bool {
+    let scale = |fee: EthersU256, percent: u32| {
+        fee.saturating_mul(EthersU256::from(100 + percent)) / EthersU256::from(100)
+    };
+    let cap = |fee: EthersU256| match policy.max_fee_per_gas_cap {
+        Some(cap) => fee.min(cap.into()),
+        None => fee,
+    };
+    // The smallest fee a replacement may pay, rounded up so that small fees are bumped too
+    let min_replacement = |fee: EthersU256| {
+        (fee.saturating_mul(EthersU256::from(100 + MIN_REPLACEMENT_BUMP_PERCENT))
+            + EthersU256::from(99))
+            / EthersU256::from(100)
+    };
+    match tx {
+        TypedTransaction::Eip1559(request) => {
+            let (Some(max_fee), Some(max_priority_fee)) =
+                (request.max_fee_per_gas, request.max_priority_fee_per_gas)
+            else {
+                return false;
+            };
+            let bumped_max_fee = cap(scale(max_fee, policy.bump_percent));
+            let bumped_max_priority_fee =
+                scale(max_priority_fee, policy.bump_percent).min(bumped_max_fee);
+            if bumped_max_fee < min_replacement(max_fee)
+                || bumped_max_priority_fee < min_replacement(max_priority_fee)
+            {
+                return false;
+            }
+            request.max_fee_per_gas = Some(bumped_max_fee);
+            request.max_priority_fee_per_gas = Some(bumped_max_priority_fee);
+            true
+        }
+        _ => {
+            let Some(gas_price) = tx.gas_price() else {
+                return false;
+            };
+            let bumped_gas_price = cap(scale(gas_price, policy.bump_percent));
+            if bumped_gas_price < min_replacement(gas_price) {
+                return false;
+            }
+            tx.set_gas_price(bumped_gas_price);
+            true
+        }
+    }
+}
+
 /// Populates the gas limit and price for a transaction
 pub(crate) async fn fill_tx_gas_params<M, D>(
     tx: ContractCall<M, D>,
@@ -213,3 +388,83 @@ where
         Ok(call)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use ethers::types::TransactionRequest;
+
+    use super::*;
+
+    #[test]
+    fn test_bump_tx_fees_respects_cap() {
+        let policy = GasEscalationPolicy {
+            bump_percent: 20,
+            max_fee_per_gas_cap: Some(130.into()),
+            ..Default::default()
+        };
+        let mut tx = TypedTransaction::Eip1559(
+            Eip1559TransactionRequest::new()
+                .max_fee_per_gas(100)
+                .max_priority_fee_per_gas(10),
+        );
+
+        assert!(bump_tx_fees(&mut tx, &policy));
+        let TypedTransaction::Eip1559(request) = &tx else {
+            panic!("Expected an EIP-1559 transaction");
+        };
+        assert_eq!(request.max_fee_per_gas, Some(120.into()));
+        assert_eq!(request.max_priority_fee_per_gas, Some(12.into()));
+
+        // Bumping up to the cap would only increase the max fee by ~8%, so the replacement
+        // would be rejected and escalation stops instead
+        assert!(!bump_tx_fees(&mut tx, &policy));
+        assert_eq!(tx.gas_price(), Some(120.into()));
+    }
+
+    #[test]
+    fn test_bump_tx_fees_capped_bump_above_min_replacement() {
+        let policy = GasEscalationPolicy {
+            bump_percent: 20,
+            max_fee_per_gas_cap: Some(115.into()),
+            ..Default::default()
+        };
+        let mut tx = TypedTransaction::Eip1559(
+            Eip1559TransactionRequest::new()
+                .max_fee_per_gas(100)
+                .max_priority_fee_per_gas(10),
+        );
+
+        // The cap still leaves room for a 15% bump
+        assert!(bump_tx_fees(&mut tx, &policy));
+        assert_eq!(tx.gas_price(), Some(115.into()));
+
+        // The cap is reached
+        assert!(!bump_tx_fees(&mut tx, &policy));
+    }
+
+    #[test]
+    fn test_bump_tx_fees_priority_fee_clamped_by_max_fee() {
+        let policy = GasEscalationPolicy {
+            bump_percent: 20,
+            ..Default::default()
+        };
+        // The priority fee can't be bumped past the bumped max fee
+        let mut tx = TypedTransaction::Eip1559(
+            Eip1559TransactionRequest::new()
+                .max_fee_per_gas(100)
+                .max_priority_fee_per_gas(110),
+        );
+        assert!(!bump_tx_fees(&mut tx, &policy));
+    }
+
+    #[test]
+    fn test_bump_tx_fees_legacy() {
+        let policy = GasEscalationPolicy::default();
+        let mut tx = TypedTransaction::Legacy(TransactionRequest::new().gas_price(100));
+        assert!(bump_tx_fees(&mut tx, &policy));
+        assert_eq!(tx.gas_price(), Some(112.into()));
+
+        let mut tx = TypedTransaction::Legacy(TransactionRequest::new());
+        assert!(!bump_tx_fees(&mut tx, &policy));
+    }
+}
@@ -52,3 +52,74 @@ impl_h256!(
     |v| fuels::prelude::ContractId::new(v.0),
     |v| H256::from(<[u8; 32]>::from(v))
 );
+
+/// Converts a message into the `Message` struct generated for each contract
+/// ABI that takes one.
+macro_rules! impl_message {
+    ($($contract:ident),+ $(,)?) => {
+        $(
+            impl From<&hyperlane_core::HyperlaneMessage> for crate::contracts::$contract::Message {
+                fn from(message: &hyperlane_core::HyperlaneMessage) -> Self {
+                    Self {
+                        version: message.version,
+                        nonce: message.nonce,
+                        origin: message.origin,
+                        sender: fuels::types::Bits256(message.sender.0),
+                        destination: message.destination,
+                        recipient: fuels::types::Bits256(message.recipient.0),
+                        body: message.body.clone(),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_message!(
+    mailbox,
+    interchain_security_module,
+    multisig_ism,
+    routing_ism
+);
+
+#[cfg(test)]
+mod test {
+    use fuels::{
+        prelude::{Bech32ContractId, ContractId},
+        types::Bits256,
+    };
+    use hyperlane_core::HyperlaneMessage;
+
+    use super::*;
+
+    #[test]
+    fn test_h256_round_trips() {
+        let h256 = H256::from_low_u64_be(0x1234_5678);
+        assert_eq!(Bits256::from_h256(&h256).into_h256(), h256);
+        assert_eq!(ContractId::from_h256(&h256).into_h256(), h256);
+        assert_eq!(Bech32ContractId::from_h256(&h256).into_h256(), h256);
+        assert_eq!((&Bits256::from_h256(&h256)).into_h256(), h256);
+        assert_eq!(Bits256::from_h256(&h256).0, h256.0);
+    }
+
+    #[test]
+    fn test_message_conversion() {
+        let message = HyperlaneMessage {
+            version: 3,
+            nonce: 4,
+            origin: 1,
+            sender: H256::repeat_byte(1),
+            destination: 2,
+            recipient: H256::repeat_byte(2),
+            body: vec![1, 2, 3],
+        };
+        let converted = crate::contracts::mailbox::Message::from(&message);
+        assert_eq!(converted.version, message.version);
+        assert_eq!(converted.nonce, message.nonce);
+        assert_eq!(converted.origin, message.origin);
+        assert_eq!(converted.sender.into_h256(), message.sender);
+        assert_eq!(converted.destination, message.destination);
+        assert_eq!(converted.recipient.into_h256(), message.recipient);
+        assert_eq!(converted.body, message.body);
+    }
+}
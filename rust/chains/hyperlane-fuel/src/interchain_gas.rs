@@ -1,29 +1,53 @@
 use std::ops::RangeInclusive;
 
 use async_trait::async_trait;
+use fuels::{core::try_from_bytes, prelude::ContractId};
+use tracing::{instrument, warn};
 
 use hyperlane_core::{
-    ChainResult, HyperlaneChain, HyperlaneContract, Indexed, Indexer, InterchainGasPaymaster,
+    ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract, Indexed, Indexer,
+    InterchainGasPaymaster, SequenceAwareIndexer,
 };
 use hyperlane_core::{HyperlaneDomain, HyperlaneProvider, InterchainGasPayment, LogMeta, H256};
 
+use crate::{
+    contracts::interchain_gas_paymaster::GasPaymentEvent, conversions::*, ConnectionConf,
+    FuelProvider,
+};
+
+/// Id the IGP logs a `GasPaymentEvent` with when gas is paid for a message
+const GAS_PAYMENT_LOG_ID: u64 = 0;
+
 /// A reference to an IGP contract on some Fuel chain
 #[derive(Debug)]
-pub struct FuelInterchainGasPaymaster {}
+pub struct FuelInterchainGasPaymaster {
+    address: H256,
+    provider: FuelProvider,
+}
+
+impl FuelInterchainGasPaymaster {
+    /// Create a new fuel IGP
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator) -> ChainResult<Self> {
+        Ok(Self {
+            address: locator.address,
+            provider: FuelProvider::new(locator.domain.clone(), conf)?,
+        })
+    }
+}
 
 impl HyperlaneContract for FuelInterchainGasPaymaster {
     fn address(&self) -> H256 {
-        todo!()
+        self.address
     }
 }
 
 impl HyperlaneChain for FuelInterchainGasPaymaster {
     fn domain(&self) -> &HyperlaneDomain {
-        todo!()
+        self.provider.domain()
     }
 
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
-        todo!()
+        self.provider.provider()
     }
 }
 
@@ -31,18 +55,63 @@ impl InterchainGasPaymaster for FuelInterchainGasPaymaster {}
 
 /// Struct that retrieves event data for a Fuel IGP contract
 #[derive(Debug)]
-pub struct FuelInterchainGasPaymasterIndexer {}
+pub struct FuelInterchainGasPaymasterIndexer {
+    contract_id: ContractId,
+    provider: FuelProvider,
+}
+
+impl FuelInterchainGasPaymasterIndexer {
+    /// Create a new fuel IGP indexer
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator) -> ChainResult<Self> {
+        Ok(Self {
+            contract_id: ContractId::from_h256(&locator.address),
+            provider: FuelProvider::new(locator.domain.clone(), conf)?,
+        })
+    }
+}
 
 #[async_trait]
 impl Indexer<InterchainGasPayment> for FuelInterchainGasPaymasterIndexer {
+    #[instrument(err, skip(self))]
     async fn fetch_logs_in_range(
         &self,
         range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<InterchainGasPayment>, LogMeta)>> {
-        todo!()
+        let mut payments = vec![];
+        for log in self
+            .provider
+            .logs_in_range(self.contract_id, range)
+            .await?
+            .into_iter()
+            .filter(|log| log.log_id == GAS_PAYMENT_LOG_ID)
+        {
+            match try_from_bytes::<GasPaymentEvent>(&log.data) {
+                Ok(event) => payments.push((
+                    InterchainGasPayment {
+                        message_id: event.message_id.into_h256(),
+                        destination: event.destination_domain,
+                        payment: event.payment.into(),
+                        gas_amount: event.gas_amount.into(),
+                    }
+                    .into(),
+                    log.meta,
+                )),
+                Err(err) => warn!(?err, meta=?log.meta, "Unable to decode gas payment"),
+            }
+        }
+        Ok(payments)
     }
 
     async fn get_finalized_block_number(&self) -> ChainResult<u32> {
-        todo!()
+        self.provider.latest_block_height().await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<InterchainGasPayment> for FuelInterchainGasPaymasterIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        // Gas payments aren't sequenced, so they can only be indexed by block
+        let tip = self.get_finalized_block_number().await?;
+        Ok((None, tip))
     }
 }
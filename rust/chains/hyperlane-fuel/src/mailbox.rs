@@ -4,24 +4,38 @@ use std::num::NonZeroU64;
 use std::ops::RangeInclusive;
 
 use async_trait::async_trait;
-use fuels::prelude::{Bech32ContractId, WalletUnlocked};
+use fuels::prelude::{Bech32ContractId, ContractId, TxParameters, WalletUnlocked};
+use fuels::programs::contract::ContractCall;
+use fuels::types::{transaction::Transaction as _, Bits256};
 use hyperlane_core::Indexed;
-use tracing::instrument;
+use tracing::{instrument, warn};
 
 use hyperlane_core::{
-    utils::bytes_to_hex, ChainCommunicationError, ChainResult, ContractLocator, HyperlaneAbi,
-    HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneMessage, HyperlaneProvider,
-    Indexer, LogMeta, Mailbox, TxCostEstimate, TxOutcome, H256, U256,
+    utils::bytes_to_hex, ChainCommunicationError, ChainResult, ContractLocator, Decode,
+    HyperlaneAbi, HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneMessage,
+    HyperlaneProvider, Indexer, LogMeta, Mailbox, SequenceAwareIndexer, TxCostEstimate, TxOutcome,
+    H256, U256,
 };
 
 use crate::{
-    contracts::mailbox::Mailbox as FuelMailboxInner, conversions::*, make_provider, ConnectionConf,
+    contracts::mailbox::Mailbox as FuelMailboxInner, conversions::*, make_provider,
+    merkle_tree_hook::warn_unsupported_lag, ConnectionConf, FuelProvider,
 };
 
+/// Id the mailbox logs the encoded message with when it is dispatched
+pub const DISPATCH_LOG_ID: u64 = u64::from_be_bytes(*b"dispatch");
+/// Id the mailbox logs the message id with when the message is processed
+pub const PROCESS_LOG_ID: u64 = u64::from_be_bytes(*b"process\0");
+
+/// Maximum number of dry runs used to discover the contracts and outputs a
+/// process tx depends on, e.g. contracts called by the recipient
+const PROCESS_DEPENDENCY_ESTIMATION_ATTEMPTS: u64 = 10;
+
 /// A reference to a Mailbox contract on some Fuel chain
 pub struct FuelMailbox {
     contract: FuelMailboxInner,
     domain: HyperlaneDomain,
+    provider: FuelProvider,
 }
 
 impl FuelMailbox {
@@ -38,8 +52,13 @@ impl FuelMailbox {
         Ok(FuelMailbox {
             contract: FuelMailboxInner::new(address, wallet),
             domain: locator.domain.clone(),
+            provider: FuelProvider::new(locator.domain.clone(), conf)?,
         })
     }
+
+    pub(crate) fn contract(&self) -> &FuelMailboxInner {
+        &self.contract
+    }
 }
 
 impl HyperlaneContract for FuelMailbox {
@@ -54,7 +73,7 @@ impl HyperlaneChain for FuelMailbox {
     }
 
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
-        todo!()
+        self.provider.provider()
     }
 }
 
@@ -68,10 +87,7 @@ impl Debug for FuelMailbox {
 impl Mailbox for FuelMailbox {
     #[instrument(level = "debug", err, ret, skip(self))]
     async fn count(&self, lag: Option<NonZeroU64>) -> ChainResult<u32> {
-        assert!(
-            lag.is_none(),
-            "Fuel does not support querying point-in-time"
-        );
+        warn_unsupported_lag(lag);
         self.contract
             .methods()
             .count()
@@ -83,17 +99,35 @@ impl Mailbox for FuelMailbox {
 
     #[instrument(level = "debug", err, ret, skip(self))]
     async fn delivered(&self, id: H256) -> ChainResult<bool> {
-        todo!()
+        self.contract
+            .methods()
+            .delivered(Bits256::from_h256(&id))
+            .simulate()
+            .await
+            .map(|r| r.value)
+            .map_err(ChainCommunicationError::from_other)
     }
 
     #[instrument(err, ret, skip(self))]
     async fn default_ism(&self) -> ChainResult<H256> {
-        todo!()
+        self.contract
+            .methods()
+            .get_default_ism()
+            .simulate()
+            .await
+            .map(|r| r.value.into_h256())
+            .map_err(ChainCommunicationError::from_other)
     }
 
     #[instrument(err, ret, skip(self))]
     async fn recipient_ism(&self, recipient: H256) -> ChainResult<H256> {
-        todo!()
+        self.contract
+            .methods()
+            .recipient_ism(Bits256::from_h256(&recipient))
+            .simulate()
+            .await
+            .map(|r| r.value.into_h256())
+            .map_err(ChainCommunicationError::from_other)
     }
 
     #[instrument(err, ret, skip(self))]
@@ -103,7 +137,40 @@ impl Mailbox for FuelMailbox {
         metadata: &[u8],
         tx_gas_limit: Option<U256>,
     ) -> ChainResult<TxOutcome> {
-        todo!()
+        let gas_price = self.provider.min_gas_price().await?;
+        let tx_params = TxParameters::new(
+            Some(gas_price),
+            tx_gas_limit.map(|gas_limit| gas_limit.as_u64()),
+            0,
+        );
+        let call = self
+            .contract
+            .methods()
+            .process(metadata.to_vec(), message.into())
+            .tx_params(tx_params)
+            // The recipient is called by the mailbox, so it and any contract it
+            // calls must be declared as inputs of the transaction
+            .append_contract(ContractId::from_h256(&message.recipient).into())
+            .estimate_tx_dependencies(Some(PROCESS_DEPENDENCY_ESTIMATION_ATTEMPTS))
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        // The tx is built and sent here rather than with `call()`, which doesn't
+        // return the id of the transaction
+        let tx = call
+            .build_tx()
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        let receipts = self.provider.send_transaction(&tx).await?;
+        let response = call
+            .get_response(receipts)
+            .map_err(ChainCommunicationError::from_other)?;
+
+        Ok(TxOutcome {
+            transaction_id: H256::from(*tx.id()).into(),
+            executed: true,
+            gas_used: response.gas_used.into(),
+            gas_price: gas_price.into(),
+        })
     }
 
     #[instrument(err, ret, skip(self), fields(msg=%message, metadata=%bytes_to_hex(metadata)))]
@@ -112,43 +179,133 @@ impl Mailbox for FuelMailbox {
         message: &HyperlaneMessage,
         metadata: &[u8],
     ) -> ChainResult<TxCostEstimate> {
-        todo!()
+        let cost = self
+            .contract
+            .methods()
+            .process(metadata.to_vec(), message.into())
+            .append_contract(ContractId::from_h256(&message.recipient).into())
+            .estimate_tx_dependencies(Some(PROCESS_DEPENDENCY_ESTIMATION_ATTEMPTS))
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .estimate_transaction_cost(None)
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+
+        Ok(TxCostEstimate {
+            gas_limit: cost.gas_used.into(),
+            gas_price: cost.gas_price.into(),
+            l2_gas_limit: None,
+        })
     }
 
     fn process_calldata(&self, message: &HyperlaneMessage, metadata: &[u8]) -> Vec<u8> {
-        todo!()
+        let call = self
+            .contract
+            .methods()
+            .process(metadata.to_vec(), message.into())
+            .contract_call;
+        encode_call(&call)
     }
 }
 
 /// Struct that retrieves event data for a Fuel Mailbox contract
 #[derive(Debug)]
-pub struct FuelMailboxIndexer {}
+pub struct FuelMailboxIndexer {
+    mailbox: FuelMailbox,
+    contract_id: ContractId,
+    provider: FuelProvider,
+}
+
+impl FuelMailboxIndexer {
+    /// Create a new fuel mailbox indexer
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator) -> ChainResult<Self> {
+        // Reads are only ever simulated, so the indexer doesn't need a funded
+        // signer of its own
+        let wallet = WalletUnlocked::new_random(None);
+        Ok(Self {
+            contract_id: ContractId::from_h256(&locator.address),
+            provider: FuelProvider::new(locator.domain.clone(), conf)?,
+            mailbox: FuelMailbox::new(conf, locator, wallet)?,
+        })
+    }
+
+    async fn logs_with_id(
+        &self,
+        log_id: u64,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Vec<u8>, LogMeta)>> {
+        Ok(self
+            .provider
+            .logs_in_range(self.contract_id, range)
+            .await?
+            .into_iter()
+            .filter(|log| log.log_id == log_id)
+            .map(|log| (log.data, log.meta))
+            .collect())
+    }
+}
 
 #[async_trait]
 impl Indexer<HyperlaneMessage> for FuelMailboxIndexer {
+    #[instrument(err, skip(self))]
     async fn fetch_logs_in_range(
         &self,
         range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<HyperlaneMessage>, LogMeta)>> {
-        todo!()
+        Ok(self
+            .logs_with_id(DISPATCH_LOG_ID, range)
+            .await?
+            .into_iter()
+            .filter_map(|(data, meta)| {
+                decode_dispatched_message(&data, &meta).map(|message| (message.into(), meta))
+            })
+            .collect())
     }
 
     async fn get_finalized_block_number(&self) -> ChainResult<u32> {
-        todo!()
+        self.provider.latest_block_height().await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<HyperlaneMessage> for FuelMailboxIndexer {
+    #[instrument(err, skip(self))]
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = Indexer::<HyperlaneMessage>::get_finalized_block_number(self).await?;
+        // TODO: need to make sure the call and tip are at the same height?
+        let count = Mailbox::count(&self.mailbox, None).await?;
+        Ok((Some(count), tip))
     }
 }
 
 #[async_trait]
 impl Indexer<H256> for FuelMailboxIndexer {
+    #[instrument(err, skip(self))]
     async fn fetch_logs_in_range(
         &self,
         range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<H256>, LogMeta)>> {
-        todo!()
+        Ok(self
+            .logs_with_id(PROCESS_LOG_ID, range)
+            .await?
+            .into_iter()
+            .filter_map(|(data, meta)| {
+                decode_processed_message_id(&data, &meta).map(|id| (id.into(), meta))
+            })
+            .collect())
     }
 
     async fn get_finalized_block_number(&self) -> ChainResult<u32> {
-        todo!()
+        self.provider.latest_block_height().await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<H256> for FuelMailboxIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        // Deliveries aren't sequenced, so they can only be indexed by block
+        let tip = Indexer::<H256>::get_finalized_block_number(self).await?;
+        Ok((None, tip))
     }
 }
 
@@ -158,7 +315,122 @@ impl HyperlaneAbi for FuelMailboxAbi {
     const SELECTOR_SIZE_BYTES: usize = 8;
 
     fn fn_map() -> HashMap<Vec<u8>, &'static str> {
-        // Can't support this without Fuels exporting it in the generated code
-        todo!()
+        // The generated code only exposes selectors through the calls it builds, so
+        // they're taken from calls to a mailbox that is never connected to
+        let contract = FuelMailboxInner::new(
+            Bech32ContractId::from_h256(&H256::zero()),
+            WalletUnlocked::new_random(None),
+        );
+        let methods = contract.methods();
+        let recipient = Bits256::from_h256(&H256::zero());
+        [
+            ("count", methods.count().contract_call),
+            ("delivered", methods.delivered(recipient).contract_call),
+            ("get_default_ism", methods.get_default_ism().contract_call),
+            (
+                "recipient_ism",
+                methods.recipient_ism(recipient).contract_call,
+            ),
+            (
+                "process",
+                methods
+                    .process(vec![], (&HyperlaneMessage::default()).into())
+                    .contract_call,
+            ),
+        ]
+        .into_iter()
+        .map(|(name, call)| (call.encoded_selector.to_vec(), name))
+        .collect()
+    }
+}
+
+/// Encodes a contract call as the selector followed by the arguments. Arguments
+/// that live on the heap, such as vectors, are encoded as pointers relative to
+/// the start of the arguments.
+fn encode_call(call: &ContractCall) -> Vec<u8> {
+    [call.encoded_selector.to_vec(), call.encoded_args.resolve(0)].concat()
+}
+
+fn decode_dispatched_message(data: &[u8], meta: &LogMeta) -> Option<HyperlaneMessage> {
+    HyperlaneMessage::read_from(&mut &data[..])
+        .map_err(|err| warn!(?err, ?meta, "Unable to decode dispatched message"))
+        .ok()
+}
+
+fn decode_processed_message_id(data: &[u8], meta: &LogMeta) -> Option<H256> {
+    if data.len() == H256::len_bytes() {
+        Some(H256::from_slice(data))
+    } else {
+        warn!(?data, ?meta, "Unexpected processed message id");
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_core::Encode;
+
+    use super::*;
+
+    fn meta() -> LogMeta {
+        LogMeta {
+            address: H256::zero(),
+            block_number: 1,
+            block_hash: H256::zero(),
+            transaction_id: H256::zero().into(),
+            transaction_index: 0,
+            log_index: 0.into(),
+        }
+    }
+
+    #[test]
+    fn test_decode_dispatched_message() {
+        let message = HyperlaneMessage {
+            nonce: 3,
+            origin: 1,
+            destination: 2,
+            body: vec![1, 2, 3],
+            ..Default::default()
+        };
+        assert_eq!(
+            decode_dispatched_message(&message.to_vec(), &meta()),
+            Some(message)
+        );
+        assert_eq!(decode_dispatched_message(&[3, 0], &meta()), None);
+    }
+
+    #[test]
+    fn test_decode_processed_message_id() {
+        let id = H256::repeat_byte(7);
+        assert_eq!(
+            decode_processed_message_id(id.as_bytes(), &meta()),
+            Some(id)
+        );
+        assert_eq!(decode_processed_message_id(&id[..31], &meta()), None);
+    }
+
+    #[test]
+    fn test_process_calldata_starts_with_selector() {
+        let selectors = FuelMailboxAbi::fn_map();
+        assert_eq!(selectors.len(), 5);
+        assert!(selectors
+            .keys()
+            .all(|selector| selector.len() == FuelMailboxAbi::SELECTOR_SIZE_BYTES));
+        let (process_selector, _) = selectors
+            .iter()
+            .find(|(_, name)| **name == "process")
+            .unwrap();
+
+        let contract = FuelMailboxInner::new(
+            Bech32ContractId::from_h256(&H256::zero()),
+            WalletUnlocked::new_random(None),
+        );
+        let call = contract
+            .methods()
+            .process(vec![1, 2], (&HyperlaneMessage::default()).into())
+            .contract_call;
+        let calldata = encode_call(&call);
+        assert_eq!(&calldata[..8], process_selector.as_slice());
+        assert!(calldata.len() > 8);
     }
 }
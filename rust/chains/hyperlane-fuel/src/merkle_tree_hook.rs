@@ -0,0 +1,113 @@
+use std::{num::NonZeroU64, ops::RangeInclusive};
+
+use async_trait::async_trait;
+use derive_new::new;
+use hyperlane_core::{
+    accumulator::incremental::IncrementalMerkle, ChainCommunicationError, ChainResult, Checkpoint,
+    HyperlaneChain, HyperlaneContract, HyperlaneMessage, Indexed, Indexer, LogMeta, MerkleTreeHook,
+    MerkleTreeInsertion, SequenceAwareIndexer, H256,
+};
+use tracing::{instrument, warn};
+
+use crate::{conversions::*, FuelMailbox, FuelMailboxIndexer};
+
+/// Fuel has instant finality, so reading the latest state is as safe as reading it
+/// `lag` blocks ago, which the contract doesn't support anyway.
+pub(crate) fn warn_unsupported_lag(lag: Option<NonZeroU64>) {
+    if let Some(lag) = lag {
+        warn!(
+            lag = lag.get(),
+            "Fuel does not support querying point-in-time, reading the latest state instead"
+        );
+    }
+}
+
+/// The Fuel mailbox is its own merkle tree hook
+#[async_trait]
+impl MerkleTreeHook for FuelMailbox {
+    #[instrument(err, ret, skip(self))]
+    async fn tree(&self, lag: Option<NonZeroU64>) -> ChainResult<IncrementalMerkle> {
+        warn_unsupported_lag(lag);
+
+        let tree = self
+            .contract()
+            .methods()
+            .tree()
+            .simulate()
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value;
+        Ok(IncrementalMerkle::new(
+            tree.branch.map(|node| node.into_h256()),
+            tree.count
+                .try_into()
+                .map_err(ChainCommunicationError::from_other)?,
+        ))
+    }
+
+    #[instrument(err, ret, skip(self))]
+    async fn latest_checkpoint(&self, lag: Option<NonZeroU64>) -> ChainResult<Checkpoint> {
+        warn_unsupported_lag(lag);
+
+        let (root, index) = self
+            .contract()
+            .methods()
+            .latest_checkpoint()
+            .simulate()
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value;
+        Ok(Checkpoint {
+            merkle_tree_hook_address: self.address(),
+            mailbox_domain: self.domain().id(),
+            root: H256::from(root.0),
+            index,
+        })
+    }
+
+    #[instrument(err, ret, skip(self))]
+    async fn count(&self, lag: Option<NonZeroU64>) -> ChainResult<u32> {
+        let tree = self.tree(lag).await?;
+
+        tree.count()
+            .try_into()
+            .map_err(ChainCommunicationError::from_other)
+    }
+}
+
+/// Struct that retrieves event data for a Fuel merkle tree hook contract
+/// For now it's just a wrapper around the FuelMailboxIndexer
+#[derive(Debug, new)]
+pub struct FuelMerkleTreeHookIndexer(FuelMailboxIndexer);
+
+#[async_trait]
+impl Indexer<MerkleTreeInsertion> for FuelMerkleTreeHookIndexer {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<MerkleTreeInsertion>, LogMeta)>> {
+        let messages = Indexer::<HyperlaneMessage>::fetch_logs_in_range(&self.0, range).await?;
+        let merkle_tree_insertions = messages
+            .into_iter()
+            .map(|(m, meta)| (message_to_merkle_tree_insertion(m.inner()).into(), meta))
+            .collect();
+        Ok(merkle_tree_insertions)
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        Indexer::<HyperlaneMessage>::get_finalized_block_number(&self.0).await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<MerkleTreeInsertion> for FuelMerkleTreeHookIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        SequenceAwareIndexer::<HyperlaneMessage>::latest_sequence_count_and_tip(&self.0).await
+    }
+}
+
+fn message_to_merkle_tree_insertion(message: &HyperlaneMessage) -> MerkleTreeInsertion {
+    let leaf_index = message.nonce;
+    let message_id = message.id();
+    MerkleTreeInsertion::new(leaf_index, message_id)
+}
@@ -1,43 +1,379 @@
+use std::ops::RangeInclusive;
+
 use async_trait::async_trait;
+use fuels::{
+    prelude::{AssetId, Bech32Address, ContractId, Provider},
+    tx::{
+        Bytes32, Chargeable, Input, Receipt, ScriptExecutionResult, Transaction, UniqueIdentifier,
+    },
+    types::{block::Block, transaction::ScriptTransaction},
+};
+use futures::{stream, StreamExt, TryStreamExt};
 
 use hyperlane_core::{
-    BlockInfo, ChainInfo, ChainResult, HyperlaneChain, HyperlaneDomain, HyperlaneProvider, TxnInfo,
-    H256, U256,
+    BlockInfo, ChainCommunicationError, ChainInfo, ChainResult, HyperlaneChain, HyperlaneDomain,
+    HyperlaneProvider, HyperlaneProviderError, LogMeta, TxnInfo, TxnReceiptInfo, H256, H512, U256,
 };
 
+use crate::{conversions::*, make_provider, ConnectionConf};
+
 /// A wrapper around a fuel provider to get generic blockchain information.
-#[derive(Debug)]
-pub struct FuelProvider {}
+#[derive(Debug, Clone)]
+pub struct FuelProvider {
+    domain: HyperlaneDomain,
+    provider: Provider,
+}
+
+/// Maximum number of requests made to the node at the same time when fetching logs
+const MAX_CONCURRENT_REQUESTS: usize = 16;
+
+/// Data logged by a contract with a `logd` instruction
+#[derive(Debug, Clone)]
+pub(crate) struct FuelLog {
+    /// The log id, which the ABI uses to identify the type of logged data
+    pub log_id: u64,
+    pub data: Vec<u8>,
+    pub meta: LogMeta,
+}
+
+impl FuelProvider {
+    /// Create a new fuel provider
+    pub fn new(domain: HyperlaneDomain, conf: &ConnectionConf) -> ChainResult<Self> {
+        Ok(Self {
+            domain,
+            provider: make_provider(conf)?,
+        })
+    }
+
+    /// Height of the latest block. Fuel has instant finality, so it is also the
+    /// latest finalized block.
+    pub(crate) async fn latest_block_height(&self) -> ChainResult<u32> {
+        let height = self
+            .provider
+            .latest_block_height()
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        height
+            .try_into()
+            .map_err(ChainCommunicationError::from_other)
+    }
+
+    async fn block_by_height(&self, height: u32) -> ChainResult<Option<Block>> {
+        Ok(self
+            .provider
+            .client
+            .block_by_height(height.into())
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .map(Into::into))
+    }
+
+    async fn receipts(&self, tx_id: &Bytes32) -> ChainResult<Vec<Receipt>> {
+        self.provider
+            .get_receipts(tx_id)
+            .await
+            .map_err(ChainCommunicationError::from_other)
+    }
+
+    /// Fetches the data logged by `contract` in successful transactions in the
+    /// blocks in `range`, in the order it was logged
+    pub(crate) async fn logs_in_range(
+        &self,
+        contract: ContractId,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<FuelLog>> {
+        // Blocks and receipts are fetched concurrently, but `buffered` keeps them in
+        // order so that logs are numbered correctly
+        let blocks: Vec<(u32, Block)> = stream::iter(range)
+            .map(|height| async move {
+                Ok::<_, ChainCommunicationError>(
+                    self.block_by_height(height)
+                        .await?
+                        .map(|block| (height, block)),
+                )
+            })
+            .buffered(MAX_CONCURRENT_REQUESTS)
+            .try_collect::<Vec<_>>()
+            .await?
+            .into_iter()
+            .flatten()
+            .collect();
+        let tx_ids = blocks
+            .iter()
+            .flat_map(|(_, block)| block.transactions.iter());
+        let receipts: Vec<Vec<Receipt>> = stream::iter(tx_ids)
+            .map(|tx_id| self.receipts(tx_id))
+            .buffered(MAX_CONCURRENT_REQUESTS)
+            .try_collect()
+            .await?;
+
+        let mut logs = vec![];
+        let mut receipts = receipts.into_iter();
+        for (height, block) in &blocks {
+            // Takes the receipts of the block's transactions only
+            let transactions = block.transactions.iter().zip(receipts.by_ref());
+            logs.extend(logs_in_block(
+                contract,
+                *height,
+                H256::from(*block.id),
+                transactions,
+            ));
+        }
+        Ok(logs)
+    }
+
+    /// Submits `tx` and waits for its receipts. Fails if the transaction reverted.
+    pub(crate) async fn send_transaction(
+        &self,
+        tx: &ScriptTransaction,
+    ) -> ChainResult<Vec<Receipt>> {
+        self.provider
+            .send_transaction(tx)
+            .await
+            .map_err(ChainCommunicationError::from_other)
+    }
+
+    /// The lowest gas price the node accepts
+    pub(crate) async fn min_gas_price(&self) -> ChainResult<u64> {
+        Ok(self
+            .provider
+            .node_info()
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .min_gas_price)
+    }
+}
+
+/// Collects the data logged by `contract` in the successful transactions of a block,
+/// given with their receipts in the order they were included. Logs are numbered
+/// across all the logs of the block.
+fn logs_in_block<'a>(
+    contract: ContractId,
+    height: u32,
+    block_hash: H256,
+    transactions: impl IntoIterator<Item = (&'a Bytes32, Vec<Receipt>)>,
+) -> Vec<FuelLog> {
+    let mut logs = vec![];
+    let mut log_index = 0u64;
+    for (transaction_index, (tx_id, receipts)) in transactions.into_iter().enumerate() {
+        let succeeded = receipts.iter().any(|receipt| {
+            matches!(
+                receipt,
+                Receipt::ScriptResult {
+                    result: ScriptExecutionResult::Success,
+                    ..
+                }
+            )
+        });
+        if !succeeded {
+            continue;
+        }
+        for receipt in receipts {
+            let Receipt::LogData { id, rb, data, .. } = receipt else {
+                continue;
+            };
+            if id == contract {
+                logs.push(FuelLog {
+                    log_id: rb,
+                    data,
+                    meta: LogMeta {
+                        address: contract.into_h256(),
+                        block_number: height.into(),
+                        block_hash,
+                        transaction_id: H256::from(**tx_id).into(),
+                        transaction_index: transaction_index as u64,
+                        log_index: log_index.into(),
+                    },
+                });
+            }
+            log_index += 1;
+        }
+    }
+    logs
+}
 
 impl HyperlaneChain for FuelProvider {
     fn domain(&self) -> &HyperlaneDomain {
-        todo!()
+        &self.domain
     }
 
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
-        todo!()
+        Box::new(self.clone())
     }
 }
 
 #[async_trait]
 impl HyperlaneProvider for FuelProvider {
-    async fn get_block_by_hash(&self, hash: &H256) -> ChainResult<BlockInfo> {
-        todo!()
+    async fn get_block_by_height(&self, height: u64) -> ChainResult<BlockInfo> {
+        let block = self
+            .block_by_height(
+                height
+                    .try_into()
+                    .map_err(ChainCommunicationError::from_other)?,
+            )
+            .await?
+            .ok_or(HyperlaneProviderError::CouldNotFindBlockByHeight(height))?;
+        Ok(BlockInfo {
+            hash: H256::from(*block.id),
+            timestamp: block
+                .header
+                .time
+                .map_or(0, |time| time.timestamp().try_into().unwrap_or_default()),
+            number: height,
+        })
     }
 
-    async fn get_txn_by_hash(&self, hash: &H256) -> ChainResult<TxnInfo> {
-        todo!()
+    async fn get_txn_by_hash(&self, hash: &H512) -> ChainResult<TxnInfo> {
+        // Fuel transaction ids are 256 bits
+        let tx_id = H256::from(*hash);
+        let response = self
+            .provider
+            .get_transaction_by_id(&format!("{tx_id:x}"))
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .ok_or(HyperlaneProviderError::CouldNotFindTransactionByHash(*hash))?;
+        let Transaction::Script(tx) = response.transaction else {
+            return Err(ChainCommunicationError::from_other_str(
+                "Only script transactions are supported",
+            ));
+        };
+
+        // The sender is the owner of the coins paying for the transaction
+        let sender = tx
+            .inputs()
+            .iter()
+            .find_map(Input::input_owner)
+            .map(|owner| H256::from(**owner))
+            .unwrap_or_default();
+        let receipt =
+            self.receipts(&tx.id())
+                .await?
+                .into_iter()
+                .find_map(|receipt| match receipt {
+                    Receipt::ScriptResult { gas_used, .. } => Some(TxnReceiptInfo {
+                        gas_used: gas_used.into(),
+                        cumulative_gas_used: gas_used.into(),
+                        effective_gas_price: Some(tx.gas_price().into()),
+                    }),
+                    _ => None,
+                });
+
+        Ok(TxnInfo {
+            hash: *hash,
+            gas_limit: tx.gas_limit().into(),
+            max_priority_fee_per_gas: None,
+            max_fee_per_gas: None,
+            gas_price: Some(tx.gas_price().into()),
+            // Fuel is UTXO based, so transactions don't have a nonce
+            nonce: 0,
+            sender,
+            recipient: None,
+            receipt,
+        })
     }
 
     async fn is_contract(&self, address: &H256) -> ChainResult<bool> {
-        todo!()
+        Ok(self
+            .provider
+            .client
+            .contract(&format!("{address:x}"))
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .is_some())
     }
 
     async fn get_balance(&self, address: String) -> ChainResult<U256> {
-        todo!()
+        let address: Bech32Address = address
+            .parse()
+            .map_err(ChainCommunicationError::from_other)?;
+        self.provider
+            .get_asset_balance(&address, AssetId::BASE)
+            .await
+            .map(Into::into)
+            .map_err(ChainCommunicationError::from_other)
     }
 
     async fn get_chain_metrics(&self) -> ChainResult<Option<ChainInfo>> {
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn contract(byte: u8) -> ContractId {
+        ContractId::new([byte; 32])
+    }
+
+    fn log(contract: ContractId, log_id: u64, data: Vec<u8>) -> Receipt {
+        Receipt::log_data(contract, 0, log_id, 0, data, 0, 0)
+    }
+
+    fn script_result(result: ScriptExecutionResult) -> Receipt {
+        Receipt::script_result(result, 0)
+    }
+
+    #[test]
+    fn test_logs_in_block() {
+        let mailbox = contract(1);
+        let tx_ids = [
+            Bytes32::new([1; 32]),
+            Bytes32::new([2; 32]),
+            Bytes32::new([3; 32]),
+        ];
+        let transactions = vec![
+            (
+                &tx_ids[0],
+                vec![
+                    log(contract(2), 5, vec![0]),
+                    log(mailbox, 5, vec![1]),
+                    script_result(ScriptExecutionResult::Success),
+                ],
+            ),
+            // Logs of reverted transactions are ignored
+            (
+                &tx_ids[1],
+                vec![
+                    log(mailbox, 5, vec![2]),
+                    script_result(ScriptExecutionResult::Revert),
+                ],
+            ),
+            (
+                &tx_ids[2],
+                vec![
+                    log(mailbox, 6, vec![3]),
+                    script_result(ScriptExecutionResult::Success),
+                ],
+            ),
+        ];
+        let block_hash = H256::repeat_byte(9);
+
+        let logs = logs_in_block(mailbox, 10, block_hash, transactions);
+
+        let summary: Vec<_> = logs
+            .iter()
+            .map(|log| {
+                (
+                    log.log_id,
+                    log.data.clone(),
+                    log.meta.transaction_id,
+                    log.meta.transaction_index,
+                    log.meta.log_index,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (5, vec![1], H256::repeat_byte(1).into(), 0, 1.into()),
+                (6, vec![3], H256::repeat_byte(3).into(), 2, 2.into()),
+            ]
+        );
+        for log in logs {
+            assert_eq!(log.meta.address, mailbox.into_h256());
+            assert_eq!(log.meta.block_number, 10);
+            assert_eq!(log.meta.block_hash, block_hash);
+        }
+    }
+}
@@ -1,44 +1,155 @@
 use async_trait::async_trait;
+use fuels::prelude::{Bech32ContractId, WalletUnlocked};
+use fuels::types::Bits256;
+use tracing::{instrument, trace, warn};
 
 use hyperlane_core::{
-    Announcement, ChainResult, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
-    HyperlaneProvider, SignedType, TxOutcome, ValidatorAnnounce, H256, U256,
+    Announcement, ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain,
+    HyperlaneContract, HyperlaneDomain, HyperlaneProvider, SignedType, TxOutcome,
+    ValidatorAnnounce, H256, H512, U256,
+};
+
+use crate::{
+    contracts::validator_announce::ValidatorAnnounce as FuelValidatorAnnounceInner, conversions::*,
+    make_provider, ConnectionConf, FuelProvider,
 };
 
 /// A reference to a ValidatorAnnounce contract on some Fuel chain
 #[derive(Debug)]
-pub struct FuelValidatorAnnounce {}
+pub struct FuelValidatorAnnounce {
+    contract: FuelValidatorAnnounceInner,
+    provider: FuelProvider,
+    /// Address of the wallet paying for announcements
+    signer_address: String,
+}
+
+impl FuelValidatorAnnounce {
+    /// Create a new fuel ValidatorAnnounce
+    pub fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator,
+        mut wallet: WalletUnlocked,
+    ) -> ChainResult<Self> {
+        wallet.set_provider(make_provider(conf)?);
+        let signer_address = wallet.address().to_string();
+        let address = Bech32ContractId::from_h256(&locator.address);
+
+        Ok(Self {
+            contract: FuelValidatorAnnounceInner::new(address, wallet),
+            provider: FuelProvider::new(locator.domain.clone(), conf)?,
+            signer_address,
+        })
+    }
+
+    async fn storage_locations(&self, validator: &H256) -> ChainResult<Vec<String>> {
+        let methods = self.contract.methods();
+        let validator = Bits256::from_h256(validator);
+        let count = methods
+            .get_announced_storage_location_count(validator)
+            .simulate()
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value;
+
+        let mut locations = Vec::with_capacity(count as usize);
+        for index in 0..count {
+            let location = methods
+                .get_announced_storage_location(validator, index)
+                .simulate()
+                .await
+                .map_err(ChainCommunicationError::from_other)?
+                .value;
+            match String::from_utf8(location) {
+                Ok(location) => locations.push(location),
+                Err(err) => warn!(?err, ?validator, "Announced storage location is not utf-8"),
+            }
+        }
+        Ok(locations)
+    }
+
+    /// Total fee of submitting the announcement, in the base asset
+    async fn announce_cost(&self, announcement: &SignedType<Announcement>) -> ChainResult<u64> {
+        let (validator, storage_location, signature) = announce_args(announcement);
+        self.contract
+            .methods()
+            .announce(validator, storage_location, signature)
+            .estimate_transaction_cost(None)
+            .await
+            .map(|cost| cost.total_fee)
+            .map_err(ChainCommunicationError::from_other)
+    }
+}
+
+fn announce_args(announcement: &SignedType<Announcement>) -> (Bits256, Vec<u8>, Vec<u8>) {
+    (
+        Bits256::from_h256(&announcement.value.validator.into()),
+        announcement.value.storage_location.as_bytes().to_vec(),
+        announcement.signature.to_vec(),
+    )
+}
 
 impl HyperlaneContract for FuelValidatorAnnounce {
     fn address(&self) -> H256 {
-        todo!()
+        self.contract.contract_id().into_h256()
     }
 }
 
 impl HyperlaneChain for FuelValidatorAnnounce {
     fn domain(&self) -> &HyperlaneDomain {
-        todo!()
+        self.provider.domain()
     }
 
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
-        todo!()
+        self.provider.provider()
     }
 }
 
 #[async_trait]
 impl ValidatorAnnounce for FuelValidatorAnnounce {
+    #[instrument(err, ret, skip(self))]
     async fn get_announced_storage_locations(
         &self,
         validators: &[H256],
     ) -> ChainResult<Vec<Vec<String>>> {
-        todo!()
+        let mut storage_locations = Vec::with_capacity(validators.len());
+        for validator in validators {
+            storage_locations.push(self.storage_locations(validator).await?);
+        }
+        Ok(storage_locations)
     }
 
+    #[instrument(err, ret, skip(self))]
     async fn announce(&self, announcement: SignedType<Announcement>) -> ChainResult<TxOutcome> {
-        todo!()
+        let gas_price = self.provider.min_gas_price().await?;
+        let (validator, storage_location, signature) = announce_args(&announcement);
+        let response = self
+            .contract
+            .methods()
+            .announce(validator, storage_location, signature)
+            .call()
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+
+        Ok(TxOutcome {
+            // fuels doesn't expose the id of the submitted transaction yet
+            transaction_id: H512::zero(),
+            executed: true,
+            gas_used: response.gas_used.into(),
+            gas_price: gas_price.into(),
+        })
     }
 
     async fn announce_tokens_needed(&self, announcement: SignedType<Announcement>) -> Option<U256> {
-        todo!()
+        let Ok(cost) = self.announce_cost(&announcement).await else {
+            trace!("Unable to estimate announce cost");
+            return None;
+        };
+
+        let Ok(balance) = self.provider.get_balance(self.signer_address.clone()).await else {
+            trace!("Unable to query balance");
+            return None;
+        };
+
+        Some(U256::from(cost).saturating_sub(balance))
     }
 }
@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+
+use hyperlane_core::{
+    AggregationIsm, ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain,
+    HyperlaneContract, HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, H256,
+};
+use hyperlane_sealevel_aggregation_ism::{
+    accounts::AggregationIsmAccount, instruction::storage_pda_key,
+};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+
+use crate::{ConnectionConf, RpcClientWithDebug, SealevelProvider};
+
+/// A reference to an AggregationIsm contract on some Sealevel chain
+#[derive(Debug)]
+pub struct SealevelAggregationIsm {
+    program_id: Pubkey,
+    domain: HyperlaneDomain,
+    provider: SealevelProvider,
+}
+
+impl SealevelAggregationIsm {
+    /// Create a new Sealevel AggregationIsm.
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator) -> Self {
+        let provider = SealevelProvider::new(locator.domain.clone(), conf);
+        let program_id = Pubkey::from(<[u8; 32]>::from(locator.address));
+
+        Self {
+            program_id,
+            domain: locator.domain.clone(),
+            provider,
+        }
+    }
+
+    fn rpc(&self) -> &RpcClientWithDebug {
+        self.provider.rpc()
+    }
+}
+
+impl HyperlaneContract for SealevelAggregationIsm {
+    fn address(&self) -> H256 {
+        self.program_id.to_bytes().into()
+    }
+}
+
+impl HyperlaneChain for SealevelAggregationIsm {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        self.provider.provider()
+    }
+}
+
+#[async_trait]
+impl AggregationIsm for SealevelAggregationIsm {
+    /// Returns the modules and threshold, which are the same for all messages
+    async fn modules_and_threshold(
+        &self,
+        _message: &HyperlaneMessage,
+    ) -> ChainResult<(Vec<H256>, u8)> {
+        let storage_account = self
+            .rpc()
+            .get_account_with_commitment(
+                &storage_pda_key(&self.program_id),
+                CommitmentConfig::finalized(),
+            )
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value
+            .ok_or_else(|| {
+                ChainCommunicationError::from_other_str(
+                    "Could not find aggregation ISM storage account",
+                )
+            })?;
+        let storage = AggregationIsmAccount::fetch(&mut storage_account.data.as_ref())
+            .map_err(ChainCommunicationError::from_other)?
+            .into_inner();
+
+        let modules = storage
+            .modules_and_threshold
+            .modules
+            .iter()
+            .map(|module| module.to_bytes().into())
+            .collect();
+        Ok((modules, storage.modules_and_threshold.threshold))
+    }
+}
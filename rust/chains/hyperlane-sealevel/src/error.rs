@@ -1,6 +1,6 @@
 use hyperlane_core::ChainCommunicationError;
 use solana_client::client_error::ClientError;
-use solana_sdk::pubkey::ParsePubkeyError;
+use solana_sdk::{pubkey::ParsePubkeyError, transaction::TransactionError};
 
 /// Errors from the crates specific to the hyperlane-sealevel
 /// implementation.
@@ -14,6 +14,14 @@ pub enum HyperlaneSealevelError {
     /// ClientError error
     #[error("{0}")]
     ClientError(#[from] ClientError),
+    /// A transaction failed in simulation
+    #[error("Transaction simulation failed: {err}, logs: {logs:?}")]
+    SimulationFailed {
+        /// The error the transaction failed with
+        err: TransactionError,
+        /// The logs emitted by the transaction
+        logs: Vec<String>,
+    },
 }
 
 impl From<HyperlaneSealevelError> for ChainCommunicationError {
@@ -2,7 +2,7 @@ use async_trait::async_trait;
 use hyperlane_core::{
     config::StrOrIntParseError, ChainCommunicationError, ChainResult, ContractLocator,
     HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneProvider, Indexed, Indexer,
-    InterchainGasPaymaster, InterchainGasPayment, LogMeta, SequenceAwareIndexer, H256, H512,
+    InterchainGasPaymaster, InterchainGasPayment, LogMeta, SequenceAwareIndexer, H256,
 };
 use hyperlane_sealevel_igp::{
     accounts::{GasPaymentAccount, ProgramDataAccount},
@@ -17,7 +17,9 @@ use std::ops::RangeInclusive;
 use tracing::{info, instrument};
 
 use crate::{
-    client::RpcClientWithDebug, utils::get_finalized_block_number, ConnectionConf, SealevelProvider,
+    client::RpcClientWithDebug,
+    utils::{account_creation_log_meta, get_finalized_block_number},
+    ConnectionConf, SealevelProvider,
 };
 use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
 
@@ -101,6 +103,7 @@ impl InterchainGasPaymaster for SealevelInterchainGasPaymaster {}
 pub struct SealevelInterchainGasPaymasterIndexer {
     rpc_client: RpcClientWithDebug,
     igp: SealevelInterchainGasPaymaster,
+    advanced_log_meta: bool,
 }
 
 /// IGP payment data on Sealevel
@@ -112,10 +115,12 @@ pub struct SealevelGasPayment {
 }
 
 impl SealevelInterchainGasPaymasterIndexer {
-    /// Create a new Sealevel IGP indexer.
+    /// Create a new Sealevel IGP indexer. `advanced_log_meta` looks up the
+    /// transaction of every indexed payment, see `account_creation_log_meta`.
     pub async fn new(
         conf: &ConnectionConf,
         igp_account_locator: ContractLocator<'_>,
+        advanced_log_meta: bool,
     ) -> ChainResult<Self> {
         // Set the `processed` commitment at rpc level
         let rpc_client = RpcClientWithDebug::new_with_commitment(
@@ -124,7 +129,11 @@ impl SealevelInterchainGasPaymasterIndexer {
         );
 
         let igp = SealevelInterchainGasPaymaster::new(conf, &igp_account_locator).await?;
-        Ok(Self { rpc_client, igp })
+        Ok(Self {
+            rpc_client,
+            igp,
+            advanced_log_meta,
+        })
     }
 
     #[instrument(err, skip(self))]
@@ -222,22 +231,23 @@ impl SealevelInterchainGasPaymasterIndexer {
             gas_amount: gas_payment_account.gas_amount.into(),
         };
 
+        let log_meta = account_creation_log_meta(
+            &self.igp.provider,
+            &self.igp.program_id,
+            &valid_payment_pda_pubkey,
+            gas_payment_account.slot,
+            sequence_number.into(),
+            self.advanced_log_meta,
+        )
+        .await?;
+
         Ok(SealevelGasPayment::new(
             Indexed::new(igp_payment).with_sequence(
                 sequence_number
                     .try_into()
                     .map_err(StrOrIntParseError::from)?,
             ),
-            LogMeta {
-                address: self.igp.program_id.to_bytes().into(),
-                block_number: gas_payment_account.slot,
-                // TODO: get these when building out scraper support.
-                // It's inconvenient to get these :|
-                block_hash: H256::zero(),
-                transaction_id: H512::zero(),
-                transaction_index: 0,
-                log_index: sequence_number.into(),
-            },
+            log_meta,
             H256::from(gas_payment_account.igp.to_bytes()),
         ))
     }
@@ -0,0 +1,39 @@
+//! Address lookup table used to compact batches of process instructions into
+//! a single versioned transaction.
+//!
+//! The table is created and extended out of band, e.g. with the sealevel
+//! client's `mailbox create-lookup-table` command, so the agent only reads it.
+
+use hyperlane_core::{ChainCommunicationError, ChainResult};
+use solana_address_lookup_table_program::state::AddressLookupTable;
+use solana_sdk::{
+    address_lookup_table_account::AddressLookupTableAccount, commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+};
+use tracing::warn;
+
+use crate::RpcClientWithDebug;
+
+/// Fetches a lookup table and the addresses it holds.
+pub(crate) async fn fetch_lookup_table(
+    rpc: &RpcClientWithDebug,
+    address: Pubkey,
+) -> ChainResult<AddressLookupTableAccount> {
+    let Some(account) = rpc
+        .get_account_with_commitment(&address, CommitmentConfig::processed())
+        .await
+        .map_err(ChainCommunicationError::from_other)?
+        .value
+    else {
+        warn!(%address, "Configured address lookup table does not exist");
+        return Err(ChainCommunicationError::from_other_str(
+            "Address lookup table does not exist",
+        ));
+    };
+    let table = AddressLookupTable::deserialize(&account.data)
+        .map_err(ChainCommunicationError::from_other)?;
+    Ok(AddressLookupTableAccount {
+        key: address,
+        addresses: table.addresses.to_vec(),
+    })
+}
@@ -1,24 +1,30 @@
 #![allow(warnings)] // FIXME remove
 
-use std::{collections::HashMap, num::NonZeroU64, ops::RangeInclusive, str::FromStr as _};
+use std::{
+    collections::{HashMap, HashSet},
+    num::NonZeroU64,
+    ops::RangeInclusive,
+    str::FromStr as _,
+};
 
 use async_trait::async_trait;
 use borsh::{BorshDeserialize, BorshSerialize};
-use jsonrpc_core::futures_util::TryFutureExt;
+use jsonrpc_core::futures_util::{future::try_join_all, TryFutureExt};
 use tracing::{debug, info, instrument, warn};
 
 use hyperlane_core::{
-    accumulator::incremental::IncrementalMerkle, BatchItem, ChainCommunicationError, ChainResult,
-    Checkpoint, ContractLocator, Decode as _, Encode as _, FixedPointNumber, HyperlaneAbi,
-    HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneMessage, HyperlaneProvider,
-    Indexed, Indexer, LogMeta, Mailbox, MerkleTreeHook, SequenceAwareIndexer, TxCostEstimate,
-    TxOutcome, H256, H512, U256,
+    accumulator::incremental::IncrementalMerkle, BatchItem, BatchResult, ChainCommunicationError,
+    ChainResult, Checkpoint, ContractLocator, Decode as _, Encode as _, FixedPointNumber,
+    HyperlaneAbi, HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneMessage,
+    HyperlaneProvider, Indexed, Indexer, LogMeta, Mailbox, MerkleTreeHook, SequenceAwareIndexer,
+    TxCostEstimate, TxOutcome, H256, H512, U256,
 };
 use hyperlane_sealevel_interchain_security_module_interface::{
-    InterchainSecurityModuleInstruction, VerifyInstruction,
+    has_unresolved_isms, verify_account_metas_pda_key, InterchainSecurityModuleInstruction,
+    VerifyInstruction, MAX_VERIFY_ACCOUNT_METAS_CALLS,
 };
 use hyperlane_sealevel_mailbox::{
-    accounts::{DispatchedMessageAccount, InboxAccount, OutboxAccount},
+    accounts::{DispatchedMessageAccount, InboxAccount, OutboxAccount, ProcessedMessageAccount},
     instruction::InboxProcess,
     mailbox_dispatched_message_pda_seeds, mailbox_inbox_pda_seeds, mailbox_outbox_pda_seeds,
     mailbox_process_authority_pda_seeds, mailbox_processed_message_pda_seeds,
@@ -30,21 +36,25 @@ use serializable_account_meta::SimulationReturnData;
 use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
 use solana_client::{
     nonblocking::rpc_client::RpcClient,
-    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcSendTransactionConfig},
+    rpc_config::{
+        RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcSendTransactionConfig,
+        RpcSimulateTransactionConfig,
+    },
     rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType},
 };
 use solana_sdk::{
     account::Account,
+    address_lookup_table_account::AddressLookupTableAccount,
     commitment_config::CommitmentConfig,
-    compute_budget::ComputeBudgetInstruction,
     hash::Hash,
     instruction::AccountMeta,
     instruction::Instruction,
-    message::Message,
+    message::{v0, Message, VersionedMessage},
+    packet::PACKET_DATA_SIZE,
     pubkey::Pubkey,
-    signature::Signature,
+    signature::{Signature, SIGNATURE_BYTES},
     signer::{keypair::Keypair, Signer as _},
-    transaction::{Transaction, VersionedTransaction},
+    transaction::{Transaction, TransactionError, VersionedTransaction},
 };
 use solana_transaction_status::{
     EncodedConfirmedBlock, EncodedTransaction, EncodedTransactionWithStatusMeta,
@@ -52,19 +62,23 @@ use solana_transaction_status::{
     UiTransaction, UiTransactionReturnData, UiTransactionStatusMeta,
 };
 
+use crate::lookup_table::fetch_lookup_table;
+use crate::priority_fee::{
+    compute_unit_price, compute_units_with_margin, estimate_compute_units, writable_accounts,
+    ComputeBudget, MAX_COMPUTE_UNITS,
+};
 use crate::RpcClientWithDebug;
 use crate::{
-    utils::{get_account_metas, get_finalized_block_number, simulate_instruction},
-    ConnectionConf, SealevelProvider,
+    utils::{
+        account_creation_log_meta, get_account_metas, get_finalized_block_number,
+        simulate_instruction,
+    },
+    ConnectionConf, PriorityFeeConf, SealevelProvider,
 };
 
 const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
 const SPL_NOOP: &str = "noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV";
 
-// The max amount of compute units for a transaction.
-// TODO: consider a more sane value and/or use IGP gas payments instead.
-const PROCESS_COMPUTE_UNITS: u32 = 1_400_000;
-
 /// A reference to a Mailbox contract on some Sealevel chain
 pub struct SealevelMailbox {
     pub(crate) program_id: Pubkey,
@@ -72,6 +86,8 @@ pub struct SealevelMailbox {
     pub(crate) outbox: (Pubkey, u8),
     pub(crate) provider: SealevelProvider,
     payer: Option<Keypair>,
+    lookup_table: Option<Pubkey>,
+    priority_fee: PriorityFeeConf,
 }
 
 impl SealevelMailbox {
@@ -98,6 +114,8 @@ impl SealevelMailbox {
             outbox,
             provider,
             payer,
+            lookup_table: conf.lookup_table.map(|address| Pubkey::from(address.0)),
+            priority_fee: conf.priority_fee.clone(),
         })
     }
 
@@ -205,14 +223,36 @@ impl SealevelMailbox {
                 metadata,
                 message,
             });
-        self.get_account_metas_with_instruction_bytes(
-            ism,
-            &instruction
-                .encode()
-                .map_err(ChainCommunicationError::from_other)?,
-            hyperlane_sealevel_interchain_security_module_interface::VERIFY_ACCOUNT_METAS_PDA_SEEDS,
-        )
-        .await
+        let instruction_data = instruction
+            .encode()
+            .map_err(ChainCommunicationError::from_other)?;
+        let verify_account_metas_pda_key = verify_account_metas_pda_key(&ism);
+
+        // ISMs that defer to other ISMs, like routing and aggregation ISMs,
+        // require the previously returned account metas to be passed in to
+        // resolve the account metas of the ISMs they defer to.
+        let mut account_metas = vec![];
+        for _ in 0..MAX_VERIFY_ACCOUNT_METAS_CALLS {
+            let mut accounts = vec![AccountMeta::new(verify_account_metas_pda_key, false)];
+            accounts.extend(account_metas);
+            let provided = accounts
+                .iter()
+                .map(|account_meta| account_meta.pubkey)
+                .collect::<Vec<_>>();
+
+            account_metas = self
+                .get_account_metas(Instruction::new_with_bytes(
+                    ism,
+                    &instruction_data,
+                    accounts,
+                ))
+                .await?;
+            if !has_unresolved_isms(&provided, &account_metas) {
+                break;
+            }
+        }
+
+        Ok(account_metas)
     }
 
     /// Gets the account metas required for the recipient's `MessageRecipientInstruction::Handle` instruction.
@@ -253,6 +293,109 @@ impl SealevelMailbox {
 
         self.get_account_metas(instruction).await
     }
+    /// Simulates the instructions to find out the compute units they need,
+    /// and prices them from the recent prioritization fees.
+    async fn compute_budget(
+        &self,
+        payer: &Pubkey,
+        instructions: &[Instruction],
+    ) -> ChainResult<ComputeBudget> {
+        let units = estimate_compute_units(self.rpc(), payer, instructions).await?;
+        let unit_price = compute_unit_price(
+            self.rpc(),
+            &self.priority_fee,
+            &writable_accounts(instructions),
+        )
+        .await?;
+        Ok(ComputeBudget { units, unit_price })
+    }
+
+    /// Builds the mailbox instruction to process a message, resolving the
+    /// accounts required by the recipient's ISM and handler.
+    async fn process_instruction(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+    ) -> ChainResult<Instruction> {
+        let recipient: Pubkey = message.recipient.0.into();
+        let mut encoded_message = vec![];
+        message.write_to(&mut encoded_message).unwrap();
+
+        let payer = self
+            .payer
+            .as_ref()
+            .ok_or_else(|| ChainCommunicationError::SignerUnavailable)?;
+
+        let (process_authority_key, _process_authority_bump) = Pubkey::try_find_program_address(
+            mailbox_process_authority_pda_seeds!(&recipient),
+            &self.program_id,
+        )
+        .ok_or_else(|| {
+            ChainCommunicationError::from_other_str(
+                "Could not find program address for process authority",
+            )
+        })?;
+        let (processed_message_account_key, _processed_message_account_bump) =
+            Pubkey::try_find_program_address(
+                mailbox_processed_message_pda_seeds!(message.id()),
+                &self.program_id,
+            )
+            .ok_or_else(|| {
+                ChainCommunicationError::from_other_str(
+                    "Could not find program address for processed message account",
+                )
+            })?;
+
+        // Get the account metas required for the recipient.InterchainSecurityModule instruction.
+        let ism_getter_account_metas = self.get_ism_getter_account_metas(recipient).await?;
+
+        // Get the recipient ISM.
+        let ism = self
+            .get_recipient_ism(recipient, ism_getter_account_metas.clone())
+            .await?;
+
+        let ixn =
+            hyperlane_sealevel_mailbox::instruction::Instruction::InboxProcess(InboxProcess {
+                metadata: metadata.to_vec(),
+                message: encoded_message.clone(),
+            });
+        let ixn_data = ixn
+            .into_instruction_data()
+            .map_err(ChainCommunicationError::from_other)?;
+
+        // Craft the accounts for the transaction.
+        let mut accounts: Vec<AccountMeta> = vec![
+            AccountMeta::new_readonly(payer.pubkey(), true),
+            AccountMeta::new_readonly(Pubkey::from_str(SYSTEM_PROGRAM).unwrap(), false),
+            AccountMeta::new(self.inbox.0, false),
+            AccountMeta::new_readonly(process_authority_key, false),
+            AccountMeta::new(processed_message_account_key, false),
+        ];
+        accounts.extend(ism_getter_account_metas);
+        accounts.extend([
+            AccountMeta::new_readonly(Pubkey::from_str(SPL_NOOP).unwrap(), false),
+            AccountMeta::new_readonly(ism, false),
+        ]);
+
+        // Get the account metas required for the ISM.Verify instruction.
+        let ism_verify_account_metas = self
+            .get_ism_verify_account_metas(ism, metadata.into(), encoded_message)
+            .await?;
+        accounts.extend(ism_verify_account_metas);
+
+        // The recipient.
+        accounts.extend([AccountMeta::new_readonly(recipient, false)]);
+
+        // Get account metas required for the Handle instruction
+        let handle_account_metas = self.get_handle_account_metas(message).await?;
+        accounts.extend(handle_account_metas);
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            data: ixn_data,
+            accounts,
+        })
+    }
 }
 
 impl HyperlaneContract for SealevelMailbox {
@@ -344,20 +487,22 @@ impl Mailbox for SealevelMailbox {
         metadata: &[u8],
         _tx_gas_limit: Option<U256>,
     ) -> ChainResult<TxOutcome> {
-        let recipient: Pubkey = message.recipient.0.into();
-        let mut encoded_message = vec![];
-        message.write_to(&mut encoded_message).unwrap();
-
         let payer = self
             .payer
             .as_ref()
             .ok_or_else(|| ChainCommunicationError::SignerUnavailable)?;
 
-        let mut instructions = Vec::with_capacity(2);
-        // Set the compute unit limit.
-        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(
-            PROCESS_COMPUTE_UNITS,
-        ));
+        let process_instruction = self.process_instruction(message, metadata).await?;
+        // Simulate the transaction before paying for it, which also sizes
+        // its compute budget.
+        let compute_budget = self
+            .compute_budget(&payer.pubkey(), std::slice::from_ref(&process_instruction))
+            .await?;
+        let instructions = compute_budget
+            .instructions()
+            .into_iter()
+            .chain([process_instruction])
+            .collect::<Vec<_>>();
 
         // "processed" level commitment does not guarantee finality.
         // roughly 5% of blocks end up on a dropped fork.
@@ -365,76 +510,6 @@ impl Mailbox for SealevelMailbox {
         // is retry logic in the agents.
         let commitment = CommitmentConfig::processed();
 
-        let (process_authority_key, _process_authority_bump) = Pubkey::try_find_program_address(
-            mailbox_process_authority_pda_seeds!(&recipient),
-            &self.program_id,
-        )
-        .ok_or_else(|| {
-            ChainCommunicationError::from_other_str(
-                "Could not find program address for process authority",
-            )
-        })?;
-        let (processed_message_account_key, _processed_message_account_bump) =
-            Pubkey::try_find_program_address(
-                mailbox_processed_message_pda_seeds!(message.id()),
-                &self.program_id,
-            )
-            .ok_or_else(|| {
-                ChainCommunicationError::from_other_str(
-                    "Could not find program address for processed message account",
-                )
-            })?;
-
-        // Get the account metas required for the recipient.InterchainSecurityModule instruction.
-        let ism_getter_account_metas = self.get_ism_getter_account_metas(recipient).await?;
-
-        // Get the recipient ISM.
-        let ism = self
-            .get_recipient_ism(recipient, ism_getter_account_metas.clone())
-            .await?;
-
-        let ixn =
-            hyperlane_sealevel_mailbox::instruction::Instruction::InboxProcess(InboxProcess {
-                metadata: metadata.to_vec(),
-                message: encoded_message.clone(),
-            });
-        let ixn_data = ixn
-            .into_instruction_data()
-            .map_err(ChainCommunicationError::from_other)?;
-
-        // Craft the accounts for the transaction.
-        let mut accounts: Vec<AccountMeta> = vec![
-            AccountMeta::new_readonly(payer.pubkey(), true),
-            AccountMeta::new_readonly(Pubkey::from_str(SYSTEM_PROGRAM).unwrap(), false),
-            AccountMeta::new(self.inbox.0, false),
-            AccountMeta::new_readonly(process_authority_key, false),
-            AccountMeta::new(processed_message_account_key, false),
-        ];
-        accounts.extend(ism_getter_account_metas);
-        accounts.extend([
-            AccountMeta::new_readonly(Pubkey::from_str(SPL_NOOP).unwrap(), false),
-            AccountMeta::new_readonly(ism, false),
-        ]);
-
-        // Get the account metas required for the ISM.Verify instruction.
-        let ism_verify_account_metas = self
-            .get_ism_verify_account_metas(ism, metadata.into(), encoded_message)
-            .await?;
-        accounts.extend(ism_verify_account_metas);
-
-        // The recipient.
-        accounts.extend([AccountMeta::new_readonly(recipient, false)]);
-
-        // Get account metas required for the Handle instruction
-        let handle_account_metas = self.get_handle_account_metas(message).await?;
-        accounts.extend(handle_account_metas);
-
-        let inbox_instruction = Instruction {
-            program_id: self.program_id,
-            data: ixn_data,
-            accounts,
-        };
-        instructions.push(inbox_instruction);
         let (recent_blockhash, _) = self
             .rpc()
             .get_latest_blockhash_with_commitment(commitment)
@@ -448,7 +523,11 @@ impl Mailbox for SealevelMailbox {
             recent_blockhash,
         );
 
-        tracing::info!(?txn, "Created sealevel transaction to process message");
+        tracing::info!(
+            ?txn,
+            ?compute_budget,
+            "Created sealevel transaction to process message"
+        );
 
         let signature = self
             .rpc()
@@ -470,22 +549,230 @@ impl Mailbox for SealevelMailbox {
         Ok(TxOutcome {
             transaction_id: txid,
             executed,
-            // TODO use correct data upon integrating IGP support
-            gas_price: U256::zero().try_into()?,
-            gas_used: U256::zero(),
+            gas_price: compute_budget.price_per_unit(txn.signatures.len() as u64),
+            gas_used: compute_budget.units.into(),
         })
     }
 
+    /// Packs as many messages of the batch as fit into a single v0
+    /// transaction, using the configured address lookup table for the
+    /// accounts shared between them. Messages whose instruction fails in
+    /// simulation are left out, and the ones that don't fit in the size or
+    /// compute unit limits are left for a later batch.
+    ///
+    /// Every message whose processed message account doesn't exist once the
+    /// transaction is confirmed, including the ones left out of it, is
+    /// reported as failed.
+    #[instrument(err, ret, skip(self, messages), fields(size=%messages.len()))]
+    async fn process_batch(
+        &self,
+        messages: &[BatchItem<HyperlaneMessage>],
+    ) -> ChainResult<BatchResult> {
+        let payer = self
+            .payer
+            .as_ref()
+            .ok_or_else(|| ChainCommunicationError::SignerUnavailable)?;
+
+        let process_instructions = try_join_all(
+            messages
+                .iter()
+                .map(|item| self.process_instruction(&item.data, &item.submission_data.metadata)),
+        )
+        .await?;
+
+        let lookup_tables = match self.lookup_table {
+            Some(address) => {
+                let table = fetch_lookup_table(self.rpc(), address).await?;
+                let missing = shared_accounts(&process_instructions, &payer.pubkey())
+                    .into_iter()
+                    .filter(|account| !table.addresses.contains(account))
+                    .collect::<Vec<_>>();
+                if !missing.is_empty() {
+                    debug!(
+                        ?missing,
+                        "Address lookup table is missing accounts shared by the batch"
+                    );
+                }
+                vec![table]
+            }
+            None => vec![],
+        };
+
+        // See `process` for why "processed" commitment is used.
+        let commitment = CommitmentConfig::processed();
+        let (recent_blockhash, _) = self
+            .rpc()
+            .get_latest_blockhash_with_commitment(commitment)
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        // The budget only changes the values of its instructions, not their
+        // size, so it can be set once the batch is settled.
+        let mut compute_budget = ComputeBudget {
+            units: MAX_COMPUTE_UNITS,
+            unit_price: 0,
+        };
+        let compile = |compute_budget: &ComputeBudget, included: &[usize]| {
+            let instructions = compute_budget
+                .instructions()
+                .into_iter()
+                .chain(included.iter().map(|&i| process_instructions[i].clone()))
+                .collect::<Vec<_>>();
+            compile_versioned_transaction(payer, &instructions, &lookup_tables, recent_blockhash)
+        };
+        let num_budget_instructions = compute_budget.instructions().len();
+
+        // Each message is budgeted the compute units it was estimated to need
+        let compute_units = messages
+            .iter()
+            .map(|item| {
+                item.submission_data
+                    .gas_limit
+                    .min(MAX_COMPUTE_UNITS.into())
+                    .as_u32()
+            })
+            .collect::<Vec<_>>();
+        let mut included = pack_batch(&compute_units, |included| {
+            compile(&compute_budget, included).is_some()
+        });
+
+        // The transaction is atomic, so drop any message whose instruction
+        // fails, rather than failing the whole batch with it.
+        let units_consumed = loop {
+            if included.is_empty() {
+                return Err(ChainCommunicationError::BatchingFailed);
+            }
+            let Some(txn) = compile(&compute_budget, &included) else {
+                return Err(ChainCommunicationError::BatchingFailed);
+            };
+            let simulation = self
+                .rpc()
+                .simulate_transaction_with_config(
+                    &txn,
+                    RpcSimulateTransactionConfig {
+                        sig_verify: false,
+                        commitment: Some(commitment),
+                        ..Default::default()
+                    },
+                )
+                .await
+                .map_err(ChainCommunicationError::from_other)?
+                .value;
+            match simulation.err {
+                Some(TransactionError::InstructionError(index, err))
+                    if usize::from(index) >= num_budget_instructions
+                        && usize::from(index) < num_budget_instructions + included.len() =>
+                {
+                    let failed = included.remove(usize::from(index) - num_budget_instructions);
+                    warn!(
+                        message_id = ?messages[failed].data.id(),
+                        ?err,
+                        "Message failed in batch simulation, leaving it out of the batch"
+                    );
+                }
+                _ => break simulation.units_consumed,
+            }
+        };
+
+        compute_budget = ComputeBudget {
+            units: compute_units_with_margin(units_consumed),
+            unit_price: compute_unit_price(
+                self.rpc(),
+                &self.priority_fee,
+                &writable_accounts(included.iter().map(|&i| &process_instructions[i])),
+            )
+            .await?,
+        };
+        let Some(txn) = compile(&compute_budget, &included) else {
+            return Err(ChainCommunicationError::BatchingFailed);
+        };
+
+        tracing::info!(
+            ?txn,
+            included = included.len(),
+            ?compute_budget,
+            "Created sealevel transaction to process batch of messages"
+        );
+
+        let signature = self
+            .rpc()
+            .send_and_confirm_transaction(&txn)
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+
+        tracing::info!(?signature, "Sealevel batch transaction sent");
+
+        // Check the processed message accounts to find out which messages
+        // were delivered.
+        let processed_message_keys = messages
+            .iter()
+            .map(|item| {
+                Pubkey::find_program_address(
+                    mailbox_processed_message_pda_seeds!(item.data.id()),
+                    &self.program_id,
+                )
+                .0
+            })
+            .collect::<Vec<_>>();
+        let processed_message_accounts = self
+            .rpc()
+            .get_multiple_accounts_with_commitment(&processed_message_keys, commitment)
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value;
+        let undelivered = processed_message_accounts
+            .iter()
+            .enumerate()
+            .filter(|(_, account)| account.is_none())
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        if !undelivered.is_empty() {
+            let undelivered_ids = undelivered
+                .iter()
+                .map(|&i| messages[i].data.id())
+                .collect::<Vec<_>>();
+            warn!(
+                ?signature,
+                undelivered = ?undelivered_ids,
+                "Not all messages of the batch were delivered"
+            );
+        }
+
+        let outcome = TxOutcome {
+            transaction_id: signature.into(),
+            executed: true,
+            gas_price: compute_budget.price_per_unit(txn.signatures.len() as u64),
+            gas_used: compute_budget.units.into(),
+        };
+        Ok(BatchResult::new(Some(outcome), undelivered))
+    }
+
+    /// The gas limit is the compute units the transaction would request, and
+    /// the gas price the lamports it would pay per compute unit, including the
+    /// signature fee. The transaction can't be simulated without a payer, in
+    /// which case both are zero.
     #[instrument(err, ret, skip(self))]
     async fn process_estimate_costs(
         &self,
-        _message: &HyperlaneMessage,
-        _metadata: &[u8],
+        message: &HyperlaneMessage,
+        metadata: &[u8],
     ) -> ChainResult<TxCostEstimate> {
-        // TODO use correct data upon integrating IGP support
+        let Some(payer) = self.payer.as_ref() else {
+            return Ok(TxCostEstimate {
+                gas_limit: U256::zero(),
+                gas_price: FixedPointNumber::zero(),
+                l2_gas_limit: None,
+            });
+        };
+
+        let process_instruction = self.process_instruction(message, metadata).await?;
+        let compute_budget = self
+            .compute_budget(&payer.pubkey(), &[process_instruction])
+            .await?;
+
         Ok(TxCostEstimate {
-            gas_limit: U256::zero(),
-            gas_price: FixedPointNumber::zero(),
+            gas_limit: compute_budget.units.into(),
+            // The payer is the only signer
+            gas_price: compute_budget.price_per_unit(1),
             l2_gas_limit: None,
         })
     }
@@ -495,20 +782,97 @@ impl Mailbox for SealevelMailbox {
     }
 }
 
+/// The accounts used by more than one of the instructions, which are worth
+/// storing in a lookup table. Signers and invoked programs can't be looked up,
+/// so they're left out.
+fn shared_accounts(instructions: &[Instruction], payer: &Pubkey) -> HashSet<Pubkey> {
+    let program_ids = instructions
+        .iter()
+        .map(|ixn| ixn.program_id)
+        .collect::<HashSet<_>>();
+    let mut uses: HashMap<Pubkey, usize> = HashMap::new();
+    for ixn in instructions {
+        let accounts = ixn
+            .accounts
+            .iter()
+            .filter(|meta| !meta.is_signer)
+            .map(|meta| meta.pubkey)
+            .collect::<HashSet<_>>();
+        for account in accounts {
+            *uses.entry(account).or_default() += 1;
+        }
+    }
+    uses.into_iter()
+        .filter(|(account, count)| *count > 1 && account != payer && !program_ids.contains(account))
+        .map(|(account, _)| account)
+        .collect()
+}
+
+/// Greedily picks the messages to include in a batch, in order, skipping the
+/// ones that would push it over the compute unit limit, or over the size limit
+/// according to `fits`.
+fn pack_batch(compute_units: &[u32], fits: impl Fn(&[usize]) -> bool) -> Vec<usize> {
+    let mut included = Vec::with_capacity(compute_units.len());
+    let mut budgeted_units = 0u32;
+    for (i, units) in compute_units.iter().enumerate() {
+        let total = budgeted_units.saturating_add(*units);
+        if total > MAX_COMPUTE_UNITS {
+            continue;
+        }
+        included.push(i);
+        if fits(&included) {
+            budgeted_units = total;
+        } else {
+            included.pop();
+        }
+    }
+    included
+}
+
+/// Compiles and signs a v0 transaction, returning `None` if the instructions
+/// don't fit in a single transaction.
+fn compile_versioned_transaction(
+    payer: &Keypair,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+) -> Option<VersionedTransaction> {
+    let message = v0::Message::try_compile(
+        &payer.pubkey(),
+        instructions,
+        lookup_tables,
+        recent_blockhash,
+    )
+    .ok()?;
+    let txn = VersionedTransaction::try_new(VersionedMessage::V0(message), &[payer]).ok()?;
+    // The signatures are prefixed by their count, which takes a single byte
+    // for any realistic number of signers.
+    let size = 1 + txn.signatures.len() * SIGNATURE_BYTES + txn.message.serialize().len();
+    (size <= PACKET_DATA_SIZE).then_some(txn)
+}
+
 /// Struct that retrieves event data for a Sealevel Mailbox contract
 #[derive(Debug)]
 pub struct SealevelMailboxIndexer {
     mailbox: SealevelMailbox,
     program_id: Pubkey,
+    advanced_log_meta: bool,
 }
 
 impl SealevelMailboxIndexer {
-    pub fn new(conf: &ConnectionConf, locator: ContractLocator) -> ChainResult<Self> {
+    /// Create a new Sealevel mailbox indexer. `advanced_log_meta` looks up the
+    /// transaction of every indexed event, see `account_creation_log_meta`.
+    pub fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator,
+        advanced_log_meta: bool,
+    ) -> ChainResult<Self> {
         let program_id = Pubkey::from(<[u8; 32]>::from(locator.address));
         let mailbox = SealevelMailbox::new(conf, locator, None)?;
         Ok(Self {
             program_id,
             mailbox,
+            advanced_log_meta,
         })
     }
 
@@ -617,20 +981,144 @@ impl SealevelMailboxIndexer {
         let hyperlane_message =
             HyperlaneMessage::read_from(&mut &dispatched_message_account.encoded_message[..])?;
 
+        let log_meta = account_creation_log_meta(
+            &self.mailbox.provider,
+            &self.mailbox.program_id,
+            &valid_message_storage_pda_pubkey,
+            dispatched_message_account.slot,
+            U256::zero(),
+            self.advanced_log_meta,
+        )
+        .await?;
+
+        Ok((hyperlane_message.into(), log_meta))
+    }
+
+    async fn get_delivered_message_with_sequence(
+        &self,
+        sequence: u32,
+    ) -> ChainResult<(Indexed<H256>, LogMeta)> {
+        let config = processed_message_accounts_config(sequence);
+        let accounts = self
+            .rpc()
+            .get_program_accounts_with_config(&self.mailbox.program_id, config)
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+
+        // Now loop through matching accounts and find the one with a valid account pubkey
+        // that proves it's an actual processed message PDA.
+        let mut valid_processed_message_pda_pubkey = Option::<Pubkey>::None;
+
+        for (pubkey, account) in accounts {
+            if account.data.len() != H256::len_bytes() {
+                continue;
+            }
+            let message_id = H256::from_slice(&account.data);
+            let (expected_pubkey, _bump) = Pubkey::try_find_program_address(
+                mailbox_processed_message_pda_seeds!(message_id),
+                &self.mailbox.program_id,
+            )
+            .ok_or_else(|| {
+                ChainCommunicationError::from_other_str(
+                    "Could not find program address for message_id",
+                )
+            })?;
+            if expected_pubkey == pubkey {
+                valid_processed_message_pda_pubkey = Some(pubkey);
+                break;
+            }
+        }
+
+        let valid_processed_message_pda_pubkey =
+            valid_processed_message_pda_pubkey.ok_or_else(|| {
+                ChainCommunicationError::from_other_str(
+                    "Could not find valid processed message PDA pubkey",
+                )
+            })?;
+
+        // Now that we have the valid processed message PDA pubkey, we can get the full account data.
+        let account = self
+            .rpc()
+            .get_account_with_commitment(
+                &valid_processed_message_pda_pubkey,
+                CommitmentConfig::finalized(),
+            )
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value
+            .ok_or_else(|| {
+                ChainCommunicationError::from_other_str("Could not find account data")
+            })?;
+        let processed_message_account = ProcessedMessageAccount::fetch(&mut account.data.as_ref())
+            .map_err(ChainCommunicationError::from_other)?
+            .into_inner();
+
+        let log_meta = account_creation_log_meta(
+            &self.mailbox.provider,
+            &self.mailbox.program_id,
+            &valid_processed_message_pda_pubkey,
+            processed_message_account.slot,
+            U256::zero(),
+            self.advanced_log_meta,
+        )
+        .await?;
+
         Ok((
-            hyperlane_message.into(),
-            LogMeta {
-                address: self.mailbox.program_id.to_bytes().into(),
-                block_number: dispatched_message_account.slot,
-                // TODO: get these when building out scraper support.
-                // It's inconvenient to get these :|
-                block_hash: H256::zero(),
-                transaction_id: H512::zero(),
-                transaction_index: 0,
-                log_index: U256::zero(),
-            },
+            Indexed::new(processed_message_account.message_id).with_sequence(sequence),
+            log_meta,
         ))
     }
+
+    async fn processed_count(&self) -> ChainResult<u32> {
+        let inbox_account = self
+            .rpc()
+            .get_account_with_commitment(&self.mailbox.inbox.0, CommitmentConfig::finalized())
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value
+            .ok_or_else(|| {
+                ChainCommunicationError::from_other_str("Could not find inbox account data")
+            })?;
+        let inbox = InboxAccount::fetch(&mut inbox_account.data.as_ref())
+            .map_err(ChainCommunicationError::from_other)?
+            .into_inner();
+        inbox
+            .processed_count
+            .try_into()
+            .map_err(ChainCommunicationError::from_other)
+    }
+}
+
+/// Config to fetch the `message_id` of the processed message PDAs with the given sequence.
+/// Only the `message_id` field is requested to keep responses small, as it's all
+/// that's needed to verify the PDA.
+fn processed_message_accounts_config(sequence: u32) -> RpcProgramAccountsConfig {
+    let target_processed_account_bytes = &[
+        &hyperlane_sealevel_mailbox::accounts::PROCESSED_MESSAGE_DISCRIMINATOR[..],
+        &u64::from(sequence).to_le_bytes()[..],
+    ]
+    .concat();
+    let target_processed_account_bytes = base64::encode(target_processed_account_bytes);
+
+    let memcmp = RpcFilterType::Memcmp(Memcmp {
+        // Ignore the first byte, which is the `initialized` bool flag.
+        offset: 1,
+        bytes: MemcmpEncodedBytes::Base64(target_processed_account_bytes),
+        encoding: None,
+    });
+    RpcProgramAccountsConfig {
+        filters: Some(vec![memcmp]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            data_slice: Some(UiDataSliceConfig {
+                offset: 1 + 8 + 8, // the offset to get the `message_id` field
+                length: 32,        // the length of the `message_id` field
+            }),
+            commitment: Some(CommitmentConfig::finalized()),
+            min_context_slot: None,
+        },
+        with_context: Some(false),
+    }
 }
 
 #[async_trait]
@@ -672,9 +1160,16 @@ impl Indexer<HyperlaneMessage> for SealevelMailboxIndexer {
 impl Indexer<H256> for SealevelMailboxIndexer {
     async fn fetch_logs_in_range(
         &self,
-        _range: RangeInclusive<u32>,
+        range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<H256>, LogMeta)>> {
-        todo!()
+        info!(?range, "Fetching SealevelMailboxIndexer delivery logs");
+
+        let delivery_capacity = range.end().saturating_sub(*range.start());
+        let mut deliveries = Vec::with_capacity(delivery_capacity as usize);
+        for sequence in range {
+            deliveries.push(self.get_delivered_message_with_sequence(sequence).await?);
+        }
+        Ok(deliveries)
     }
 
     async fn get_finalized_block_number(&self) -> ChainResult<u32> {
@@ -684,11 +1179,11 @@ impl Indexer<H256> for SealevelMailboxIndexer {
 
 #[async_trait]
 impl SequenceAwareIndexer<H256> for SealevelMailboxIndexer {
+    #[instrument(err, skip(self))]
     async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
-        // TODO: implement when sealevel scraper support is implemented
-        info!("Message delivery indexing not implemented");
         let tip = Indexer::<H256>::get_finalized_block_number(self).await?;
-        Ok((Some(1), tip))
+        let count = self.processed_count().await?;
+        Ok((Some(count), tip))
     }
 }
 
@@ -702,3 +1197,135 @@ impl HyperlaneAbi for SealevelMailboxAbi {
         todo!()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use hyperlane_sealevel_mailbox::accounts::ProcessedMessage;
+
+    use super::*;
+
+    fn memcmp(config: &RpcProgramAccountsConfig) -> &Memcmp {
+        let Some([RpcFilterType::Memcmp(memcmp)]) = config.filters.as_deref() else {
+            panic!("Expected a single memcmp filter");
+        };
+        memcmp
+    }
+
+    #[test]
+    fn test_processed_message_accounts_config_matches_account_layout() {
+        let message_id = H256::repeat_byte(7);
+        let account = ProcessedMessageAccount::from(ProcessedMessage::new(42, message_id, 100));
+        // 1 byte initialized flag, followed by the processed message
+        let mut data = vec![0; 1 + 8 + 8 + 32 + 8];
+        account.store_in_slice(&mut data).unwrap();
+
+        let config = processed_message_accounts_config(42);
+        assert!(memcmp(&config).bytes_match(&data));
+        assert!(!memcmp(&processed_message_accounts_config(43)).bytes_match(&data));
+
+        let data_slice = config.account_config.data_slice.unwrap();
+        assert_eq!(
+            &data[data_slice.offset..data_slice.offset + data_slice.length],
+            message_id.as_bytes()
+        );
+    }
+
+    fn instruction(program_id: Pubkey, accounts: &[Pubkey], payer: &Pubkey) -> Instruction {
+        Instruction {
+            program_id,
+            accounts: std::iter::once(AccountMeta::new(*payer, true))
+                .chain(
+                    accounts
+                        .iter()
+                        .map(|account| AccountMeta::new(*account, false)),
+                )
+                .collect(),
+            data: vec![],
+        }
+    }
+
+    #[test]
+    fn test_shared_accounts() {
+        let payer = Pubkey::new_unique();
+        let program = Pubkey::new_unique();
+        let other_program = Pubkey::new_unique();
+        let shared = Pubkey::new_unique();
+        let instructions = [
+            instruction(
+                program,
+                &[shared, Pubkey::new_unique(), other_program],
+                &payer,
+            ),
+            // Used twice by the same instruction, which doesn't make it shared
+            instruction(other_program, &[shared, payer, program, program], &payer),
+        ];
+
+        assert_eq!(
+            shared_accounts(&instructions, &payer),
+            HashSet::from([shared])
+        );
+        assert!(shared_accounts(&instructions[..1], &payer).is_empty());
+    }
+
+    #[test]
+    fn test_compile_versioned_transaction() {
+        let payer = Keypair::new();
+        let accounts = (0..40).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        let instructions = [instruction(
+            Pubkey::new_unique(),
+            &accounts,
+            &payer.pubkey(),
+        )];
+
+        // 40 addresses of 32 bytes don't fit in a packet
+        assert!(
+            compile_versioned_transaction(&payer, &instructions, &[], Hash::default()).is_none()
+        );
+
+        // Looked up addresses only take a byte each
+        let lookup_table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: accounts,
+        };
+        let txn = compile_versioned_transaction(
+            &payer,
+            &instructions,
+            &[lookup_table.clone()],
+            Hash::default(),
+        )
+        .unwrap();
+        assert_eq!(txn.signatures.len(), 1);
+        let VersionedMessage::V0(message) = &txn.message else {
+            panic!("Expected a v0 message");
+        };
+        assert_eq!(message.address_table_lookups.len(), 1);
+        assert_eq!(
+            message.address_table_lookups[0].account_key,
+            lookup_table.key
+        );
+        assert_eq!(message.address_table_lookups[0].writable_indexes.len(), 40);
+        assert!(txn.verify_with_results().into_iter().all(|valid| valid));
+    }
+
+    #[test]
+    fn test_pack_batch_within_compute_unit_limit() {
+        let fits = |_: &[usize]| true;
+        assert_eq!(
+            pack_batch(&[600_000, 900_000, 700_000, 100_000], fits),
+            vec![0, 2, 3]
+        );
+        assert_eq!(pack_batch(&[MAX_COMPUTE_UNITS, 1], fits), vec![0]);
+        assert_eq!(pack_batch(&[], fits), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_pack_batch_within_size_limit() {
+        // Only two messages fit in the transaction, whichever they are
+        let packed = pack_batch(&[100_000; 4], |included| included.len() <= 2);
+        assert_eq!(packed, vec![0, 1]);
+
+        // A message that doesn't fit doesn't use up the compute units
+        let packed = pack_batch(&[600_000; 3], |included| included != [0, 1]);
+        assert_eq!(packed, vec![0, 2]);
+    }
+}
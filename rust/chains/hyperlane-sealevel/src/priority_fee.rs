@@ -0,0 +1,241 @@
+//! Compute budget and priority fee of the transactions sent by the agents.
+
+use hyperlane_core::{ChainCommunicationError, ChainResult, FixedPointNumber};
+use solana_client::rpc_config::RpcSimulateTransactionConfig;
+use solana_sdk::{
+    commitment_config::CommitmentConfig, compute_budget::ComputeBudgetInstruction,
+    instruction::Instruction, message::Message, pubkey::Pubkey, transaction::Transaction,
+};
+
+use crate::{error::HyperlaneSealevelError, PriorityFeeConf, RpcClientWithDebug};
+
+/// The max amount of compute units for a transaction.
+pub(crate) const MAX_COMPUTE_UNITS: u32 = 1_400_000;
+
+/// Lamports paid per signature, regardless of the priority fee.
+const LAMPORTS_PER_SIGNATURE: u64 = 5000;
+
+/// Extra compute units requested on top of the simulated ones, in percent.
+/// Account state can change between the simulation and the execution.
+const COMPUTE_UNITS_MARGIN_PERCENT: u64 = 10;
+
+const MICRO_LAMPORTS_PER_LAMPORT: u64 = 1_000_000;
+
+/// The compute budget of a transaction.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ComputeBudget {
+    /// Max compute units the transaction may consume
+    pub units: u32,
+    /// Priority fee per compute unit, in micro-lamports
+    pub unit_price: u64,
+}
+
+impl ComputeBudget {
+    /// The instructions setting this budget, to be prepended to the
+    /// transaction.
+    pub fn instructions(&self) -> [Instruction; 2] {
+        [
+            ComputeBudgetInstruction::set_compute_unit_limit(self.units),
+            ComputeBudgetInstruction::set_compute_unit_price(self.unit_price),
+        ]
+    }
+
+    /// The total fee in lamports of a transaction with this budget. The
+    /// priority fee is charged on the requested compute units, whether they
+    /// are consumed or not.
+    pub fn fee(&self, num_signatures: u64) -> u64 {
+        let micro_lamports = u128::from(self.units) * u128::from(self.unit_price);
+        let priority_fee = (micro_lamports + u128::from(MICRO_LAMPORTS_PER_LAMPORT) - 1)
+            / u128::from(MICRO_LAMPORTS_PER_LAMPORT);
+        LAMPORTS_PER_SIGNATURE
+            .saturating_mul(num_signatures)
+            .saturating_add(u64::try_from(priority_fee).unwrap_or(u64::MAX))
+    }
+
+    /// The fee in lamports per requested compute unit, so that multiplying it
+    /// by `units` gives the total fee.
+    pub fn price_per_unit(&self, num_signatures: u64) -> FixedPointNumber {
+        FixedPointNumber::from(self.fee(num_signatures)) / u64::from(self.units.max(1))
+    }
+}
+
+/// Simulates the instructions with the max compute budget and returns the
+/// compute units to request for them, erroring if they would fail.
+pub(crate) async fn estimate_compute_units(
+    rpc: &RpcClientWithDebug,
+    payer: &Pubkey,
+    instructions: &[Instruction],
+) -> ChainResult<u32> {
+    let instructions = std::iter::once(ComputeBudgetInstruction::set_compute_unit_limit(
+        MAX_COMPUTE_UNITS,
+    ))
+    .chain(instructions.iter().cloned())
+    .collect::<Vec<_>>();
+    let txn = Transaction::new_unsigned(Message::new(&instructions, Some(payer)));
+    let simulation = rpc
+        .simulate_transaction_with_config(
+            &txn,
+            RpcSimulateTransactionConfig {
+                sig_verify: false,
+                replace_recent_blockhash: true,
+                commitment: Some(CommitmentConfig::processed()),
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(ChainCommunicationError::from_other)?
+        .value;
+    if let Some(err) = simulation.err {
+        return Err(HyperlaneSealevelError::SimulationFailed {
+            err,
+            logs: simulation.logs.unwrap_or_default(),
+        }
+        .into());
+    }
+    Ok(compute_units_with_margin(simulation.units_consumed))
+}
+
+/// The compute units to request for a transaction that consumed the given
+/// units in simulation.
+pub(crate) fn compute_units_with_margin(units_consumed: Option<u64>) -> u32 {
+    let units = units_consumed
+        .unwrap_or(u64::MAX)
+        .min(u64::from(MAX_COMPUTE_UNITS));
+    let units = units + units * COMPUTE_UNITS_MARGIN_PERCENT / 100;
+    units.min(u64::from(MAX_COMPUTE_UNITS)) as u32
+}
+
+/// The compute unit price to pay for a transaction writing to the given
+/// accounts, based on the prioritization fees recently paid to write to them.
+pub(crate) async fn compute_unit_price(
+    rpc: &RpcClientWithDebug,
+    conf: &PriorityFeeConf,
+    writable_accounts: &[Pubkey],
+) -> ChainResult<u64> {
+    let fees = rpc
+        .get_recent_prioritization_fees(writable_accounts)
+        .await
+        .map_err(ChainCommunicationError::from_other)?
+        .into_iter()
+        .map(|fee| fee.prioritization_fee)
+        .collect::<Vec<_>>();
+    Ok(percentile(fees, conf.percentile).min(conf.max_compute_unit_price))
+}
+
+/// The writable accounts of the instructions, which are the ones fees are
+/// competed for.
+pub(crate) fn writable_accounts<'a>(
+    instructions: impl IntoIterator<Item = &'a Instruction>,
+) -> Vec<Pubkey> {
+    let mut accounts = instructions
+        .into_iter()
+        .flat_map(|ixn| ixn.accounts.iter())
+        .filter(|meta| meta.is_writable)
+        .map(|meta| meta.pubkey)
+        .collect::<Vec<_>>();
+    accounts.sort();
+    accounts.dedup();
+    accounts
+}
+
+fn percentile(mut values: Vec<u64>, percentile: u8) -> u64 {
+    if values.is_empty() {
+        return 0;
+    }
+    values.sort_unstable();
+    let index = (values.len() - 1) * usize::from(percentile.min(100)) / 100;
+    values[index]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_percentile() {
+        assert_eq!(percentile(vec![], 50), 0);
+        assert_eq!(percentile(vec![7], 0), 7);
+        assert_eq!(percentile(vec![7], 100), 7);
+
+        let fees = vec![40, 10, 30, 20, 50];
+        assert_eq!(percentile(fees.clone(), 0), 10);
+        assert_eq!(percentile(fees.clone(), 50), 30);
+        assert_eq!(percentile(fees.clone(), 75), 40);
+        assert_eq!(percentile(fees.clone(), 100), 50);
+        // Out of range percentiles are capped
+        assert_eq!(percentile(fees, u8::MAX), 50);
+    }
+
+    #[test]
+    fn test_fee() {
+        let budget = ComputeBudget {
+            units: 200_000,
+            unit_price: 0,
+        };
+        assert_eq!(budget.fee(1), LAMPORTS_PER_SIGNATURE);
+        assert_eq!(budget.fee(2), 2 * LAMPORTS_PER_SIGNATURE);
+
+        // 200_000 units at 5 micro-lamports is exactly 1 lamport
+        let budget = ComputeBudget {
+            units: 200_000,
+            unit_price: 5,
+        };
+        assert_eq!(budget.fee(1), LAMPORTS_PER_SIGNATURE + 1);
+        // Any fraction of a lamport is rounded up
+        let budget = ComputeBudget {
+            units: 200_001,
+            unit_price: 5,
+        };
+        assert_eq!(budget.fee(1), LAMPORTS_PER_SIGNATURE + 2);
+    }
+
+    #[test]
+    fn test_fee_saturates() {
+        let budget = ComputeBudget {
+            units: u32::MAX,
+            unit_price: u64::MAX,
+        };
+        assert_eq!(budget.fee(1), u64::MAX);
+        let budget = ComputeBudget {
+            units: 0,
+            unit_price: 0,
+        };
+        assert_eq!(budget.fee(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_price_per_unit() {
+        let budget = ComputeBudget {
+            units: 200_000,
+            unit_price: 5,
+        };
+        assert_eq!(
+            budget.price_per_unit(1) * FixedPointNumber::from(budget.units),
+            FixedPointNumber::from(budget.fee(1))
+        );
+
+        // No units are charged as one rather than dividing by zero
+        let budget = ComputeBudget {
+            units: 0,
+            unit_price: 5,
+        };
+        assert_eq!(
+            budget.price_per_unit(1),
+            FixedPointNumber::from(LAMPORTS_PER_SIGNATURE)
+        );
+    }
+
+    #[test]
+    fn test_compute_units_with_margin() {
+        assert_eq!(compute_units_with_margin(Some(0)), 0);
+        assert_eq!(compute_units_with_margin(Some(100_000)), 110_000);
+        // Capped to the max compute units of a transaction
+        assert_eq!(
+            compute_units_with_margin(Some(1_300_000)),
+            MAX_COMPUTE_UNITS
+        );
+        assert_eq!(compute_units_with_margin(Some(u64::MAX)), MAX_COMPUTE_UNITS);
+        // Without a simulated value, the max is requested
+        assert_eq!(compute_units_with_margin(None), MAX_COMPUTE_UNITS);
+    }
+}
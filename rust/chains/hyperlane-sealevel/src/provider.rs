@@ -3,10 +3,14 @@ use std::{str::FromStr, sync::Arc};
 use async_trait::async_trait;
 
 use hyperlane_core::{
-    BlockInfo, ChainInfo, ChainResult, HyperlaneChain, HyperlaneDomain, HyperlaneProvider, TxnInfo,
-    H256, U256,
+    BlockInfo, ChainCommunicationError, ChainInfo, ChainResult, HyperlaneChain, HyperlaneDomain,
+    HyperlaneProvider, HyperlaneProviderError, TxnInfo, TxnReceiptInfo, H256, H512, U256,
 };
-use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use solana_client::rpc_config::{RpcBlockConfig, RpcTransactionConfig};
+use solana_sdk::{
+    commitment_config::CommitmentConfig, hash::Hash, pubkey::Pubkey, signature::Signature,
+};
+use solana_transaction_status::{TransactionDetails, UiConfirmedBlock, UiTransactionEncoding};
 
 use crate::{client::RpcClientWithDebug, error::HyperlaneSealevelError, ConnectionConf};
 
@@ -44,6 +48,26 @@ impl SealevelProvider {
             .map_err(Into::<HyperlaneSealevelError>::into)?;
         Ok(balance.into())
     }
+
+    /// Get a finalized block, with the signatures of its transactions if
+    /// `transaction_details` asks for them
+    pub(crate) async fn get_block(
+        &self,
+        slot: u64,
+        transaction_details: TransactionDetails,
+    ) -> ChainResult<UiConfirmedBlock> {
+        let config = RpcBlockConfig {
+            encoding: None,
+            transaction_details: Some(transaction_details),
+            rewards: Some(false),
+            commitment: Some(CommitmentConfig::finalized()),
+            max_supported_transaction_version: Some(0),
+        };
+        self.rpc_client
+            .get_block_with_config(slot, config)
+            .await
+            .map_err(|err| HyperlaneSealevelError::from(err).into())
+    }
 }
 
 impl HyperlaneChain for SealevelProvider {
@@ -61,12 +85,72 @@ impl HyperlaneChain for SealevelProvider {
 
 #[async_trait]
 impl HyperlaneProvider for SealevelProvider {
-    async fn get_block_by_hash(&self, _hash: &H256) -> ChainResult<BlockInfo> {
-        todo!() // FIXME
+    /// Blocks are looked up by slot, as Solana RPCs can't look them up by hash
+    async fn get_block_by_height(&self, height: u64) -> ChainResult<BlockInfo> {
+        let block = self.get_block(height, TransactionDetails::None).await?;
+        let hash = Hash::from_str(&block.blockhash).map_err(ChainCommunicationError::from_other)?;
+        Ok(BlockInfo {
+            hash: hash.into(),
+            timestamp: block
+                .block_time
+                .ok_or(HyperlaneProviderError::CouldNotFindBlockByHeight(height))?
+                .try_into()
+                .map_err(ChainCommunicationError::from_other)?,
+            number: height,
+        })
     }
 
-    async fn get_txn_by_hash(&self, _hash: &H256) -> ChainResult<TxnInfo> {
-        todo!() // FIXME
+    async fn get_txn_by_hash(&self, hash: &H512) -> ChainResult<TxnInfo> {
+        let signature = Signature::new(hash.as_bytes());
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(CommitmentConfig::finalized()),
+            max_supported_transaction_version: Some(0),
+        };
+        let txn = self
+            .rpc_client
+            .get_transaction_with_config(&signature, config)
+            .await
+            .map_err(Into::<HyperlaneSealevelError>::into)?
+            .transaction;
+        let meta = txn
+            .meta
+            .ok_or(HyperlaneProviderError::CouldNotFindTransactionByHash(*hash))?;
+        let transaction = txn
+            .transaction
+            .decode()
+            .ok_or(HyperlaneProviderError::CouldNotFindTransactionByHash(*hash))?;
+
+        // The fee payer is always the first account of the transaction
+        let sender = transaction
+            .message
+            .static_account_keys()
+            .first()
+            .map(|payer| H256::from(payer.to_bytes()))
+            .unwrap_or_default();
+        let compute_units: U256 = Option::<u64>::from(meta.compute_units_consumed)
+            .unwrap_or_default()
+            .into();
+
+        // Fees aren't priced per compute unit, so gas is reported as the fee
+        // paid in lamports at a price of 1
+        Ok(TxnInfo {
+            hash: *hash,
+            gas_limit: compute_units,
+            max_priority_fee_per_gas: None,
+            max_fee_per_gas: None,
+            gas_price: Some(U256::one()),
+            // Solana transactions are deduplicated by their recent blockhash
+            // rather than a nonce
+            nonce: 0,
+            sender,
+            recipient: None,
+            receipt: Some(TxnReceiptInfo {
+                gas_used: meta.fee.into(),
+                cumulative_gas_used: meta.fee.into(),
+                effective_gas_price: Some(U256::one()),
+            }),
+        })
     }
 
     async fn is_contract(&self, _address: &H256) -> ChainResult<bool> {
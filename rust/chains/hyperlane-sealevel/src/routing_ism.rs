@@ -0,0 +1,84 @@
+use async_trait::async_trait;
+
+use hyperlane_core::{
+    ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, RoutingIsm, H256,
+};
+use hyperlane_sealevel_routing_ism::{accounts::RoutingIsmAccount, instruction::storage_pda_key};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+
+use crate::{ConnectionConf, RpcClientWithDebug, SealevelProvider};
+
+/// A reference to a RoutingIsm contract on some Sealevel chain
+#[derive(Debug)]
+pub struct SealevelRoutingIsm {
+    program_id: Pubkey,
+    domain: HyperlaneDomain,
+    provider: SealevelProvider,
+}
+
+impl SealevelRoutingIsm {
+    /// Create a new Sealevel RoutingIsm.
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator) -> Self {
+        let provider = SealevelProvider::new(locator.domain.clone(), conf);
+        let program_id = Pubkey::from(<[u8; 32]>::from(locator.address));
+
+        Self {
+            program_id,
+            domain: locator.domain.clone(),
+            provider,
+        }
+    }
+
+    fn rpc(&self) -> &RpcClientWithDebug {
+        self.provider.rpc()
+    }
+}
+
+impl HyperlaneContract for SealevelRoutingIsm {
+    fn address(&self) -> H256 {
+        self.program_id.to_bytes().into()
+    }
+}
+
+impl HyperlaneChain for SealevelRoutingIsm {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        self.provider.provider()
+    }
+}
+
+#[async_trait]
+impl RoutingIsm for SealevelRoutingIsm {
+    /// Returns the ISM that the message's origin domain is routed to
+    async fn route(&self, message: &HyperlaneMessage) -> ChainResult<H256> {
+        let storage_account = self
+            .rpc()
+            .get_account_with_commitment(
+                &storage_pda_key(&self.program_id),
+                CommitmentConfig::finalized(),
+            )
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value
+            .ok_or_else(|| {
+                ChainCommunicationError::from_other_str(
+                    "Could not find routing ISM storage account",
+                )
+            })?;
+        let storage = RoutingIsmAccount::fetch(&mut storage_account.data.as_ref())
+            .map_err(ChainCommunicationError::from_other)?
+            .into_inner();
+
+        storage
+            .routes
+            .get(&message.origin)
+            .map(|ism| ism.to_bytes().into())
+            .ok_or_else(|| {
+                ChainCommunicationError::from_other_str("No route for the message's origin domain")
+            })
+    }
+}
@@ -1,4 +1,4 @@
-use hyperlane_core::{config::OperationBatchConfig, ChainCommunicationError};
+use hyperlane_core::{config::OperationBatchConfig, ChainCommunicationError, H256};
 use url::Url;
 
 /// Sealevel connection configuration
@@ -8,6 +8,30 @@ pub struct ConnectionConf {
     pub url: Url,
     /// Operation batching configuration
     pub operation_batch: OperationBatchConfig,
+    /// Address lookup table used when batching process transactions. It is
+    /// created out of band, and batches are packed without one if not set.
+    pub lookup_table: Option<H256>,
+    /// Priority fee configuration
+    pub priority_fee: PriorityFeeConf,
+}
+
+/// How the priority fee of transactions is set
+#[derive(Debug, Clone)]
+pub struct PriorityFeeConf {
+    /// Percentile of the recent prioritization fees paid to write to the same
+    /// accounts that a transaction pays per compute unit.
+    pub percentile: u8,
+    /// The compute unit price, in micro-lamports, is never set above this value.
+    pub max_compute_unit_price: u64,
+}
+
+impl Default for PriorityFeeConf {
+    fn default() -> Self {
+        Self {
+            percentile: 75,
+            max_compute_unit_price: 100_000,
+        }
+    }
 }
 
 /// An error type when parsing a connection configuration.
@@ -1,19 +1,25 @@
+use std::str::FromStr;
+
 use base64::Engine;
 use borsh::{BorshDeserialize, BorshSerialize};
-use hyperlane_core::{ChainCommunicationError, ChainResult};
+use hyperlane_core::{ChainCommunicationError, ChainResult, LogMeta, H256, H512, U256};
 
 use serializable_account_meta::{SerializableAccountMeta, SimulationReturnData};
-use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient, rpc_client::GetConfirmedSignaturesForAddress2Config,
+};
 use solana_sdk::{
     commitment_config::CommitmentConfig,
+    hash::Hash,
     instruction::{AccountMeta, Instruction},
     message::Message,
-    signature::{Keypair, Signer},
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
     transaction::Transaction,
 };
-use solana_transaction_status::UiReturnDataEncoding;
+use solana_transaction_status::{TransactionDetails, UiReturnDataEncoding};
 
-use crate::client::RpcClientWithDebug;
+use crate::{client::RpcClientWithDebug, SealevelProvider};
 
 /// Simulates an instruction, and attempts to deserialize it into a T.
 /// If no return data at all was returned, returns Ok(None).
@@ -91,3 +97,81 @@ pub async fn get_finalized_block_number(rpc_client: &RpcClientWithDebug) -> Chai
         .expect("sealevel block height exceeds u32::MAX");
     Ok(height)
 }
+
+/// Builds the `LogMeta` of an event recorded in `account`, created in `slot`.
+/// Sealevel programs record events in PDAs rather than logs, so the
+/// transaction is found through the signatures that touched the account.
+///
+/// That takes two RPC round trips per event and only the scraper needs the
+/// transaction, so unless `advanced_log_meta` is set the block hash,
+/// transaction id and transaction index are left zeroed.
+pub async fn account_creation_log_meta(
+    provider: &SealevelProvider,
+    program_id: &Pubkey,
+    account: &Pubkey,
+    slot: u64,
+    log_index: U256,
+    advanced_log_meta: bool,
+) -> ChainResult<LogMeta> {
+    let mut log_meta = LogMeta {
+        address: program_id.to_bytes().into(),
+        block_number: slot,
+        block_hash: H256::zero(),
+        transaction_id: H512::zero(),
+        transaction_index: 0,
+        log_index,
+    };
+    if advanced_log_meta {
+        let (block_hash, signature, transaction_index) =
+            account_creation_transaction(provider, account, slot).await?;
+        log_meta.block_hash = block_hash.into();
+        log_meta.transaction_id = signature.into();
+        log_meta.transaction_index = transaction_index as u64;
+    }
+    Ok(log_meta)
+}
+
+/// Finds the block hash, signature and index in the block of the transaction
+/// that created `account` in `slot`
+async fn account_creation_transaction(
+    provider: &SealevelProvider,
+    account: &Pubkey,
+    slot: u64,
+) -> ChainResult<(Hash, Signature, usize)> {
+    let config = GetConfirmedSignaturesForAddress2Config {
+        commitment: Some(CommitmentConfig::finalized()),
+        ..Default::default()
+    };
+    let signatures = provider
+        .rpc()
+        .get_signatures_for_address_with_config(account, config)
+        .await
+        .map_err(ChainCommunicationError::from_other)?;
+    // Signatures are returned newest first, so the account was created by the
+    // earliest successful transaction in its slot
+    let signature = signatures
+        .into_iter()
+        .rev()
+        .find(|status| status.slot == slot && status.err.is_none())
+        .ok_or_else(|| {
+            ChainCommunicationError::from_other_str("Could not find transaction creating account")
+        })?
+        .signature;
+
+    let block = provider
+        .get_block(slot, TransactionDetails::Signatures)
+        .await?;
+    let transaction_index = block
+        .signatures
+        .unwrap_or_default()
+        .iter()
+        .position(|block_signature| *block_signature == signature)
+        .ok_or_else(|| {
+            ChainCommunicationError::from_other_str("Could not find transaction in its block")
+        })?;
+    let block_hash =
+        Hash::from_str(&block.blockhash).map_err(ChainCommunicationError::from_other)?;
+    let signature = Signature::from_str(&signature).map_err(ChainCommunicationError::from_other)?;
+
+    Ok((block_hash, signature, transaction_index))
+}
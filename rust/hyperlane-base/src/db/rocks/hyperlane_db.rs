@@ -4,10 +4,11 @@ use paste::paste;
 use tracing::{debug, instrument, trace};
 
 use hyperlane_core::{
-    GasPaymentKey, HyperlaneDomain, HyperlaneLogStore, HyperlaneMessage,
-    HyperlaneSequenceAwareIndexerStoreReader, HyperlaneWatermarkedLogStore, Indexed,
-    InterchainGasExpenditure, InterchainGasPayment, InterchainGasPaymentMeta, LogMeta,
-    MerkleTreeInsertion, PendingOperationStatus, H256,
+    CheckpointWithMessageId, DeliveryLedgerEntry, GasPaymentKey, HyperlaneDomain,
+    HyperlaneLogStore, HyperlaneMessage, HyperlaneSequenceAwareIndexerStoreReader,
+    HyperlaneWatermarkedLogStore, Indexed, InterchainGasExpenditure, InterchainGasPayment,
+    InterchainGasPaymentMeta, LogMeta, MerkleTreeInsertion, PendingOperationQueueState,
+    PendingOperationStatus, H256,
 };
 
 use super::{
@@ -30,11 +31,16 @@ const GAS_EXPENDITURE_FOR_MESSAGE_ID: &str = "gas_expenditure_for_message_id_v2_
 const STATUS_BY_MESSAGE_ID: &str = "status_by_message_id_";
 const PENDING_MESSAGE_RETRY_COUNT_FOR_MESSAGE_ID: &str =
     "pending_message_retry_count_for_message_id_";
+const PENDING_OPERATION_QUEUE_STATE_FOR_MESSAGE_ID: &str =
+    "pending_operation_queue_state_for_message_id_";
 const MERKLE_TREE_INSERTION: &str = "merkle_tree_insertion_";
 const MERKLE_LEAF_INDEX_BY_MESSAGE_ID: &str = "merkle_leaf_index_by_message_id_";
 const MERKLE_TREE_INSERTION_BLOCK_NUMBER_BY_LEAF_INDEX: &str =
     "merkle_tree_insertion_block_number_by_leaf_index_";
 const LATEST_INDEXED_GAS_PAYMENT_BLOCK: &str = "latest_indexed_gas_payment_block";
+const SIGNED_CHECKPOINT_BY_INDEX: &str = "signed_checkpoint_by_index_";
+const HIGHEST_SIGNED_CHECKPOINT_INDEX: &str = "highest_signed_checkpoint_index_";
+const DELIVERY_LEDGER_ENTRY_BY_MESSAGE_ID: &str = "delivery_ledger_entry_by_message_id_";
 
 /// Rocks DB result type
 pub type DbResult<T> = std::result::Result<T, DbError>;
@@ -129,6 +135,32 @@ impl HyperlaneRocksDB {
         self.retrieve_highest_seen_message_nonce_number(&Default::default())
     }
 
+    /// Record that the validator signed a checkpoint at its index
+    pub fn store_signed_checkpoint(&self, checkpoint: &CheckpointWithMessageId) -> DbResult<()> {
+        self.store_signed_checkpoint_by_index(&checkpoint.index, checkpoint)?;
+        let current_max = self.retrieve_highest_signed_checkpoint_index()?;
+        if current_max.map_or(true, |max| checkpoint.index > max) {
+            self.store_highest_signed_checkpoint_index_number(
+                &Default::default(),
+                &checkpoint.index,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Retrieve the checkpoint the validator signed at an index, if any
+    pub fn retrieve_signed_checkpoint(
+        &self,
+        index: u32,
+    ) -> DbResult<Option<CheckpointWithMessageId>> {
+        self.retrieve_signed_checkpoint_by_index(&index)
+    }
+
+    /// Retrieve the index of the highest checkpoint the validator signed
+    pub fn retrieve_highest_signed_checkpoint_index(&self) -> DbResult<Option<u32>> {
+        self.retrieve_highest_signed_checkpoint_index_number(&Default::default())
+    }
+
     /// If the provided gas payment, identified by its metadata, has not been
     /// processed, processes the gas payment and records it as processed.
     /// Returns whether the gas payment was processed for the first time.
@@ -278,6 +310,13 @@ impl HyperlaneRocksDB {
             .unwrap_or_default()
             .complete(message_id))
     }
+
+    /// Iterate over all delivery ledger entries, without looking up their messages
+    pub fn retrieve_delivery_ledger_entries(
+        &self,
+    ) -> impl Iterator<Item = DbResult<DeliveryLedgerEntry>> + '_ {
+        self.iterate_decodable(DELIVERY_LEDGER_ENTRY_BY_MESSAGE_ID)
+    }
 }
 
 #[async_trait]
@@ -519,6 +558,13 @@ make_store_and_retrieve!(
     H256,
     u32
 );
+make_store_and_retrieve!(
+    pub,
+    pending_operation_queue_state_by_message_id,
+    PENDING_OPERATION_QUEUE_STATE_FOR_MESSAGE_ID,
+    H256,
+    PendingOperationQueueState
+);
 make_store_and_retrieve!(
     pub,
     merkle_tree_insertion_by_leaf_index,
@@ -543,3 +589,12 @@ make_store_and_retrieve!(
 // There's no unit struct Encode/Decode impl, so just use `bool`, have visibility be private (by omitting the first argument), and wrap
 // with a function that always uses the `Default::default()` key
 make_store_and_retrieve!(, highest_seen_message_nonce_number, HIGHEST_SEEN_MESSAGE_NONCE, bool, u32);
+make_store_and_retrieve!(pub(self), signed_checkpoint_by_index, SIGNED_CHECKPOINT_BY_INDEX, u32, CheckpointWithMessageId);
+make_store_and_retrieve!(, highest_signed_checkpoint_index_number, HIGHEST_SIGNED_CHECKPOINT_INDEX, bool, u32);
+make_store_and_retrieve!(
+    pub,
+    delivery_ledger_entry_by_message_id,
+    DELIVERY_LEDGER_ENTRY_BY_MESSAGE_ID,
+    H256,
+    DeliveryLedgerEntry
+);
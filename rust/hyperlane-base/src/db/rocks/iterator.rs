@@ -2,30 +2,44 @@ use std::marker::PhantomData;
 
 use rocksdb::DBIterator;
 
-use hyperlane_core::{Decode, Encode};
+use hyperlane_core::Decode;
+
+use crate::db::DbError;
 
 /// An iterator over a prefix that deserializes values
 pub struct PrefixIterator<'a, V> {
     iter: DBIterator<'a>,
-    prefix: &'a [u8],
+    prefix: Vec<u8>,
     _phantom: PhantomData<*const V>,
 }
 
+impl<'a, V> PrefixIterator<'a, V> {
+    /// Iterate over the values of the keys starting with `prefix`. `iter` must be
+    /// positioned at the first of those keys.
+    pub(crate) fn new(iter: DBIterator<'a>, prefix: Vec<u8>) -> Self {
+        Self {
+            iter,
+            prefix,
+            _phantom: PhantomData,
+        }
+    }
+}
+
 impl<'a, V> Iterator for PrefixIterator<'a, V>
 where
-    V: Encode + Decode,
+    V: Decode,
 {
-    type Item = V;
+    type Item = Result<V, DbError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let prefix = self.prefix;
-        self.iter.find_map(|r| {
-            let (k, v) = r.expect("Database error when iterating prefixed keys");
-            if k.strip_prefix(prefix).is_some() {
-                Some(V::read_from(&mut &v[..]).expect("!corrupt"))
-            } else {
-                None
-            }
-        })
+        let (k, v) = match self.iter.next()? {
+            Ok(kv) => kv,
+            Err(err) => return Some(Err(err.into())),
+        };
+        // Keys are sorted, so the keys with the prefix are contiguous
+        if !k.starts_with(&self.prefix) {
+            return None;
+        }
+        Some(V::read_from(&mut &v[..]).map_err(Into::into))
     }
 }
@@ -1,10 +1,12 @@
 use std::path::PathBuf;
 use std::{io, path::Path, sync::Arc};
 
-use hyperlane_core::{ChainCommunicationError, HyperlaneProtocolError};
+use hyperlane_core::{ChainCommunicationError, Decode, HyperlaneProtocolError};
 use rocksdb::{Options, DB as Rocks};
 use tracing::info;
 
+use self::iterator::PrefixIterator;
+
 pub use hyperlane_db::*;
 pub use typed_db::*;
 
@@ -109,4 +111,9 @@ impl DB {
     pub fn retrieve(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
         Ok(self.0.get(key)?)
     }
+
+    /// Iterate over the values of all keys starting with `prefix`
+    pub fn prefix_iterator<V: Decode>(&self, prefix: &[u8]) -> PrefixIterator<'_, V> {
+        PrefixIterator::new(self.0.prefix_iterator(prefix), prefix.to_vec())
+    }
 }
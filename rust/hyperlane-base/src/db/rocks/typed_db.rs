@@ -1,6 +1,6 @@
 use hyperlane_core::{Decode, Encode, HyperlaneDomain};
 
-use crate::db::{DbError, DB};
+use crate::db::{iterator::PrefixIterator, DbError, DB};
 
 type Result<T> = std::result::Result<T, DbError>;
 
@@ -85,4 +85,10 @@ impl TypedDB {
     ) -> Result<Option<V>> {
         self.retrieve_decodable(prefix, key.to_vec())
     }
+
+    /// Iterate over the decodable values of all keys with the given prefix
+    pub fn iterate_decodable<V: Decode>(&self, prefix: impl AsRef<[u8]>) -> PrefixIterator<'_, V> {
+        self.db
+            .prefix_iterator(&self.prefixed_key(prefix.as_ref(), &[]))
+    }
 }
@@ -1,2 +1,4 @@
+mod auth;
 mod base_server;
+pub use auth::require_bearer_token;
 pub use base_server::Server;
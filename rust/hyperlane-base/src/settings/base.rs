@@ -151,13 +151,15 @@ impl Settings {
     build_contract_fns!(build_validator_announce, build_validator_announces -> dyn ValidatorAnnounce);
     build_contract_fns!(build_provider, build_providers -> dyn HyperlaneProvider);
 
-    /// Build a contract sync for type `T` using log store `D`
+    /// Build a contract sync for type `T` using log store `D`. See
+    /// `TryFromWithMetrics` for `advanced_log_meta`.
     pub async fn sequenced_contract_sync<T, D>(
         &self,
         domain: &HyperlaneDomain,
         metrics: &CoreMetrics,
         sync_metrics: &ContractSyncMetrics,
         db: Arc<D>,
+        advanced_log_meta: bool,
     ) -> eyre::Result<Arc<SequencedDataContractSync<T>>>
     where
         T: Indexable + Debug,
@@ -166,7 +168,8 @@ impl Settings {
     {
         let setup = self.chain_setup(domain)?;
         // Currently, all indexers are of the `SequenceIndexer` type
-        let indexer = SequenceIndexer::<T>::try_from_with_metrics(setup, metrics).await?;
+        let indexer =
+            SequenceIndexer::<T>::try_from_with_metrics(setup, metrics, advanced_log_meta).await?;
         Ok(Arc::new(ContractSync::new(
             domain.clone(),
             db.clone() as SequenceAwareLogStore<_>,
@@ -182,6 +185,7 @@ impl Settings {
         metrics: &CoreMetrics,
         sync_metrics: &ContractSyncMetrics,
         db: Arc<D>,
+        advanced_log_meta: bool,
     ) -> eyre::Result<Arc<WatermarkContractSync<T>>>
     where
         T: Indexable + Debug,
@@ -190,7 +194,8 @@ impl Settings {
     {
         let setup = self.chain_setup(domain)?;
         // Currently, all indexers are of the `SequenceIndexer` type
-        let indexer = SequenceIndexer::<T>::try_from_with_metrics(setup, metrics).await?;
+        let indexer =
+            SequenceIndexer::<T>::try_from_with_metrics(setup, metrics, advanced_log_meta).await?;
         Ok(Arc::new(ContractSync::new(
             domain.clone(),
             db.clone() as WatermarkLogStore<_>,
@@ -199,6 +204,37 @@ impl Settings {
         )))
     }
 
+    /// Build a contract sync for type `T` using log store `D`, with the
+    /// cursor type `T` is indexed with on the domain's protocol
+    pub async fn contract_sync<T, D>(
+        &self,
+        domain: &HyperlaneDomain,
+        metrics: &CoreMetrics,
+        sync_metrics: &ContractSyncMetrics,
+        db: Arc<D>,
+        advanced_log_meta: bool,
+    ) -> Result<Arc<dyn ContractSyncer<T>>>
+    where
+        T: Indexable + Debug + Send + Sync + Clone + Eq + Hash + 'static,
+        SequenceIndexer<T>: TryFromWithMetrics<ChainConf>,
+        D: HyperlaneLogStore<T>
+            + HyperlaneSequenceAwareIndexerStoreReader<T>
+            + HyperlaneWatermarkedLogStore<T>
+            + 'static,
+    {
+        let sync = match T::indexing_cursor(domain.domain_protocol()) {
+            CursorType::SequenceAware => self
+                .sequenced_contract_sync(domain, metrics, sync_metrics, db, advanced_log_meta)
+                .await
+                .map(|r| r as Arc<dyn ContractSyncer<T>>)?,
+            CursorType::RateLimited => self
+                .watermark_contract_sync(domain, metrics, sync_metrics, db, advanced_log_meta)
+                .await
+                .map(|r| r as Arc<dyn ContractSyncer<T>>)?,
+        };
+        Ok(sync)
+    }
+
     /// Build multiple contract syncs.
     /// All contracts have to implement both sequenced and
     /// watermark trait bounds
@@ -208,6 +244,7 @@ impl Settings {
         metrics: &CoreMetrics,
         sync_metrics: &ContractSyncMetrics,
         dbs: HashMap<HyperlaneDomain, Arc<D>>,
+        advanced_log_meta: bool,
     ) -> Result<HashMap<HyperlaneDomain, Arc<dyn ContractSyncer<T>>>>
     where
         T: Indexable + Debug + Send + Sync + Clone + Eq + Hash + 'static,
@@ -220,26 +257,15 @@ impl Settings {
         // TODO: parallelize these calls again
         let mut syncs = vec![];
         for domain in domains {
-            let sync = match T::indexing_cursor(domain.domain_protocol()) {
-                CursorType::SequenceAware => self
-                    .sequenced_contract_sync(
-                        domain,
-                        metrics,
-                        sync_metrics,
-                        dbs.get(domain).unwrap().clone(),
-                    )
-                    .await
-                    .map(|r| r as Arc<dyn ContractSyncer<T>>)?,
-                CursorType::RateLimited => self
-                    .watermark_contract_sync(
-                        domain,
-                        metrics,
-                        sync_metrics,
-                        dbs.get(domain).unwrap().clone(),
-                    )
-                    .await
-                    .map(|r| r as Arc<dyn ContractSyncer<T>>)?,
-            };
+            let sync = self
+                .contract_sync(
+                    domain,
+                    metrics,
+                    sync_metrics,
+                    dbs.get(domain).unwrap().clone(),
+                    advanced_log_meta,
+                )
+                .await?;
             syncs.push(sync);
         }
 
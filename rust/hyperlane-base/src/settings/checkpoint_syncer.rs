@@ -1,16 +1,17 @@
 use crate::{
-    CheckpointSyncer, GcsStorageClientBuilder, LocalStorage, S3Storage, GCS_SERVICE_ACCOUNT_KEY,
-    GCS_USER_SECRET,
+    CheckpointSyncer, GcsStorageClientBuilder, HttpStorage, LocalStorage, S3Storage,
+    GCS_SERVICE_ACCOUNT_KEY, GCS_USER_SECRET, HTTP_STORAGE_AUTH_TOKEN,
 };
-use core::str::FromStr;
+use core::{fmt, str::FromStr};
 use eyre::{eyre, Context, Report, Result};
 use prometheus::IntGauge;
 use rusoto_core::Region;
 use std::{env, path::PathBuf};
+use url::Url;
 use ya_gcp::{AuthFlow, ServiceAccountAuth};
 
 /// Checkpoint Syncer types
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub enum CheckpointSyncerConf {
     /// A local checkpoint syncer
     LocalStorage {
@@ -38,6 +39,51 @@ pub enum CheckpointSyncerConf {
         /// `gcloud auth application-default login`
         user_secrets: Option<String>,
     },
+    /// A checkpoint syncer on any HTTP server that supports `GET` and `PUT`, e.g. WebDAV
+    Http {
+        /// Base url of the store
+        url: Url,
+        /// Bearer token used to authenticate writes - reads are always unauthenticated
+        auth_token: Option<String>,
+    },
+}
+
+impl fmt::Debug for CheckpointSyncerConf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LocalStorage { path } => {
+                f.debug_struct("LocalStorage").field("path", path).finish()
+            }
+            Self::S3 {
+                bucket,
+                folder,
+                region,
+            } => f
+                .debug_struct("S3")
+                .field("bucket", bucket)
+                .field("folder", folder)
+                .field("region", region)
+                .finish(),
+            Self::Gcs {
+                bucket,
+                folder,
+                service_account_key,
+                user_secrets,
+            } => f
+                .debug_struct("Gcs")
+                .field("bucket", bucket)
+                .field("folder", folder)
+                .field("service_account_key", service_account_key)
+                .field("user_secrets", user_secrets)
+                .finish(),
+            // Never print the auth token
+            Self::Http { url, auth_token } => f
+                .debug_struct("Http")
+                .field("url", url)
+                .field("authenticated", &auth_token.is_some())
+                .finish(),
+        }
+    }
 }
 
 impl FromStr for CheckpointSyncerConf {
@@ -90,6 +136,14 @@ impl FromStr for CheckpointSyncerConf {
                     })
                 }
             }
+            // the auth token is only needed by validators writing to the store, and is read
+            // from env variables to keep it out of the announced storage location
+            "http" | "https" => Ok(Self::Http {
+                url: s
+                    .parse()
+                    .context("Invalid url when parsing storage location")?,
+                auth_token: env::var(HTTP_STORAGE_AUTH_TOKEN).ok(),
+            }),
             _ => Err(eyre!("Unknown storage location prefix `{prefix}`")),
         }
     }
@@ -136,6 +190,38 @@ impl CheckpointSyncerConf {
                         .await?,
                 )
             }
+            CheckpointSyncerConf::Http { url, auth_token } => Box::new(HttpStorage::new(
+                url.clone(),
+                auth_token.clone(),
+                latest_index_gauge,
+            )?),
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_http_storage_location() {
+        let conf: CheckpointSyncerConf = "https://example.com/validators/0x1234".parse().unwrap();
+        let CheckpointSyncerConf::Http { url, .. } = conf else {
+            panic!("Expected an http checkpoint syncer, got {conf:?}");
+        };
+        assert_eq!(url.as_str(), "https://example.com/validators/0x1234");
+
+        assert!("http://".parse::<CheckpointSyncerConf>().is_err());
+    }
+
+    #[test]
+    fn test_http_debug_redacts_auth_token() {
+        let conf = CheckpointSyncerConf::Http {
+            url: "https://example.com/validators/0x1234".parse().unwrap(),
+            auth_token: Some("secret-token".to_owned()),
+        };
+        let debug = format!("{conf:?}");
+        assert!(!debug.contains("secret-token"));
+        assert!(debug.contains("authenticated: true"));
+    }
+}
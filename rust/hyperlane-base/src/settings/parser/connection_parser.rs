@@ -1,5 +1,7 @@
+use std::time::Duration;
+
 use eyre::eyre;
-use h_eth::TransactionOverrides;
+use h_eth::{GasEscalationPolicy, TransactionOverrides};
 use hyperlane_core::config::{ConfigErrResultExt, OperationBatchConfig};
 use hyperlane_core::{config::ConfigParsingError, HyperlaneDomainProtocol};
 use url::Url;
@@ -62,6 +64,36 @@ pub fn build_ethereum_connection_conf(
                 .get_opt_key("maxPriorityFeePerGas")
                 .parse_u256()
                 .end(),
+            gas_escalation: value_parser
+                .get_opt_key("gasEscalation")
+                .take_err(err, || &value_parser.cwp + "gas_escalation")
+                .flatten()
+                .map(|escalation| {
+                    let default = GasEscalationPolicy::default();
+                    GasEscalationPolicy {
+                        interval: escalation
+                            .chain(err)
+                            .get_opt_key("intervalSecs")
+                            .parse_u64()
+                            .map(Duration::from_secs)
+                            .unwrap_or(default.interval),
+                        bump_percent: escalation
+                            .chain(err)
+                            .get_opt_key("bumpPercent")
+                            .parse_u32()
+                            .unwrap_or(default.bump_percent),
+                        max_replacements: escalation
+                            .chain(err)
+                            .get_opt_key("maxReplacements")
+                            .parse_u32()
+                            .unwrap_or(default.max_replacements),
+                        max_fee_per_gas_cap: escalation
+                            .chain(err)
+                            .get_opt_key("maxFeePerGasCap")
+                            .parse_u256()
+                            .end(),
+                    }
+                }),
         })
         .unwrap_or_default();
 
@@ -171,12 +203,48 @@ pub fn build_connection_conf(
             .iter()
             .next()
             .map(|url| ChainConnectionConf::Fuel(h_fuel::ConnectionConf { url: url.clone() })),
-        HyperlaneDomainProtocol::Sealevel => rpcs.iter().next().map(|url| {
-            ChainConnectionConf::Sealevel(h_sealevel::ConnectionConf {
-                url: url.clone(),
-                operation_batch,
+        HyperlaneDomainProtocol::Sealevel => {
+            let lookup_table = chain
+                .chain(err)
+                .get_opt_key("lookupTable")
+                .parse_address_hash()
+                .end();
+            let priority_fee = chain
+                .get_opt_key("priorityFee")
+                .take_err(err, || &chain.cwp + "priority_fee")
+                .flatten()
+                .map(|value_parser| {
+                    let default = h_sealevel::PriorityFeeConf::default();
+                    h_sealevel::PriorityFeeConf {
+                        percentile: value_parser
+                            .chain(err)
+                            .get_opt_key("percentile")
+                            .parse_u16()
+                            .and_then(|percentile| {
+                                u8::try_from(percentile)
+                                    .ok()
+                                    .filter(|percentile| *percentile <= 100)
+                                    .ok_or_else(|| eyre!("Expected a percentile between 0 and 100"))
+                                    .into_config_result(|| &value_parser.cwp + "percentile")
+                            })
+                            .unwrap_or(default.percentile),
+                        max_compute_unit_price: value_parser
+                            .chain(err)
+                            .get_opt_key("maxComputeUnitPrice")
+                            .parse_u64()
+                            .unwrap_or(default.max_compute_unit_price),
+                    }
+                })
+                .unwrap_or_default();
+            rpcs.iter().next().map(|url| {
+                ChainConnectionConf::Sealevel(h_sealevel::ConnectionConf {
+                    url: url.clone(),
+                    operation_batch,
+                    lookup_table,
+                    priority_fee,
+                })
             })
-        }),
+        }
         HyperlaneDomainProtocol::Cosmos => {
             build_cosmos_connection_conf(rpcs, chain, err, operation_batch)
         }
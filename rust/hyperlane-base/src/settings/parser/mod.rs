@@ -199,6 +199,12 @@ fn parse_chain(
         .parse_u32()
         .unwrap_or(1);
 
+    let max_submissions_in_flight = chain
+        .chain(&mut err)
+        .get_opt_key("maxSubmissionsInFlight")
+        .parse_u32()
+        .unwrap_or(1);
+
     cfg_unwrap_all!(&chain.cwp, err: [domain]);
     let connection = build_connection_conf(
         domain.domain_protocol(),
@@ -209,6 +215,7 @@ fn parse_chain(
         OperationBatchConfig {
             batch_contract_address,
             max_batch_size,
+            max_submissions_in_flight,
         },
     );
 
@@ -334,12 +341,27 @@ fn parse_signer(signer: ValueParser) -> ConfigResult<SignerConf> {
                 prefix: prefix.to_string(),
             })
         }};
+        (remote) => {{
+            let url = signer
+                .chain(&mut err)
+                .get_key("url")
+                .parse_from_str("Expected remote signer url")
+                .end();
+            let address = signer
+                .chain(&mut err)
+                .get_key("address")
+                .parse_from_str("Expected remote signer address")
+                .end();
+            cfg_unwrap_all!(&signer.cwp, err: [url, address]);
+            err.into_result(SignerConf::Remote { url, address })
+        }};
     }
 
     match signer_type {
         Some("hexKey") => parse_signer!(hexKey),
         Some("aws") => parse_signer!(aws),
         Some("cosmosKey") => parse_signer!(cosmosKey),
+        Some("remote") => parse_signer!(remote),
         Some(t) => {
             Err(eyre!("Unknown signer type `{t}`")).into_config_result(|| &signer.cwp + "type")
         }
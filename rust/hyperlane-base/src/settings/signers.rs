@@ -3,11 +3,12 @@ use ed25519_dalek::SecretKey;
 use ethers::prelude::{AwsSigner, LocalWallet};
 use ethers::utils::hex::ToHex;
 use eyre::{bail, Context, Report};
-use hyperlane_core::H256;
+use hyperlane_core::{H160, H256};
 use hyperlane_sealevel::Keypair;
 use rusoto_core::Region;
 use rusoto_kms::KmsClient;
 use tracing::instrument;
+use url::Url;
 
 use super::aws_credentials::AwsChainCredentialsProvider;
 use crate::types::utils;
@@ -35,6 +36,15 @@ pub enum SignerConf {
         /// Prefix for cosmos address
         prefix: String,
     },
+    /// An external signing service implementing the `eth_sign` JSON-RPC
+    /// method, e.g. Web3Signer. Only supports signing checkpoints and
+    /// announcements, not transactions.
+    Remote {
+        /// Url of the signing service
+        url: Url,
+        /// Address of the key held by the signing service
+        address: H160,
+    },
     /// Assume node will sign on RPC calls
     #[default]
     Node,
@@ -83,6 +93,9 @@ impl BuildableWithSignerConf for hyperlane_ethereum::Signers {
                 let signer = AwsSigner::new(client, id, 0).await?;
                 hyperlane_ethereum::Signers::Aws(signer)
             }
+            SignerConf::Remote { url, address } => hyperlane_ethereum::Signers::Remote(
+                hyperlane_ethereum::RemoteSigner::new(url.clone(), (*address).into())?,
+            ),
             SignerConf::CosmosKey { .. } => {
                 bail!("cosmosKey signer is not supported by Ethereum")
             }
@@ -0,0 +1,293 @@
+use std::{fmt, time::Duration};
+
+use async_trait::async_trait;
+use eyre::{bail, Context, Result};
+use hyperlane_core::{SignedAnnouncement, SignedCheckpointWithMessageId};
+use prometheus::IntGauge;
+use reqwest::{Client, StatusCode};
+use url::Url;
+
+use crate::CheckpointSyncer;
+
+/// Bearer token used to authenticate writes to HTTP checkpoint syncers
+pub const HTTP_STORAGE_AUTH_TOKEN: &str = "HTTP_STORAGE_AUTH_TOKEN";
+
+/// The timeout for requests to the HTTP store
+const HTTP_REQUEST_TIMEOUT_SECONDS: u64 = 30;
+
+/// Type for reading/writing to any HTTP server that serves files with `GET`
+/// and stores them with `PUT`, e.g. a WebDAV server. Reads are unauthenticated,
+/// so relayers can fetch signatures from a plain static web server.
+#[derive(Clone)]
+pub struct HttpStorage {
+    /// The base url of the store. Always ends with a `/`.
+    url: Url,
+    /// Optional bearer token used to authenticate writes.
+    auth_token: Option<String>,
+    client: Client,
+    /// The latest seen signed checkpoint index.
+    latest_index: Option<IntGauge>,
+}
+
+impl fmt::Debug for HttpStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpStorage")
+            .field("url", &self.url.as_str())
+            .field("authenticated", &self.auth_token.is_some())
+            .finish()
+    }
+}
+
+impl HttpStorage {
+    /// Create a new HttpStorage checkpoint syncer instance.
+    pub fn new(
+        mut url: Url,
+        auth_token: Option<String>,
+        latest_index: Option<IntGauge>,
+    ) -> Result<Self> {
+        // Without the trailing slash, joining a key would replace the last path segment
+        if !url.path().ends_with('/') {
+            url.set_path(&format!("{}/", url.path()));
+        }
+        let client = Client::builder()
+            .timeout(Duration::from_secs(HTTP_REQUEST_TIMEOUT_SECONDS))
+            .build()?;
+        Ok(Self {
+            url,
+            auth_token,
+            client,
+            latest_index,
+        })
+    }
+
+    fn key_url(&self, key: &str) -> Result<Url> {
+        self.url
+            .join(key)
+            .with_context(|| format!("Building url for key {key}"))
+    }
+
+    async fn write(&self, key: &str, body: String) -> Result<()> {
+        let url = self.key_url(key)?;
+        let mut req = self
+            .client
+            .put(url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body);
+        if let Some(token) = &self.auth_token {
+            req = req.bearer_auth(token);
+        }
+        let res = req
+            .send()
+            .await
+            .with_context(|| format!("Writing to {url}"))?;
+        if !res.status().is_success() {
+            bail!("Writing to {url} failed with status {}", res.status());
+        }
+        Ok(())
+    }
+
+    /// Returns `None` if the store doesn't have the key.
+    async fn read(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let url = self.key_url(key)?;
+        let res = self
+            .client
+            .get(url.clone())
+            .send()
+            .await
+            .with_context(|| format!("Reading from {url}"))?;
+        match res.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => Ok(Some(res.bytes().await?.to_vec())),
+            status => bail!("Reading from {url} failed with status {status}"),
+        }
+    }
+
+    fn checkpoint_key(index: u32) -> String {
+        format!("checkpoint_{index}_with_id.json")
+    }
+
+    fn latest_index_key() -> &'static str {
+        "checkpoint_latest_index.json"
+    }
+
+    fn announcement_key() -> &'static str {
+        "announcement.json"
+    }
+}
+
+#[async_trait]
+impl CheckpointSyncer for HttpStorage {
+    async fn latest_index(&self) -> Result<Option<u32>> {
+        let ret = self
+            .read(HttpStorage::latest_index_key())
+            .await?
+            .map(|data| serde_json::from_slice(&data))
+            .transpose()
+            .map_err(Into::into);
+
+        if let Ok(Some(latest_index)) = ret {
+            if let Some(gauge) = &self.latest_index {
+                gauge.set(latest_index as i64);
+            }
+        }
+
+        ret
+    }
+
+    async fn write_latest_index(&self, index: u32) -> Result<()> {
+        let serialized_index = serde_json::to_string(&index)?;
+        self.write(HttpStorage::latest_index_key(), serialized_index)
+            .await
+    }
+
+    async fn fetch_checkpoint(&self, index: u32) -> Result<Option<SignedCheckpointWithMessageId>> {
+        self.read(&HttpStorage::checkpoint_key(index))
+            .await?
+            .map(|data| serde_json::from_slice(&data))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    async fn write_checkpoint(
+        &self,
+        signed_checkpoint: &SignedCheckpointWithMessageId,
+    ) -> Result<()> {
+        let serialized_checkpoint = serde_json::to_string_pretty(signed_checkpoint)?;
+        self.write(
+            &HttpStorage::checkpoint_key(signed_checkpoint.value.index),
+            serialized_checkpoint,
+        )
+        .await
+    }
+
+    async fn write_announcement(&self, signed_announcement: &SignedAnnouncement) -> Result<()> {
+        let serialized_announcement = serde_json::to_string_pretty(signed_announcement)?;
+        self.write(HttpStorage::announcement_key(), serialized_announcement)
+            .await
+    }
+
+    fn announcement_location(&self) -> String {
+        self.url.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::HashMap,
+        net::SocketAddr,
+        sync::{Arc, Mutex},
+    };
+
+    use axum::{
+        body::Bytes,
+        extract::{Path, State},
+        http::{header::AUTHORIZATION, HeaderMap, StatusCode as AxumStatusCode},
+        routing, Router,
+    };
+    use hyperlane_core::{Checkpoint, CheckpointWithMessageId, Signature, H256};
+
+    use super::*;
+
+    const TOKEN: &str = "secret";
+
+    type Files = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+    async fn get_file(
+        State(files): State<Files>,
+        Path(key): Path<String>,
+    ) -> Result<Vec<u8>, AxumStatusCode> {
+        files
+            .lock()
+            .unwrap()
+            .get(&key)
+            .cloned()
+            .ok_or(AxumStatusCode::NOT_FOUND)
+    }
+
+    async fn put_file(
+        State(files): State<Files>,
+        Path(key): Path<String>,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> AxumStatusCode {
+        let authorized = headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .map_or(false, |value| value == format!("Bearer {TOKEN}"));
+        if !authorized {
+            return AxumStatusCode::UNAUTHORIZED;
+        }
+        files.lock().unwrap().insert(key, body.to_vec());
+        AxumStatusCode::CREATED
+    }
+
+    /// Local stand-in for a WebDAV server, storing files in memory
+    fn setup_test_server() -> (SocketAddr, Files) {
+        let files = Files::default();
+        let app = Router::new()
+            .route("/validator/*key", routing::get(get_file).put(put_file))
+            .with_state(files.clone());
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        (addr, files)
+    }
+
+    fn dummy_checkpoint(index: u32) -> SignedCheckpointWithMessageId {
+        SignedCheckpointWithMessageId {
+            value: CheckpointWithMessageId {
+                checkpoint: Checkpoint {
+                    merkle_tree_hook_address: H256::random(),
+                    mailbox_domain: 1,
+                    root: H256::random(),
+                    index,
+                },
+                message_id: H256::random(),
+            },
+            signature: Signature {
+                r: 1.into(),
+                s: 2.into(),
+                v: 27,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_and_read_checkpoints() {
+        let (addr, files) = setup_test_server();
+        let url: Url = format!("http://{addr}/validator").parse().unwrap();
+        let storage = HttpStorage::new(url, Some(TOKEN.to_owned()), None).unwrap();
+
+        assert_eq!(storage.latest_index().await.unwrap(), None);
+        assert_eq!(storage.fetch_checkpoint(3).await.unwrap(), None);
+
+        let checkpoint = dummy_checkpoint(3);
+        storage.write_checkpoint(&checkpoint).await.unwrap();
+        storage.update_latest_index(3).await.unwrap();
+
+        assert_eq!(storage.latest_index().await.unwrap(), Some(3));
+        assert_eq!(storage.fetch_checkpoint(3).await.unwrap(), Some(checkpoint));
+        assert!(files
+            .lock()
+            .unwrap()
+            .contains_key("checkpoint_3_with_id.json"));
+        assert_eq!(
+            storage.announcement_location(),
+            format!("http://{addr}/validator/")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unauthorized_writes_fail() {
+        let (addr, files) = setup_test_server();
+        let url: Url = format!("http://{addr}/validator/").parse().unwrap();
+
+        // Reads don't require the token
+        let storage = HttpStorage::new(url, None, None).unwrap();
+        assert!(storage.write_latest_index(1).await.is_err());
+        assert_eq!(storage.latest_index().await.unwrap(), None);
+        assert!(files.lock().unwrap().is_empty());
+    }
+}
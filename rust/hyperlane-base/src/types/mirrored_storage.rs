@@ -0,0 +1,426 @@
+use std::{future::Future, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use eyre::{bail, Result};
+use futures_util::future::join_all;
+use hyperlane_core::{SignedAnnouncement, SignedCheckpointWithMessageId, H160};
+use tokio::time::timeout;
+use tracing::{debug, warn};
+
+use crate::CheckpointSyncer;
+
+/// The default time a single store is given to complete a request before it's
+/// considered failed.
+const DEFAULT_STORE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A checkpoint syncer backed by several stores, e.g. an S3 bucket, a GCS bucket and
+/// a local directory.
+///
+/// Requests go to every store concurrently, each store being given at most
+/// `store_timeout`, so that a hung store doesn't stall the validator. The primary
+/// store's result is used if it succeeded, otherwise the mirrors' results are, so
+/// writes only fail if no store accepted them. Reads fail over through the stores in
+/// order until one of them returns a valid checkpoint. Stores that missed writes while
+/// they were unavailable are not backfilled; readers fall back to the other stores for
+/// those checkpoints.
+#[derive(Debug, Clone)]
+pub struct MirroredCheckpointSyncer {
+    /// The stores, in order of preference for reads. The first one is the primary store.
+    syncers: Vec<Arc<dyn CheckpointSyncer>>,
+    /// If set, fetched checkpoints that weren't signed by this address are skipped
+    expected_signer: Option<H160>,
+    /// The time a single store is given to complete a request
+    store_timeout: Duration,
+}
+
+impl MirroredCheckpointSyncer {
+    /// Create a new mirrored checkpoint syncer. Fails if no stores are provided.
+    pub fn new(syncers: Vec<Arc<dyn CheckpointSyncer>>) -> Result<Self> {
+        if syncers.is_empty() {
+            bail!("A mirrored checkpoint syncer requires at least one store");
+        }
+        Ok(Self {
+            syncers,
+            expected_signer: None,
+            store_timeout: DEFAULT_STORE_TIMEOUT,
+        })
+    }
+
+    /// Skip checkpoints that weren't signed by `signer` when reading, and try the next
+    /// store instead.
+    pub fn with_expected_signer(mut self, signer: H160) -> Self {
+        self.expected_signer = Some(signer);
+        self
+    }
+
+    /// Consider a store failed if it doesn't complete a request within `store_timeout`.
+    pub fn with_store_timeout(mut self, store_timeout: Duration) -> Self {
+        self.store_timeout = store_timeout;
+        self
+    }
+
+    /// The stores, the primary store first
+    pub fn stores(&self) -> &[Arc<dyn CheckpointSyncer>] {
+        &self.syncers
+    }
+
+    /// The announcement locations of all stores, the primary store first
+    pub fn announcement_locations(&self) -> Vec<String> {
+        self.syncers
+            .iter()
+            .map(|syncer| syncer.announcement_location())
+            .collect()
+    }
+
+    /// Returns whether the checkpoint is consistent with the requested index and
+    /// the expected signer
+    fn is_valid_checkpoint(&self, checkpoint: &SignedCheckpointWithMessageId, index: u32) -> bool {
+        if checkpoint.value.index != index {
+            warn!(
+                index,
+                checkpoint_index = checkpoint.value.index,
+                "Checkpoint index mismatch"
+            );
+            return false;
+        }
+        let Some(expected_signer) = self.expected_signer else {
+            return true;
+        };
+        match checkpoint.recover() {
+            Ok(signer) if signer == expected_signer => true,
+            result => {
+                warn!(
+                    index,
+                    ?expected_signer,
+                    ?result,
+                    "Checkpoint signature mismatch"
+                );
+                false
+            }
+        }
+    }
+
+    /// Runs `request` against every store concurrently, failing the stores that
+    /// don't complete it within `store_timeout`. Results are in the order of the stores.
+    async fn request_all<'a, T, F, Fut>(&'a self, request: F) -> Vec<Result<T>>
+    where
+        F: Fn(&'a Arc<dyn CheckpointSyncer>) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let request = &request;
+        join_all(
+            self.syncers
+                .iter()
+                .map(|syncer| async move { timeout(self.store_timeout, request(syncer)).await? }),
+        )
+        .await
+    }
+
+    /// Logs the stores that failed a request, and returns the primary store's result
+    /// if it succeeded, or else the result of the first mirror that succeeded, combined
+    /// with `combine_mirrors`.
+    fn primary_or_mirrors<T>(
+        &self,
+        description: &str,
+        results: Vec<Result<T>>,
+        combine_mirrors: impl Fn(T, T) -> T,
+    ) -> Result<T> {
+        let mut results = self.syncers.iter().zip(results).map(|(syncer, result)| {
+            result.map_err(|err| {
+                warn!(
+                    location = syncer.announcement_location(),
+                    error = ?err,
+                    "Checkpoint store failed {description}"
+                );
+                err
+            })
+        });
+        // There is always a primary store
+        let primary_result = results.next().unwrap();
+        let mirror_results: Vec<_> = results.collect();
+        if primary_result.is_ok() {
+            return primary_result;
+        }
+        mirror_results
+            .into_iter()
+            .filter_map(Result::ok)
+            .reduce(combine_mirrors)
+            .map_or(primary_result, Ok)
+    }
+}
+
+#[async_trait]
+impl CheckpointSyncer for MirroredCheckpointSyncer {
+    /// The primary store's latest index, or if it's unavailable, the highest latest
+    /// index among the mirrors
+    async fn latest_index(&self) -> Result<Option<u32>> {
+        let results = self.request_all(|syncer| syncer.latest_index()).await;
+        self.primary_or_mirrors("reading the latest index", results, Option::max)
+    }
+
+    async fn write_latest_index(&self, index: u32) -> Result<()> {
+        let results = self
+            .request_all(|syncer| syncer.write_latest_index(index))
+            .await;
+        self.primary_or_mirrors("writing the latest index", results, |_, _| ())
+    }
+
+    async fn fetch_checkpoint(&self, index: u32) -> Result<Option<SignedCheckpointWithMessageId>> {
+        let mut failures = 0;
+        let mut last_err = None;
+        for syncer in &self.syncers {
+            let result = match timeout(self.store_timeout, syncer.fetch_checkpoint(index)).await {
+                Ok(result) => result,
+                Err(elapsed) => Err(elapsed.into()),
+            };
+            match result {
+                Ok(Some(checkpoint)) if self.is_valid_checkpoint(&checkpoint, index) => {
+                    return Ok(Some(checkpoint));
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    debug!(
+                        location = syncer.announcement_location(),
+                        index,
+                        error = ?err,
+                        "Failed to fetch checkpoint; trying the next store"
+                    );
+                    failures += 1;
+                    last_err = Some(err);
+                }
+            }
+        }
+        match last_err {
+            // Only surface the error if there was nothing else to fall back to
+            Some(err) if failures == self.syncers.len() => Err(err),
+            _ => Ok(None),
+        }
+    }
+
+    async fn write_checkpoint(
+        &self,
+        signed_checkpoint: &SignedCheckpointWithMessageId,
+    ) -> Result<()> {
+        let results = self
+            .request_all(|syncer| syncer.write_checkpoint(signed_checkpoint))
+            .await;
+        self.primary_or_mirrors("writing a checkpoint", results, |_, _| ())
+    }
+
+    /// Writes the same announcement to every store. Use `stores` to write each store
+    /// an announcement of its own location.
+    async fn write_announcement(&self, signed_announcement: &SignedAnnouncement) -> Result<()> {
+        let results = self
+            .request_all(|syncer| syncer.write_announcement(signed_announcement))
+            .await;
+        self.primary_or_mirrors("writing an announcement", results, |_, _| ())
+    }
+
+    /// The primary store's location. See `announcement_locations` for all of them.
+    fn announcement_location(&self) -> String {
+        self.syncers[0].announcement_location()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ethers::signers::LocalWallet;
+    use hyperlane_core::{
+        Checkpoint, CheckpointWithMessageId, HyperlaneSigner, HyperlaneSignerExt, H256,
+    };
+    use hyperlane_ethereum::Signers;
+
+    use super::*;
+    use crate::LocalStorage;
+
+    fn signer(key: &str) -> Signers {
+        key.parse::<LocalWallet>().unwrap().into()
+    }
+
+    fn validator() -> Signers {
+        signer("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80")
+    }
+
+    fn impostor() -> Signers {
+        signer("59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d")
+    }
+
+    async fn signed_checkpoint(signer: &Signers, index: u32) -> SignedCheckpointWithMessageId {
+        let checkpoint = CheckpointWithMessageId {
+            checkpoint: Checkpoint {
+                merkle_tree_hook_address: H256::zero(),
+                mailbox_domain: 1,
+                root: H256::zero(),
+                index,
+            },
+            message_id: H256::zero(),
+        };
+        signer.sign(checkpoint).await.unwrap()
+    }
+
+    fn local_storage(dir: &tempfile::TempDir) -> Arc<dyn CheckpointSyncer> {
+        Arc::new(LocalStorage::new(dir.path().to_path_buf(), None).unwrap())
+    }
+
+    /// A store that never completes a request
+    #[derive(Debug)]
+    struct HungStorage;
+
+    #[async_trait]
+    impl CheckpointSyncer for HungStorage {
+        async fn latest_index(&self) -> Result<Option<u32>> {
+            std::future::pending().await
+        }
+
+        async fn write_latest_index(&self, _index: u32) -> Result<()> {
+            std::future::pending().await
+        }
+
+        async fn fetch_checkpoint(
+            &self,
+            _index: u32,
+        ) -> Result<Option<SignedCheckpointWithMessageId>> {
+            std::future::pending().await
+        }
+
+        async fn write_checkpoint(
+            &self,
+            _signed_checkpoint: &SignedCheckpointWithMessageId,
+        ) -> Result<()> {
+            std::future::pending().await
+        }
+
+        async fn write_announcement(
+            &self,
+            _signed_announcement: &SignedAnnouncement,
+        ) -> Result<()> {
+            std::future::pending().await
+        }
+
+        fn announcement_location(&self) -> String {
+            "hung://".to_owned()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_writes_go_to_all_stores() {
+        let (primary_dir, mirror_dir) =
+            (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+        let (primary, mirror) = (local_storage(&primary_dir), local_storage(&mirror_dir));
+        let syncer = MirroredCheckpointSyncer::new(vec![primary.clone(), mirror.clone()]).unwrap();
+
+        let checkpoint = signed_checkpoint(&validator(), 4).await;
+        syncer.write_checkpoint(&checkpoint).await.unwrap();
+        syncer.update_latest_index(4).await.unwrap();
+
+        for store in [primary, mirror] {
+            assert_eq!(
+                store.fetch_checkpoint(4).await.unwrap(),
+                Some(checkpoint.clone())
+            );
+            assert_eq!(store.latest_index().await.unwrap(), Some(4));
+        }
+        assert_eq!(
+            syncer.announcement_location(),
+            format!("file://{}", primary_dir.path().display())
+        );
+        assert_eq!(
+            syncer.announcement_locations(),
+            vec![
+                format!("file://{}", primary_dir.path().display()),
+                format!("file://{}", mirror_dir.path().display()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_primary_latest_index_takes_precedence() {
+        let (primary_dir, mirror_dir) =
+            (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+        let (primary, mirror) = (local_storage(&primary_dir), local_storage(&mirror_dir));
+        let syncer = MirroredCheckpointSyncer::new(vec![primary.clone(), mirror.clone()]).unwrap();
+
+        primary.write_latest_index(3).await.unwrap();
+        mirror.write_latest_index(5).await.unwrap();
+        assert_eq!(syncer.latest_index().await.unwrap(), Some(3));
+
+        // Falls back to the mirrors once the primary store is unavailable
+        std::fs::remove_dir_all(primary_dir.path()).unwrap();
+        assert_eq!(syncer.latest_index().await.unwrap(), Some(5));
+    }
+
+    #[tokio::test]
+    async fn test_hung_store_does_not_stall_requests() {
+        let primary_dir = tempfile::tempdir().unwrap();
+        let primary = local_storage(&primary_dir);
+        let syncer = MirroredCheckpointSyncer::new(vec![primary.clone(), Arc::new(HungStorage)])
+            .unwrap()
+            .with_store_timeout(Duration::from_millis(10));
+
+        let checkpoint = signed_checkpoint(&validator(), 1).await;
+        syncer.write_checkpoint(&checkpoint).await.unwrap();
+        syncer.update_latest_index(1).await.unwrap();
+        assert_eq!(syncer.latest_index().await.unwrap(), Some(1));
+        assert_eq!(syncer.fetch_checkpoint(1).await.unwrap(), Some(checkpoint));
+        assert_eq!(syncer.fetch_checkpoint(2).await.unwrap(), None);
+
+        // Fails once the primary store is unavailable too
+        let syncer = MirroredCheckpointSyncer::new(vec![Arc::new(HungStorage)])
+            .unwrap()
+            .with_store_timeout(Duration::from_millis(10));
+        assert!(syncer.write_checkpoint(&checkpoint).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_writes_succeed_while_a_store_is_down() {
+        let (primary_dir, mirror_dir) =
+            (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+        let (primary, mirror) = (local_storage(&primary_dir), local_storage(&mirror_dir));
+        let syncer = MirroredCheckpointSyncer::new(vec![primary, mirror.clone()]).unwrap();
+
+        // Writing to the primary store fails once its directory is gone
+        std::fs::remove_dir_all(primary_dir.path()).unwrap();
+        let checkpoint = signed_checkpoint(&validator(), 2).await;
+        syncer.write_checkpoint(&checkpoint).await.unwrap();
+        assert_eq!(
+            mirror.fetch_checkpoint(2).await.unwrap(),
+            Some(checkpoint.clone())
+        );
+        assert_eq!(syncer.fetch_checkpoint(2).await.unwrap(), Some(checkpoint));
+
+        // Fails once no store accepts the write
+        std::fs::remove_dir_all(mirror_dir.path()).unwrap();
+        let checkpoint = signed_checkpoint(&validator(), 3).await;
+        assert!(syncer.write_checkpoint(&checkpoint).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reads_fail_over_to_consistent_checkpoints() {
+        let (primary_dir, mirror_dir) =
+            (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+        let (primary, mirror) = (local_storage(&primary_dir), local_storage(&mirror_dir));
+        let validator = validator();
+        let syncer = MirroredCheckpointSyncer::new(vec![primary.clone(), mirror.clone()])
+            .unwrap()
+            .with_expected_signer(validator.eth_address());
+
+        // Only available in the mirror
+        let checkpoint = signed_checkpoint(&validator, 1).await;
+        mirror.write_checkpoint(&checkpoint).await.unwrap();
+        assert_eq!(syncer.fetch_checkpoint(1).await.unwrap(), Some(checkpoint));
+
+        // The primary store serves a checkpoint that wasn't signed by the validator
+        let forged = signed_checkpoint(&impostor(), 5).await;
+        let checkpoint = signed_checkpoint(&validator, 5).await;
+        primary.write_checkpoint(&forged).await.unwrap();
+        mirror.write_checkpoint(&checkpoint).await.unwrap();
+        assert_eq!(syncer.fetch_checkpoint(5).await.unwrap(), Some(checkpoint));
+
+        // No store has a valid checkpoint
+        primary
+            .write_checkpoint(&signed_checkpoint(&impostor(), 6).await)
+            .await
+            .unwrap();
+        assert_eq!(syncer.fetch_checkpoint(6).await.unwrap(), None);
+    }
+}
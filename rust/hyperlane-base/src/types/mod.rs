@@ -1,5 +1,7 @@
 mod gcs_storage;
+mod http_storage;
 mod local_storage;
+mod mirrored_storage;
 mod multisig;
 mod s3_storage;
 
@@ -7,6 +9,8 @@ mod s3_storage;
 pub mod utils;
 
 pub use gcs_storage::*;
+pub use http_storage::*;
 pub use local_storage::*;
+pub use mirrored_storage::*;
 pub use multisig::*;
 pub use s3_storage::*;
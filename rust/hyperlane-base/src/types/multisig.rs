@@ -32,6 +32,31 @@ impl MultisigCheckpointSyncer {
         origin: &HyperlaneDomain,
         destination: &HyperlaneDomain,
     ) -> Vec<u32> {
+        let latest_indices = self.get_validator_latest_checkpoints(validators).await;
+
+        if let Some(app_context) = &self.app_context {
+            self.metrics
+                .validator_metrics
+                .set_validator_latest_checkpoints(
+                    origin,
+                    destination,
+                    app_context.clone(),
+                    &latest_indices,
+                )
+                .await;
+        }
+
+        // Filter out any validators that did not return a latest index
+        latest_indices.values().copied().flatten().collect()
+    }
+
+    /// Gets the latest checkpoint index from each validator's checkpoint syncer.
+    /// Validators without a checkpoint syncer are left out, and validators that
+    /// did not return a latest index are recorded as `None`.
+    pub async fn get_validator_latest_checkpoints(
+        &self,
+        validators: &[H256],
+    ) -> HashMap<H160, Option<u32>> {
         // Get the latest_index from each validator's checkpoint syncer.
         // If a validator does not return a latest index, None is recorded so
         // this can be surfaced in the metrics.
@@ -59,20 +84,7 @@ impl MultisigCheckpointSyncer {
             }
         }
 
-        if let Some(app_context) = &self.app_context {
-            self.metrics
-                .validator_metrics
-                .set_validator_latest_checkpoints(
-                    origin,
-                    destination,
-                    app_context.clone(),
-                    &latest_indices,
-                )
-                .await;
-        }
-
-        // Filter out any validators that did not return a latest index
-        latest_indices.values().copied().flatten().collect()
+        latest_indices
     }
 
     /// Attempts to get the latest checkpoint with a quorum of signatures among
@@ -29,6 +29,9 @@ pub struct OperationBatchConfig {
     pub batch_contract_address: Option<H256>,
     /// Batch size
     pub max_batch_size: u32,
+    /// Max number of submissions that may be in flight at once, each using its own
+    /// sequential signer nonce. Only used when batching is disabled (i.e. `max_batch_size` is 1).
+    pub max_submissions_in_flight: u32,
 }
 
 /// A trait that allows for constructing `Self` from a raw config type.
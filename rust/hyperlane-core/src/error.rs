@@ -11,7 +11,7 @@ use crate::rpc_clients::RpcClientError;
 use std::string::FromUtf8Error;
 
 use crate::HyperlaneProviderError;
-use crate::{Error as PrimitiveTypeError, HyperlaneSignerError, H256, U256};
+use crate::{Error as PrimitiveTypeError, HyperlaneSignerError, H256, H512, U256};
 
 /// The result of interacting with a chain.
 pub type ChainResult<T> = Result<T, ChainCommunicationError>;
@@ -79,6 +79,10 @@ pub enum ChainCommunicationError {
     /// A transaction submission timed out
     #[error("Transaction submission timed out")]
     TransactionTimeout(),
+    /// None of the dispatched transactions, which replace each other, were included
+    /// before the submission timed out. Any of them may still be included later.
+    #[error("Transaction submission timed out, none of {0:?} were included")]
+    TransactionsNotIncluded(Vec<H512>),
     /// No signer is available and was required for the operation
     #[error("Signer unavailable")]
     SignerUnavailable,
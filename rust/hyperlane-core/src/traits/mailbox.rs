@@ -4,8 +4,8 @@ use std::num::NonZeroU64;
 use async_trait::async_trait;
 
 use crate::{
-    traits::TxOutcome, utils::domain_hash, BatchItem, ChainCommunicationError, ChainResult,
-    HyperlaneContract, HyperlaneMessage, TxCostEstimate, H256, U256,
+    traits::TxOutcome, utils::domain_hash, BatchItem, BatchResult, ChainCommunicationError,
+    ChainResult, HyperlaneContract, HyperlaneMessage, TxCostEstimate, H256, U256,
 };
 
 /// Interface for the Mailbox chain contract. Allows abstraction over different
@@ -40,11 +40,12 @@ pub trait Mailbox: HyperlaneContract + Send + Sync + Debug {
         tx_gas_limit: Option<U256>,
     ) -> ChainResult<TxOutcome>;
 
-    /// Process a message with a proof against the provided signed checkpoint
+    /// Process a batch of messages in a single transaction, reporting the
+    /// messages that weren't processed by it
     async fn process_batch(
         &self,
         _messages: &[BatchItem<HyperlaneMessage>],
-    ) -> ChainResult<TxOutcome> {
+    ) -> ChainResult<BatchResult> {
         // Batching is not supported by default
         Err(ChainCommunicationError::BatchingFailed)
     }
@@ -8,7 +8,7 @@ use std::{
 
 use crate::{
     ChainResult, Decode, Encode, FixedPointNumber, HyperlaneDomain, HyperlaneMessage,
-    HyperlaneProtocolError, TryBatchAs, TxOutcome, H256, U256,
+    HyperlaneProtocolError, MessageSubmissionData, TryBatchAs, TxOutcome, H256, H512, U256,
 };
 use async_trait::async_trait;
 use num::CheckedDiv;
@@ -135,6 +135,9 @@ pub enum PendingOperationStatus {
     /// The operation has been submitted and is awaiting confirmation
     #[strum(to_string = "Confirm({0})")]
     Confirm(ConfirmReason),
+    /// The operation was dropped by an operator and should not be attempted again
+    #[strum(to_string = "Dropped by operator")]
+    Dropped,
 }
 
 impl Encode for PendingOperationStatus {
@@ -203,6 +206,9 @@ pub enum ReprepareReason {
     #[strum(to_string = "Delivery transaction reverted or reorged")]
     /// Delivery transaction reverted or reorged
     RevertedOrReorged,
+    #[strum(to_string = "Message not allowed by the relayer policies")]
+    /// Message not allowed by the relayer policies
+    NotAllowedByPolicies,
 }
 
 #[derive(Display, Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -222,6 +228,52 @@ pub enum ConfirmReason {
     ErrorRecordingProcessSuccess,
 }
 
+/// Snapshot of the in-memory queue state of an operation that is not captured by its status,
+/// persisted so that the submitter queues can be rehydrated after a restart.
+/// WARNING: This struct is serialized to JSON and stored in the database, so to keep backwards compatibility,
+/// new fields must be optional.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PendingOperationQueueState {
+    /// Unix timestamp (in seconds) before which the operation should not be attempted again
+    pub next_attempt_after: Option<u64>,
+    /// Hash of the last transaction submitted for this operation, which may still be awaiting confirmation
+    pub submitted_tx_id: Option<H512>,
+    /// Hashes of the transactions that `submitted_tx_id` replaced with bumped fees. Since they
+    /// share its nonce, any one of them may be the one that lands.
+    #[serde(default)]
+    pub replaced_tx_ids: Vec<H512>,
+    /// The metadata and gas estimate of the last successful prepare call, which the
+    /// submitted transactions were built from
+    #[serde(default)]
+    pub submission_data: Option<MessageSubmissionData>,
+}
+
+impl Encode for PendingOperationQueueState {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: Write,
+    {
+        let serialized = serde_json::to_vec(self)
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "Failed to serialize"))?;
+        writer.write(&serialized)
+    }
+}
+
+impl Decode for PendingOperationQueueState {
+    fn read_from<R>(reader: &mut R) -> Result<Self, HyperlaneProtocolError>
+    where
+        R: std::io::Read,
+        Self: Sized,
+    {
+        serde_json::from_reader(reader).map_err(|err| {
+            HyperlaneProtocolError::IoError(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Failed to deserialize. Error: {}", err),
+            ))
+        })
+    }
+}
+
 /// Utility fn to calculate the total estimated cost of an operation batch
 pub fn total_estimated_cost(ops: &[Box<dyn PendingOperation>]) -> U256 {
     ops.iter()
@@ -326,4 +378,27 @@ mod test {
         let decoded = PendingOperationStatus::read_from(&mut &encoded[..]).unwrap();
         assert_eq!(status, decoded);
     }
+
+    #[test]
+    fn test_encoding_pending_operation_queue_state() {
+        let state = PendingOperationQueueState {
+            next_attempt_after: Some(1_700_000_000),
+            submitted_tx_id: Some(H512::from_low_u64_be(42)),
+            replaced_tx_ids: vec![H512::from_low_u64_be(41)],
+            submission_data: Some(MessageSubmissionData {
+                metadata: vec![1, 2, 3],
+                gas_limit: U256::from(100_000u64),
+            }),
+        };
+        let encoded = state.to_vec();
+        let decoded = PendingOperationQueueState::read_from(&mut &encoded[..]).unwrap();
+        assert_eq!(state, decoded);
+    }
+
+    #[test]
+    fn test_decoding_pending_operation_queue_state_without_optional_fields() {
+        let encoded = br#"{"next_attempt_after":null,"submitted_tx_id":null}"#;
+        let decoded = PendingOperationQueueState::read_from(&mut &encoded[..]).unwrap();
+        assert_eq!(decoded, PendingOperationQueueState::default());
+    }
 }
@@ -4,7 +4,7 @@ use async_trait::async_trait;
 use auto_impl::auto_impl;
 use thiserror::Error;
 
-use crate::{BlockInfo, ChainInfo, ChainResult, HyperlaneChain, TxnInfo, H256, U256};
+use crate::{BlockInfo, ChainInfo, ChainResult, HyperlaneChain, TxnInfo, H256, H512, U256};
 
 /// Interface for a provider. Allows abstraction over different provider types
 /// for different chains.
@@ -16,11 +16,14 @@ use crate::{BlockInfo, ChainInfo, ChainResult, HyperlaneChain, TxnInfo, H256, U2
 #[async_trait]
 #[auto_impl(&, Box, Arc)]
 pub trait HyperlaneProvider: HyperlaneChain + Send + Sync + Debug {
-    /// Get block info for a given block hash
-    async fn get_block_by_hash(&self, hash: &H256) -> ChainResult<BlockInfo>;
+    /// Get block info for a given block height. Blocks are looked up by height
+    /// because the `LogMeta` of an indexed event doesn't always carry the block
+    /// hash, e.g. on Sealevel.
+    async fn get_block_by_height(&self, height: u64) -> ChainResult<BlockInfo>;
 
-    /// Get txn info for a given txn hash
-    async fn get_txn_by_hash(&self, hash: &H256) -> ChainResult<TxnInfo>;
+    /// Get txn info for a given txn hash. Hashes are 512 bits wide to fit
+    /// Sealevel signatures; chains with 256 bit hashes use the low 256 bits.
+    async fn get_txn_by_hash(&self, hash: &H512) -> ChainResult<TxnInfo>;
 
     /// Returns whether a contract exists at the provided address
     async fn is_contract(&self, address: &H256) -> ChainResult<bool>;
@@ -41,7 +44,10 @@ pub enum HyperlaneProviderError {
     /// The provider did not return the gas which was used
     #[error("Provider did not return gas used")]
     NoGasUsed,
-    /// Could not find a transaction, block, or other object
-    #[error("Could not find object from provider with hash {0:?}")]
-    CouldNotFindObjectByHash(H256),
+    /// Could not find a block at the given height
+    #[error("Could not find block from provider at height {0}")]
+    CouldNotFindBlockByHeight(u64),
+    /// Could not find a transaction by its hash
+    #[error("Could not find transaction from provider with hash {0:?}")]
+    CouldNotFindTransactionByHash(H512),
 }
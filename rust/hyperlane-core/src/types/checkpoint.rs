@@ -1,10 +1,14 @@
 use std::fmt::Debug;
+use std::io::{Read, Write};
 
 use derive_more::Deref;
 use serde::{Deserialize, Serialize};
 use sha3::{digest::Update, Digest, Keccak256};
 
-use crate::{utils::domain_hash, Signable, Signature, SignedType, H256};
+use crate::{
+    utils::domain_hash, Decode, Encode, HyperlaneProtocolError, Signable, Signature, SignedType,
+    H256,
+};
 
 /// An Hyperlane checkpoint
 #[derive(Copy, Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
@@ -53,6 +57,37 @@ impl Signable for CheckpointWithMessageId {
 /// Signed (checkpoint, messageId) tuple
 pub type SignedCheckpointWithMessageId = SignedType<CheckpointWithMessageId>;
 
+impl Encode for CheckpointWithMessageId {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: Write,
+    {
+        Ok(self.merkle_tree_hook_address.write_to(writer)?
+            + self.mailbox_domain.write_to(writer)?
+            + self.root.write_to(writer)?
+            + self.index.write_to(writer)?
+            + self.message_id.write_to(writer)?)
+    }
+}
+
+impl Decode for CheckpointWithMessageId {
+    fn read_from<R>(reader: &mut R) -> Result<Self, HyperlaneProtocolError>
+    where
+        R: Read,
+        Self: Sized,
+    {
+        Ok(Self {
+            checkpoint: Checkpoint {
+                merkle_tree_hook_address: H256::read_from(reader)?,
+                mailbox_domain: u32::read_from(reader)?,
+                root: H256::read_from(reader)?,
+                index: u32::read_from(reader)?,
+            },
+            message_id: H256::read_from(reader)?,
+        })
+    }
+}
+
 /// A checkpoint and multiple signatures
 #[derive(Clone, Debug)]
 pub struct MultisigSignedCheckpoint {
@@ -188,6 +188,54 @@ impl Add for InterchainGasExpenditure {
     }
 }
 
+/// A message delivered by the relayer, as recorded in its profitability ledger
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeliveryLedgerEntry {
+    /// ID of the message
+    pub message_id: H256,
+    /// Destination domain of the message
+    pub destination: u32,
+    /// Sender of the message
+    pub sender: H256,
+    /// App context of the message, if it matched one
+    pub app_context: Option<String>,
+    /// Total amount of origin tokens paid for the message
+    pub payment: U256,
+    /// Total amount of destination gas used delivering the message
+    pub gas_used: U256,
+    /// Total amount of destination tokens spent delivering the message
+    pub tokens_used: U256,
+    /// The payment and cost in USD at the time of delivery, if the prices of the origin
+    /// and destination tokens were known
+    pub usd: Option<(f64, f64)>,
+}
+
+impl Encode for DeliveryLedgerEntry {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: Write,
+    {
+        let serialized = serde_json::to_vec(self)
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "Failed to serialize"))?;
+        writer.write(&serialized)
+    }
+}
+
+impl Decode for DeliveryLedgerEntry {
+    fn read_from<R>(reader: &mut R) -> Result<Self, HyperlaneProtocolError>
+    where
+        R: Read,
+        Self: Sized,
+    {
+        serde_json::from_reader(reader).map_err(|err| {
+            HyperlaneProtocolError::IoError(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Failed to deserialize. Error: {}", err),
+            ))
+        })
+    }
+}
+
 /// Uniquely identifying metadata for an InterchainGasPayment
 #[derive(Debug)]
 pub struct InterchainGasPaymentMeta {
@@ -1,10 +1,11 @@
 use std::sync::Arc;
 
-use crate::{ChainResult, Mailbox, U256};
+use crate::{ChainResult, Mailbox, TxOutcome, U256};
 use derive_new::new;
+use serde::{Deserialize, Serialize};
 
 /// State for the next submission attempt generated by a prepare call.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct MessageSubmissionData {
     /// Transaction metadata - currently only applies to Messages, so this field can be made optional or generic if other
     /// operations are submitted in the future.
@@ -25,6 +26,16 @@ pub struct BatchItem<T> {
     pub mailbox: Arc<dyn Mailbox>,
 }
 
+/// The result of submitting a batch of items in a single transaction.
+#[derive(new, Clone, Debug)]
+pub struct BatchResult {
+    /// The outcome of the transaction, if one was submitted
+    pub outcome: Option<TxOutcome>,
+    /// The indexes of the items that weren't included in the transaction or
+    /// weren't processed by it. These items should be submitted again.
+    pub failed_indexes: Vec<usize>,
+}
+
 /// Need to define a trait instead of using TryInto because the latter is not
 /// object safe
 pub trait TryBatchAs<T> {
@@ -6,7 +6,7 @@ use crate::{
     cmd_utils::{create_and_write_keypair, create_new_directory, deploy_program},
     read_core_program_ids,
     router::ChainMetadata,
-    Context, GasOverheadSubCmd, GetSetCmd, IgpCmd, IgpSubCmd,
+    Context, GasOverheadSubCmd, GetSetCmd, IgpCmd, IgpSubCmd, SetGasOracleSubCmd,
 };
 
 use std::{
@@ -16,6 +16,7 @@ use std::{
 };
 
 use solana_sdk::{
+    instruction::AccountMeta,
     pubkey::Pubkey,
     signature::{Keypair, Signer as _},
 };
@@ -24,7 +25,8 @@ use hyperlane_core::H256;
 
 use hyperlane_sealevel_igp::{
     accounts::{
-        GasOracle, GasPaymentAccount, IgpAccount, InterchainGasPaymasterType, OverheadIgpAccount,
+        CpiGasOracle, DataAccountGasOracle, GasOracle, GasPaymentAccount, IgpAccount,
+        InterchainGasPaymasterType, OverheadIgpAccount,
         ProgramDataAccount as IgpProgramDataAccount, RemoteGasData,
     },
     igp_program_data_pda_seeds,
@@ -194,12 +196,30 @@ pub(crate) fn process_igp_cmd(mut ctx: Context, cmd: IgpCmd) {
                 hyperlane_sealevel_igp::overhead_igp_pda_seeds!(salt),
                 &payment_details.program_id,
             );
+            // Gas oracles reading from a data account or an oracle program
+            // require it to be passed in.
+            let igp = ctx
+                .client
+                .get_account_with_commitment(&igp_account, ctx.commitment)
+                .unwrap()
+                .value
+                .expect("IGP account not found. Make sure you are connected to the right RPC.");
+            let igp = IgpAccount::fetch(&mut &igp.data[..]).unwrap().into_inner();
+            let gas_oracle_accounts = igp
+                .gas_oracles
+                .get(&payment_details.destination_domain)
+                .and_then(GasOracle::oracle_account)
+                .map(|oracle_account| AccountMeta::new_readonly(*oracle_account, false))
+                .into_iter()
+                .collect();
+
             let (ixn, gas_payment_data_account) =
                 hyperlane_sealevel_igp::instruction::pay_for_gas_instruction(
                     payment_details.program_id,
                     ctx.payer_pubkey,
                     igp_account,
                     Some(overhead_igp_account),
+                    gas_oracle_accounts,
                     unique_gas_payment_keypair.pubkey(),
                     H256::from_str(&payment_details.message_id).unwrap(),
                     payment_details.destination_domain,
@@ -281,14 +301,31 @@ pub(crate) fn process_igp_cmd(mut ctx: Context, cmd: IgpCmd) {
             );
             match args.cmd {
                 GetSetCmd::Set(set_args) => {
-                    let remote_gas_data = RemoteGasData {
-                        token_exchange_rate: set_args.token_exchange_rate,
-                        gas_price: set_args.gas_price,
-                        token_decimals: set_args.token_decimals,
+                    let gas_oracle = match set_args.gas_oracle {
+                        SetGasOracleSubCmd::RemoteGasData(remote_gas_data) => {
+                            GasOracle::RemoteGasData(RemoteGasData {
+                                token_exchange_rate: remote_gas_data.token_exchange_rate,
+                                gas_price: remote_gas_data.gas_price,
+                                token_decimals: remote_gas_data.token_decimals,
+                            })
+                        }
+                        SetGasOracleSubCmd::DataAccount(data_account) => {
+                            GasOracle::DataAccount(DataAccountGasOracle {
+                                data_account: data_account.data_account,
+                                data_offset: data_account.data_offset,
+                                token_decimals: data_account.token_decimals,
+                                max_staleness: data_account.max_staleness,
+                            })
+                        }
+                        SetGasOracleSubCmd::Cpi(cpi) => GasOracle::Cpi(CpiGasOracle {
+                            program_id: cpi.oracle_program_id,
+                            token_decimals: cpi.token_decimals,
+                            max_staleness: cpi.max_staleness,
+                        }),
                     };
                     let gas_oracle_config = GasOracleConfig {
                         domain: args.remote_domain,
-                        gas_oracle: Some(GasOracle::RemoteGasData(remote_gas_data)),
+                        gas_oracle: Some(gas_oracle),
                     };
                     let instruction =
                         hyperlane_sealevel_igp::instruction::set_gas_oracle_configs_instruction(
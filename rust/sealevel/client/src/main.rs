@@ -6,6 +6,7 @@
 use std::{path::PathBuf, str::FromStr};
 
 use clap::{Args, Parser, Subcommand, ValueEnum};
+use solana_address_lookup_table_program::instruction::{create_lookup_table, extend_lookup_table};
 use solana_clap_utils::input_validators::{is_keypair, is_url, normalize_to_url_if_moniker};
 use solana_cli_config::{Config, CONFIG_FILE};
 use solana_client::rpc_client::RpcClient;
@@ -23,7 +24,7 @@ use account_utils::DiscriminatorEncode;
 use hyperlane_core::{H160, H256};
 use hyperlane_sealevel_connection_client::router::RemoteRouterConfig;
 use hyperlane_sealevel_igp::{
-    accounts::{InterchainGasPaymasterType, OverheadIgpAccount},
+    accounts::{GasOracle, IgpAccount, InterchainGasPaymasterType, OverheadIgpAccount},
     igp_gas_payment_pda_seeds, igp_program_data_pda_seeds,
 };
 use hyperlane_sealevel_mailbox::{
@@ -58,6 +59,7 @@ use hyperlane_sealevel_validator_announce::{
 };
 use warp_route::parse_token_account_data;
 
+mod aggregation_ism;
 mod artifacts;
 mod cmd_utils;
 mod context;
@@ -66,12 +68,15 @@ mod helloworld;
 mod igp;
 mod multisig_ism;
 mod router;
+mod routing_ism;
 mod serde;
 mod warp_route;
 
+use crate::aggregation_ism::process_aggregation_ism_cmd;
 use crate::helloworld::process_helloworld_cmd;
 use crate::igp::process_igp_cmd;
 use crate::multisig_ism::process_multisig_ism_message_id_cmd;
+use crate::routing_ism::process_routing_ism_cmd;
 use crate::warp_route::process_warp_route_cmd;
 pub(crate) use crate::{context::*, core::*};
 
@@ -79,6 +84,9 @@ pub(crate) use crate::{context::*, core::*};
 const DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT: u32 = 200_000;
 const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
 const MAX_HEAP_FRAME_BYTES: u32 = 256 * 1024;
+// The max number of addresses that fit in a single extend instruction
+// without exceeding the transaction size limit
+const MAX_ADDRESSES_PER_EXTEND: usize = 20;
 
 const ECLIPSE_DOMAIN: u32 = 13375; // TODO import from hyperlane
 
@@ -109,6 +117,8 @@ enum HyperlaneSealevelCmd {
     Igp(IgpCmd),
     ValidatorAnnounce(ValidatorAnnounceCmd),
     MultisigIsmMessageId(MultisigIsmMessageIdCmd),
+    RoutingIsm(RoutingIsmCmd),
+    AggregationIsm(AggregationIsmCmd),
     WarpRoute(WarpRouteCmd),
     HelloWorld(HelloWorldCmd),
 }
@@ -202,6 +212,7 @@ enum MailboxSubCmd {
     Delivered(Delivered),
     TransferOwnership(TransferOwnership),
     SetDefaultIsm(SetDefaultIsm),
+    CreateLookupTable(CreateLookupTable),
 }
 
 const MAILBOX_PROG_ID: Pubkey = pubkey!("692KZJaoe2KRcD6uhCQDLLXnLNA5ZLnfvdqjE4aX9iu1");
@@ -234,6 +245,17 @@ struct SetDefaultIsm {
     default_ism: Pubkey,
 }
 
+/// Creates an address lookup table for the relayer to batch process
+/// transactions with, holding the mailbox accounts and any `accounts` shared
+/// by the messages it delivers, e.g. those of ISMs and recipients.
+#[derive(Args)]
+struct CreateLookupTable {
+    #[arg(long, short, default_value_t = MAILBOX_PROG_ID)]
+    program_id: Pubkey,
+    #[arg(long, short, num_args = 0..)]
+    accounts: Vec<Pubkey>,
+}
+
 #[derive(Args)]
 struct Outbox {
     #[arg(long, short, default_value_t = ECLIPSE_DOMAIN)]
@@ -504,6 +526,23 @@ struct GasOracleConfigArgs {
 
 #[derive(Args)]
 struct SetGasOracleArgs {
+    #[command(subcommand)]
+    gas_oracle: SetGasOracleSubCmd,
+}
+
+#[derive(Subcommand)]
+enum SetGasOracleSubCmd {
+    /// Gas data stored in the IGP account
+    RemoteGasData(SetRemoteGasDataArgs),
+    /// Gas data read from a custom data account holding borsh-encoded `OracleGasData`.
+    /// Pyth price accounts aren't supported.
+    DataAccount(SetDataAccountGasOracleArgs),
+    /// Gas data returned by a CPI into an oracle program
+    Cpi(SetCpiGasOracleArgs),
+}
+
+#[derive(Args)]
+struct SetRemoteGasDataArgs {
     #[arg(long)]
     token_exchange_rate: u128,
     #[arg(long)]
@@ -512,6 +551,30 @@ struct SetGasOracleArgs {
     token_decimals: u8,
 }
 
+#[derive(Args)]
+struct SetDataAccountGasOracleArgs {
+    #[arg(long)]
+    data_account: Pubkey,
+    #[arg(long, default_value_t = 0)]
+    data_offset: u32,
+    #[arg(long)]
+    token_decimals: u8,
+    /// Max age in seconds of the gas data
+    #[arg(long)]
+    max_staleness: u64,
+}
+
+#[derive(Args)]
+struct SetCpiGasOracleArgs {
+    #[arg(long)]
+    oracle_program_id: Pubkey,
+    #[arg(long)]
+    token_decimals: u8,
+    /// Max age in seconds of the returned data
+    #[arg(long)]
+    max_staleness: u64,
+}
+
 #[derive(Args)]
 struct GetGasOracleArgs;
 
@@ -645,6 +708,121 @@ struct MultisigIsmMessageIdSetValidatorsAndThreshold {
     threshold: u8,
 }
 
+#[derive(Args)]
+struct RoutingIsmCmd {
+    #[command(subcommand)]
+    cmd: RoutingIsmSubCmd,
+}
+
+#[derive(Subcommand)]
+enum RoutingIsmSubCmd {
+    Deploy(RoutingIsmDeploy),
+    Init(RoutingIsmInit),
+    SetRoute(RoutingIsmSetRoute),
+    RemoveRoute(RoutingIsmRemoveRoute),
+    Query(RoutingIsmQuery),
+    TransferOwnership(TransferOwnership),
+}
+
+#[derive(Args)]
+struct RoutingIsmDeploy {
+    #[command(flatten)]
+    env_args: EnvironmentArgs,
+    #[arg(long)]
+    built_so_dir: PathBuf,
+    #[arg(long)]
+    chain: String,
+    #[arg(long)]
+    context: String,
+}
+
+#[derive(Args)]
+struct RoutingIsmInit {
+    #[arg(long, short)]
+    program_id: Pubkey,
+}
+
+#[derive(Args)]
+struct RoutingIsmSetRoute {
+    #[arg(long, short)]
+    program_id: Pubkey,
+    #[arg(long)]
+    domain: u32,
+    #[arg(long)]
+    ism: Pubkey,
+}
+
+#[derive(Args)]
+struct RoutingIsmRemoveRoute {
+    #[arg(long, short)]
+    program_id: Pubkey,
+    #[arg(long)]
+    domain: u32,
+}
+
+#[derive(Args)]
+struct RoutingIsmQuery {
+    #[arg(long, short)]
+    program_id: Pubkey,
+}
+
+#[derive(Args)]
+struct AggregationIsmCmd {
+    #[command(subcommand)]
+    cmd: AggregationIsmSubCmd,
+}
+
+#[derive(Subcommand)]
+enum AggregationIsmSubCmd {
+    Deploy(AggregationIsmDeploy),
+    Init(AggregationIsmInit),
+    SetModulesAndThreshold(AggregationIsmSetModulesAndThreshold),
+    Query(AggregationIsmQuery),
+    TransferOwnership(TransferOwnership),
+}
+
+#[derive(Args)]
+struct AggregationIsmDeploy {
+    #[command(flatten)]
+    env_args: EnvironmentArgs,
+    #[arg(long)]
+    built_so_dir: PathBuf,
+    #[arg(long)]
+    chain: String,
+    #[arg(long)]
+    context: String,
+    #[arg(long, value_delimiter = ',')]
+    modules: Vec<Pubkey>,
+    #[arg(long)]
+    threshold: u8,
+}
+
+#[derive(Args)]
+struct AggregationIsmInit {
+    #[arg(long, short)]
+    program_id: Pubkey,
+    #[arg(long, value_delimiter = ',')]
+    modules: Vec<Pubkey>,
+    #[arg(long)]
+    threshold: u8,
+}
+
+#[derive(Args)]
+struct AggregationIsmSetModulesAndThreshold {
+    #[arg(long, short)]
+    program_id: Pubkey,
+    #[arg(long, value_delimiter = ',')]
+    modules: Vec<Pubkey>,
+    #[arg(long)]
+    threshold: u8,
+}
+
+#[derive(Args)]
+struct AggregationIsmQuery {
+    #[arg(long, short)]
+    program_id: Pubkey,
+}
+
 #[derive(Args)]
 pub(crate) struct HelloWorldCmd {
     #[command(subcommand)]
@@ -748,6 +926,8 @@ fn main() {
         HyperlaneSealevelCmd::MultisigIsmMessageId(cmd) => {
             process_multisig_ism_message_id_cmd(ctx, cmd)
         }
+        HyperlaneSealevelCmd::RoutingIsm(cmd) => process_routing_ism_cmd(ctx, cmd),
+        HyperlaneSealevelCmd::AggregationIsm(cmd) => process_aggregation_ism_cmd(ctx, cmd),
         HyperlaneSealevelCmd::Core(cmd) => process_core_cmd(ctx, cmd),
         HyperlaneSealevelCmd::WarpRoute(cmd) => process_warp_route_cmd(ctx, cmd),
         HyperlaneSealevelCmd::HelloWorld(cmd) => process_helloworld_cmd(ctx, cmd),
@@ -872,6 +1052,42 @@ fn process_mailbox_cmd(ctx: Context, cmd: MailboxCmd) {
                 )
                 .send_with_payer();
         }
+        MailboxSubCmd::CreateLookupTable(create) => {
+            let recent_slot = ctx
+                .client
+                .get_slot_with_commitment(CommitmentConfig::finalized())
+                .unwrap();
+            let (instruction, lookup_table) =
+                create_lookup_table(ctx.payer_pubkey, ctx.payer_pubkey, recent_slot);
+            ctx.new_txn()
+                .add_with_description(
+                    instruction,
+                    format!("Creating address lookup table {}", lookup_table),
+                )
+                .send_with_payer();
+
+            let (inbox_account, _inbox_bump) =
+                Pubkey::find_program_address(mailbox_inbox_pda_seeds!(), &create.program_id);
+            let accounts = [system_program::id(), inbox_account, spl_noop::id()]
+                .into_iter()
+                .chain(create.accounts)
+                .collect::<Vec<_>>();
+            for chunk in accounts.chunks(MAX_ADDRESSES_PER_EXTEND) {
+                let instruction = extend_lookup_table(
+                    lookup_table,
+                    ctx.payer_pubkey,
+                    Some(ctx.payer_pubkey),
+                    chunk.to_vec(),
+                );
+                ctx.new_txn()
+                    .add_with_description(
+                        instruction,
+                        format!("Adding {} addresses to the lookup table", chunk.len()),
+                    )
+                    .send_with_payer();
+            }
+            println!("Address lookup table: {}", lookup_table);
+        }
     };
 }
 
@@ -1066,8 +1282,9 @@ fn process_token_cmd(ctx: Context, cmd: TokenCmd) {
             // 11.   [writeable] Gas payment PDA.
             // 12.   [] OPTIONAL - The Overhead IGP program, if the configured IGP is an Overhead IGP.
             // 13.   [writeable] The IGP account.
+            // 14.   [] OPTIONAL - The gas oracle account, if the IGP's gas oracle reads from one.
             //       ---- End if ----
-            // 14..N [??..??] Plugin-specific accounts.
+            // 15..N [??..??] Plugin-specific accounts.
             let mut accounts = vec![
                 AccountMeta::new_readonly(system_program::id(), false),
                 AccountMeta::new_readonly(spl_noop::id(), false),
@@ -1094,7 +1311,7 @@ fn process_token_cmd(ctx: Context, cmd: TokenCmd) {
                     AccountMeta::new(gas_payment_pda, false),
                 ]);
 
-                match igp_account_type {
+                let igp_account_id = match igp_account_type {
                     InterchainGasPaymasterType::OverheadIgp(overhead_igp_account_id) => {
                         let overhead_igp_account = ctx
                             .client
@@ -1110,10 +1327,29 @@ fn process_token_cmd(ctx: Context, cmd: TokenCmd) {
                             AccountMeta::new_readonly(overhead_igp_account_id, false),
                             AccountMeta::new(overhead_igp_account.inner, false),
                         ]);
+                        overhead_igp_account.inner
                     }
                     InterchainGasPaymasterType::Igp(igp_account_id) => {
                         accounts.push(AccountMeta::new(igp_account_id, false));
+                        igp_account_id
                     }
+                };
+
+                let igp_account = ctx
+                    .client
+                    .get_account_with_commitment(&igp_account_id, ctx.commitment)
+                    .unwrap()
+                    .value
+                    .unwrap();
+                let igp_account = IgpAccount::fetch(&mut &igp_account.data[..])
+                    .unwrap()
+                    .into_inner();
+                if let Some(gas_oracle_account) = igp_account
+                    .gas_oracles
+                    .get(&xfer.destination_domain)
+                    .and_then(GasOracle::oracle_account)
+                {
+                    accounts.push(AccountMeta::new_readonly(*gas_oracle_account, false));
                 }
             }
 
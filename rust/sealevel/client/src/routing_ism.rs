@@ -0,0 +1,142 @@
+use std::path::Path;
+
+use solana_program::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+use crate::{
+    artifacts::{write_json, SingularProgramIdArtifact},
+    cmd_utils::{create_and_write_keypair, create_new_directory, deploy_program},
+    Context, RoutingIsmCmd, RoutingIsmSubCmd,
+};
+
+use hyperlane_sealevel_routing_ism::{
+    accounts::RoutingIsmAccount,
+    instruction::{
+        init_instruction, set_routes_instruction, storage_pda_key, transfer_ownership_instruction,
+        RouteConfig,
+    },
+};
+
+pub(crate) fn process_routing_ism_cmd(mut ctx: Context, cmd: RoutingIsmCmd) {
+    match cmd.cmd {
+        RoutingIsmSubCmd::Deploy(deploy) => {
+            let environments_dir = create_new_directory(
+                &deploy.env_args.environments_dir,
+                &deploy.env_args.environment,
+            );
+            let ism_dir = create_new_directory(&environments_dir, "routing-ism");
+            let chain_dir = create_new_directory(&ism_dir, &deploy.chain);
+            let context_dir = create_new_directory(&chain_dir, &deploy.context);
+            let key_dir = create_new_directory(&context_dir, "keys");
+
+            let ism_program_id = deploy_routing_ism(&mut ctx, &deploy.built_so_dir, true, &key_dir);
+
+            write_json::<SingularProgramIdArtifact>(
+                &context_dir.join("program-ids.json"),
+                ism_program_id.into(),
+            );
+        }
+        RoutingIsmSubCmd::Init(init) => {
+            let instruction = init_instruction(init.program_id, ctx.payer_pubkey).unwrap();
+            ctx.new_txn().add(instruction).send_with_payer();
+        }
+        RoutingIsmSubCmd::SetRoute(set_route) => {
+            set_routes(
+                &mut ctx,
+                set_route.program_id,
+                vec![RouteConfig {
+                    domain: set_route.domain,
+                    ism: Some(set_route.ism),
+                }],
+            );
+        }
+        RoutingIsmSubCmd::RemoveRoute(remove_route) => {
+            set_routes(
+                &mut ctx,
+                remove_route.program_id,
+                vec![RouteConfig {
+                    domain: remove_route.domain,
+                    ism: None,
+                }],
+            );
+        }
+        RoutingIsmSubCmd::Query(query) => {
+            let accounts = ctx
+                .client
+                .get_multiple_accounts_with_commitment(
+                    &[storage_pda_key(&query.program_id)],
+                    ctx.commitment,
+                )
+                .unwrap()
+                .value;
+            let storage = RoutingIsmAccount::fetch(&mut &accounts[0].as_ref().unwrap().data[..])
+                .unwrap()
+                .into_inner();
+            println!("Routing ISM storage: {:#?}", storage);
+        }
+        RoutingIsmSubCmd::TransferOwnership(transfer_ownership) => {
+            let instruction = transfer_ownership_instruction(
+                transfer_ownership.program_id,
+                ctx.payer_pubkey,
+                Some(transfer_ownership.new_owner),
+            )
+            .unwrap();
+
+            ctx.new_txn()
+                .add_with_description(
+                    instruction,
+                    format!("Transfer ownership to {}", transfer_ownership.new_owner),
+                )
+                .send_with_payer();
+        }
+    }
+}
+
+fn deploy_routing_ism(
+    ctx: &mut Context,
+    built_so_dir: &Path,
+    use_existing_keys: bool,
+    key_dir: &Path,
+) -> Pubkey {
+    let (keypair, keypair_path) = create_and_write_keypair(
+        key_dir,
+        "hyperlane_sealevel_routing_ism-keypair.json",
+        use_existing_keys,
+    );
+    let program_id = keypair.pubkey();
+
+    deploy_program(
+        ctx.payer_keypair_path(),
+        keypair_path.to_str().unwrap(),
+        built_so_dir
+            .join("hyperlane_sealevel_routing_ism.so")
+            .to_str()
+            .unwrap(),
+        &ctx.client.url(),
+    );
+
+    println!("Deployed Routing ISM at program ID {}", program_id);
+
+    // Initialize
+    let instruction = init_instruction(program_id, ctx.payer_pubkey).unwrap();
+
+    ctx.new_txn()
+        .add_with_description(
+            instruction,
+            format!(
+                "Initializing Routing ISM with payer & owner {}",
+                ctx.payer_pubkey
+            ),
+        )
+        .send_with_payer();
+
+    program_id
+}
+
+fn set_routes(ctx: &mut Context, program_id: Pubkey, routes: Vec<RouteConfig>) {
+    let description = format!("Set routes {:?}", routes);
+    let instruction = set_routes_instruction(program_id, ctx.payer_pubkey, routes).unwrap();
+    ctx.new_txn()
+        .add_with_description(instruction, description)
+        .send_with_payer();
+}
@@ -1,8 +1,9 @@
 use access_control::AccessControl;
 use borsh::{BorshDeserialize, BorshSerialize};
 use hyperlane_core::H256;
-use hyperlane_sealevel_igp::instruction::{
-    Instruction as IgpInstruction, PayForGas as IgpPayForGas,
+use hyperlane_sealevel_igp::{
+    accounts::InterchainGasPaymasterType,
+    instruction::{Instruction as IgpInstruction, PayForGas as IgpPayForGas},
 };
 use hyperlane_sealevel_mailbox::instruction::{
     Instruction as MailboxInstruction, OutboxDispatch as MailboxOutboxDispatch,
@@ -137,6 +138,10 @@ pub trait HyperlaneRouterDispatch: HyperlaneRouter + HyperlaneConnectionClient {
     /// Dispatches a message to the remote router for the provided destination domain,
     /// paying for gas with the IGP.
     /// Errors if there is no IGP configured.
+    ///
+    /// `payment_account_metas` are the accounts of the IGP's `PayForGas` instruction,
+    /// including the gas oracle account if the IGP's gas oracle for the destination
+    /// domain reads from one. See `GasOracle::oracle_account`.
     #[allow(clippy::too_many_arguments)]
     fn dispatch_with_gas(
         &self,
@@ -160,7 +165,7 @@ pub trait HyperlaneRouterDispatch: HyperlaneRouter + HyperlaneConnectionClient {
         )?;
 
         // Call the IGP to pay for gas.
-        let (igp_program_id, _) = self
+        let (igp_program_id, igp_account_type) = self
             .interchain_gas_paymaster()
             .ok_or(ProgramError::InvalidArgument)?;
 
@@ -170,6 +175,10 @@ pub trait HyperlaneRouterDispatch: HyperlaneRouter + HyperlaneConnectionClient {
                 message_id,
                 destination_domain,
                 gas_amount,
+                with_overhead_igp: matches!(
+                    igp_account_type,
+                    InterchainGasPaymasterType::OverheadIgp(_)
+                ),
             }),
             payment_account_metas,
         );
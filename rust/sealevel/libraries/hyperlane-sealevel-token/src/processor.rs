@@ -12,7 +12,7 @@ use hyperlane_sealevel_connection_client::{
     },
     HyperlaneConnectionClient, HyperlaneConnectionClientSetterAccessControl,
 };
-use hyperlane_sealevel_igp::accounts::InterchainGasPaymasterType;
+use hyperlane_sealevel_igp::accounts::{GasOracle, IgpAccount, InterchainGasPaymasterType};
 use hyperlane_sealevel_mailbox::{
     mailbox_message_dispatch_authority_pda_seeds, mailbox_process_authority_pda_seeds,
 };
@@ -270,8 +270,10 @@ where
     /// 11.   `[writeable]` Gas payment PDA.
     /// 12.   `[]` OPTIONAL - The Overhead IGP program, if the configured IGP is an Overhead IGP.
     /// 13.   `[writeable]` The IGP account.
+    /// 14.   `[]` OPTIONAL - The gas oracle account, if the IGP's gas oracle for the
+    ///       destination domain reads from one.
     ///      ---- End if ----
-    /// 14..N `[??..??]` Plugin-specific accounts.
+    /// 15..N `[??..??]` Plugin-specific accounts.
     pub fn transfer_remote(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
@@ -369,6 +371,7 @@ where
                 // 4. `[writeable]` Gas payment PDA.
                 // 5. `[writeable]` The IGP account.
                 // 6. `[]` Overhead IGP account (optional).
+                // 7. `[]` Gas oracle account (optional).
 
                 let mut igp_payment_account_metas = vec![
                     AccountMeta::new_readonly(solana_program::system_program::id(), false),
@@ -385,11 +388,12 @@ where
                     igp_payment_pda_account.clone(),
                 ];
 
-                match igp_account_type {
+                let igp_account = match igp_account_type {
                     InterchainGasPaymasterType::Igp(_) => {
                         igp_payment_account_metas
                             .push(AccountMeta::new(*configured_igp_account.key, false));
                         igp_payment_account_infos.push(configured_igp_account.clone());
+                        configured_igp_account
                     }
                     InterchainGasPaymasterType::OverheadIgp(_) => {
                         // Account 13: The inner IGP account.
@@ -402,9 +406,27 @@ where
                         ]);
                         igp_payment_account_infos
                             .extend([inner_igp_account.clone(), configured_igp_account.clone()]);
+                        inner_igp_account
                     }
                 };
 
+                // Account 14: The gas oracle account, if the IGP's gas oracle reads from one.
+                // The IGP verifies the IGP account itself.
+                let igp = IgpAccount::fetch(&mut &igp_account.data.borrow()[..])?.into_inner();
+                if let Some(gas_oracle_account_key) = igp
+                    .gas_oracles
+                    .get(&xfer.destination_domain)
+                    .and_then(GasOracle::oracle_account)
+                {
+                    let gas_oracle_account = next_account_info(accounts_iter)?;
+                    if gas_oracle_account.key != gas_oracle_account_key {
+                        return Err(ProgramError::InvalidArgument);
+                    }
+                    igp_payment_account_metas
+                        .push(AccountMeta::new_readonly(*gas_oracle_account.key, false));
+                    igp_payment_account_infos.push(gas_oracle_account.clone());
+                }
+
                 Some((igp_payment_account_metas, igp_payment_account_infos))
             } else {
                 None
@@ -0,0 +1,92 @@
+//! Helpers for ISMs that defer to other ISMs via CPI, like routing and
+//! aggregation ISMs.
+
+use borsh::BorshDeserialize;
+use serializable_account_meta::{SerializableAccountMeta, SimulationReturnData};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    program::{get_return_data, invoke},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{verify_account_metas_pda_key, InterchainSecurityModuleInstruction, VerifyInstruction};
+
+/// Invokes the `Verify` instruction of an ISM, passing it `account_infos`.
+pub fn verify(
+    ism: &Pubkey,
+    metadata: Vec<u8>,
+    message: Vec<u8>,
+    account_infos: &[AccountInfo],
+) -> ProgramResult {
+    let instruction =
+        InterchainSecurityModuleInstruction::Verify(VerifyInstruction { metadata, message });
+    let account_metas = account_infos
+        .iter()
+        .map(|account_info| AccountMeta {
+            pubkey: *account_info.key,
+            is_signer: account_info.is_signer,
+            is_writable: account_info.is_writable,
+        })
+        .collect();
+    invoke(
+        &Instruction::new_with_bytes(*ism, &instruction.encode()?, account_metas),
+        account_infos,
+    )
+}
+
+/// Gets the account metas required by the `Verify` instruction of an ISM by
+/// invoking its `VerifyAccountMetas` instruction.
+///
+/// The ISM and its `VerifyAccountMetas` PDA are looked up in `account_infos`,
+/// all of which are passed on after the PDA in case the ISM defers to other
+/// ISMs in turn. Returns `None` if either of them wasn't provided, in which
+/// case the caller is expected to return them so they're provided next time.
+pub fn verify_account_metas(
+    ism: &Pubkey,
+    metadata: Vec<u8>,
+    message: Vec<u8>,
+    account_infos: &[AccountInfo],
+) -> Result<Option<Vec<AccountMeta>>, ProgramError> {
+    let pda_key = verify_account_metas_pda_key(ism);
+    let pda_info = match account_infos.iter().find(|info| info.key == &pda_key) {
+        Some(pda_info) if account_infos.iter().any(|info| info.key == ism) => pda_info,
+        _ => return Ok(None),
+    };
+
+    let mut infos = vec![pda_info.clone()];
+    infos.extend(
+        account_infos
+            .iter()
+            .filter(|info| info.key != &pda_key && info.key != ism)
+            .cloned(),
+    );
+    let instruction = InterchainSecurityModuleInstruction::VerifyAccountMetas(VerifyInstruction {
+        metadata,
+        message,
+    });
+    let account_metas = infos
+        .iter()
+        .map(|info| AccountMeta::new_readonly(*info.key, false))
+        .collect();
+    invoke(
+        &Instruction::new_with_bytes(*ism, &instruction.encode()?, account_metas),
+        &infos,
+    )?;
+
+    // If there's no return data at all, the ISM doesn't require any accounts.
+    let account_metas = match get_return_data() {
+        Some((program_id, return_data)) if &program_id == ism => {
+            SimulationReturnData::<Vec<SerializableAccountMeta>>::try_from_slice(&return_data)
+                .map_err(|err| ProgramError::BorshIoError(err.to_string()))?
+                .return_data
+                .into_iter()
+                .map(AccountMeta::from)
+                .collect()
+        }
+        _ => vec![],
+    };
+    Ok(Some(account_metas))
+}
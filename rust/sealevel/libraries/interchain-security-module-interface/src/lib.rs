@@ -1,7 +1,9 @@
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::program_error::ProgramError;
+use solana_program::{instruction::AccountMeta, program_error::ProgramError, pubkey::Pubkey};
 use spl_type_length_value::discriminator::Discriminator;
 
+pub mod cpi;
+
 /// Instructions that a Hyperlane interchain security module is expected to process.
 /// The first 8 bytes of the encoded instruction is a discriminator that
 /// allows programs to implement the required interface.
@@ -12,8 +14,15 @@ pub enum InterchainSecurityModuleInstruction {
     /// Verifies a message.
     Verify(VerifyInstruction),
     /// Gets the list of AccountMetas required for the `Verify` instruction.
-    /// The only account expected to be passed into this instruction is the
-    /// read-only PDA relating to the program ID and the seeds `VERIFY_ACCOUNT_METAS_PDA_SEEDS`
+    /// The first account expected to be passed into this instruction is the
+    /// read-only PDA relating to the program ID and the seeds `VERIFY_ACCOUNT_METAS_PDA_SEEDS`.
+    ///
+    /// ISMs that defer to other ISMs, like routing and aggregation ISMs, get
+    /// the account metas of those ISMs by invoking their `VerifyAccountMetas`
+    /// instruction, which requires their program and PDA to be passed in too.
+    /// These ISMs return the program and PDA of any ISM they defer to, so
+    /// callers are expected to call this instruction again with the returned
+    /// account metas following the PDA, until `has_unresolved_isms` is false.
     VerifyAccountMetas(VerifyInstruction),
 }
 
@@ -47,6 +56,29 @@ const VERIFY_ACCOUNT_METAS_DISCRIMINATOR_SLICE: &[u8] = &VERIFY_ACCOUNT_METAS_DI
 pub const VERIFY_ACCOUNT_METAS_PDA_SEEDS: &[&[u8]] =
     &[b"hyperlane_ism", b"-", b"verify", b"-", b"account_metas"];
 
+/// The max number of times `VerifyAccountMetas` is expected to be called to
+/// resolve the account metas of ISMs deferring to other ISMs. Each call
+/// resolves one more level of nesting, and ISMs can't be nested deeper than
+/// the CPI depth limit allows anyway.
+pub const MAX_VERIFY_ACCOUNT_METAS_CALLS: usize = 4;
+
+/// Gets the PDA expected to be passed into the `VerifyAccountMetas`
+/// instruction of an ISM.
+pub fn verify_account_metas_pda_key(ism: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(VERIFY_ACCOUNT_METAS_PDA_SEEDS, ism).0
+}
+
+/// Whether the account metas returned by a `VerifyAccountMetas` instruction
+/// include an ISM, followed by its `VerifyAccountMetas` PDA, that wasn't in
+/// the `provided` accounts. The account metas of such an ISM can only be
+/// resolved by calling `VerifyAccountMetas` again with the returned accounts.
+pub fn has_unresolved_isms(provided: &[Pubkey], account_metas: &[AccountMeta]) -> bool {
+    account_metas.windows(2).any(|pair| {
+        !provided.contains(&pair[1].pubkey)
+            && pair[1].pubkey == verify_account_metas_pda_key(&pair[0].pubkey)
+    })
+}
+
 impl InterchainSecurityModuleInstruction {
     pub fn encode(&self) -> Result<Vec<u8>, ProgramError> {
         let mut buf = vec![];
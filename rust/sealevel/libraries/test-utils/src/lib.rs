@@ -18,7 +18,8 @@ use solana_sdk::{
 use spl_token_2022::{extension::StateWithExtensions, state::Account};
 
 use hyperlane_sealevel_interchain_security_module_interface::{
-    InterchainSecurityModuleInstruction, VerifyInstruction, VERIFY_ACCOUNT_METAS_PDA_SEEDS,
+    has_unresolved_isms, verify_account_metas_pda_key, InterchainSecurityModuleInstruction,
+    VerifyInstruction, MAX_VERIFY_ACCOUNT_METAS_CALLS,
 };
 use hyperlane_sealevel_mailbox::{
     instruction::{InboxProcess, Init as InitMailbox, Instruction as MailboxInstruction},
@@ -225,6 +226,9 @@ pub async fn get_recipient_ism(
 }
 
 /// Gets the account metas required for the ISM's `Verify` instruction.
+/// ISMs that defer to other ISMs require `VerifyAccountMetas` to be called
+/// again with the previously returned account metas, until the account metas
+/// of all the ISMs they defer to are resolved.
 pub async fn get_ism_verify_account_metas(
     banks_client: &mut BanksClient,
     payer: &Keypair,
@@ -236,15 +240,30 @@ pub async fn get_ism_verify_account_metas(
         metadata,
         message,
     });
+    let instruction_data = instruction.encode().unwrap();
+    let verify_account_metas_pda_key = verify_account_metas_pda_key(&ism);
+
+    let mut account_metas = vec![];
+    for _ in 0..MAX_VERIFY_ACCOUNT_METAS_CALLS {
+        let mut accounts = vec![AccountMeta::new(verify_account_metas_pda_key, false)];
+        accounts.extend(account_metas);
+        let provided = accounts
+            .iter()
+            .map(|account_meta| account_meta.pubkey)
+            .collect::<Vec<_>>();
+
+        account_metas = get_account_metas(
+            banks_client,
+            payer,
+            Instruction::new_with_bytes(ism, &instruction_data, accounts),
+        )
+        .await?;
+        if !has_unresolved_isms(&provided, &account_metas) {
+            break;
+        }
+    }
 
-    get_account_metas_with_instruction_bytes(
-        banks_client,
-        payer,
-        ism,
-        &instruction.encode().unwrap(),
-        VERIFY_ACCOUNT_METAS_PDA_SEEDS,
-    )
-    .await
+    Ok(account_metas)
 }
 
 /// Gets the account metas required for the recipient's `MessageRecipientInstruction::Handle` instruction.
@@ -8,7 +8,7 @@ use hyperlane_sealevel_connection_client::{
     router::{HyperlaneRouterAccessControl, HyperlaneRouterDispatch, RemoteRouterConfig},
     HyperlaneConnectionClient,
 };
-use hyperlane_sealevel_igp::accounts::InterchainGasPaymasterType;
+use hyperlane_sealevel_igp::accounts::{GasOracle, IgpAccount, InterchainGasPaymasterType};
 use hyperlane_sealevel_mailbox::{
     mailbox_message_dispatch_authority_pda_seeds, mailbox_process_authority_pda_seeds,
 };
@@ -171,6 +171,8 @@ fn init(program_id: &Pubkey, accounts: &[AccountInfo], init: Init) -> ProgramRes
 /// 11. `[writeable]` The gas payment PDA.
 /// 12. `[]` OPTIONAL - The Overhead IGP program, if the configured IGP is an Overhead IGP.
 /// 13. `[writeable]` The IGP account.
+/// 14. `[]` OPTIONAL - The gas oracle account, if the IGP's gas oracle for the
+///     destination domain reads from one.
 ///     ---- end if an IGP is configured ----
 fn send_hello_world(
     program_id: &Pubkey,
@@ -268,6 +270,7 @@ fn send_hello_world(
             // 4. `[writeable]` Gas payment PDA.
             // 5. `[writeable]` The IGP account.
             // 6. `[]` Overhead IGP account (optional).
+            // 7. `[]` Gas oracle account (optional).
 
             let mut igp_payment_account_metas = vec![
                 AccountMeta::new_readonly(solana_program::system_program::id(), false),
@@ -284,11 +287,12 @@ fn send_hello_world(
                 igp_payment_pda_account_info.clone(),
             ];
 
-            match igp_account_type {
+            let igp_account_info = match igp_account_type {
                 InterchainGasPaymasterType::Igp(_) => {
                     igp_payment_account_metas
                         .push(AccountMeta::new(*configured_igp_account_info.key, false));
                     igp_payment_account_infos.push(configured_igp_account_info.clone());
+                    configured_igp_account_info
                 }
                 InterchainGasPaymasterType::OverheadIgp(_) => {
                     // Account 13: The inner IGP account.
@@ -303,9 +307,29 @@ fn send_hello_world(
                         inner_igp_account_info.clone(),
                         configured_igp_account_info.clone(),
                     ]);
+                    inner_igp_account_info
                 }
             };
 
+            // Account 14: The gas oracle account, if the IGP's gas oracle reads from one.
+            // The IGP verifies the IGP account itself.
+            let igp = IgpAccount::fetch(&mut &igp_account_info.data.borrow()[..])?.into_inner();
+            if let Some(gas_oracle_account_key) = igp
+                .gas_oracles
+                .get(&hello_world.destination)
+                .and_then(GasOracle::oracle_account)
+            {
+                let gas_oracle_account_info = next_account_info(accounts_iter)?;
+                if gas_oracle_account_info.key != gas_oracle_account_key {
+                    return Err(ProgramError::InvalidArgument);
+                }
+                igp_payment_account_metas.push(AccountMeta::new_readonly(
+                    *gas_oracle_account_info.key,
+                    false,
+                ));
+                igp_payment_account_infos.push(gas_oracle_account_info.clone());
+            }
+
             Some((igp_payment_account_metas, igp_payment_account_infos))
         } else {
             None
@@ -1,6 +1,10 @@
+use borsh::BorshSerialize;
 use hyperlane_core::H256;
 
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use solana_program::{
     instruction::{AccountMeta, Instruction},
@@ -10,7 +14,7 @@ use solana_program::{
 };
 use solana_program_test::*;
 use solana_sdk::{
-    instruction::InstructionError, signature::Signature, signature::Signer,
+    account::Account, instruction::InstructionError, signature::Signature, signature::Signer,
     signer::keypair::Keypair, transaction::TransactionError,
 };
 
@@ -24,11 +28,12 @@ use access_control::AccessControl;
 use account_utils::{AccountData, DiscriminatorPrefixed, DiscriminatorPrefixedData};
 use hyperlane_sealevel_igp::{
     accounts::{
-        GasOracle, GasPaymentAccount, GasPaymentData, Igp, IgpAccount, OverheadIgp,
-        OverheadIgpAccount, ProgramData, ProgramDataAccount, RemoteGasData, SOL_DECIMALS,
-        TOKEN_EXCHANGE_RATE_SCALE,
+        DataAccountGasOracle, GasOracle, GasPaymentAccount, GasPaymentData, Igp, IgpAccount,
+        OverheadIgp, OverheadIgpAccount, ProgramData, ProgramDataAccount, RemoteGasData,
+        SOL_DECIMALS, TOKEN_EXCHANGE_RATE_SCALE,
     },
     error::Error as IgpError,
+    gas_oracle::{OracleGasData, OraclePrice},
     igp_gas_payment_pda_seeds, igp_pda_seeds, igp_program_data_pda_seeds,
     instruction::{
         GasOracleConfig, GasOverheadConfig, InitIgp, InitOverheadIgp,
@@ -42,6 +47,8 @@ const TEST_DESTINATION_DOMAIN: u32 = 11111;
 const TEST_GAS_AMOUNT: u64 = 300000;
 const TEST_GAS_OVERHEAD_AMOUNT: u64 = 100000;
 const LOCAL_DECIMALS: u8 = SOL_DECIMALS;
+const DATA_ACCOUNT_DATA_OFFSET: u32 = 8;
+const DATA_ACCOUNT_MAX_STALENESS: u64 = 3600;
 
 async fn setup_client() -> (BanksClient, Keypair) {
     let program_id = igp_program_id();
@@ -56,6 +63,51 @@ async fn setup_client() -> (BanksClient, Keypair) {
     (banks_client, payer)
 }
 
+/// Sets up a client with a data account holding gas data published
+/// `publish_age` seconds ago, at `DATA_ACCOUNT_DATA_OFFSET`.
+async fn setup_client_with_data_account(publish_age: i64) -> (BanksClient, Keypair, Pubkey) {
+    let program_id = igp_program_id();
+    let mut program_test = ProgramTest::new(
+        "hyperlane_sealevel_igp",
+        program_id,
+        processor!(igp_process_instruction),
+    );
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    let oracle_data = OracleGasData {
+        // 0.2 exchange rate (remote token less valuable)
+        token_exchange_rate: OraclePrice { price: 2, expo: -1 },
+        // 150 gas price
+        gas_price: OraclePrice {
+            price: 150,
+            expo: 0,
+        },
+        publish_time: now - publish_age,
+    };
+    let mut data = vec![0u8; DATA_ACCOUNT_DATA_OFFSET as usize];
+    data.extend(oracle_data.try_to_vec().unwrap());
+
+    let data_account = Pubkey::new_unique();
+    program_test.add_account(
+        data_account,
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            // Data accounts are owned by the program updating them.
+            owner: Pubkey::new_unique(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    let (banks_client, payer, _recent_blockhash) = program_test.start().await;
+
+    (banks_client, payer, data_account)
+}
+
 async fn initialize(
     banks_client: &mut BanksClient,
     payer: &Keypair,
@@ -742,6 +794,27 @@ async fn quote_gas_payment(
     gas_amount: u64,
     igp_key: Pubkey,
     overhead_igp_key: Option<Pubkey>,
+) -> Result<u64, BanksClientError> {
+    quote_gas_payment_with_gas_oracle_accounts(
+        banks_client,
+        payer,
+        destination_domain,
+        gas_amount,
+        igp_key,
+        overhead_igp_key,
+        vec![],
+    )
+    .await
+}
+
+async fn quote_gas_payment_with_gas_oracle_accounts(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    destination_domain: u32,
+    gas_amount: u64,
+    igp_key: Pubkey,
+    overhead_igp_key: Option<Pubkey>,
+    gas_oracle_accounts: Vec<AccountMeta>,
 ) -> Result<u64, BanksClientError> {
     let mut accounts = vec![
         AccountMeta::new_readonly(system_program::id(), false),
@@ -750,12 +823,14 @@ async fn quote_gas_payment(
     if let Some(overhead_igp_key) = overhead_igp_key {
         accounts.push(AccountMeta::new_readonly(overhead_igp_key, false));
     }
+    accounts.extend(gas_oracle_accounts);
 
     let instruction = Instruction::new_with_borsh(
         igp_program_id(),
         &IgpInstruction::QuoteGasPayment(QuoteGasPayment {
             destination_domain,
             gas_amount,
+            with_overhead_igp: overhead_igp_key.is_some(),
         }),
         accounts,
     );
@@ -976,6 +1051,30 @@ async fn pay_for_gas(
     destination_domain: u32,
     gas_amount: u64,
     message_id: H256,
+) -> Result<(Pubkey, Keypair, Signature), BanksClientError> {
+    pay_for_gas_with_gas_oracle_accounts(
+        banks_client,
+        payer,
+        igp,
+        overhead_igp,
+        vec![],
+        destination_domain,
+        gas_amount,
+        message_id,
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn pay_for_gas_with_gas_oracle_accounts(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    igp: Pubkey,
+    overhead_igp: Option<Pubkey>,
+    gas_oracle_accounts: Vec<AccountMeta>,
+    destination_domain: u32,
+    gas_amount: u64,
+    message_id: H256,
 ) -> Result<(Pubkey, Keypair, Signature), BanksClientError> {
     let program_id = igp_program_id();
     let unique_payment_account = Keypair::new();
@@ -993,6 +1092,7 @@ async fn pay_for_gas(
     // 4. `[writeable]` Gas payment PDA.
     // 5. `[writeable]` The IGP account.
     // 6. `[]` Overhead IGP account (optional).
+    // 7..N `[]` Gas oracle accounts (optional).
     let mut accounts = vec![
         AccountMeta::new_readonly(system_program::id(), false),
         AccountMeta::new(payer.pubkey(), true),
@@ -1004,6 +1104,7 @@ async fn pay_for_gas(
     if let Some(overhead_igp) = overhead_igp {
         accounts.push(AccountMeta::new_readonly(overhead_igp, false));
     }
+    accounts.extend(gas_oracle_accounts);
 
     let instruction = Instruction::new_with_borsh(
         program_id,
@@ -1011,6 +1112,7 @@ async fn pay_for_gas(
             destination_domain,
             gas_amount,
             message_id,
+            with_overhead_igp: overhead_igp.is_some(),
         }),
         accounts,
     );
@@ -1271,6 +1373,179 @@ async fn test_pay_for_gas_errors_if_no_gas_oracle() {
     );
 }
 
+// ============ Data account gas oracle ============
+
+fn data_account_gas_oracle(data_account: Pubkey) -> GasOracle {
+    GasOracle::DataAccount(DataAccountGasOracle {
+        data_account,
+        data_offset: DATA_ACCOUNT_DATA_OFFSET,
+        token_decimals: LOCAL_DECIMALS,
+        max_staleness: DATA_ACCOUNT_MAX_STALENESS,
+    })
+}
+
+#[tokio::test]
+async fn test_pay_for_gas_with_data_account_gas_oracle() {
+    let (mut banks_client, payer, data_account) = setup_client_with_data_account(60).await;
+    let message_id = H256::random();
+
+    initialize(&mut banks_client, &payer).await.unwrap();
+
+    let (igp_key, overhead_igp_key) = setup_test_igps(
+        &mut banks_client,
+        &payer,
+        TEST_DESTINATION_DOMAIN,
+        data_account_gas_oracle(data_account),
+        Some(TEST_GAS_OVERHEAD_AMOUNT),
+    )
+    .await;
+
+    let quote = quote_gas_payment_with_gas_oracle_accounts(
+        &mut banks_client,
+        &payer,
+        TEST_DESTINATION_DOMAIN,
+        TEST_GAS_AMOUNT - TEST_GAS_OVERHEAD_AMOUNT,
+        igp_key,
+        Some(overhead_igp_key),
+        vec![AccountMeta::new_readonly(data_account, false)],
+    )
+    .await
+    .unwrap();
+    // 300,000 destination gas * 150 gas price * 0.2 exchange rate
+    assert_eq!(quote, 9000000u64);
+
+    let igp_balance_before = banks_client.get_balance(igp_key).await.unwrap();
+
+    let (gas_payment_pda_key, unique_payment_account, payment_tx_signature) =
+        pay_for_gas_with_gas_oracle_accounts(
+            &mut banks_client,
+            &payer,
+            igp_key,
+            Some(overhead_igp_key),
+            vec![AccountMeta::new_readonly(data_account, false)],
+            TEST_DESTINATION_DOMAIN,
+            TEST_GAS_AMOUNT - TEST_GAS_OVERHEAD_AMOUNT,
+            message_id,
+        )
+        .await
+        .unwrap();
+
+    let igp_balance_after = banks_client.get_balance(igp_key).await.unwrap();
+    assert_eq!(igp_balance_after - igp_balance_before, quote);
+
+    assert_gas_payment(
+        &mut banks_client,
+        igp_key,
+        payment_tx_signature,
+        unique_payment_account.pubkey(),
+        gas_payment_pda_key,
+        TEST_DESTINATION_DOMAIN,
+        TEST_GAS_AMOUNT,
+        quote,
+        message_id,
+        0,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_quote_gas_payment_errors_if_data_account_is_stale() {
+    let (mut banks_client, payer, data_account) =
+        setup_client_with_data_account(2 * DATA_ACCOUNT_MAX_STALENESS as i64).await;
+
+    let (igp_key, _) = setup_test_igps(
+        &mut banks_client,
+        &payer,
+        TEST_DESTINATION_DOMAIN,
+        data_account_gas_oracle(data_account),
+        None,
+    )
+    .await;
+
+    assert_transaction_error(
+        quote_gas_payment_with_gas_oracle_accounts(
+            &mut banks_client,
+            &payer,
+            TEST_DESTINATION_DOMAIN,
+            TEST_GAS_AMOUNT,
+            igp_key,
+            None,
+            vec![AccountMeta::new_readonly(data_account, false)],
+        )
+        .await,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(IgpError::StaleGasOracleData as u32),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn test_pay_for_gas_errors_if_data_account_not_provided() {
+    let (mut banks_client, payer, data_account) = setup_client_with_data_account(60).await;
+
+    initialize(&mut banks_client, &payer).await.unwrap();
+
+    let (igp_key, _) = setup_test_igps(
+        &mut banks_client,
+        &payer,
+        TEST_DESTINATION_DOMAIN,
+        data_account_gas_oracle(data_account),
+        None,
+    )
+    .await;
+
+    assert_transaction_error(
+        pay_for_gas_with_gas_oracle_accounts(
+            &mut banks_client,
+            &payer,
+            igp_key,
+            None,
+            vec![],
+            TEST_DESTINATION_DOMAIN,
+            TEST_GAS_AMOUNT,
+            H256::random(),
+        )
+        .await,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(IgpError::MissingGasOracleAccount as u32),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn test_pay_for_gas_errors_if_overhead_igp_not_owned_by_program() {
+    let (mut banks_client, payer, data_account) = setup_client_with_data_account(60).await;
+
+    initialize(&mut banks_client, &payer).await.unwrap();
+
+    let (igp_key, _) = setup_test_igps(
+        &mut banks_client,
+        &payer,
+        TEST_DESTINATION_DOMAIN,
+        data_account_gas_oracle(data_account),
+        None,
+    )
+    .await;
+
+    // The account passed as the overhead IGP must be owned by the IGP program.
+    assert_transaction_error(
+        pay_for_gas_with_gas_oracle_accounts(
+            &mut banks_client,
+            &payer,
+            igp_key,
+            Some(Pubkey::new_unique()),
+            vec![AccountMeta::new_readonly(data_account, false)],
+            TEST_DESTINATION_DOMAIN,
+            TEST_GAS_AMOUNT,
+            H256::random(),
+        )
+        .await,
+        TransactionError::InstructionError(0, InstructionError::IncorrectProgramId),
+    );
+}
+
 // ============ Claim ============
 
 #[tokio::test]
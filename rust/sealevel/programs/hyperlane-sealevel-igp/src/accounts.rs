@@ -45,7 +45,22 @@ impl InterchainGasPaymasterType {
 pub enum GasOracle {
     /// Remote gas data stored directly in the variant data.
     RemoteGasData(RemoteGasData),
-    // Future gas oracle variants could include a Pyth type, generalized CPI type, etc.
+    /// Remote gas data read from an external data account.
+    DataAccount(DataAccountGasOracle),
+    /// Remote gas data returned by a CPI into an oracle program.
+    Cpi(CpiGasOracle),
+}
+
+impl GasOracle {
+    /// The external account the gas oracle reads from, if any. It's expected
+    /// to be passed to `PayForGas` and `QuoteGasPayment` after the IGP accounts.
+    pub fn oracle_account(&self) -> Option<&Pubkey> {
+        match self {
+            GasOracle::RemoteGasData(_) => None,
+            GasOracle::DataAccount(oracle) => Some(&oracle.data_account),
+            GasOracle::Cpi(oracle) => Some(&oracle.program_id),
+        }
+    }
 }
 
 impl Default for GasOracle {
@@ -182,37 +197,29 @@ impl SizedData for Igp {
 }
 
 impl Igp {
-    /// Quotes a gas payment.
+    /// Returns the gas oracle for the destination domain.
     /// Returns an error if a gas oracle is not set for the destination domain.
+    pub fn gas_oracle(&self, destination_domain: u32) -> Result<&GasOracle, Error> {
+        self.gas_oracles
+            .get(&destination_domain)
+            .ok_or(Error::NoGasOracleSetForDestinationDomain)
+    }
+
+    /// Quotes a gas payment using gas data stored in the IGP.
+    /// Returns an error if a gas oracle is not set for the destination domain,
+    /// or if the gas oracle reads from an external account, see
+    /// `gas_oracle::remote_gas_data`.
     pub fn quote_gas_payment(
         &self,
         destination_domain: u32,
         gas_amount: u64,
     ) -> Result<u64, Error> {
-        let oracle = self
-            .gas_oracles
-            .get(&destination_domain)
-            .ok_or(Error::NoGasOracleSetForDestinationDomain)?;
-        let GasOracle::RemoteGasData(RemoteGasData {
-            token_exchange_rate,
-            gas_price,
-            token_decimals,
-        }) = oracle;
-
-        // Arithmetic is done using U256 to avoid overflows.
-
-        // The total cost quoted in the destination chain's native token.
-        let destination_gas_cost = U256::from(gas_amount) * U256::from(*gas_price);
-
-        // Convert to the local native token (decimals not yet accounted for).
-        let origin_cost = (destination_gas_cost * U256::from(*token_exchange_rate))
-            / U256::from(TOKEN_EXCHANGE_RATE_SCALE);
-
-        // Convert from the remote token's decimals to the local token's decimals.
-        let origin_cost = convert_decimals(origin_cost, *token_decimals, SOL_DECIMALS);
-
-        // Panics if an overflow occurs.
-        Ok(origin_cost.as_u64())
+        match self.gas_oracle(destination_domain)? {
+            GasOracle::RemoteGasData(remote_gas_data) => {
+                Ok(remote_gas_data.quote_gas_payment(gas_amount))
+            }
+            GasOracle::DataAccount(_) | GasOracle::Cpi(_) => Err(Error::MissingGasOracleAccount),
+        }
     }
 }
 
@@ -244,6 +251,73 @@ pub struct RemoteGasData {
     pub token_decimals: u8,
 }
 
+impl RemoteGasData {
+    /// Quotes the payment in lamports for the given amount of gas.
+    pub fn quote_gas_payment(&self, gas_amount: u64) -> u64 {
+        let RemoteGasData {
+            token_exchange_rate,
+            gas_price,
+            token_decimals,
+        } = self;
+
+        // Arithmetic is done using U256 to avoid overflows.
+
+        // The total cost quoted in the destination chain's native token.
+        let destination_gas_cost = U256::from(gas_amount) * U256::from(*gas_price);
+
+        // Convert to the local native token (decimals not yet accounted for).
+        let origin_cost = (destination_gas_cost * U256::from(*token_exchange_rate))
+            / U256::from(TOKEN_EXCHANGE_RATE_SCALE);
+
+        // Convert from the remote token's decimals to the local token's decimals.
+        let origin_cost = convert_decimals(origin_cost, *token_decimals, SOL_DECIMALS);
+
+        // Panics if an overflow occurs.
+        origin_cost.as_u64()
+    }
+}
+
+/// A gas oracle reading remote gas data from an external account kept up to
+/// date by an off-chain updater. This isn't a Pyth price account reader: the
+/// account data at `data_offset` is expected to be a borsh-encoded
+/// `OracleGasData`, i.e. 32 little-endian bytes laid out as:
+///
+/// | Offset | Type  | Field                       |
+/// |--------|-------|-----------------------------|
+/// | 0      | `u64` | `token_exchange_rate.price` |
+/// | 8      | `i32` | `token_exchange_rate.expo`  |
+/// | 12     | `u64` | `gas_price.price`           |
+/// | 20     | `i32` | `gas_price.expo`            |
+/// | 24     | `i64` | `publish_time`              |
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct DataAccountGasOracle {
+    /// The account holding the gas data.
+    pub data_account: Pubkey,
+    /// The offset of the `OracleGasData` in the account data.
+    pub data_offset: u32,
+    /// The number of decimals for the remote token.
+    pub token_decimals: u8,
+    /// The max age in seconds of the gas data.
+    pub max_staleness: u64,
+}
+
+/// A gas oracle invoking an oracle program that returns remote gas data.
+/// The program is expected to process `GetRemoteGasDataInstruction`s and
+/// set a borsh-encoded `OracleGasData` as the return data.
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct CpiGasOracle {
+    /// The oracle program.
+    pub program_id: Pubkey,
+    /// The number of decimals for the remote token.
+    pub token_decimals: u8,
+    /// The max age in seconds of the returned data.
+    pub max_staleness: u64,
+}
+
 /// A discriminator used to easily identify gas payment accounts.
 /// This is the first 8 bytes of the account data.
 pub const GAS_PAYMENT_DISCRIMINATOR: &[u8; 8] = b"GASPAYMT";
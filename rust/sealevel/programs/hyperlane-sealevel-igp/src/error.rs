@@ -9,6 +9,15 @@ pub enum Error {
     /// No gas oracle set for destination domain.
     #[error("No gas oracle set for destination domain")]
     NoGasOracleSetForDestinationDomain = 1,
+    /// The gas oracle account for the destination domain wasn't provided.
+    #[error("Gas oracle account not provided")]
+    MissingGasOracleAccount = 2,
+    /// The gas oracle data is older than the max staleness allowed.
+    #[error("Stale gas oracle data")]
+    StaleGasOracleData = 3,
+    /// The gas oracle data couldn't be read or converted.
+    #[error("Invalid gas oracle data")]
+    InvalidGasOracleData = 4,
 }
 
 impl From<Error> for ProgramError {
@@ -0,0 +1,262 @@
+//! Gas oracles that read remote gas data from outside of the IGP account.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::{Clock, UnixTimestamp},
+    instruction::{AccountMeta, Instruction},
+    program::{get_return_data, invoke},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use hyperlane_core::U256;
+
+use crate::{
+    accounts::{CpiGasOracle, DataAccountGasOracle, GasOracle, RemoteGasData},
+    error::Error,
+};
+
+/// The number of decimals of `RemoteGasData::token_exchange_rate`, see
+/// `TOKEN_EXCHANGE_RATE_SCALE`.
+const TOKEN_EXCHANGE_RATE_DECIMALS: i64 = 19;
+
+/// First 8 bytes of `hash::hashv(&[b"hyperlane-gas-oracle:get-remote-gas-data"])`
+pub const GET_REMOTE_GAS_DATA_DISCRIMINATOR: [u8; 8] = [159, 42, 206, 25, 233, 233, 171, 186];
+
+/// A fixed-point price, representing `price * 10^expo`.
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Default, Clone, Copy)]
+pub struct OraclePrice {
+    /// The price, without the exponent applied.
+    pub price: u64,
+    /// The decimal exponent of the price.
+    pub expo: i32,
+}
+
+impl OraclePrice {
+    /// Returns the price scaled by 10^`decimals`, or `None` if it doesn't fit
+    /// in a u128.
+    fn scaled(&self, decimals: i64) -> Option<u128> {
+        let exponent = i64::from(self.expo) + decimals;
+        let scale = U256::from(10u64).checked_pow(U256::from(exponent.unsigned_abs()))?;
+        let scaled = if exponent >= 0 {
+            U256::from(self.price).checked_mul(scale)?
+        } else {
+            U256::from(self.price) / scale
+        };
+        if scaled > U256::from(u128::MAX) {
+            return None;
+        }
+        Some(scaled.as_u128())
+    }
+}
+
+/// Remote gas data as provided by an external gas oracle, either stored in a
+/// data account or returned by an oracle program. See `DataAccountGasOracle`
+/// for its encoding.
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Default, Clone)]
+pub struct OracleGasData {
+    /// The amount of local tokens one remote token is worth.
+    pub token_exchange_rate: OraclePrice,
+    /// The gas price for the remote chain, in the smallest unit of the
+    /// remote token.
+    pub gas_price: OraclePrice,
+    /// The unix timestamp the data was published at.
+    pub publish_time: UnixTimestamp,
+}
+
+impl OracleGasData {
+    /// Converts the oracle data into remote gas data, erroring if the prices
+    /// are out of range.
+    pub fn to_remote_gas_data(&self, token_decimals: u8) -> Result<RemoteGasData, Error> {
+        Ok(RemoteGasData {
+            token_exchange_rate: self
+                .token_exchange_rate
+                .scaled(TOKEN_EXCHANGE_RATE_DECIMALS)
+                .ok_or(Error::InvalidGasOracleData)?,
+            gas_price: self
+                .gas_price
+                .scaled(0)
+                .ok_or(Error::InvalidGasOracleData)?,
+            token_decimals,
+        })
+    }
+}
+
+/// Instruction invoked on the program of a `Cpi` gas oracle. The program
+/// is expected to set a borsh-encoded `OracleGasData` as its return data.
+/// The first 8 bytes of the encoded instruction is a discriminator that
+/// allows programs to implement the required interface.
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq, Clone)]
+pub struct GetRemoteGasDataInstruction {
+    /// The destination domain to get the remote gas data of.
+    pub destination_domain: u32,
+}
+
+impl GetRemoteGasDataInstruction {
+    /// Encodes the instruction, prefixed by its discriminator.
+    pub fn encode(&self) -> Result<Vec<u8>, ProgramError> {
+        let mut buf = GET_REMOTE_GAS_DATA_DISCRIMINATOR.to_vec();
+        buf.extend_from_slice(
+            &self
+                .try_to_vec()
+                .map_err(|err| ProgramError::BorshIoError(err.to_string()))?[..],
+        );
+        Ok(buf)
+    }
+
+    /// Decodes the instruction, erroring if it isn't prefixed by its
+    /// discriminator.
+    pub fn decode(buf: &[u8]) -> Result<Self, ProgramError> {
+        match buf.strip_prefix(&GET_REMOTE_GAS_DATA_DISCRIMINATOR[..]) {
+            Some(data) => {
+                Self::try_from_slice(data).map_err(|_| ProgramError::InvalidInstructionData)
+            }
+            None => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// Returns the remote gas data of a gas oracle, reading it from
+/// `oracle_accounts` if the gas oracle is an external one.
+///
+/// For a `DataAccount` gas oracle, the data account is expected to be the
+/// first of `oracle_accounts`. For a `Cpi` gas oracle, the oracle program is
+/// expected to be the first, followed by the accounts it requires.
+pub fn remote_gas_data(
+    gas_oracle: &GasOracle,
+    destination_domain: u32,
+    oracle_accounts: &[AccountInfo],
+) -> Result<RemoteGasData, ProgramError> {
+    let (oracle_data, token_decimals, max_staleness) = match gas_oracle {
+        GasOracle::RemoteGasData(remote_gas_data) => return Ok(remote_gas_data.clone()),
+        GasOracle::DataAccount(oracle) => (
+            read_custom_data_account(oracle, oracle_accounts)?,
+            oracle.token_decimals,
+            oracle.max_staleness,
+        ),
+        GasOracle::Cpi(oracle) => (
+            invoke_oracle_program(oracle, destination_domain, oracle_accounts)?,
+            oracle.token_decimals,
+            oracle.max_staleness,
+        ),
+    };
+
+    let age = Clock::get()?
+        .unix_timestamp
+        .saturating_sub(oracle_data.publish_time);
+    if age > i64::try_from(max_staleness).unwrap_or(i64::MAX) {
+        return Err(Error::StaleGasOracleData.into());
+    }
+
+    Ok(oracle_data.to_remote_gas_data(token_decimals)?)
+}
+
+/// Returns the first of `oracle_accounts`, erroring if it isn't `expected`.
+fn first_oracle_account<'a, 'b>(
+    expected: &Pubkey,
+    oracle_accounts: &'a [AccountInfo<'b>],
+) -> Result<&'a AccountInfo<'b>, Error> {
+    match oracle_accounts.first() {
+        Some(account_info) if account_info.key == expected => Ok(account_info),
+        _ => Err(Error::MissingGasOracleAccount),
+    }
+}
+
+/// Reads the `OracleGasData` of a custom data account, laid out as documented
+/// on `DataAccountGasOracle`. Price feed accounts with their own layout, such as
+/// Pyth price accounts, aren't supported and fail to decode.
+fn read_custom_data_account(
+    oracle: &DataAccountGasOracle,
+    oracle_accounts: &[AccountInfo],
+) -> Result<OracleGasData, ProgramError> {
+    let data_account_info = first_oracle_account(&oracle.data_account, oracle_accounts)?;
+    let data = data_account_info.try_borrow_data()?;
+    let mut oracle_data = data
+        .get(oracle.data_offset as usize..)
+        .ok_or(Error::InvalidGasOracleData)?;
+    OracleGasData::deserialize(&mut oracle_data).map_err(|_| Error::InvalidGasOracleData.into())
+}
+
+fn invoke_oracle_program(
+    oracle: &CpiGasOracle,
+    destination_domain: u32,
+    oracle_accounts: &[AccountInfo],
+) -> Result<OracleGasData, ProgramError> {
+    first_oracle_account(&oracle.program_id, oracle_accounts)?;
+
+    let instruction = Instruction {
+        program_id: oracle.program_id,
+        accounts: oracle_accounts[1..]
+            .iter()
+            .map(|account_info| AccountMeta::new_readonly(*account_info.key, false))
+            .collect(),
+        data: GetRemoteGasDataInstruction { destination_domain }.encode()?,
+    };
+    invoke(&instruction, oracle_accounts)?;
+
+    match get_return_data() {
+        Some((program_id, return_data)) if program_id == oracle.program_id => {
+            OracleGasData::try_from_slice(&return_data)
+                .map_err(|_| Error::InvalidGasOracleData.into())
+        }
+        _ => Err(Error::InvalidGasOracleData.into()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::accounts::TOKEN_EXCHANGE_RATE_SCALE;
+
+    #[test]
+    fn test_to_remote_gas_data() {
+        let oracle_data = OracleGasData {
+            // 0.25
+            token_exchange_rate: OraclePrice {
+                price: 25,
+                expo: -2,
+            },
+            // 15 gwei
+            gas_price: OraclePrice { price: 15, expo: 9 },
+            publish_time: 0,
+        };
+        assert_eq!(
+            oracle_data.to_remote_gas_data(18).unwrap(),
+            RemoteGasData {
+                token_exchange_rate: TOKEN_EXCHANGE_RATE_SCALE / 4,
+                gas_price: 15_000_000_000,
+                token_decimals: 18,
+            }
+        );
+
+        // Out of range prices
+        let oracle_data = OracleGasData {
+            gas_price: OraclePrice {
+                price: u64::MAX,
+                expo: 30,
+            },
+            ..oracle_data
+        };
+        assert_eq!(
+            oracle_data.to_remote_gas_data(18),
+            Err(Error::InvalidGasOracleData)
+        );
+    }
+
+    #[test]
+    fn test_get_remote_gas_data_instruction_encoding() {
+        let instruction = GetRemoteGasDataInstruction {
+            destination_domain: 1234,
+        };
+        let encoded = instruction.encode().unwrap();
+        assert_eq!(&encoded[..8], &GET_REMOTE_GAS_DATA_DISCRIMINATOR[..]);
+        assert_eq!(
+            GetRemoteGasDataInstruction::decode(&encoded).unwrap(),
+            instruction
+        );
+        assert!(GetRemoteGasDataInstruction::decode(&encoded[8..]).is_err());
+    }
+}
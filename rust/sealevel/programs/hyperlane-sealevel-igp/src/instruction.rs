@@ -72,6 +72,8 @@ pub struct PayForGas {
     pub destination_domain: u32,
     /// The gas amount.
     pub gas_amount: u64,
+    /// Whether an overhead IGP account is passed after the IGP account.
+    pub with_overhead_igp: bool,
 }
 
 /// Quotes a gas payment.
@@ -81,6 +83,8 @@ pub struct QuoteGasPayment {
     pub destination_domain: u32,
     /// The gas amount.
     pub gas_amount: u64,
+    /// Whether an overhead IGP account is passed after the IGP account.
+    pub with_overhead_igp: bool,
 }
 
 /// A config for setting a destination gas overhead.
@@ -268,6 +272,7 @@ pub fn pay_for_gas_instruction(
     payer: Pubkey,
     igp: Pubkey,
     overhead_igp: Option<Pubkey>,
+    gas_oracle_accounts: Vec<AccountMeta>,
     unique_gas_payment_account_pubkey: Pubkey,
     message_id: H256,
     destination_domain: u32,
@@ -286,6 +291,7 @@ pub fn pay_for_gas_instruction(
         message_id,
         destination_domain,
         gas_amount,
+        with_overhead_igp: overhead_igp.is_some(),
     });
 
     // Accounts:
@@ -295,7 +301,8 @@ pub fn pay_for_gas_instruction(
     // 3. `[signer]` Unique gas payment account.
     // 4. `[writeable]` Gas payment PDA.
     // 5. `[writeable]` The IGP account.
-    // 6. `[]` Overhead IGP account (optional).
+    // 6. `[]` Overhead IGP account (optional, see `PayForGas::with_overhead_igp`).
+    // 7..N `[]` Gas oracle accounts (optional).
     let mut accounts = vec![
         AccountMeta::new_readonly(solana_program::system_program::id(), false),
         AccountMeta::new(payer, true),
@@ -307,6 +314,7 @@ pub fn pay_for_gas_instruction(
     if let Some(overhead_igp) = overhead_igp {
         accounts.push(AccountMeta::new_readonly(overhead_igp, false));
     }
+    accounts.extend(gas_oracle_accounts);
 
     let instruction = SolanaInstruction {
         program_id,
@@ -6,6 +6,7 @@
 
 pub mod accounts;
 pub mod error;
+pub mod gas_oracle;
 pub mod instruction;
 pub mod pda_seeds;
 pub mod processor;
@@ -30,6 +30,7 @@ use crate::{
         GasPaymentAccount, GasPaymentData, Igp, IgpAccount, OverheadIgp, OverheadIgpAccount,
         ProgramData, ProgramDataAccount,
     },
+    gas_oracle::remote_gas_data,
     igp_gas_payment_pda_seeds, igp_pda_seeds, igp_program_data_pda_seeds,
     instruction::{
         GasOracleConfig, GasOverheadConfig, InitIgp, InitOverheadIgp,
@@ -264,7 +265,9 @@ fn init_igp_variant<T: account_utils::DiscriminatorPrefixedData + SizedData>(
 /// 3. `[signer]` Unique gas payment account.
 /// 4. `[writeable]` Gas payment PDA.
 /// 5. `[writeable]` The IGP account.
-/// 6. `[]` Overhead IGP account (optional).
+/// 6. `[]` Overhead IGP account, if `payment.with_overhead_igp` is set.
+/// 7..N `[]` Gas oracle accounts, if the gas oracle for the destination domain
+///    reads from a data account or an oracle program. See `gas_oracle::remote_gas_data`.
 fn pay_for_gas(program_id: &Pubkey, accounts: &[AccountInfo], payment: PayForGas) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
 
@@ -329,14 +332,13 @@ fn pay_for_gas(program_id: &Pubkey, accounts: &[AccountInfo], payment: PayForGas
         return Err(ProgramError::InvalidSeeds);
     }
 
+    let gas_oracle = igp.gas_oracle(payment.destination_domain)?;
+
     // Account 6: Overhead IGP account (optional).
     // The caller is expected to only provide an overhead IGP they are comfortable
     // with / have configured themselves.
-    let gas_amount = if let Some(overhead_igp_info) = accounts_iter.next() {
-        if overhead_igp_info.owner != program_id {
-            return Err(ProgramError::IncorrectProgramId);
-        }
-
+    let gas_amount = if payment.with_overhead_igp {
+        let overhead_igp_info = next_overhead_igp_info(program_id, accounts_iter)?;
         let overhead_igp =
             OverheadIgpAccount::fetch(&mut &overhead_igp_info.data.borrow()[..])?.into_inner();
         let overhead_igp_key = Pubkey::create_program_address(
@@ -352,7 +354,13 @@ fn pay_for_gas(program_id: &Pubkey, accounts: &[AccountInfo], payment: PayForGas
         payment.gas_amount
     };
 
-    let required_payment = igp.quote_gas_payment(payment.destination_domain, gas_amount)?;
+    // Accounts 7..N: Gas oracle accounts.
+    let remote_gas_data = remote_gas_data(
+        gas_oracle,
+        payment.destination_domain,
+        accounts_iter.as_slice(),
+    )?;
+    let required_payment = remote_gas_data.quote_gas_payment(gas_amount);
 
     // Transfer the required payment to the IGP.
     invoke(
@@ -409,7 +417,9 @@ fn pay_for_gas(program_id: &Pubkey, accounts: &[AccountInfo], payment: PayForGas
 /// Accounts:
 /// 0. `[executable]` The system program.
 /// 1. `[]` The IGP account.
-/// 2. `[]` The overhead IGP account (optional).
+/// 2. `[]` The overhead IGP account, if `payment.with_overhead_igp` is set.
+/// 3..N `[]` Gas oracle accounts, if the gas oracle for the destination domain
+///    reads from a data account or an oracle program. See `gas_oracle::remote_gas_data`.
 fn quote_gas_payment(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -431,14 +441,14 @@ fn quote_gas_payment(
         return Err(ProgramError::IncorrectProgramId);
     }
 
+    let igp = IgpAccount::fetch(&mut &igp_info.data.borrow()[..])?.into_inner();
+    let gas_oracle = igp.gas_oracle(payment.destination_domain)?;
+
     // Account 2: Overhead IGP account (optional).
     // The caller is expected to only provide an overhead IGP they are comfortable
     // with / have configured themselves.
-    let gas_amount = if let Some(overhead_igp_info) = accounts_iter.next() {
-        if overhead_igp_info.owner != program_id {
-            return Err(ProgramError::IncorrectProgramId);
-        }
-
+    let gas_amount = if payment.with_overhead_igp {
+        let overhead_igp_info = next_overhead_igp_info(program_id, accounts_iter)?;
         let overhead_igp =
             OverheadIgpAccount::fetch(&mut &overhead_igp_info.data.borrow()[..])?.into_inner();
 
@@ -451,15 +461,32 @@ fn quote_gas_payment(
         payment.gas_amount
     };
 
-    let igp = IgpAccount::fetch(&mut &igp_info.data.borrow()[..])?.into_inner();
-
-    let required_payment = igp.quote_gas_payment(payment.destination_domain, gas_amount)?;
+    // Accounts 3..N: Gas oracle accounts.
+    let remote_gas_data = remote_gas_data(
+        gas_oracle,
+        payment.destination_domain,
+        accounts_iter.as_slice(),
+    )?;
+    let required_payment = remote_gas_data.quote_gas_payment(gas_amount);
 
     set_return_data(&SimulationReturnData::new(required_payment).try_to_vec()?);
 
     Ok(())
 }
 
+/// Returns the next account, which is expected to be an overhead IGP account
+/// owned by this program.
+fn next_overhead_igp_info<'a, 'b>(
+    program_id: &Pubkey,
+    accounts_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+) -> Result<&'a AccountInfo<'b>, ProgramError> {
+    let overhead_igp_info = next_account_info(accounts_iter)?;
+    if overhead_igp_info.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(overhead_igp_info)
+}
+
 /// Sets the beneficiary of an IGP.
 ///
 /// Accounts:
@@ -3,13 +3,19 @@
 //! new PDA accounts.
 
 use account_utils::DiscriminatorEncode;
+use borsh::BorshSerialize;
 use hyperlane_core::{Encode, HyperlaneMessage, H256, U256};
 use hyperlane_sealevel_connection_client::{
     gas_router::GasRouterConfig, router::RemoteRouterConfig,
 };
 use hyperlane_sealevel_igp::{
-    accounts::{GasPaymentAccount, GasPaymentData, InterchainGasPaymasterType},
+    accounts::{
+        DataAccountGasOracle, GasOracle, GasPaymentAccount, GasPaymentData,
+        InterchainGasPaymasterType, SOL_DECIMALS,
+    },
+    gas_oracle::{OracleGasData, OraclePrice},
     igp_gas_payment_pda_seeds,
+    instruction::{GasOracleConfig, Instruction as IgpInstruction},
 };
 use hyperlane_sealevel_mailbox::{
     accounts::{DispatchedMessage, DispatchedMessageAccount},
@@ -38,16 +44,21 @@ use solana_program::{
     instruction::{AccountMeta, Instruction},
     pubkey,
     pubkey::Pubkey,
+    rent::Rent,
 };
 use solana_program_test::*;
 use solana_sdk::{
+    account::Account,
     instruction::InstructionError,
     signature::Signer,
     signer::keypair::Keypair,
     transaction::{Transaction, TransactionError},
 };
 use spl_token_2022::instruction::initialize_mint2;
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 /// There are 1e9 lamports in one SOL.
 const ONE_SOL_IN_LAMPORTS: u64 = 1000000000;
@@ -57,11 +68,17 @@ const LOCAL_DECIMALS_U32: u32 = LOCAL_DECIMALS as u32;
 const REMOTE_DOMAIN: u32 = 4321;
 const REMOTE_DECIMALS: u8 = 18;
 const REMOTE_GAS_AMOUNT: u64 = 200000;
+/// The gas price held by the gas oracle data account.
+const GAS_ORACLE_GAS_PRICE: u64 = 3;
 
 fn hyperlane_sealevel_token_id() -> Pubkey {
     pubkey!("3MzUPjP5LEkiHH82nEAe28Xtz9ztuMqWc8UmuKxrpVQH")
 }
 
+fn gas_oracle_data_account() -> Pubkey {
+    pubkey!("Cm8FUXBhMKaJ9KP1RMWtYQAJkNcuznpyJ2CXq6MXXLMW")
+}
+
 async fn setup_client() -> (BanksClient, Keypair) {
     let program_id = hyperlane_sealevel_token_id();
     let mut program_test = ProgramTest::new(
@@ -104,11 +121,71 @@ async fn setup_client() -> (BanksClient, Keypair) {
         processor!(hyperlane_sealevel_test_ism::program::process_instruction),
     );
 
+    // Gas data for a data account gas oracle, see `set_data_account_gas_oracle`.
+    let oracle_data = OracleGasData {
+        // The remote token is worth as much as the local token
+        token_exchange_rate: OraclePrice { price: 1, expo: 0 },
+        gas_price: OraclePrice {
+            price: GAS_ORACLE_GAS_PRICE,
+            expo: 0,
+        },
+        publish_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64,
+    };
+    let data = oracle_data.try_to_vec().unwrap();
+    program_test.add_account(
+        gas_oracle_data_account(),
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: Pubkey::new_unique(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
     let (banks_client, payer, _recent_blockhash) = program_test.start().await;
 
     (banks_client, payer)
 }
 
+/// Makes the IGP read the gas data for `REMOTE_DOMAIN` from the gas oracle
+/// data account.
+async fn set_data_account_gas_oracle(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    igp: Pubkey,
+) -> Result<(), BanksClientError> {
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[Instruction::new_with_borsh(
+            igp_program_id(),
+            &IgpInstruction::SetGasOracleConfigs(vec![GasOracleConfig {
+                domain: REMOTE_DOMAIN,
+                gas_oracle: Some(GasOracle::DataAccount(DataAccountGasOracle {
+                    data_account: gas_oracle_data_account(),
+                    data_offset: 0,
+                    token_decimals: SOL_DECIMALS,
+                    max_staleness: 3600,
+                })),
+            }]),
+            vec![
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+                AccountMeta::new(igp, false),
+                AccountMeta::new_readonly(payer.pubkey(), true),
+            ],
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+
+    Ok(())
+}
+
 struct HyperlaneTokenAccounts {
     token: Pubkey,
     token_bump: u8,
@@ -830,6 +907,125 @@ async fn test_transfer_remote() {
     );
 }
 
+#[tokio::test]
+async fn test_transfer_remote_with_data_account_gas_oracle() {
+    let program_id = hyperlane_sealevel_token_id();
+    let mailbox_program_id = mailbox_id();
+
+    let token_sender = Keypair::new();
+    let token_sender_pubkey = token_sender.pubkey();
+
+    // Mint 100 tokens to the token sender's ATA.
+    let sender_initial_balance = 100 * 10u64.pow(LOCAL_DECIMALS_U32);
+    let (
+        mut banks_client,
+        payer,
+        mailbox_accounts,
+        igp_accounts,
+        hyperlane_token_accounts,
+        token_sender_ata,
+    ) = transfer_from_remote(
+        convert_decimals(
+            sender_initial_balance.into(),
+            LOCAL_DECIMALS,
+            REMOTE_DECIMALS,
+        )
+        .unwrap(),
+        None,
+        None,
+        Some(token_sender_pubkey),
+    )
+    .await
+    .unwrap();
+
+    transfer_lamports(
+        &mut banks_client,
+        &payer,
+        &token_sender_pubkey,
+        ONE_SOL_IN_LAMPORTS,
+    )
+    .await;
+
+    set_data_account_gas_oracle(&mut banks_client, &payer, igp_accounts.igp)
+        .await
+        .unwrap();
+
+    let unique_message_account_keypair = Keypair::new();
+    let (dispatched_message_key, _dispatched_message_bump) = Pubkey::find_program_address(
+        mailbox_dispatched_message_pda_seeds!(&unique_message_account_keypair.pubkey()),
+        &mailbox_program_id,
+    );
+    let (gas_payment_pda_key, _gas_payment_pda_bump) = Pubkey::find_program_address(
+        igp_gas_payment_pda_seeds!(&unique_message_account_keypair.pubkey()),
+        &igp_program_id(),
+    );
+
+    let transfer_amount = 69 * 10u64.pow(LOCAL_DECIMALS_U32);
+
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[Instruction::new_with_bytes(
+            program_id,
+            &HyperlaneTokenInstruction::TransferRemote(TransferRemote {
+                destination_domain: REMOTE_DOMAIN,
+                recipient: H256::random(),
+                amount_or_id: transfer_amount.into(),
+            })
+            .encode()
+            .unwrap(),
+            // Same accounts as in `test_transfer_remote`, with the gas oracle
+            // account after the IGP accounts.
+            vec![
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+                AccountMeta::new_readonly(spl_noop::id(), false),
+                AccountMeta::new_readonly(hyperlane_token_accounts.token, false),
+                AccountMeta::new_readonly(mailbox_accounts.program, false),
+                AccountMeta::new(mailbox_accounts.outbox, false),
+                AccountMeta::new_readonly(hyperlane_token_accounts.dispatch_authority, false),
+                AccountMeta::new_readonly(token_sender_pubkey, true),
+                AccountMeta::new_readonly(unique_message_account_keypair.pubkey(), true),
+                AccountMeta::new(dispatched_message_key, false),
+                AccountMeta::new_readonly(igp_accounts.program, false),
+                AccountMeta::new(igp_accounts.program_data, false),
+                AccountMeta::new(gas_payment_pda_key, false),
+                AccountMeta::new_readonly(igp_accounts.overhead_igp, false),
+                AccountMeta::new(igp_accounts.igp, false),
+                AccountMeta::new_readonly(gas_oracle_data_account(), false),
+                AccountMeta::new_readonly(spl_token_2022::id(), false),
+                AccountMeta::new(hyperlane_token_accounts.mint, false),
+                AccountMeta::new(token_sender_ata, false),
+            ],
+        )],
+        Some(&token_sender_pubkey),
+        &[&token_sender, &unique_message_account_keypair],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    assert_token_balance(
+        &mut banks_client,
+        &token_sender_ata,
+        sender_initial_balance - transfer_amount,
+    )
+    .await;
+
+    // The payment is quoted using the gas data in the data account.
+    let gas_payment_account_data = banks_client
+        .get_account(gas_payment_pda_key)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let gas_payment = GasPaymentAccount::fetch(&mut &gas_payment_account_data[..])
+        .unwrap()
+        .into_inner();
+    assert_eq!(gas_payment.gas_amount, REMOTE_GAS_AMOUNT);
+    assert_eq!(
+        gas_payment.payment,
+        REMOTE_GAS_AMOUNT * GAS_ORACLE_GAS_PRICE
+    );
+}
+
 #[tokio::test]
 async fn test_enroll_remote_router() {
     let program_id = hyperlane_sealevel_token_id();
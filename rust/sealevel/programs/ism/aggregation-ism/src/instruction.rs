@@ -0,0 +1,193 @@
+use account_utils::{DiscriminatorData, DiscriminatorEncode, PROGRAM_INSTRUCTION_DISCRIMINATOR};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction as SolanaInstruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use std::collections::HashSet;
+
+use crate::{aggregation_ism_storage_pda_seeds, error::Error};
+
+#[derive(BorshDeserialize, BorshSerialize, Debug, PartialEq)]
+pub enum Instruction {
+    /// Initializes the program with the modules and threshold.
+    ///
+    /// Accounts:
+    /// 0. `[signer]` The new owner and payer of the storage PDA.
+    /// 1. `[writable]` The storage PDA account.
+    /// 2. `[executable]` The system program account.
+    Initialize(ModulesAndThreshold),
+    /// Sets the modules and threshold.
+    ///
+    /// Accounts:
+    /// 0. `[signer]` The owner and payer of any storage PDA reallocation.
+    /// 1. `[writable]` The storage PDA account.
+    /// 2. `[executable]` The system program account.
+    SetModulesAndThreshold(ModulesAndThreshold),
+    /// Gets the owner from the storage PDA.
+    ///
+    /// Accounts:
+    /// 0. `[]` The storage PDA account.
+    GetOwner,
+    /// Sets the owner in the storage PDA.
+    ///
+    /// Accounts:
+    /// 0. `[signer]` The current owner.
+    /// 1. `[writable]` The storage PDA account.
+    TransferOwnership(Option<Pubkey>),
+}
+
+impl DiscriminatorData for Instruction {
+    const DISCRIMINATOR: [u8; Self::DISCRIMINATOR_LENGTH] = PROGRAM_INSTRUCTION_DISCRIMINATOR;
+}
+
+impl TryFrom<&[u8]> for Instruction {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        Self::try_from_slice(data).map_err(|_| ProgramError::InvalidInstructionData)
+    }
+}
+
+/// A configuration of the ISMs to aggregate and the number of them that
+/// must verify a message.
+#[derive(BorshDeserialize, BorshSerialize, Debug, PartialEq, Default, Clone)]
+pub struct ModulesAndThreshold {
+    pub modules: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+impl ModulesAndThreshold {
+    /// Validates the modules and threshold.
+    /// Returns an error if the threshold is zero, the threshold exceeds the
+    /// number of modules, or if the modules have any duplicates.
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        let modules_len = self.modules.len();
+
+        // Ensure the threshold is non-zero and doesn't exceed the number of modules.
+        if self.threshold == 0 || self.threshold as usize > modules_len {
+            return Err(Error::InvalidModulesAndThreshold.into());
+        }
+
+        // If the modules have any duplicates, error.
+        let mut set = HashSet::with_capacity(modules_len);
+        for module in &self.modules {
+            if !set.insert(module) {
+                return Err(Error::InvalidModulesAndThreshold.into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Gets the storage PDA key of the program.
+pub fn storage_pda_key(program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(aggregation_ism_storage_pda_seeds!(), program_id).0
+}
+
+/// Creates an Initialize instruction.
+pub fn init_instruction(
+    program_id: Pubkey,
+    payer: Pubkey,
+    modules_and_threshold: ModulesAndThreshold,
+) -> Result<SolanaInstruction, ProgramError> {
+    // Accounts:
+    // 0. `[signer]` The new owner and payer of the storage PDA.
+    // 1. `[writable]` The storage PDA account.
+    // 2. `[executable]` The system program account.
+    let instruction = SolanaInstruction {
+        program_id,
+        data: Instruction::Initialize(modules_and_threshold).encode()?,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(storage_pda_key(&program_id), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    };
+    Ok(instruction)
+}
+
+/// Creates a SetModulesAndThreshold instruction.
+pub fn set_modules_and_threshold_instruction(
+    program_id: Pubkey,
+    owner_payer: Pubkey,
+    modules_and_threshold: ModulesAndThreshold,
+) -> Result<SolanaInstruction, ProgramError> {
+    // Accounts:
+    // 0. `[signer]` The owner and payer of any storage PDA reallocation.
+    // 1. `[writable]` The storage PDA account.
+    // 2. `[executable]` The system program account.
+    let instruction = SolanaInstruction {
+        program_id,
+        data: Instruction::SetModulesAndThreshold(modules_and_threshold).encode()?,
+        accounts: vec![
+            AccountMeta::new(owner_payer, true),
+            AccountMeta::new(storage_pda_key(&program_id), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    };
+    Ok(instruction)
+}
+
+/// Creates a TransferOwnership instruction.
+pub fn transfer_ownership_instruction(
+    program_id: Pubkey,
+    owner_payer: Pubkey,
+    new_owner: Option<Pubkey>,
+) -> Result<SolanaInstruction, ProgramError> {
+    // 0. `[signer]` The current owner.
+    // 1. `[writable]` The storage PDA account.
+    let instruction = SolanaInstruction {
+        program_id,
+        data: Instruction::TransferOwnership(new_owner).encode()?,
+        accounts: vec![
+            AccountMeta::new(owner_payer, true),
+            AccountMeta::new(storage_pda_key(&program_id), false),
+        ],
+    };
+    Ok(instruction)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_modules_and_threshold_validate() {
+        let modules = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+
+        // Threshold below and equal to the number of modules
+        for threshold in 1..=2 {
+            let m = ModulesAndThreshold {
+                modules: modules.clone(),
+                threshold,
+            };
+            assert!(m.validate().is_ok());
+        }
+
+        // Zero threshold
+        let m = ModulesAndThreshold {
+            modules: modules.clone(),
+            threshold: 0,
+        };
+        assert_eq!(m.validate(), Err(Error::InvalidModulesAndThreshold.into()));
+
+        // Threshold exceeding the number of modules
+        let m = ModulesAndThreshold {
+            modules: modules.clone(),
+            threshold: 3,
+        };
+        assert_eq!(m.validate(), Err(Error::InvalidModulesAndThreshold.into()));
+
+        // Duplicate modules
+        let m = ModulesAndThreshold {
+            modules: vec![modules[0], modules[1], modules[0]],
+            threshold: 2,
+        };
+        assert_eq!(m.validate(), Err(Error::InvalidModulesAndThreshold.into()));
+    }
+}
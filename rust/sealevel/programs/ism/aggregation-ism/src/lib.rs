@@ -0,0 +1,11 @@
+//! An Interchain Security Module that verifies messages using a threshold
+//! of its configured ISMs.
+
+#![deny(warnings)]
+#![deny(unsafe_code)]
+
+pub mod accounts;
+pub mod error;
+pub mod instruction;
+pub mod metadata;
+pub mod processor;
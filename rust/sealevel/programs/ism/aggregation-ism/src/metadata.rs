@@ -0,0 +1,116 @@
+use hyperlane_core::Encode;
+
+use crate::error::Error;
+
+/// The metadata of each of the modules of an aggregation ISM.
+#[derive(Debug, PartialEq)]
+pub struct AggregationIsmMetadata {
+    /// The metadata of each module, in the order of the modules.
+    /// None if the module isn't used to verify the message.
+    pub modules_metadata: Vec<Option<Vec<u8>>>,
+}
+
+/// The length of the range of a module's metadata, i.e. a u32 start
+/// and a u32 end.
+const RANGE_LENGTH: usize = 8;
+
+/// Format of metadata:
+/// [8i:8i+4]   Start of the metadata of module i, or 0 if module i has no metadata
+/// [8i+4:8i+8] End of the metadata of module i
+/// [????:????] The metadata of the modules
+impl AggregationIsmMetadata {
+    /// Parses the metadata of an aggregation ISM with `module_count` modules.
+    pub fn from_bytes(bytes: &[u8], module_count: usize) -> Result<Self, Error> {
+        let ranges_len = module_count * RANGE_LENGTH;
+        if bytes.len() < ranges_len {
+            return Err(Error::InvalidMetadata);
+        }
+
+        let modules_metadata = bytes[..ranges_len]
+            .chunks(RANGE_LENGTH)
+            .map(|range| {
+                // These cannot panic since each range is RANGE_LENGTH bytes.
+                let start = u32::from_be_bytes(range[..4].try_into().unwrap()) as usize;
+                let end = u32::from_be_bytes(range[4..].try_into().unwrap()) as usize;
+                if start == 0 {
+                    return Ok(None);
+                }
+                if start < ranges_len || end < start || end > bytes.len() {
+                    return Err(Error::InvalidMetadata);
+                }
+                Ok(Some(bytes[start..end].to_vec()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { modules_metadata })
+    }
+}
+
+impl Encode for AggregationIsmMetadata {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: std::io::Write,
+    {
+        let mut bytes_written = 0;
+        let mut offset = self.modules_metadata.len() * RANGE_LENGTH;
+        for module_metadata in &self.modules_metadata {
+            let (start, end) = match module_metadata {
+                Some(module_metadata) => (offset, offset + module_metadata.len()),
+                None => (0, 0),
+            };
+            bytes_written += writer.write(&(start as u32).to_be_bytes())?;
+            bytes_written += writer.write(&(end as u32).to_be_bytes())?;
+            offset += end - start;
+        }
+        for module_metadata in self.modules_metadata.iter().flatten() {
+            bytes_written += writer.write(module_metadata)?;
+        }
+        Ok(bytes_written)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        let metadata = AggregationIsmMetadata {
+            modules_metadata: vec![Some(vec![1, 2, 3]), None, Some(vec![]), Some(vec![4, 5])],
+        };
+        let bytes = metadata.to_vec();
+        assert_eq!(
+            &bytes[..16],
+            &[0, 0, 0, 32, 0, 0, 0, 35, 0, 0, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(&bytes[32..], &[1, 2, 3, 4, 5]);
+        assert_eq!(
+            AggregationIsmMetadata::from_bytes(&bytes, 4).unwrap(),
+            metadata
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_errors_if_invalid() {
+        // Too short for the ranges
+        assert_eq!(
+            AggregationIsmMetadata::from_bytes(&[0; 15], 2),
+            Err(Error::InvalidMetadata)
+        );
+        // Range ending past the metadata
+        assert_eq!(
+            AggregationIsmMetadata::from_bytes(&[0, 0, 0, 8, 0, 0, 0, 10, 1], 1),
+            Err(Error::InvalidMetadata)
+        );
+        // Range starting within the ranges
+        assert_eq!(
+            AggregationIsmMetadata::from_bytes(&[0, 0, 0, 4, 0, 0, 0, 8], 1),
+            Err(Error::InvalidMetadata)
+        );
+        // Range ending before it starts
+        assert_eq!(
+            AggregationIsmMetadata::from_bytes(&[0, 0, 0, 9, 0, 0, 0, 8, 1], 1),
+            Err(Error::InvalidMetadata)
+        );
+    }
+}
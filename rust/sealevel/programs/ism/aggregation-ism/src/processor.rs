@@ -0,0 +1,460 @@
+use hyperlane_core::ModuleType;
+
+use access_control::AccessControl;
+use account_utils::{create_pda_account, DiscriminatorDecode, SizedData};
+use serializable_account_meta::{SerializableAccountMeta, SimulationReturnData};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::AccountMeta,
+    program::set_return_data,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    accounts::{AggregationIsmAccount, AggregationIsmData},
+    error::Error,
+    instruction::{Instruction, ModulesAndThreshold},
+    metadata::AggregationIsmMetadata,
+};
+
+use hyperlane_sealevel_interchain_security_module_interface::{
+    cpi, verify_account_metas_pda_key, InterchainSecurityModuleInstruction,
+};
+
+use borsh::BorshSerialize;
+
+const ISM_TYPE: ModuleType = ModuleType::Aggregation;
+
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+/// PDA seeds relating to the storage PDA account, which holds the owner
+/// and the modules and threshold. These are the same as
+/// `VERIFY_ACCOUNT_METAS_PDA_SEEDS`, so the modules and threshold are
+/// available to the `VerifyAccountMetas` instruction.
+#[macro_export]
+macro_rules! aggregation_ism_storage_pda_seeds {
+    () => {{
+        &[b"hyperlane_ism", b"-", b"verify", b"-", b"account_metas"]
+    }};
+
+    ($bump_seed:expr) => {{
+        &[
+            b"hyperlane_ism",
+            b"-",
+            b"verify",
+            b"-",
+            b"account_metas",
+            &[$bump_seed],
+        ]
+    }};
+}
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // First, try to decode the instruction as an interchain security module
+    // interface supported function based off the discriminator.
+    if let Ok(ism_instruction) = InterchainSecurityModuleInstruction::decode(instruction_data) {
+        return match ism_instruction {
+            InterchainSecurityModuleInstruction::Type => {
+                set_return_data(
+                    &SimulationReturnData::new(ISM_TYPE as u32)
+                        .try_to_vec()
+                        .map_err(|err| ProgramError::BorshIoError(err.to_string()))?[..],
+                );
+                Ok(())
+            }
+            InterchainSecurityModuleInstruction::Verify(verify_data) => verify(
+                program_id,
+                accounts,
+                verify_data.metadata,
+                verify_data.message,
+            ),
+            InterchainSecurityModuleInstruction::VerifyAccountMetas(verify_data) => {
+                let account_metas = verify_account_metas(
+                    program_id,
+                    accounts,
+                    verify_data.metadata,
+                    verify_data.message,
+                )?;
+                // Wrap it in the SimulationReturnData because serialized account_metas
+                // may end with zero byte(s), which are incorrectly truncated as
+                // simulated transaction return data.
+                // See `SimulationReturnData` for details.
+                let bytes = SimulationReturnData::new(account_metas)
+                    .try_to_vec()
+                    .map_err(|err| ProgramError::BorshIoError(err.to_string()))?;
+                set_return_data(&bytes[..]);
+                Ok(())
+            }
+        };
+    }
+
+    match Instruction::decode(instruction_data)? {
+        // Initializes the program.
+        Instruction::Initialize(modules_and_threshold) => {
+            initialize(program_id, accounts, modules_and_threshold)
+        }
+        // Sets the modules and threshold.
+        Instruction::SetModulesAndThreshold(modules_and_threshold) => {
+            set_modules_and_threshold(program_id, accounts, modules_and_threshold)
+        }
+        // Gets the owner of this program from the storage account.
+        Instruction::GetOwner => get_owner(program_id, accounts),
+        // Sets the owner of this program in the storage account.
+        Instruction::TransferOwnership(new_owner) => {
+            transfer_ownership(program_id, accounts, new_owner)
+        }
+    }
+}
+
+/// Initializes the program, creating the storage PDA account.
+///
+/// Accounts:
+/// 0. `[signer]` The new owner and payer of the storage PDA.
+/// 1. `[writable]` The storage PDA account.
+/// 2. `[executable]` The system program account.
+fn initialize(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    modules_and_threshold: ModulesAndThreshold,
+) -> ProgramResult {
+    // Validate the provided modules and threshold.
+    modules_and_threshold.validate()?;
+
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The new owner of this program and payer of the storage PDA.
+    let owner_account = next_account_info(accounts_iter)?;
+    if !owner_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Account 1: The storage PDA account.
+    let storage_pda_account = next_account_info(accounts_iter)?;
+    let (storage_pda_key, storage_pda_bump_seed) =
+        Pubkey::find_program_address(aggregation_ism_storage_pda_seeds!(), program_id);
+    if *storage_pda_account.key != storage_pda_key {
+        return Err(Error::AccountOutOfOrder.into());
+    }
+
+    // Ensure the storage PDA account isn't already initialized.
+    if let Ok(Some(_)) =
+        AggregationIsmAccount::fetch_data(&mut &storage_pda_account.data.borrow()[..])
+    {
+        return Err(Error::AlreadyInitialized.into());
+    }
+
+    // Account 2: The system program account.
+    let system_program_account = next_account_info(accounts_iter)?;
+    if !solana_program::system_program::check_id(system_program_account.key) {
+        return Err(Error::AccountOutOfOrder.into());
+    }
+
+    // Create the storage PDA account.
+    let storage_account = AggregationIsmAccount::from(AggregationIsmData {
+        bump_seed: storage_pda_bump_seed,
+        owner: Some(*owner_account.key),
+        modules_and_threshold,
+    });
+    create_pda_account(
+        owner_account,
+        &Rent::get()?,
+        storage_account.size(),
+        program_id,
+        system_program_account,
+        storage_pda_account,
+        aggregation_ism_storage_pda_seeds!(storage_pda_bump_seed),
+    )?;
+
+    // Store the storage data.
+    storage_account.store(storage_pda_account, false)?;
+
+    Ok(())
+}
+
+/// Verifies a message using the modules used to verify it, see `used_modules`.
+/// Each of them must verify the message.
+///
+/// Accounts:
+/// 0. `[]` The storage PDA account.
+/// For each module used to verify the message, in the order of the modules:
+///   - `[executable]` The module.
+///   - `[]` The `VerifyAccountMetas` PDA of the module.
+///   - `[??]` The accounts required by the module's `Verify` instruction.
+fn verify(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    metadata_bytes: Vec<u8>,
+    message_bytes: Vec<u8>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The storage PDA account.
+    let storage_pda_account = next_account_info(accounts_iter)?;
+    let storage_data = storage_data(program_id, storage_pda_account)?;
+    let modules = used_modules(&storage_data.modules_and_threshold, &metadata_bytes)?;
+
+    // Accounts 1..N: The modules and their accounts.
+    let modules_accounts = modules_accounts(&modules, accounts_iter.as_slice())?;
+
+    for ((module, module_metadata), module_accounts) in modules.into_iter().zip(modules_accounts) {
+        cpi::verify(
+            &module,
+            module_metadata,
+            message_bytes.clone(),
+            module_accounts,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Gets the list of AccountMetas required by the `Verify` instruction.
+///
+/// The account metas of a module can only be resolved if the module and its
+/// `VerifyAccountMetas` PDA are passed in, so until then only they are
+/// returned for the module.
+///
+/// Accounts:
+/// 0. `[]` The storage PDA account, which is this program's PDA relating to
+///         the seeds VERIFY_ACCOUNT_METAS_PDA_SEEDS.
+/// 1..N. `[]` OPTIONAL - The account metas returned by a previous call.
+fn verify_account_metas(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    metadata_bytes: Vec<u8>,
+    message_bytes: Vec<u8>,
+) -> Result<Vec<SerializableAccountMeta>, ProgramError> {
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The storage PDA account.
+    let storage_pda_account = next_account_info(accounts_iter)?;
+    let storage_data = storage_data(program_id, storage_pda_account)?;
+    let modules = used_modules(&storage_data.modules_and_threshold, &metadata_bytes)?;
+
+    // Accounts 1..N: The account metas returned by a previous call.
+    let previous_accounts = accounts_iter.as_slice();
+
+    let mut account_metas = vec![AccountMeta::new_readonly(*storage_pda_account.key, false)];
+    for (module, module_metadata) in modules {
+        account_metas.push(AccountMeta::new_readonly(module, false));
+        account_metas.push(AccountMeta::new_readonly(
+            verify_account_metas_pda_key(&module),
+            false,
+        ));
+        if let Some(module_account_metas) = cpi::verify_account_metas(
+            &module,
+            module_metadata,
+            message_bytes.clone(),
+            previous_accounts,
+        )? {
+            account_metas.extend(module_account_metas);
+        }
+    }
+
+    Ok(account_metas.into_iter().map(Into::into).collect())
+}
+
+/// Gets the modules used to verify a message, alongside their metadata.
+/// These are the first `threshold` modules that the metadata includes
+/// metadata for.
+fn used_modules(
+    modules_and_threshold: &ModulesAndThreshold,
+    metadata_bytes: &[u8],
+) -> Result<Vec<(Pubkey, Vec<u8>)>, ProgramError> {
+    let metadata =
+        AggregationIsmMetadata::from_bytes(metadata_bytes, modules_and_threshold.modules.len())?;
+    let threshold = modules_and_threshold.threshold as usize;
+
+    let modules = modules_and_threshold
+        .modules
+        .iter()
+        .zip(metadata.modules_metadata)
+        .filter_map(|(module, module_metadata)| {
+            module_metadata.map(|module_metadata| (*module, module_metadata))
+        })
+        .take(threshold)
+        .collect::<Vec<_>>();
+    if modules.len() < threshold {
+        return Err(Error::ThresholdNotMet.into());
+    }
+
+    Ok(modules)
+}
+
+/// Splits the accounts passed in after the storage PDA into the accounts
+/// required by each of the modules' `Verify` instruction, which follow the
+/// module and its `VerifyAccountMetas` PDA.
+///
+/// A module's accounts can include the program and PDA of a later module if
+/// the module defers to it, but never its own, so each module is searched
+/// for backwards from where the next module starts.
+fn modules_accounts<'a, 'b>(
+    modules: &[(Pubkey, Vec<u8>)],
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Vec<&'a [AccountInfo<'b>]>, ProgramError> {
+    let mut modules_accounts = Vec::with_capacity(modules.len());
+    let mut end = accounts.len();
+    for (module, _) in modules.iter().rev() {
+        let module_pda_key = verify_account_metas_pda_key(module);
+        let start = (0..end.saturating_sub(1))
+            .rev()
+            .find(|&i| accounts[i].key == module && accounts[i + 1].key == &module_pda_key)
+            .ok_or(Error::AccountOutOfOrder)?;
+        modules_accounts.push(&accounts[start + 2..end]);
+        end = start;
+    }
+    // The first module must immediately follow the storage PDA.
+    if end != 0 {
+        return Err(Error::AccountOutOfOrder.into());
+    }
+
+    modules_accounts.reverse();
+    Ok(modules_accounts)
+}
+
+/// Sets the modules and threshold.
+///
+/// Accounts:
+/// 0. `[signer]` The owner and payer of any storage PDA reallocation.
+/// 1. `[writable]` The storage PDA account.
+/// 2. `[executable]` The system program account.
+fn set_modules_and_threshold(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    modules_and_threshold: ModulesAndThreshold,
+) -> ProgramResult {
+    // Validate the provided modules and threshold.
+    modules_and_threshold.validate()?;
+
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The owner of this program.
+    // This is verified as correct further below.
+    let owner_account = next_account_info(accounts_iter)?;
+
+    // Account 1: The storage PDA account.
+    let storage_pda_account = next_account_info(accounts_iter)?;
+    let mut storage_data = storage_data(program_id, storage_pda_account)?;
+    // Ensure the owner account is the owner of this program.
+    storage_data.ensure_owner_signer(owner_account)?;
+
+    // Account 2: The system program account.
+    let system_program_account = next_account_info(accounts_iter)?;
+    if !solana_program::system_program::check_id(system_program_account.key) {
+        return Err(Error::AccountOutOfOrder.into());
+    }
+
+    storage_data.modules_and_threshold = modules_and_threshold;
+
+    // Store the new modules and threshold, reallocating the account if more space is needed.
+    AggregationIsmAccount::from(storage_data).store_with_rent_exempt_realloc(
+        storage_pda_account,
+        &Rent::get()?,
+        owner_account,
+        system_program_account,
+    )?;
+
+    Ok(())
+}
+
+/// Gets the owner of this program from the storage account, and returns it as return data.
+/// Intended to be used by instructions querying the owner.
+///
+/// Accounts:
+/// 0. `[]` The storage PDA account.
+fn get_owner(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The storage PDA account.
+    let storage_pda_account = next_account_info(accounts_iter)?;
+
+    let storage_data = storage_data(program_id, storage_pda_account)?;
+
+    // Wrap it in the SimulationReturnData because serialized `storage_data.owner`
+    // may end with zero byte(s), which are incorrectly truncated as
+    // simulated transaction return data.
+    // See `SimulationReturnData` for details.
+    let bytes = SimulationReturnData::new(storage_data.owner)
+        .try_to_vec()
+        .map_err(|err| ProgramError::BorshIoError(err.to_string()))?;
+    set_return_data(&bytes[..]);
+    Ok(())
+}
+
+/// Gets the storage data of this program.
+/// Returns an Err if the provided account isn't the storage PDA.
+fn storage_data(
+    program_id: &Pubkey,
+    storage_pda_account: &AccountInfo,
+) -> Result<AggregationIsmData, ProgramError> {
+    let storage_data =
+        AggregationIsmAccount::fetch_data(&mut &storage_pda_account.data.borrow()[..])?
+            .ok_or(Error::AccountNotInitialized)?;
+    // Confirm the key of the storage_pda_account is the correct PDA
+    // using the stored bump seed.
+    let storage_pda_key = Pubkey::create_program_address(
+        aggregation_ism_storage_pda_seeds!(storage_data.bump_seed),
+        program_id,
+    )?;
+    // This check validates that the provided storage_pda_account is valid
+    if *storage_pda_account.key != storage_pda_key {
+        return Err(Error::AccountOutOfOrder.into());
+    }
+    // Extra sanity check that the owner of the PDA account is this program
+    if storage_pda_account.owner != program_id {
+        return Err(Error::ProgramIdNotOwner.into());
+    }
+
+    Ok(*storage_data)
+}
+
+/// Transfers ownership to a new owner.
+///
+/// Accounts:
+/// 0. `[signer]` The current owner.
+/// 1. `[writable]` The storage PDA account.
+fn transfer_ownership(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_owner: Option<Pubkey>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The current owner.
+    // This is verified as correct further below.
+    let owner_account = next_account_info(accounts_iter)?;
+
+    // Account 1: The storage PDA account.
+    let storage_pda_account = next_account_info(accounts_iter)?;
+    let mut storage_data = storage_data(program_id, storage_pda_account)?;
+
+    // Transfer ownership. This errors if `owner_account` is not a signer or the owner.
+    storage_data.transfer_ownership(owner_account, new_owner)?;
+
+    // Store the new owner.
+    AggregationIsmAccount::from(storage_data).store(storage_pda_account, false)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use hyperlane_sealevel_interchain_security_module_interface::VERIFY_ACCOUNT_METAS_PDA_SEEDS;
+
+    #[test]
+    fn test_storage_pda_seeds_are_verify_account_metas_pda_seeds() {
+        let seeds: &[&[u8]] = aggregation_ism_storage_pda_seeds!();
+        assert_eq!(seeds, VERIFY_ACCOUNT_METAS_PDA_SEEDS);
+    }
+}
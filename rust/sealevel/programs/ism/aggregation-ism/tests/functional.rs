@@ -0,0 +1,558 @@
+//! Contains functional tests for things that cannot be done
+//! strictly in unit tests. This includes CPIs, like verifying
+//! messages with the aggregated ISMs.
+
+use borsh::BorshDeserialize;
+use solana_program::{instruction::Instruction, pubkey, pubkey::Pubkey};
+
+use hyperlane_core::{Encode, HyperlaneMessage, ModuleType, H256};
+use hyperlane_sealevel_aggregation_ism::{
+    accounts::{AggregationIsmAccount, AggregationIsmData},
+    aggregation_ism_storage_pda_seeds,
+    error::Error as AggregationIsmError,
+    instruction::{
+        init_instruction, set_modules_and_threshold_instruction, storage_pda_key,
+        transfer_ownership_instruction, ModulesAndThreshold,
+    },
+    metadata::AggregationIsmMetadata,
+    processor::process_instruction,
+};
+use hyperlane_sealevel_interchain_security_module_interface::{
+    InterchainSecurityModuleInstruction, VerifyInstruction,
+};
+use hyperlane_sealevel_test_ism::{program::TestIsmError, test_client::TestIsmTestClient};
+use hyperlane_test_utils::{
+    assert_transaction_error, clone_keypair, get_ism_verify_account_metas, new_funded_keypair,
+    process_instruction as process_test_instruction,
+};
+use serializable_account_meta::SimulationReturnData;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    message::Message,
+    signature::Signer,
+    signer::keypair::Keypair,
+    transaction::{Transaction, TransactionError},
+};
+
+const ONE_SOL_IN_LAMPORTS: u64 = 1000000000;
+
+fn aggregation_ism_id() -> Pubkey {
+    pubkey!("FURL86bsYndGdPWjPjUFBhxb8mA2dfjmCKfGcc4T1BCD")
+}
+
+fn nested_aggregation_ism_id() -> Pubkey {
+    pubkey!("EAz52hfZRXGWAYomM1Wp7gFTxfB3ShcqMnRd7eCKTc6t")
+}
+
+fn rejecting_test_ism_id() -> Pubkey {
+    pubkey!("D2sEcs3wUeZ5qBVZDZcZDgCCfa6jM2yU1euojuG8CYRV")
+}
+
+async fn setup_client() -> (BanksClient, Keypair) {
+    let mut program_test = ProgramTest::new(
+        "hyperlane_sealevel_aggregation_ism",
+        aggregation_ism_id(),
+        processor!(process_instruction),
+    );
+    program_test.add_program(
+        "hyperlane_sealevel_aggregation_ism",
+        nested_aggregation_ism_id(),
+        processor!(process_instruction),
+    );
+    for test_ism_id in [hyperlane_sealevel_test_ism::id(), rejecting_test_ism_id()] {
+        program_test.add_program(
+            "hyperlane_sealevel_test_ism",
+            test_ism_id,
+            processor!(hyperlane_sealevel_test_ism::program::process_instruction),
+        );
+    }
+
+    let (banks_client, payer, _recent_blockhash) = program_test.start().await;
+
+    // The accepting test ISM
+    TestIsmTestClient::new(banks_client.clone(), clone_keypair(&payer))
+        .init()
+        .await
+        .unwrap();
+    // The rejecting test ISM
+    let mut rejecting_test_ism = TestIsmTestClient::new_with_program_id(
+        banks_client.clone(),
+        clone_keypair(&payer),
+        rejecting_test_ism_id(),
+    );
+    rejecting_test_ism.init().await.unwrap();
+    rejecting_test_ism.set_accept(false).await.unwrap();
+
+    (banks_client, payer)
+}
+
+/// The accepting and rejecting test ISMs, in that order.
+fn test_isms() -> Vec<Pubkey> {
+    vec![hyperlane_sealevel_test_ism::id(), rejecting_test_ism_id()]
+}
+
+async fn initialize(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    program_id: Pubkey,
+    modules_and_threshold: ModulesAndThreshold,
+) -> Result<(), BanksClientError> {
+    process_test_instruction(
+        banks_client,
+        init_instruction(program_id, payer.pubkey(), modules_and_threshold).unwrap(),
+        payer,
+        &[payer],
+    )
+    .await?;
+    Ok(())
+}
+
+async fn set_modules_and_threshold(
+    banks_client: &mut BanksClient,
+    owner: &Keypair,
+    program_id: Pubkey,
+    modules_and_threshold: ModulesAndThreshold,
+) -> Result<(), BanksClientError> {
+    process_test_instruction(
+        banks_client,
+        set_modules_and_threshold_instruction(program_id, owner.pubkey(), modules_and_threshold)
+            .unwrap(),
+        owner,
+        &[owner],
+    )
+    .await?;
+    Ok(())
+}
+
+async fn get_storage_data(
+    banks_client: &mut BanksClient,
+    program_id: Pubkey,
+) -> AggregationIsmData {
+    let storage_account_data = banks_client
+        .get_account(storage_pda_key(&program_id))
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    *AggregationIsmAccount::fetch_data(&mut &storage_account_data[..])
+        .unwrap()
+        .unwrap()
+}
+
+fn message() -> HyperlaneMessage {
+    HyperlaneMessage {
+        version: 3,
+        nonce: 1,
+        origin: 1234,
+        sender: H256::random(),
+        destination: 4321,
+        recipient: H256::random(),
+        body: vec![1, 2, 3],
+    }
+}
+
+/// Aggregation ISM metadata that includes (empty) metadata for the modules
+/// at the given indices, as the test ISM doesn't require any.
+fn metadata(module_count: usize, indices: &[usize]) -> Vec<u8> {
+    AggregationIsmMetadata {
+        modules_metadata: (0..module_count)
+            .map(|i| indices.contains(&i).then(Vec::new))
+            .collect(),
+    }
+    .to_vec()
+}
+
+async fn verify(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    ism: Pubkey,
+    metadata: Vec<u8>,
+) -> Result<(), BanksClientError> {
+    let message = message().to_vec();
+    let account_metas =
+        get_ism_verify_account_metas(banks_client, payer, ism, metadata.clone(), message.clone())
+            .await?;
+    process_test_instruction(
+        banks_client,
+        Instruction::new_with_bytes(
+            ism,
+            &InterchainSecurityModuleInstruction::Verify(VerifyInstruction { metadata, message })
+                .encode()
+                .unwrap(),
+            account_metas,
+        ),
+        payer,
+        &[payer],
+    )
+    .await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_initialize() {
+    let program_id = aggregation_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    let modules_and_threshold = ModulesAndThreshold {
+        modules: test_isms(),
+        threshold: 1,
+    };
+    initialize(
+        &mut banks_client,
+        &payer,
+        program_id,
+        modules_and_threshold.clone(),
+    )
+    .await
+    .unwrap();
+
+    let (_, storage_pda_bump_seed) =
+        Pubkey::find_program_address(aggregation_ism_storage_pda_seeds!(), &program_id);
+    assert_eq!(
+        get_storage_data(&mut banks_client, program_id).await,
+        AggregationIsmData {
+            bump_seed: storage_pda_bump_seed,
+            owner: Some(payer.pubkey()),
+            modules_and_threshold: modules_and_threshold.clone(),
+        },
+    );
+
+    // Initializing again errors
+    let new_payer = new_funded_keypair(&mut banks_client, &payer, ONE_SOL_IN_LAMPORTS).await;
+    assert_transaction_error(
+        initialize(
+            &mut banks_client,
+            &new_payer,
+            program_id,
+            modules_and_threshold,
+        )
+        .await,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AggregationIsmError::AlreadyInitialized as u32),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn test_initialize_errors_if_invalid_modules_and_threshold() {
+    let program_id = aggregation_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    assert_transaction_error(
+        initialize(
+            &mut banks_client,
+            &payer,
+            program_id,
+            ModulesAndThreshold {
+                modules: test_isms(),
+                threshold: 0,
+            },
+        )
+        .await,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AggregationIsmError::InvalidModulesAndThreshold as u32),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn test_set_modules_and_threshold() {
+    let program_id = aggregation_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    initialize(
+        &mut banks_client,
+        &payer,
+        program_id,
+        ModulesAndThreshold {
+            modules: vec![hyperlane_sealevel_test_ism::id()],
+            threshold: 1,
+        },
+    )
+    .await
+    .unwrap();
+
+    // Growing the account
+    let modules_and_threshold = ModulesAndThreshold {
+        modules: vec![
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ],
+        threshold: 2,
+    };
+    set_modules_and_threshold(
+        &mut banks_client,
+        &payer,
+        program_id,
+        modules_and_threshold.clone(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        get_storage_data(&mut banks_client, program_id)
+            .await
+            .modules_and_threshold,
+        modules_and_threshold,
+    );
+
+    // Errors if not the owner
+    let non_owner = new_funded_keypair(&mut banks_client, &payer, ONE_SOL_IN_LAMPORTS).await;
+    assert_transaction_error(
+        set_modules_and_threshold(
+            &mut banks_client,
+            &non_owner,
+            program_id,
+            modules_and_threshold.clone(),
+        )
+        .await,
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument),
+    );
+
+    // Errors if invalid
+    assert_transaction_error(
+        set_modules_and_threshold(
+            &mut banks_client,
+            &payer,
+            program_id,
+            ModulesAndThreshold {
+                threshold: 4,
+                ..modules_and_threshold
+            },
+        )
+        .await,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AggregationIsmError::InvalidModulesAndThreshold as u32),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn test_transfer_ownership() {
+    let program_id = aggregation_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    let modules_and_threshold = ModulesAndThreshold {
+        modules: test_isms(),
+        threshold: 1,
+    };
+    initialize(
+        &mut banks_client,
+        &payer,
+        program_id,
+        modules_and_threshold.clone(),
+    )
+    .await
+    .unwrap();
+
+    let new_owner = new_funded_keypair(&mut banks_client, &payer, ONE_SOL_IN_LAMPORTS).await;
+    process_test_instruction(
+        &mut banks_client,
+        transfer_ownership_instruction(program_id, payer.pubkey(), Some(new_owner.pubkey()))
+            .unwrap(),
+        &payer,
+        &[&payer],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        get_storage_data(&mut banks_client, program_id).await.owner,
+        Some(new_owner.pubkey()),
+    );
+
+    // The previous owner can no longer set the modules and threshold
+    assert_transaction_error(
+        set_modules_and_threshold(
+            &mut banks_client,
+            &payer,
+            program_id,
+            modules_and_threshold.clone(),
+        )
+        .await,
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument),
+    );
+    set_modules_and_threshold(
+        &mut banks_client,
+        &new_owner,
+        program_id,
+        modules_and_threshold,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_verify() {
+    let program_id = aggregation_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    initialize(
+        &mut banks_client,
+        &payer,
+        program_id,
+        ModulesAndThreshold {
+            modules: test_isms(),
+            threshold: 1,
+        },
+    )
+    .await
+    .unwrap();
+
+    // Verified by the accepting ISM
+    verify(&mut banks_client, &payer, program_id, metadata(2, &[0]))
+        .await
+        .unwrap();
+    // The rejecting ISM isn't used once the threshold is met
+    verify(&mut banks_client, &payer, program_id, metadata(2, &[0, 1]))
+        .await
+        .unwrap();
+
+    // Verified by the rejecting ISM
+    assert_transaction_error(
+        verify(&mut banks_client, &payer, program_id, metadata(2, &[1])).await,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TestIsmError::VerifyNotAccepted as u32),
+        ),
+    );
+
+    // Requiring both ISMs
+    set_modules_and_threshold(
+        &mut banks_client,
+        &payer,
+        program_id,
+        ModulesAndThreshold {
+            modules: test_isms(),
+            threshold: 2,
+        },
+    )
+    .await
+    .unwrap();
+    assert_transaction_error(
+        verify(&mut banks_client, &payer, program_id, metadata(2, &[0, 1])).await,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TestIsmError::VerifyNotAccepted as u32),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn test_verify_errors_if_threshold_not_met() {
+    let program_id = aggregation_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    initialize(
+        &mut banks_client,
+        &payer,
+        program_id,
+        ModulesAndThreshold {
+            modules: test_isms(),
+            threshold: 2,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_transaction_error(
+        verify(&mut banks_client, &payer, program_id, metadata(2, &[0])).await,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AggregationIsmError::ThresholdNotMet as u32),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn test_verify_nested_aggregation_ism() {
+    let program_id = aggregation_ism_id();
+    let nested_program_id = nested_aggregation_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    // The nested ISM and the outer ISM both use the accepting test ISM.
+    initialize(
+        &mut banks_client,
+        &payer,
+        nested_program_id,
+        ModulesAndThreshold {
+            modules: test_isms(),
+            threshold: 1,
+        },
+    )
+    .await
+    .unwrap();
+    initialize(
+        &mut banks_client,
+        &payer,
+        program_id,
+        ModulesAndThreshold {
+            modules: vec![nested_program_id, hyperlane_sealevel_test_ism::id()],
+            threshold: 2,
+        },
+    )
+    .await
+    .unwrap();
+
+    let outer_metadata = |nested_metadata: Vec<u8>| {
+        AggregationIsmMetadata {
+            modules_metadata: vec![Some(nested_metadata), Some(vec![])],
+        }
+        .to_vec()
+    };
+
+    verify(
+        &mut banks_client,
+        &payer,
+        program_id,
+        outer_metadata(metadata(2, &[0])),
+    )
+    .await
+    .unwrap();
+
+    assert_transaction_error(
+        verify(
+            &mut banks_client,
+            &payer,
+            program_id,
+            outer_metadata(metadata(2, &[1])),
+        )
+        .await,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TestIsmError::VerifyNotAccepted as u32),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn test_ism_type() {
+    let program_id = aggregation_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let type_bytes = banks_client
+        .simulate_transaction(Transaction::new_unsigned(Message::new_with_blockhash(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &InterchainSecurityModuleInstruction::Type.encode().unwrap(),
+                vec![],
+            )],
+            Some(&payer.pubkey()),
+            &recent_blockhash,
+        )))
+        .await
+        .unwrap()
+        .simulation_details
+        .unwrap()
+        .return_data
+        .unwrap()
+        .data;
+    let type_u32 = SimulationReturnData::<u32>::try_from_slice(type_bytes.as_slice())
+        .unwrap()
+        .return_data;
+    assert_eq!(type_u32, ModuleType::Aggregation as u32);
+}
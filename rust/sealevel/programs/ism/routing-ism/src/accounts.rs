@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use access_control::AccessControl;
+use account_utils::{AccountData, SizedData};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+/// The data of the storage PDA account, holding the owner and the ISM
+/// each origin domain is routed to.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, PartialEq)]
+pub struct RoutingIsmData {
+    pub bump_seed: u8,
+    pub owner: Option<Pubkey>,
+    pub routes: HashMap<u32, Pubkey>,
+}
+
+impl SizedData for RoutingIsmData {
+    fn size(&self) -> usize {
+        // 1 byte bump seed
+        // 1 byte Option variant + 32 byte owner pubkey
+        // 4 byte routes length + 36 bytes per route (4 byte domain + 32 byte ISM pubkey)
+        1 + 1 + 32 + 4 + self.routes.len() * (4 + 32)
+    }
+}
+
+impl AccessControl for RoutingIsmData {
+    fn owner(&self) -> Option<&Pubkey> {
+        self.owner.as_ref()
+    }
+
+    fn set_owner(&mut self, new_owner: Option<Pubkey>) -> Result<(), ProgramError> {
+        self.owner = new_owner;
+        Ok(())
+    }
+}
+
+pub type RoutingIsmAccount = AccountData<RoutingIsmData>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_routing_ism_data_size() {
+        let data = RoutingIsmData {
+            bump_seed: 0,
+            owner: Some(Pubkey::new_unique()),
+            routes: HashMap::from([(1, Pubkey::new_unique()), (2, Pubkey::new_unique())]),
+        };
+        let serialized = data.try_to_vec().unwrap();
+        assert_eq!(data.size(), serialized.len());
+    }
+}
@@ -0,0 +1,126 @@
+use account_utils::{DiscriminatorData, DiscriminatorEncode, PROGRAM_INSTRUCTION_DISCRIMINATOR};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction as SolanaInstruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::routing_ism_storage_pda_seeds;
+
+#[derive(BorshDeserialize, BorshSerialize, Debug, PartialEq)]
+pub enum Instruction {
+    /// Initializes the program.
+    ///
+    /// Accounts:
+    /// 0. `[signer]` The new owner and payer of the storage PDA.
+    /// 1. `[writable]` The storage PDA account.
+    /// 2. `[executable]` The system program account.
+    Initialize,
+    /// Sets or removes the ISMs that origin domains are routed to.
+    ///
+    /// Accounts:
+    /// 0. `[signer]` The owner and payer of any storage PDA reallocation.
+    /// 1. `[writable]` The storage PDA account.
+    /// 2. `[executable]` The system program account.
+    SetRoutes(Vec<RouteConfig>),
+    /// Gets the owner from the storage PDA.
+    ///
+    /// Accounts:
+    /// 0. `[]` The storage PDA account.
+    GetOwner,
+    /// Sets the owner in the storage PDA.
+    ///
+    /// Accounts:
+    /// 0. `[signer]` The current owner.
+    /// 1. `[writable]` The storage PDA account.
+    TransferOwnership(Option<Pubkey>),
+}
+
+impl DiscriminatorData for Instruction {
+    const DISCRIMINATOR: [u8; Self::DISCRIMINATOR_LENGTH] = PROGRAM_INSTRUCTION_DISCRIMINATOR;
+}
+
+impl TryFrom<&[u8]> for Instruction {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        Self::try_from_slice(data).map_err(|_| ProgramError::InvalidInstructionData)
+    }
+}
+
+/// The ISM to route messages from an origin domain to.
+/// If `ism` is None, the route is removed.
+#[derive(BorshDeserialize, BorshSerialize, Debug, PartialEq, Clone)]
+pub struct RouteConfig {
+    pub domain: u32,
+    pub ism: Option<Pubkey>,
+}
+
+/// Gets the storage PDA key of the program.
+pub fn storage_pda_key(program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(routing_ism_storage_pda_seeds!(), program_id).0
+}
+
+/// Creates an Initialize instruction.
+pub fn init_instruction(
+    program_id: Pubkey,
+    payer: Pubkey,
+) -> Result<SolanaInstruction, ProgramError> {
+    // Accounts:
+    // 0. `[signer]` The new owner and payer of the storage PDA.
+    // 1. `[writable]` The storage PDA account.
+    // 2. `[executable]` The system program account.
+    let instruction = SolanaInstruction {
+        program_id,
+        data: Instruction::Initialize.encode()?,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(storage_pda_key(&program_id), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    };
+    Ok(instruction)
+}
+
+/// Creates a SetRoutes instruction.
+pub fn set_routes_instruction(
+    program_id: Pubkey,
+    owner_payer: Pubkey,
+    routes: Vec<RouteConfig>,
+) -> Result<SolanaInstruction, ProgramError> {
+    // Accounts:
+    // 0. `[signer]` The owner and payer of any storage PDA reallocation.
+    // 1. `[writable]` The storage PDA account.
+    // 2. `[executable]` The system program account.
+    let instruction = SolanaInstruction {
+        program_id,
+        data: Instruction::SetRoutes(routes).encode()?,
+        accounts: vec![
+            AccountMeta::new(owner_payer, true),
+            AccountMeta::new(storage_pda_key(&program_id), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    };
+    Ok(instruction)
+}
+
+/// Creates a TransferOwnership instruction.
+pub fn transfer_ownership_instruction(
+    program_id: Pubkey,
+    owner_payer: Pubkey,
+    new_owner: Option<Pubkey>,
+) -> Result<SolanaInstruction, ProgramError> {
+    // 0. `[signer]` The current owner.
+    // 1. `[writable]` The storage PDA account.
+    let instruction = SolanaInstruction {
+        program_id,
+        data: Instruction::TransferOwnership(new_owner).encode()?,
+        accounts: vec![
+            AccountMeta::new(owner_payer, true),
+            AccountMeta::new(storage_pda_key(&program_id), false),
+        ],
+    };
+    Ok(instruction)
+}
@@ -0,0 +1,403 @@
+use hyperlane_core::{Decode, HyperlaneMessage, ModuleType};
+
+use access_control::AccessControl;
+use account_utils::{create_pda_account, DiscriminatorDecode, SizedData};
+use serializable_account_meta::{SerializableAccountMeta, SimulationReturnData};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::AccountMeta,
+    program::set_return_data,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    accounts::{RoutingIsmAccount, RoutingIsmData},
+    error::Error,
+    instruction::{Instruction, RouteConfig},
+};
+
+use hyperlane_sealevel_interchain_security_module_interface::{
+    cpi, verify_account_metas_pda_key, InterchainSecurityModuleInstruction,
+};
+
+use borsh::BorshSerialize;
+
+const ISM_TYPE: ModuleType = ModuleType::Routing;
+
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+/// PDA seeds relating to the storage PDA account, which holds the owner
+/// and the routes. These are the same as `VERIFY_ACCOUNT_METAS_PDA_SEEDS`,
+/// so the routes are available to the `VerifyAccountMetas` instruction.
+#[macro_export]
+macro_rules! routing_ism_storage_pda_seeds {
+    () => {{
+        &[b"hyperlane_ism", b"-", b"verify", b"-", b"account_metas"]
+    }};
+
+    ($bump_seed:expr) => {{
+        &[
+            b"hyperlane_ism",
+            b"-",
+            b"verify",
+            b"-",
+            b"account_metas",
+            &[$bump_seed],
+        ]
+    }};
+}
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // First, try to decode the instruction as an interchain security module
+    // interface supported function based off the discriminator.
+    if let Ok(ism_instruction) = InterchainSecurityModuleInstruction::decode(instruction_data) {
+        return match ism_instruction {
+            InterchainSecurityModuleInstruction::Type => {
+                set_return_data(
+                    &SimulationReturnData::new(ISM_TYPE as u32)
+                        .try_to_vec()
+                        .map_err(|err| ProgramError::BorshIoError(err.to_string()))?[..],
+                );
+                Ok(())
+            }
+            InterchainSecurityModuleInstruction::Verify(verify_data) => verify(
+                program_id,
+                accounts,
+                verify_data.metadata,
+                verify_data.message,
+            ),
+            InterchainSecurityModuleInstruction::VerifyAccountMetas(verify_data) => {
+                let account_metas = verify_account_metas(
+                    program_id,
+                    accounts,
+                    verify_data.metadata,
+                    verify_data.message,
+                )?;
+                // Wrap it in the SimulationReturnData because serialized account_metas
+                // may end with zero byte(s), which are incorrectly truncated as
+                // simulated transaction return data.
+                // See `SimulationReturnData` for details.
+                let bytes = SimulationReturnData::new(account_metas)
+                    .try_to_vec()
+                    .map_err(|err| ProgramError::BorshIoError(err.to_string()))?;
+                set_return_data(&bytes[..]);
+                Ok(())
+            }
+        };
+    }
+
+    match Instruction::decode(instruction_data)? {
+        // Initializes the program.
+        Instruction::Initialize => initialize(program_id, accounts),
+        // Sets or removes the ISMs that origin domains are routed to.
+        Instruction::SetRoutes(routes) => set_routes(program_id, accounts, routes),
+        // Gets the owner of this program from the storage account.
+        Instruction::GetOwner => get_owner(program_id, accounts),
+        // Sets the owner of this program in the storage account.
+        Instruction::TransferOwnership(new_owner) => {
+            transfer_ownership(program_id, accounts, new_owner)
+        }
+    }
+}
+
+/// Initializes the program, creating the storage PDA account.
+///
+/// Accounts:
+/// 0. `[signer]` The new owner and payer of the storage PDA.
+/// 1. `[writable]` The storage PDA account.
+/// 2. `[executable]` The system program account.
+fn initialize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The new owner of this program and payer of the storage PDA.
+    let owner_account = next_account_info(accounts_iter)?;
+    if !owner_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Account 1: The storage PDA account.
+    let storage_pda_account = next_account_info(accounts_iter)?;
+    let (storage_pda_key, storage_pda_bump_seed) =
+        Pubkey::find_program_address(routing_ism_storage_pda_seeds!(), program_id);
+    if *storage_pda_account.key != storage_pda_key {
+        return Err(Error::AccountOutOfOrder.into());
+    }
+
+    // Ensure the storage PDA account isn't already initialized.
+    if let Ok(Some(_)) = RoutingIsmAccount::fetch_data(&mut &storage_pda_account.data.borrow()[..])
+    {
+        return Err(Error::AlreadyInitialized.into());
+    }
+
+    // Account 2: The system program account.
+    let system_program_account = next_account_info(accounts_iter)?;
+    if !solana_program::system_program::check_id(system_program_account.key) {
+        return Err(Error::AccountOutOfOrder.into());
+    }
+
+    // Create the storage PDA account, without any routes.
+    let storage_account = RoutingIsmAccount::from(RoutingIsmData {
+        bump_seed: storage_pda_bump_seed,
+        owner: Some(*owner_account.key),
+        routes: Default::default(),
+    });
+    create_pda_account(
+        owner_account,
+        &Rent::get()?,
+        storage_account.size(),
+        program_id,
+        system_program_account,
+        storage_pda_account,
+        routing_ism_storage_pda_seeds!(storage_pda_bump_seed),
+    )?;
+
+    // Store the storage data.
+    storage_account.store(storage_pda_account, false)?;
+
+    Ok(())
+}
+
+/// Verifies a message using the ISM that the message's origin domain is
+/// routed to.
+///
+/// Accounts:
+/// 0. `[]` The storage PDA account.
+/// 1. `[executable]` The ISM the message's origin domain is routed to.
+/// 2. `[]` The `VerifyAccountMetas` PDA of the ISM. Not used when verifying,
+///         but required to resolve the account metas of the ISM.
+/// 3..N. `[??]` The accounts required by the ISM's `Verify` instruction.
+fn verify(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    metadata_bytes: Vec<u8>,
+    message_bytes: Vec<u8>,
+) -> ProgramResult {
+    let message = HyperlaneMessage::read_from(&mut &message_bytes[..])
+        .map_err(|_| ProgramError::InvalidArgument)?;
+
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The storage PDA account.
+    let storage_pda_account = next_account_info(accounts_iter)?;
+    let storage_data = storage_data(program_id, storage_pda_account)?;
+    let ism = route(&storage_data, message.origin)?;
+
+    // Account 1: The ISM the message's origin domain is routed to.
+    let ism_account = next_account_info(accounts_iter)?;
+    if *ism_account.key != ism {
+        return Err(Error::AccountOutOfOrder.into());
+    }
+
+    // Account 2: The `VerifyAccountMetas` PDA of the ISM.
+    let _ism_verify_account_metas_pda_account = next_account_info(accounts_iter)?;
+
+    // Accounts 3..N: The accounts required by the ISM's `Verify` instruction.
+    cpi::verify(
+        &ism,
+        metadata_bytes,
+        message_bytes,
+        accounts_iter.as_slice(),
+    )
+}
+
+/// Gets the list of AccountMetas required by the `Verify` instruction.
+///
+/// The account metas of the ISM that the message's origin domain is routed to
+/// can only be resolved if the ISM and its `VerifyAccountMetas` PDA are passed
+/// in, so until then only they are returned alongside the storage PDA.
+///
+/// Accounts:
+/// 0. `[]` The storage PDA account, which is this program's PDA relating to
+///         the seeds VERIFY_ACCOUNT_METAS_PDA_SEEDS.
+/// 1..N. `[]` OPTIONAL - The account metas returned by a previous call.
+fn verify_account_metas(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    metadata_bytes: Vec<u8>,
+    message_bytes: Vec<u8>,
+) -> Result<Vec<SerializableAccountMeta>, ProgramError> {
+    let message = HyperlaneMessage::read_from(&mut &message_bytes[..])
+        .map_err(|_| ProgramError::InvalidArgument)?;
+
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The storage PDA account.
+    let storage_pda_account = next_account_info(accounts_iter)?;
+    let storage_data = storage_data(program_id, storage_pda_account)?;
+    let ism = route(&storage_data, message.origin)?;
+
+    let mut account_metas = vec![
+        AccountMeta::new_readonly(*storage_pda_account.key, false),
+        AccountMeta::new_readonly(ism, false),
+        AccountMeta::new_readonly(verify_account_metas_pda_key(&ism), false),
+    ];
+
+    // Accounts 1..N: The account metas returned by a previous call.
+    if let Some(ism_account_metas) = cpi::verify_account_metas(
+        &ism,
+        metadata_bytes,
+        message_bytes,
+        accounts_iter.as_slice(),
+    )? {
+        account_metas.extend(ism_account_metas);
+    }
+
+    Ok(account_metas.into_iter().map(Into::into).collect())
+}
+
+/// Gets the ISM that messages from the origin domain are routed to.
+fn route(storage_data: &RoutingIsmData, origin: u32) -> Result<Pubkey, Error> {
+    storage_data
+        .routes
+        .get(&origin)
+        .copied()
+        .ok_or(Error::NoRouteForDomain)
+}
+
+/// Sets or removes the ISMs that origin domains are routed to.
+///
+/// Accounts:
+/// 0. `[signer]` The owner and payer of any storage PDA reallocation.
+/// 1. `[writable]` The storage PDA account.
+/// 2. `[executable]` The system program account.
+fn set_routes(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    routes: Vec<RouteConfig>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The owner of this program.
+    // This is verified as correct further below.
+    let owner_account = next_account_info(accounts_iter)?;
+
+    // Account 1: The storage PDA account.
+    let storage_pda_account = next_account_info(accounts_iter)?;
+    let mut storage_data = storage_data(program_id, storage_pda_account)?;
+    // Ensure the owner account is the owner of this program.
+    storage_data.ensure_owner_signer(owner_account)?;
+
+    // Account 2: The system program account.
+    let system_program_account = next_account_info(accounts_iter)?;
+    if !solana_program::system_program::check_id(system_program_account.key) {
+        return Err(Error::AccountOutOfOrder.into());
+    }
+
+    for route in routes {
+        match route.ism {
+            Some(ism) => storage_data.routes.insert(route.domain, ism),
+            None => storage_data.routes.remove(&route.domain),
+        };
+    }
+
+    // Store the new routes, reallocating the account if more space is needed.
+    RoutingIsmAccount::from(storage_data).store_with_rent_exempt_realloc(
+        storage_pda_account,
+        &Rent::get()?,
+        owner_account,
+        system_program_account,
+    )?;
+
+    Ok(())
+}
+
+/// Gets the owner of this program from the storage account, and returns it as return data.
+/// Intended to be used by instructions querying the owner.
+///
+/// Accounts:
+/// 0. `[]` The storage PDA account.
+fn get_owner(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The storage PDA account.
+    let storage_pda_account = next_account_info(accounts_iter)?;
+
+    let storage_data = storage_data(program_id, storage_pda_account)?;
+
+    // Wrap it in the SimulationReturnData because serialized `storage_data.owner`
+    // may end with zero byte(s), which are incorrectly truncated as
+    // simulated transaction return data.
+    // See `SimulationReturnData` for details.
+    let bytes = SimulationReturnData::new(storage_data.owner)
+        .try_to_vec()
+        .map_err(|err| ProgramError::BorshIoError(err.to_string()))?;
+    set_return_data(&bytes[..]);
+    Ok(())
+}
+
+/// Gets the storage data of this program.
+/// Returns an Err if the provided account isn't the storage PDA.
+fn storage_data(
+    program_id: &Pubkey,
+    storage_pda_account: &AccountInfo,
+) -> Result<RoutingIsmData, ProgramError> {
+    let storage_data = RoutingIsmAccount::fetch_data(&mut &storage_pda_account.data.borrow()[..])?
+        .ok_or(Error::AccountNotInitialized)?;
+    // Confirm the key of the storage_pda_account is the correct PDA
+    // using the stored bump seed.
+    let storage_pda_key = Pubkey::create_program_address(
+        routing_ism_storage_pda_seeds!(storage_data.bump_seed),
+        program_id,
+    )?;
+    // This check validates that the provided storage_pda_account is valid
+    if *storage_pda_account.key != storage_pda_key {
+        return Err(Error::AccountOutOfOrder.into());
+    }
+    // Extra sanity check that the owner of the PDA account is this program
+    if storage_pda_account.owner != program_id {
+        return Err(Error::ProgramIdNotOwner.into());
+    }
+
+    Ok(*storage_data)
+}
+
+/// Transfers ownership to a new owner.
+///
+/// Accounts:
+/// 0. `[signer]` The current owner.
+/// 1. `[writable]` The storage PDA account.
+fn transfer_ownership(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_owner: Option<Pubkey>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The current owner.
+    // This is verified as correct further below.
+    let owner_account = next_account_info(accounts_iter)?;
+
+    // Account 1: The storage PDA account.
+    let storage_pda_account = next_account_info(accounts_iter)?;
+    let mut storage_data = storage_data(program_id, storage_pda_account)?;
+
+    // Transfer ownership. This errors if `owner_account` is not a signer or the owner.
+    storage_data.transfer_ownership(owner_account, new_owner)?;
+
+    // Store the new owner.
+    RoutingIsmAccount::from(storage_data).store(storage_pda_account, false)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use hyperlane_sealevel_interchain_security_module_interface::VERIFY_ACCOUNT_METAS_PDA_SEEDS;
+
+    #[test]
+    fn test_storage_pda_seeds_are_verify_account_metas_pda_seeds() {
+        let seeds: &[&[u8]] = routing_ism_storage_pda_seeds!();
+        assert_eq!(seeds, VERIFY_ACCOUNT_METAS_PDA_SEEDS);
+    }
+}
@@ -0,0 +1,514 @@
+//! Contains functional tests for things that cannot be done
+//! strictly in unit tests. This includes CPIs, like verifying
+//! messages with the ISMs that domains are routed to.
+
+use std::collections::HashMap;
+
+use borsh::BorshDeserialize;
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+};
+
+use hyperlane_core::{Encode, HyperlaneMessage, ModuleType, H256};
+use hyperlane_sealevel_interchain_security_module_interface::{
+    verify_account_metas_pda_key, InterchainSecurityModuleInstruction, VerifyInstruction,
+};
+use hyperlane_sealevel_routing_ism::{
+    accounts::{RoutingIsmAccount, RoutingIsmData},
+    error::Error as RoutingIsmError,
+    instruction::{
+        init_instruction, set_routes_instruction, storage_pda_key, transfer_ownership_instruction,
+        RouteConfig,
+    },
+    processor::process_instruction,
+    routing_ism_storage_pda_seeds,
+};
+use hyperlane_sealevel_test_ism::{program::TestIsmError, test_client::TestIsmTestClient};
+use hyperlane_test_utils::{
+    assert_transaction_error, clone_keypair, get_ism_verify_account_metas, new_funded_keypair,
+    process_instruction as process_test_instruction,
+};
+use serializable_account_meta::SimulationReturnData;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    message::Message,
+    signature::Signer,
+    signer::keypair::Keypair,
+    transaction::{Transaction, TransactionError},
+};
+
+const ORIGIN_DOMAIN: u32 = 1234;
+const OTHER_ORIGIN_DOMAIN: u32 = 4321;
+const ONE_SOL_IN_LAMPORTS: u64 = 1000000000;
+
+fn routing_ism_id() -> Pubkey {
+    pubkey!("HvuQR6w3GzG19p3VpToLkUr9RaMTRQQvX5AAAhyf4WS8")
+}
+
+fn nested_routing_ism_id() -> Pubkey {
+    pubkey!("5YRicfC9rnwJFTe2u5wcA2mXAqSVvDtXUrR6zyRaWwGv")
+}
+
+fn rejecting_test_ism_id() -> Pubkey {
+    pubkey!("EiwtdF9VuT6WfKyV619pXLyUuFE8birL7f9Mb5L4ExHc")
+}
+
+async fn setup_client() -> (BanksClient, Keypair) {
+    let mut program_test = ProgramTest::new(
+        "hyperlane_sealevel_routing_ism",
+        routing_ism_id(),
+        processor!(process_instruction),
+    );
+    program_test.add_program(
+        "hyperlane_sealevel_routing_ism",
+        nested_routing_ism_id(),
+        processor!(process_instruction),
+    );
+    for test_ism_id in [hyperlane_sealevel_test_ism::id(), rejecting_test_ism_id()] {
+        program_test.add_program(
+            "hyperlane_sealevel_test_ism",
+            test_ism_id,
+            processor!(hyperlane_sealevel_test_ism::program::process_instruction),
+        );
+    }
+
+    let (mut banks_client, payer, _recent_blockhash) = program_test.start().await;
+
+    // The accepting test ISM
+    TestIsmTestClient::new(banks_client.clone(), clone_keypair(&payer))
+        .init()
+        .await
+        .unwrap();
+    // The rejecting test ISM
+    let mut rejecting_test_ism = TestIsmTestClient::new_with_program_id(
+        banks_client.clone(),
+        clone_keypair(&payer),
+        rejecting_test_ism_id(),
+    );
+    rejecting_test_ism.init().await.unwrap();
+    rejecting_test_ism.set_accept(false).await.unwrap();
+
+    initialize(&mut banks_client, &payer, routing_ism_id())
+        .await
+        .unwrap();
+
+    (banks_client, payer)
+}
+
+async fn initialize(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    program_id: Pubkey,
+) -> Result<(), BanksClientError> {
+    process_test_instruction(
+        banks_client,
+        init_instruction(program_id, payer.pubkey()).unwrap(),
+        payer,
+        &[payer],
+    )
+    .await?;
+    Ok(())
+}
+
+async fn set_routes(
+    banks_client: &mut BanksClient,
+    owner: &Keypair,
+    program_id: Pubkey,
+    routes: Vec<RouteConfig>,
+) -> Result<(), BanksClientError> {
+    process_test_instruction(
+        banks_client,
+        set_routes_instruction(program_id, owner.pubkey(), routes).unwrap(),
+        owner,
+        &[owner],
+    )
+    .await?;
+    Ok(())
+}
+
+async fn get_storage_data(banks_client: &mut BanksClient, program_id: Pubkey) -> RoutingIsmData {
+    let storage_account_data = banks_client
+        .get_account(storage_pda_key(&program_id))
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    *RoutingIsmAccount::fetch_data(&mut &storage_account_data[..])
+        .unwrap()
+        .unwrap()
+}
+
+fn message(origin: u32) -> HyperlaneMessage {
+    HyperlaneMessage {
+        version: 3,
+        nonce: 1,
+        origin,
+        sender: H256::random(),
+        destination: 4000,
+        recipient: H256::random(),
+        body: vec![1, 2, 3],
+    }
+}
+
+async fn verify(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    ism: Pubkey,
+    message: &HyperlaneMessage,
+) -> Result<(), BanksClientError> {
+    let account_metas =
+        get_ism_verify_account_metas(banks_client, payer, ism, vec![], message.to_vec()).await?;
+    process_test_instruction(
+        banks_client,
+        Instruction::new_with_bytes(
+            ism,
+            &InterchainSecurityModuleInstruction::Verify(VerifyInstruction {
+                metadata: vec![],
+                message: message.to_vec(),
+            })
+            .encode()
+            .unwrap(),
+            account_metas,
+        ),
+        payer,
+        &[payer],
+    )
+    .await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_initialize() {
+    let program_id = routing_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    let (_, storage_pda_bump_seed) =
+        Pubkey::find_program_address(routing_ism_storage_pda_seeds!(), &program_id);
+    assert_eq!(
+        get_storage_data(&mut banks_client, program_id).await,
+        RoutingIsmData {
+            bump_seed: storage_pda_bump_seed,
+            owner: Some(payer.pubkey()),
+            routes: HashMap::new(),
+        },
+    );
+
+    // Initializing again errors
+    let new_payer = new_funded_keypair(&mut banks_client, &payer, ONE_SOL_IN_LAMPORTS).await;
+    assert_transaction_error(
+        initialize(&mut banks_client, &new_payer, program_id).await,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(RoutingIsmError::AlreadyInitialized as u32),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn test_set_routes() {
+    let program_id = routing_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    let ism = Pubkey::new_unique();
+    let other_ism = Pubkey::new_unique();
+    set_routes(
+        &mut banks_client,
+        &payer,
+        program_id,
+        vec![
+            RouteConfig {
+                domain: ORIGIN_DOMAIN,
+                ism: Some(ism),
+            },
+            RouteConfig {
+                domain: OTHER_ORIGIN_DOMAIN,
+                ism: Some(other_ism),
+            },
+        ],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        get_storage_data(&mut banks_client, program_id).await.routes,
+        HashMap::from([(ORIGIN_DOMAIN, ism), (OTHER_ORIGIN_DOMAIN, other_ism)]),
+    );
+
+    // Removing a route
+    set_routes(
+        &mut banks_client,
+        &payer,
+        program_id,
+        vec![RouteConfig {
+            domain: OTHER_ORIGIN_DOMAIN,
+            ism: None,
+        }],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        get_storage_data(&mut banks_client, program_id).await.routes,
+        HashMap::from([(ORIGIN_DOMAIN, ism)]),
+    );
+}
+
+#[tokio::test]
+async fn test_set_routes_errors_if_not_owner() {
+    let program_id = routing_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    let non_owner = new_funded_keypair(&mut banks_client, &payer, ONE_SOL_IN_LAMPORTS).await;
+    assert_transaction_error(
+        set_routes(
+            &mut banks_client,
+            &non_owner,
+            program_id,
+            vec![RouteConfig {
+                domain: ORIGIN_DOMAIN,
+                ism: Some(Pubkey::new_unique()),
+            }],
+        )
+        .await,
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument),
+    );
+}
+
+#[tokio::test]
+async fn test_transfer_ownership() {
+    let program_id = routing_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    let new_owner = new_funded_keypair(&mut banks_client, &payer, ONE_SOL_IN_LAMPORTS).await;
+    process_test_instruction(
+        &mut banks_client,
+        transfer_ownership_instruction(program_id, payer.pubkey(), Some(new_owner.pubkey()))
+            .unwrap(),
+        &payer,
+        &[&payer],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        get_storage_data(&mut banks_client, program_id).await.owner,
+        Some(new_owner.pubkey()),
+    );
+
+    // The previous owner can no longer set routes
+    assert_transaction_error(
+        set_routes(&mut banks_client, &payer, program_id, vec![]).await,
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument),
+    );
+    set_routes(&mut banks_client, &new_owner, program_id, vec![])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_verify_account_metas() {
+    let program_id = routing_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    let test_ism_id = hyperlane_sealevel_test_ism::id();
+    set_routes(
+        &mut banks_client,
+        &payer,
+        program_id,
+        vec![RouteConfig {
+            domain: ORIGIN_DOMAIN,
+            ism: Some(test_ism_id),
+        }],
+    )
+    .await
+    .unwrap();
+
+    let account_metas = get_ism_verify_account_metas(
+        &mut banks_client,
+        &payer,
+        program_id,
+        vec![],
+        message(ORIGIN_DOMAIN).to_vec(),
+    )
+    .await
+    .unwrap();
+    let (test_ism_storage_pda_key, _) = Pubkey::find_program_address(
+        hyperlane_sealevel_test_ism::test_ism_storage_pda_seeds!(),
+        &test_ism_id,
+    );
+    assert_eq!(
+        account_metas,
+        vec![
+            AccountMeta::new_readonly(storage_pda_key(&program_id), false),
+            AccountMeta::new_readonly(test_ism_id, false),
+            AccountMeta::new_readonly(verify_account_metas_pda_key(&test_ism_id), false),
+            AccountMeta::new_readonly(test_ism_storage_pda_key, false),
+        ],
+    );
+}
+
+#[tokio::test]
+async fn test_verify() {
+    let program_id = routing_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    set_routes(
+        &mut banks_client,
+        &payer,
+        program_id,
+        vec![
+            RouteConfig {
+                domain: ORIGIN_DOMAIN,
+                ism: Some(hyperlane_sealevel_test_ism::id()),
+            },
+            RouteConfig {
+                domain: OTHER_ORIGIN_DOMAIN,
+                ism: Some(rejecting_test_ism_id()),
+            },
+        ],
+    )
+    .await
+    .unwrap();
+
+    // Routed to the accepting ISM
+    verify(
+        &mut banks_client,
+        &payer,
+        program_id,
+        &message(ORIGIN_DOMAIN),
+    )
+    .await
+    .unwrap();
+
+    // Routed to the rejecting ISM
+    assert_transaction_error(
+        verify(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &message(OTHER_ORIGIN_DOMAIN),
+        )
+        .await,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TestIsmError::VerifyNotAccepted as u32),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn test_verify_errors_if_no_route() {
+    let program_id = routing_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    assert_transaction_error(
+        verify(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &message(ORIGIN_DOMAIN),
+        )
+        .await,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(RoutingIsmError::NoRouteForDomain as u32),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn test_verify_nested_routing_ism() {
+    let program_id = routing_ism_id();
+    let nested_program_id = nested_routing_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    initialize(&mut banks_client, &payer, nested_program_id)
+        .await
+        .unwrap();
+    set_routes(
+        &mut banks_client,
+        &payer,
+        nested_program_id,
+        vec![
+            RouteConfig {
+                domain: ORIGIN_DOMAIN,
+                ism: Some(hyperlane_sealevel_test_ism::id()),
+            },
+            RouteConfig {
+                domain: OTHER_ORIGIN_DOMAIN,
+                ism: Some(rejecting_test_ism_id()),
+            },
+        ],
+    )
+    .await
+    .unwrap();
+    set_routes(
+        &mut banks_client,
+        &payer,
+        program_id,
+        vec![
+            RouteConfig {
+                domain: ORIGIN_DOMAIN,
+                ism: Some(nested_program_id),
+            },
+            RouteConfig {
+                domain: OTHER_ORIGIN_DOMAIN,
+                ism: Some(nested_program_id),
+            },
+        ],
+    )
+    .await
+    .unwrap();
+
+    verify(
+        &mut banks_client,
+        &payer,
+        program_id,
+        &message(ORIGIN_DOMAIN),
+    )
+    .await
+    .unwrap();
+
+    assert_transaction_error(
+        verify(
+            &mut banks_client,
+            &payer,
+            program_id,
+            &message(OTHER_ORIGIN_DOMAIN),
+        )
+        .await,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TestIsmError::VerifyNotAccepted as u32),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn test_ism_type() {
+    let program_id = routing_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let type_bytes = banks_client
+        .simulate_transaction(Transaction::new_unsigned(Message::new_with_blockhash(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &InterchainSecurityModuleInstruction::Type.encode().unwrap(),
+                vec![],
+            )],
+            Some(&payer.pubkey()),
+            &recent_blockhash,
+        )))
+        .await
+        .unwrap()
+        .simulation_details
+        .unwrap()
+        .return_data
+        .unwrap()
+        .data;
+    let type_u32 = SimulationReturnData::<u32>::try_from_slice(type_bytes.as_slice())
+        .unwrap()
+        .return_data;
+    assert_eq!(type_u32, ModuleType::Routing as u32);
+}
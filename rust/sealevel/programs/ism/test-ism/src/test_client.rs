@@ -17,20 +17,32 @@ use crate::{id, program::TestIsmInstruction, test_ism_storage_pda_seeds};
 pub struct TestIsmTestClient {
     banks_client: BanksClient,
     payer: Keypair,
+    program_id: Pubkey,
 }
 
 impl TestIsmTestClient {
     /// Creates a new `TestIsmTestClient`.
     pub fn new(banks_client: BanksClient, payer: Keypair) -> Self {
+        Self::new_with_program_id(banks_client, payer, id())
+    }
+
+    /// Creates a new `TestIsmTestClient` for the Test ISM program deployed
+    /// at `program_id`, allowing for multiple instances of the program.
+    pub fn new_with_program_id(
+        banks_client: BanksClient,
+        payer: Keypair,
+        program_id: Pubkey,
+    ) -> Self {
         Self {
             banks_client,
             payer,
+            program_id,
         }
     }
 
     /// Initializes the Test ISM program.
     pub async fn init(&mut self) -> Result<(), BanksClientError> {
-        let program_id = id();
+        let program_id = self.program_id;
 
         let payer_pubkey = self.payer.pubkey();
 
@@ -43,7 +55,7 @@ impl TestIsmTestClient {
                 // 2. `[writeable]` Storage PDA.
                 AccountMeta::new_readonly(system_program::id(), false),
                 AccountMeta::new(payer_pubkey, true),
-                AccountMeta::new(Self::get_storage_pda_key(), false),
+                AccountMeta::new(self.get_storage_pda_key(), false),
             ],
         };
 
@@ -60,14 +72,14 @@ impl TestIsmTestClient {
 
     /// Sets the Test ISM to accept or reject.
     pub async fn set_accept(&mut self, accept: bool) -> Result<(), BanksClientError> {
-        let program_id = id();
+        let program_id = self.program_id;
 
         let instruction = Instruction {
             program_id,
             data: TestIsmInstruction::SetAccept(accept).try_to_vec().unwrap(),
             accounts: vec![
                 // 0. `[writeable]` Storage PDA.
-                AccountMeta::new(Self::get_storage_pda_key(), false),
+                AccountMeta::new(self.get_storage_pda_key(), false),
             ],
         };
 
@@ -82,15 +94,14 @@ impl TestIsmTestClient {
         Ok(())
     }
 
-    fn get_storage_pda_key() -> Pubkey {
-        let program_id = id();
+    fn get_storage_pda_key(&self) -> Pubkey {
         let (storage_pda_key, _storage_pda_bump) =
-            Pubkey::find_program_address(test_ism_storage_pda_seeds!(), &program_id);
+            Pubkey::find_program_address(test_ism_storage_pda_seeds!(), &self.program_id);
         storage_pda_key
     }
 
     /// Gets the program ID.
     pub fn id(&self) -> Pubkey {
-        id()
+        self.program_id
     }
 }
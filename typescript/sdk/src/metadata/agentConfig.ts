@@ -49,6 +49,7 @@ export enum AgentSignerKeyType {
   Hex = 'hexKey',
   Node = 'node',
   Cosmos = 'cosmosKey',
+  Remote = 'remote',
 }
 
 const AgentSignerHexKeySchema = z
@@ -78,18 +79,32 @@ const AgentSignerNodeSchema = z
     type: z.literal(AgentSignerKeyType.Node),
   })
   .describe('Assume the local node will sign on RPC calls automatically');
+const AgentSignerRemoteSchema = z
+  .object({
+    type: z.literal(AgentSignerKeyType.Remote),
+    url: z
+      .string()
+      .url()
+      .describe('The url of the eth_sign JSON-RPC endpoint, e.g. Web3Signer'),
+    address: ZHash.describe('The address of the key held by the signer'),
+  })
+  .describe(
+    'An external signing service. Only supports signing checkpoints and announcements, not transactions.',
+  );
 
 const AgentSignerSchema = z.union([
   AgentSignerHexKeySchema,
   AgentSignerAwsKeySchema,
   AgentSignerCosmosKeySchema,
   AgentSignerNodeSchema,
+  AgentSignerRemoteSchema,
 ]);
 
 export type AgentSignerHexKey = z.infer<typeof AgentSignerHexKeySchema>;
 export type AgentSignerAwsKey = z.infer<typeof AgentSignerAwsKeySchema>;
 export type AgentSignerCosmosKey = z.infer<typeof AgentSignerNodeSchema>;
 export type AgentSignerNode = z.infer<typeof AgentSignerNodeSchema>;
+export type AgentSignerRemote = z.infer<typeof AgentSignerRemoteSchema>;
 export type AgentSigner = z.infer<typeof AgentSignerSchema>;
 
 // Additional chain metadata for Cosmos chains required by the agents.
@@ -134,6 +149,29 @@ export const AgentChainMetadataSchema = ChainMetadataSchemaObject.merge(
       .nativeEnum(RpcConsensusType)
       .describe('The consensus type to use when multiple RPCs are configured.')
       .optional(),
+    lookupTable: z
+      .string()
+      .optional()
+      .describe(
+        'Sealevel only. The address lookup table used to batch message deliveries, created with the sealevel client. Batches are packed without one if not specified.',
+      ),
+    priorityFee: z
+      .object({
+        percentile: z
+          .number()
+          .int()
+          .min(0)
+          .max(100)
+          .optional()
+          .describe(
+            'The percentile of recent prioritization fees to pay per compute unit.',
+          ),
+        maxComputeUnitPrice: ZUint.optional().describe(
+          'The max price to pay per compute unit, in micro-lamports.',
+        ),
+      })
+      .optional()
+      .describe('Sealevel only. How the priority fee of transactions is set.'),
     signer: AgentSignerSchema.optional().describe(
       'The signer to use for this chain',
     ),
@@ -255,8 +293,18 @@ export enum GasPaymentEnforcementPolicyType {
   None = 'none',
   Minimum = 'minimum',
   OnChainFeeQuoting = 'onChainFeeQuoting',
+  UsdCost = 'usdCost',
 }
 
+const ZDecimal = z.union([
+  z.string().regex(/^\d+(\.\d+)?$/),
+  z.number().nonnegative(),
+]);
+const TokenPriceSchema = z.object({
+  usd: ZDecimal.describe('The price of one whole token in USD.'),
+  decimals: ZUint.describe('The number of decimals of the token.'),
+});
+
 const GasPaymentEnforcementBaseSchema = z.object({
   matchingList: MatchingListSchema.optional().describe(
     'An optional matching list, any message that matches will use this policy. By default all messages will match.',
@@ -277,6 +325,23 @@ const GasPaymentEnforcementSchema = z.union([
       .regex(/^\d+ ?\/ ?[1-9]\d*$/)
       .optional(),
   }),
+  GasPaymentEnforcementBaseSchema.extend({
+    type: z.literal(GasPaymentEnforcementPolicyType.UsdCost),
+    margin: ZDecimal.optional().describe(
+      'The payment in USD must be at least the estimated cost of delivery in USD times this value. Defaults to 1.',
+    ),
+    tokenPrices: z
+      .record(TokenPriceSchema)
+      .optional()
+      .describe('Native token prices by domain id.'),
+    tokenPricesPath: z
+      .string()
+      .min(1)
+      .optional()
+      .describe(
+        'Path to a JSON file with native token prices by domain id, e.g. written by a price feed. Prices in the file take precedence over `tokenPrices`.',
+      ),
+  }),
 ]);
 export type GasPaymentEnforcement = z.infer<typeof GasPaymentEnforcementSchema>;
 
@@ -330,12 +395,56 @@ export const RelayerAgentConfigSchema = AgentConfigSchema.extend({
     .describe(
       'If true, allows local storage based checkpoint syncers. Not intended for production use.',
     ),
+  ccipReadGateways: z
+    .object({
+      requestTimeout: ZUint.optional().describe(
+        'Timeout for a single request to a gateway in seconds.',
+      ),
+      failureThreshold: ZUint.optional().describe(
+        'Number of consecutive failures after which a gateway is skipped.',
+      ),
+      cooldown: ZUint.optional().describe(
+        'How long a failing gateway is skipped for in seconds.',
+      ),
+      cacheTtl: ZUint.optional().describe(
+        'How long gateway responses are reused for in seconds.',
+      ),
+    })
+    .optional()
+    .describe('Settings for querying the gateways of CCIP-read ISMs.'),
   metricAppContexts: z
     .union([z.array(MetricAppContextSchema), z.string().min(1)])
     .optional()
     .describe(
       'A list of app contexts and their matching lists to use for metrics. A message will be classified as the first matching app context.',
     ),
+  operatorApiToken: z
+    .string()
+    .min(1)
+    .optional()
+    .describe(
+      'Bearer token required by the operator API, which allows pausing destinations, dropping or reprioritizing messages, updating policies and dry running metadata building. The API is disabled if not specified.',
+    ),
+  policyConfigPath: z
+    .string()
+    .min(1)
+    .optional()
+    .describe(
+      'Path to a JSON file with policies (whitelist, blacklist, addressBlacklist, gasPaymentEnforcement, transactionGasLimit, metricAppContexts) that is watched and applied on top of this config whenever it changes.',
+    ),
+  tokenPrices: z
+    .record(TokenPriceSchema)
+    .optional()
+    .describe(
+      'Native token prices by domain id, used to value payments and delivery costs in the profitability ledger.',
+    ),
+  tokenPricesPath: z
+    .string()
+    .min(1)
+    .optional()
+    .describe(
+      'Path to a JSON file with native token prices by domain id, e.g. written by a price feed, used by the profitability ledger. Prices in the file take precedence over `tokenPrices`.',
+    ),
 });
 
 export type RelayerConfig = z.infer<typeof RelayerAgentConfigSchema>;
@@ -345,10 +454,55 @@ export const ScraperAgentConfigSchema = AgentConfigSchema.extend({
   chainsToScrape: CommaSeperatedChainList.describe(
     'Comma separated list of chain names to scrape',
   ),
+  queryApi: z
+    .boolean()
+    .optional()
+    .describe(
+      'If true, serves a read-only API over the scraped messages and gas payments on the agent server.',
+    ),
 });
 
 export type ScraperConfig = z.infer<typeof ScraperAgentConfigSchema>;
 
+const CheckpointSyncerSchema = z.discriminatedUnion('type', [
+  z
+    .object({
+      type: z.literal('localStorage'),
+      path: z.string().min(1).describe('Path to the local storage location'),
+    })
+    .describe('A local checkpoint syncer'),
+  z
+    .object({
+      type: z.literal('s3'),
+      bucket: z.string().min(1),
+      region: z.string().min(1),
+      folder: z
+        .string()
+        .min(1)
+        .optional()
+        .describe(
+          'The folder/key-prefix to use, defaults to the root of the bucket',
+        ),
+    })
+    .describe('A checkpoint syncer that uses S3'),
+  z
+    .object({
+      type: z.literal('http'),
+      url: z
+        .string()
+        .url()
+        .describe('Base url of the store, which must accept PUT requests'),
+      authToken: z
+        .string()
+        .min(1)
+        .optional()
+        .describe('Bearer token used to authenticate writes'),
+    })
+    .describe(
+      'A checkpoint syncer that uses any HTTP server supporting GET and PUT, e.g. WebDAV',
+    ),
+]);
+
 export const ValidatorAgentConfigSchema = AgentConfigSchema.extend({
   db: z
     .string()
@@ -360,31 +514,30 @@ export const ValidatorAgentConfigSchema = AgentConfigSchema.extend({
     .min(1)
     .describe('Name of the chain to validate messages on'),
   validator: AgentSignerSchema.describe('The validator attestation signer'),
-  checkpointSyncer: z.discriminatedUnion('type', [
-    z
-      .object({
-        type: z.literal('localStorage'),
-        path: z.string().min(1).describe('Path to the local storage location'),
-      })
-      .describe('A local checkpoint syncer'),
-    z
-      .object({
-        type: z.literal('s3'),
-        bucket: z.string().min(1),
-        region: z.string().min(1),
-        folder: z
-          .string()
-          .min(1)
-          .optional()
-          .describe(
-            'The folder/key-prefix to use, defaults to the root of the bucket',
-          ),
-      })
-      .describe('A checkpoint syncer that uses S3'),
-  ]),
+  checkpointSyncer: CheckpointSyncerSchema,
+  checkpointSyncerMirrors: z
+    .array(CheckpointSyncerSchema)
+    .optional()
+    .describe(
+      'Additional checkpoint syncers that checkpoints are mirrored to. Every mirror is announced so that relayers can fail over to it.',
+    ),
   interval: ZUint.optional().describe(
     'How long to wait between checking for new checkpoints in seconds.',
   ),
+  slashingProtectionImportPath: z
+    .string()
+    .min(1)
+    .optional()
+    .describe(
+      'Path to slashing protection data exported from another instance of this validator, imported on startup.',
+    ),
+  operatorApiToken: z
+    .string()
+    .min(1)
+    .optional()
+    .describe(
+      'Bearer token required by the operator endpoints, such as the slashing protection export. They are disabled if not specified.',
+    ),
 });
 
 export type ValidatorConfig = z.infer<typeof ValidatorAgentConfigSchema>;
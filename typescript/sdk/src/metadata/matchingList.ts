@@ -14,14 +14,55 @@ const DomainSchema = z.union([
 
 const AddressSchema = z.union([z.literal('*'), ZHash, z.array(ZHash)]);
 
-const MatchingListElementSchema = z.object({
-  originDomain: DomainSchema.optional(),
-  senderAddress: AddressSchema.optional(),
-  destinationDomain: DomainSchema.optional(),
-  recipientAddress: AddressSchema.optional(),
+const RangeSchema = z.object({
+  min: z.number().int().nonnegative().optional(),
+  max: z.number().int().nonnegative().optional(),
 });
 
+const BodyPrefixSchema = z.union([
+  z.literal('*'),
+  z.string().regex(/^(0x)?([0-9a-fA-F]{2})*$/),
+  z.array(z.string().regex(/^(0x)?([0-9a-fA-F]{2})*$/)),
+]);
+
+export type MatchingListElement = {
+  originDomain?: z.infer<typeof DomainSchema>;
+  senderAddress?: z.infer<typeof AddressSchema>;
+  destinationDomain?: z.infer<typeof DomainSchema>;
+  recipientAddress?: z.infer<typeof AddressSchema>;
+  senderAddressRegex?: string;
+  nonce?: z.infer<typeof RangeSchema>;
+  bodyPrefix?: z.infer<typeof BodyPrefixSchema>;
+  bodyLength?: z.infer<typeof RangeSchema>;
+  not?: MatchingListElement[];
+};
+
+const MatchingListElementSchema: z.ZodType<MatchingListElement> = z.lazy(() =>
+  z.object({
+    originDomain: DomainSchema.optional(),
+    senderAddress: AddressSchema.optional(),
+    destinationDomain: DomainSchema.optional(),
+    recipientAddress: AddressSchema.optional(),
+    senderAddressRegex: z
+      .string()
+      .optional()
+      .describe(
+        'Regex matched against the 0x-prefixed, 32 byte, lowercase hex encoding of the sender address',
+      ),
+    nonce: RangeSchema.optional().describe('Inclusive range of message nonces'),
+    bodyPrefix: BodyPrefixSchema.optional().describe(
+      'Hex prefix (or list of prefixes) the message body must start with, e.g. a function selector',
+    ),
+    bodyLength: RangeSchema.optional().describe(
+      'Inclusive range of message body lengths, in bytes',
+    ),
+    not: z
+      .array(MatchingListElementSchema)
+      .optional()
+      .describe('Messages matching any of these elements are excluded'),
+  }),
+);
+
 export const MatchingListSchema = z.array(MatchingListElementSchema);
 
-export type MatchingListElement = z.infer<typeof MatchingListElementSchema>;
 export type MatchingList = z.infer<typeof MatchingListSchema>;